@@ -0,0 +1,67 @@
+use log::{Level, Log, Metadata, Record};
+use ruffle_core::backend::log::is_avm_error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A handle to the number of errors reported by the player.
+#[derive(Clone, Default)]
+pub struct ErrorCount(Arc<AtomicUsize>);
+
+impl ErrorCount {
+    pub fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// A logger which counts every error raised by the movie's scripts, such as
+/// uncaught AVM exceptions, so they can be turned into a failing exit code.
+///
+/// Errors from other parts of the player, such as a font that fails to load,
+/// don't count; they are classified the same way the scanner does.
+///
+/// Everything is also forwarded to `env_logger`, which writes to stderr so the
+/// movie's trace output on stdout stays clean.
+struct CliLogger {
+    inner: env_logger::Logger,
+    errors: ErrorCount,
+}
+
+impl Log for CliLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() == Level::Error || self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if record.level() == Level::Error
+            && is_avm_error(record.level(), record.target(), &record.args().to_string())
+        {
+            self.errors.0.fetch_add(1, Ordering::Relaxed);
+        }
+
+        if self.inner.matches(record) {
+            self.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Installs the counting logger, replacing `env_logger::init`.
+pub fn init() -> ErrorCount {
+    let inner = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn"))
+        .format_timestamp(None)
+        .build();
+    let max_level = inner.filter().max(log::LevelFilter::Error);
+    let errors = ErrorCount::default();
+
+    log::set_boxed_logger(Box::new(CliLogger {
+        inner,
+        errors: errors.clone(),
+    }))
+    .expect("Logger should only be initialized once");
+    log::set_max_level(max_level);
+
+    errors
+}
@@ -0,0 +1,150 @@
+mod logging;
+
+use clap::Clap;
+use ruffle_core::backend::audio::NullAudioBackend;
+use ruffle_core::backend::locale::NullLocaleBackend;
+use ruffle_core::backend::log::LogBackend;
+use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
+use ruffle_core::backend::render::NullRenderer;
+use ruffle_core::backend::storage::MemoryStorageBackend;
+use ruffle_core::backend::ui::NullUiBackend;
+use ruffle_core::backend::video::NullVideoBackend;
+use ruffle_core::external::{ExternalInterfaceMethod, ExternalInterfaceProvider};
+use ruffle_core::tag_utils::SwfMovie;
+use ruffle_core::Player;
+use std::cell::Cell;
+use std::error::Error;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clap, Debug)]
+#[clap(name = "Ruffle CLI", author, version)]
+struct Opt {
+    /// The swf file to run
+    #[clap(name = "swf", parse(from_os_str))]
+    swf: PathBuf,
+
+    /// A "flashvars" parameter to provide to the movie.
+    /// This can be repeated multiple times, for example -Pkey=value -Pfoo=bar
+    #[clap(short = 'P', number_of_values = 1)]
+    parameters: Vec<String>,
+
+    /// Number of frames to run. Without this, the movie runs until it calls fscommand("quit")
+    #[clap(long, short)]
+    frames: Option<u32>,
+
+    /// Seed for the random number generator, so that Math.random gives the same results every run
+    #[clap(long)]
+    seed: Option<u64>,
+
+    /// Number of seconds a single script may run before it is aborted
+    #[clap(long)]
+    max_execution_duration: Option<f64>,
+}
+
+/// Prints `trace()` output to stdout.
+struct StdoutLogBackend;
+
+impl LogBackend for StdoutLogBackend {
+    fn avm_trace(&self, message: &str) {
+        let stdout = std::io::stdout();
+        let mut stdout = stdout.lock();
+        let _ = writeln!(stdout, "{}", message);
+        let _ = stdout.flush();
+    }
+}
+
+/// Stops the run when the movie calls `fscommand("quit")`.
+struct QuitProvider {
+    quit: Rc<Cell<bool>>,
+}
+
+impl ExternalInterfaceProvider for QuitProvider {
+    fn get_method(&self, _name: &str) -> Option<Box<dyn ExternalInterfaceMethod>> {
+        None
+    }
+
+    fn on_callback_available(&self, _name: &str) {}
+
+    fn on_fs_command(&self, command: &str, _args: &str) -> bool {
+        if command.eq_ignore_ascii_case("quit") {
+            self.quit.set(true);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+fn parse_parameters(parameters: &[String]) -> impl Iterator<Item = (String, String)> + '_ {
+    parameters.iter().map(|parameter| {
+        let mut split = parameter.splitn(2, '=');
+        if let (Some(key), Some(value)) = (split.next(), split.next()) {
+            (key.to_owned(), value.to_owned())
+        } else {
+            (parameter.clone(), "".to_string())
+        }
+    })
+}
+
+fn run(opt: &Opt) -> Result<(), Box<dyn Error>> {
+    let mut movie = SwfMovie::from_path(&opt.swf, None)?;
+    movie.append_parameters(parse_parameters(&opt.parameters));
+
+    let base_path = opt.swf.parent().unwrap_or_else(|| Path::new(""));
+    let (mut executor, channel) = NullExecutor::new();
+    let player = Player::new(
+        Box::new(NullRenderer::new()),
+        Box::new(NullAudioBackend::new()),
+        Box::new(NullNavigatorBackend::with_base_path(base_path, channel)),
+        Box::new(MemoryStorageBackend::default()),
+        Box::new(NullLocaleBackend::new()),
+        Box::new(NullVideoBackend::new()),
+        Box::new(StdoutLogBackend),
+        Box::new(NullUiBackend::new()),
+    )?;
+    let mut player = player.lock().unwrap();
+
+    let quit = Rc::new(Cell::new(false));
+    player.add_external_interface(Box::new(QuitProvider { quit: quit.clone() }));
+    if let Some(seed) = opt.seed {
+        player.set_random_seed(seed);
+    }
+    if let Some(max_execution_duration) = opt.max_execution_duration {
+        if !(max_execution_duration > 0.0 && max_execution_duration.is_finite()) {
+            return Err("Max execution duration must be a positive number of seconds".into());
+        }
+        player.set_max_execution_duration(Duration::from_secs_f64(max_execution_duration));
+    }
+    player.set_root_movie(Arc::new(movie));
+
+    let mut frames_run = 0;
+    while !quit.get() && opt.frames.map_or(true, |frames| frames_run < frames) {
+        player.run_frame();
+        let frame_time = 1000.0 / player.frame_rate();
+        player.update_timers(frame_time);
+        executor.poll_all()?;
+        frames_run += 1;
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let errors = logging::init();
+    let opt = Opt::parse();
+
+    if let Err(e) = run(&opt) {
+        eprintln!("Unable to run {}: {}", opt.swf.to_string_lossy(), e);
+        std::process::exit(2);
+    }
+
+    let num_errors = errors.get();
+    if num_errors > 0 {
+        eprintln!("{} error(s) occurred while running the movie", num_errors);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,51 @@
+//! Runs the command line player on small test movies and checks its output and exit code.
+
+use std::process::{Command, Output};
+
+fn run(swf: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ruffle_cli"))
+        .arg(format!("tests/swfs/{}", swf))
+        .args(args)
+        .env_remove("RUST_LOG")
+        .output()
+        .expect("Failed to run ruffle_cli")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).replace("\r\n", "\n")
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).to_string()
+}
+
+#[test]
+fn fscommand_quit_stops_the_movie() {
+    let output = run("quit.swf", &[]);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    // The rest of the frame still runs, but the next frame never does.
+    assert_eq!(stdout(&output), "before quit\nafter quit\n");
+}
+
+#[test]
+fn avm_errors_fail_the_run() {
+    let output = run("avm_error.swf", &["--frames", "10"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "still running\n");
+    assert!(stderr(&output).contains("1 error(s) occurred while running the movie"));
+}
+
+#[test]
+fn other_errors_dont_fail_the_run() {
+    let output = run("other_error.swf", &["--frames", "10"]);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "placed\n");
+    assert!(stderr(&output).contains("Unable to instantiate display node id 99"));
+}
+
+#[test]
+fn missing_movie_is_reported() {
+    let output = run("does_not_exist.swf", &[]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("Unable to run"));
+}
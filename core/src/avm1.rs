@@ -19,6 +19,7 @@ pub mod function;
 pub mod property_decl;
 
 pub mod activation;
+pub mod bytecode;
 mod callable_value;
 pub mod debug;
 pub mod error;
@@ -352,10 +353,14 @@ impl<'gc> Avm1<'gc> {
             active_clip,
         );
 
-        let broadcaster = global
-            .get(broadcaster_name, &mut activation)
-            .unwrap()
-            .coerce_to_object(&mut activation);
+        // The broadcaster may be nested in another object, such as `System.IME`.
+        let mut broadcaster = global;
+        for name in broadcaster_name.split('.') {
+            broadcaster = broadcaster
+                .get(name, &mut activation)
+                .unwrap()
+                .coerce_to_object(&mut activation);
+        }
 
         let has_listener =
             as_broadcaster::broadcast_internal(&mut activation, broadcaster, args, method)
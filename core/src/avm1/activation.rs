@@ -1,3 +1,4 @@
+use crate::avm1::bytecode::{CatchVar, FunctionDefinition, Op, PushValue, TryBlock};
 use crate::avm1::callable_value::CallableValue;
 use crate::avm1::error::Error;
 use crate::avm1::function::{Avm1Function, ExecutionReason, FunctionObject};
@@ -5,11 +6,11 @@ use crate::avm1::object::{Object, TObject};
 use crate::avm1::property::Attribute;
 use crate::avm1::scope::Scope;
 use crate::avm1::{
-    fscommand, globals, scope, skip_actions, start_drag, ArrayObject, AvmString, ScriptObject,
-    Value,
+    fscommand, globals, scope, start_drag, ArrayObject, AvmString, ScriptObject, Value,
 };
 use crate::backend::navigator::{NavigationMethod, RequestOptions};
 use crate::context::UpdateContext;
+use crate::debugger::Location;
 use crate::display_object::{DisplayObject, MovieClip, TDisplayObject, TDisplayObjectContainer};
 use crate::ecma_conversions::f64_to_wrapping_u32;
 use crate::tag_utils::SwfSlice;
@@ -17,6 +18,7 @@ use crate::vminterface::Instantiator;
 use crate::{avm_error, avm_warn};
 use gc_arena::{Gc, GcCell, MutationContext};
 use indexmap::IndexMap;
+use instant::Instant;
 use rand::Rng;
 use smallvec::SmallVec;
 use std::borrow::Cow;
@@ -24,8 +26,6 @@ use std::cell::{Ref, RefMut};
 use std::convert::TryFrom;
 use std::fmt;
 use swf::avm1::read::Reader;
-use swf::avm1::types::{Action, CatchVar, Function, TryBlock};
-use swf::SwfStr;
 use url::form_urlencoded;
 
 macro_rules! avm_debug {
@@ -431,23 +431,52 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     pub fn run_actions(&mut self, code: SwfSlice) -> Result<ReturnType<'gc>, Error<'gc>> {
-        let mut read = Reader::new(&code.movie.data()[code.start..], self.swf_version());
+        let gc_context = self.context.gc_context;
+        let actions = self
+            .context
+            .library
+            .library_for_movie_mut(code.movie.clone())
+            .avm1_actions(&code, self.swf_version(), gc_context);
+        let mut ip = 0;
+
+        self.context.debugger.enter_frame(&self.id.name);
+        let result = loop {
+            if self.context.debugger.is_attached() {
+                if let Some(offset) = actions.offset(ip) {
+                    self.check_breakpoint(Location::Avm1 { offset });
+                }
+            }
 
-        loop {
-            let result = self.do_action(&code, &mut read);
+            let result = self.do_action(&code, actions.ops(), &mut ip);
             match result {
                 Ok(FrameControl::Return(return_type)) => break Ok(return_type),
                 Ok(FrameControl::Continue) => {}
                 Err(e) => break Err(e),
             }
+        };
+        self.context.debugger.exit_frame();
+
+        result
+    }
+
+    /// Stop in the debugger if it wants to break before the code at `location`.
+    fn check_breakpoint(&mut self, location: Location) {
+        if self.context.debugger.should_break(location) {
+            let mut debugger = std::mem::take(self.context.debugger);
+            debugger.pause(location, self);
+            *self.context.debugger = debugger;
+
+            // Time spent paused doesn't count towards the script timeout.
+            self.context.update_start = Instant::now();
         }
     }
 
-    /// Run a single action from a given action reader.
-    fn do_action<'b>(
+    /// Run the action at `ip` and advance `ip` to the next action to run.
+    fn do_action(
         &mut self,
-        data: &'b SwfSlice,
-        reader: &mut Reader<'b>,
+        data: &SwfSlice,
+        ops: &[Op<'gc>],
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.actions_since_timeout_check += 1;
         if self.actions_since_timeout_check >= 2000 {
@@ -457,156 +486,140 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             }
         }
 
-        if reader.get_ref().as_ptr() as usize >= data.as_ref().as_ptr_range().end as usize {
+        let op = match ops.get(*ip) {
+            Some(op) => op,
             //Executing beyond the end of a function constitutes an implicit return.
-            Ok(FrameControl::Return(ReturnType::Implicit))
-        } else if let Some(action) = reader.read_action()? {
-            avm_debug!(
-                self.context.avm1,
-                "({}) Action: {:?}",
-                self.id.depth(),
-                action
-            );
-
-            match action {
-                Action::Add => self.action_add(),
-                Action::Add2 => self.action_add_2(),
-                Action::And => self.action_and(),
-                Action::AsciiToChar => self.action_ascii_to_char(),
-                Action::BitAnd => self.action_bit_and(),
-                Action::BitLShift => self.action_bit_lshift(),
-                Action::BitOr => self.action_bit_or(),
-                Action::BitRShift => self.action_bit_rshift(),
-                Action::BitURShift => self.action_bit_urshift(),
-                Action::BitXor => self.action_bit_xor(),
-                Action::Call => self.action_call(),
-                Action::CallFunction => self.action_call_function(),
-                Action::CallMethod => self.action_call_method(),
-                Action::CastOp => self.action_cast_op(),
-                Action::CharToAscii => self.action_char_to_ascii(),
-                Action::CloneSprite => self.action_clone_sprite(),
-                Action::ConstantPool(constant_pool) => {
-                    self.action_constant_pool(&constant_pool[..])
-                }
-                Action::Decrement => self.action_decrement(),
-                Action::DefineFunction {
-                    name,
-                    params,
-                    actions,
-                } => self.action_define_function(
-                    name,
-                    &params[..],
-                    data.to_unbounded_subslice(actions).unwrap(),
-                ),
-                Action::DefineFunction2(func) => self.action_define_function_2(&func, data),
-                Action::DefineLocal => self.action_define_local(),
-                Action::DefineLocal2 => self.action_define_local_2(),
-                Action::Delete => self.action_delete(),
-                Action::Delete2 => self.action_delete_2(),
-                Action::Divide => self.action_divide(),
-                Action::EndDrag => self.action_end_drag(),
-                Action::Enumerate => self.action_enumerate(),
-                Action::Enumerate2 => self.action_enumerate_2(),
-                Action::Equals => self.action_equals(),
-                Action::Equals2 => self.action_equals_2(),
-                Action::Extends => self.action_extends(),
-                Action::GetMember => self.action_get_member(),
-                Action::GetProperty => self.action_get_property(),
-                Action::GetTime => self.action_get_time(),
-                Action::GetVariable => self.action_get_variable(),
-                Action::GetUrl { url, target } => self.action_get_url(url, target),
-                Action::GetUrl2 {
-                    send_vars_method,
-                    is_target_sprite,
-                    is_load_vars,
-                } => self.action_get_url_2(send_vars_method, is_target_sprite, is_load_vars),
-                Action::GotoFrame(frame) => self.action_goto_frame(frame),
-                Action::GotoFrame2 {
-                    set_playing,
-                    scene_offset,
-                } => self.action_goto_frame_2(set_playing, scene_offset),
-                Action::Greater => self.action_greater(),
-                Action::GotoLabel(label) => self.action_goto_label(label),
-                Action::If { offset } => self.action_if(offset, reader, data),
-                Action::Increment => self.action_increment(),
-                Action::InitArray => self.action_init_array(),
-                Action::InitObject => self.action_init_object(),
-                Action::ImplementsOp => self.action_implements_op(),
-                Action::InstanceOf => self.action_instance_of(),
-                Action::Jump { offset } => self.action_jump(offset, reader, data),
-                Action::Less => self.action_less(),
-                Action::Less2 => self.action_less_2(),
-                Action::MBAsciiToChar => self.action_mb_ascii_to_char(),
-                Action::MBCharToAscii => self.action_mb_char_to_ascii(),
-                Action::MBStringLength => self.action_mb_string_length(),
-                Action::MBStringExtract => self.action_mb_string_extract(),
-                Action::Modulo => self.action_modulo(),
-                Action::Multiply => self.action_multiply(),
-                Action::NextFrame => self.action_next_frame(),
-                Action::NewMethod => self.action_new_method(),
-                Action::NewObject => self.action_new_object(),
-                Action::Not => self.action_not(),
-                Action::Or => self.action_or(),
-                Action::Play => self.action_play(),
-                Action::Pop => self.action_pop(),
-                Action::PreviousFrame => self.action_prev_frame(),
-                Action::Push(values) => self.action_push(&values[..]),
-                Action::PushDuplicate => self.action_push_duplicate(),
-                Action::RandomNumber => self.action_random_number(),
-                Action::RemoveSprite => self.action_remove_sprite(),
-                Action::Return => self.action_return(),
-                Action::SetMember => self.action_set_member(),
-                Action::SetProperty => self.action_set_property(),
-                Action::SetTarget(target) => {
-                    self.action_set_target(&target.to_str_lossy(self.encoding()))
-                }
-                Action::SetTarget2 => self.action_set_target2(),
-                Action::SetVariable => self.action_set_variable(),
-                Action::StackSwap => self.action_stack_swap(),
-                Action::StartDrag => self.action_start_drag(),
-                Action::Stop => self.action_stop(),
-                Action::StopSounds => self.action_stop_sounds(),
-                Action::StoreRegister(register) => self.action_store_register(register),
-                Action::StrictEquals => self.action_strict_equals(),
-                Action::StringAdd => self.action_string_add(),
-                Action::StringEquals => self.action_string_equals(),
-                Action::StringExtract => self.action_string_extract(),
-                Action::StringGreater => self.action_string_greater(),
-                Action::StringLength => self.action_string_length(),
-                Action::StringLess => self.action_string_less(),
-                Action::Subtract => self.action_subtract(),
-                Action::TargetPath => self.action_target_path(),
-                Action::ToggleQuality => self.toggle_quality(),
-                Action::ToInteger => self.action_to_integer(),
-                Action::ToNumber => self.action_to_number(),
-                Action::ToString => self.action_to_string(),
-                Action::Trace => self.action_trace(),
-                Action::TypeOf => self.action_type_of(),
-                Action::WaitForFrame {
-                    frame,
-                    num_actions_to_skip,
-                } => self.action_wait_for_frame(frame, num_actions_to_skip, reader),
-                Action::WaitForFrame2 {
-                    num_actions_to_skip,
-                } => self.action_wait_for_frame_2(num_actions_to_skip, reader),
-                Action::With { actions } => {
-                    self.action_with(data.to_unbounded_subslice(actions).unwrap())
-                }
-                Action::Throw => self.action_throw(),
-                Action::Try(try_block) => self.action_try(&try_block, data),
-                _ => self.unknown_op(action),
+            None => return Ok(FrameControl::Return(ReturnType::Implicit)),
+        };
+        *ip += 1;
+
+        avm_debug!(self.context.avm1, "({}) Action: {:?}", self.id.depth(), op);
+
+        match op {
+            Op::Add => self.action_add(),
+            Op::Add2 => self.action_add_2(),
+            Op::And => self.action_and(),
+            Op::AsciiToChar => self.action_ascii_to_char(),
+            Op::BitAnd => self.action_bit_and(),
+            Op::BitLShift => self.action_bit_lshift(),
+            Op::BitOr => self.action_bit_or(),
+            Op::BitRShift => self.action_bit_rshift(),
+            Op::BitURShift => self.action_bit_urshift(),
+            Op::BitXor => self.action_bit_xor(),
+            Op::Call => self.action_call(),
+            Op::CallFunction => self.action_call_function(),
+            Op::CallMethod => self.action_call_method(),
+            Op::CastOp => self.action_cast_op(),
+            Op::CharToAscii => self.action_char_to_ascii(),
+            Op::CloneSprite => self.action_clone_sprite(),
+            Op::ConstantPool { pool } => self.action_constant_pool(*pool),
+            Op::Decrement => self.action_decrement(),
+            Op::DefineFunction { function } => self.action_define_function(function),
+            Op::DefineFunction2 { function } => self.action_define_function_2(function),
+            Op::DefineLocal => self.action_define_local(),
+            Op::DefineLocal2 => self.action_define_local_2(),
+            Op::Delete => self.action_delete(),
+            Op::Delete2 => self.action_delete_2(),
+            Op::Divide => self.action_divide(),
+            Op::EndDrag => self.action_end_drag(),
+            Op::Enumerate => self.action_enumerate(),
+            Op::Enumerate2 => self.action_enumerate_2(),
+            Op::Equals => self.action_equals(),
+            Op::Equals2 => self.action_equals_2(),
+            Op::Extends => self.action_extends(),
+            Op::GetMember => self.action_get_member(),
+            Op::GetProperty => self.action_get_property(),
+            Op::GetTime => self.action_get_time(),
+            Op::GetVariable => self.action_get_variable(),
+            Op::GetUrl { url, target } => self.action_get_url(url, target),
+            Op::GetUrl2 {
+                send_vars_method,
+                is_target_sprite,
+                is_load_vars,
+            } => self.action_get_url_2(*send_vars_method, *is_target_sprite, *is_load_vars),
+            Op::GotoFrame { frame } => self.action_goto_frame(*frame),
+            Op::GotoFrame2 {
+                set_playing,
+                scene_offset,
+            } => self.action_goto_frame_2(*set_playing, *scene_offset),
+            Op::Greater => self.action_greater(),
+            Op::GotoLabel { label } => self.action_goto_label(label),
+            Op::If { target } => self.action_if(*target, ip),
+            Op::Increment => self.action_increment(),
+            Op::InitArray => self.action_init_array(),
+            Op::InitObject => self.action_init_object(),
+            Op::ImplementsOp => self.action_implements_op(),
+            Op::InstanceOf => self.action_instance_of(),
+            Op::Jump { target } => self.action_jump(*target, ip),
+            Op::Less => self.action_less(),
+            Op::Less2 => self.action_less_2(),
+            Op::MBAsciiToChar => self.action_mb_ascii_to_char(),
+            Op::MBCharToAscii => self.action_mb_char_to_ascii(),
+            Op::MBStringLength => self.action_mb_string_length(),
+            Op::MBStringExtract => self.action_mb_string_extract(),
+            Op::Modulo => self.action_modulo(),
+            Op::Multiply => self.action_multiply(),
+            Op::NextFrame => self.action_next_frame(),
+            Op::NewMethod => self.action_new_method(),
+            Op::NewObject => self.action_new_object(),
+            Op::Not => self.action_not(),
+            Op::Or => self.action_or(),
+            Op::Play => self.action_play(),
+            Op::Pop => self.action_pop(),
+            Op::PreviousFrame => self.action_prev_frame(),
+            Op::Push { values } => self.action_push(&values[..]),
+            Op::PushDuplicate => self.action_push_duplicate(),
+            Op::RandomNumber => self.action_random_number(),
+            Op::RemoveSprite => self.action_remove_sprite(),
+            Op::Return => self.action_return(),
+            Op::SetMember => self.action_set_member(),
+            Op::SetProperty => self.action_set_property(),
+            Op::SetTarget { target } => self.action_set_target(target),
+            Op::SetTarget2 => self.action_set_target2(),
+            Op::SetVariable => self.action_set_variable(),
+            Op::StackSwap => self.action_stack_swap(),
+            Op::StartDrag => self.action_start_drag(),
+            Op::Stop => self.action_stop(),
+            Op::StopSounds => self.action_stop_sounds(),
+            Op::StoreRegister { register } => self.action_store_register(*register),
+            Op::StrictEquals => self.action_strict_equals(),
+            Op::StringAdd => self.action_string_add(),
+            Op::StringEquals => self.action_string_equals(),
+            Op::StringExtract => self.action_string_extract(),
+            Op::StringGreater => self.action_string_greater(),
+            Op::StringLength => self.action_string_length(),
+            Op::StringLess => self.action_string_less(),
+            Op::Subtract => self.action_subtract(),
+            Op::TargetPath => self.action_target_path(),
+            Op::ToggleQuality => self.toggle_quality(),
+            Op::ToInteger => self.action_to_integer(),
+            Op::ToNumber => self.action_to_number(),
+            Op::ToString => self.action_to_string(),
+            Op::Trace => self.action_trace(),
+            Op::TypeOf => self.action_type_of(),
+            Op::WaitForFrame { frame, skip_target } => {
+                self.action_wait_for_frame(*frame, *skip_target, ip)
             }
-        } else {
+            Op::WaitForFrame2 { skip_target } => self.action_wait_for_frame_2(*skip_target, ip),
+            Op::With { actions } => self.action_with(actions.clone()),
+            Op::Throw => self.action_throw(),
+            Op::Try { block } => self.action_try(block),
+            Op::Unknown { opcode } => self.unknown_op(*opcode),
             //The explicit end opcode was encountered so return here
-            Ok(FrameControl::Return(ReturnType::Implicit))
+            Op::End => Ok(FrameControl::Return(ReturnType::Implicit)),
+            Op::Undecodable { offset } => {
+                // Read the action again to report why it can't be executed.
+                let mut reader = Reader::new(&data.movie.data()[*offset..], self.swf_version());
+                reader.read_action()?;
+                Err(Error::InvalidSwf(swf::error::Error::invalid_data(
+                    "Invalid AVM1 action",
+                )))
+            }
         }
     }
 
-    fn unknown_op(
-        &mut self,
-        action: swf::avm1::types::Action,
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        avm_error!(self, "Unknown AVM1 opcode: {:?}", action);
+    fn unknown_op(&mut self, opcode: u8) -> Result<FrameControl<'gc>, Error<'gc>> {
+        avm_error!(self, "Unknown AVM1 opcode: {:#04x}", opcode);
         Ok(FrameControl::Continue)
     }
 
@@ -887,19 +900,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
     fn action_constant_pool(
         &mut self,
-        constant_pool: &[&'_ SwfStr],
+        constant_pool: GcCell<'gc, Vec<Value<'gc>>>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        self.context.avm1.constant_pool = GcCell::allocate(
-            self.context.gc_context,
-            constant_pool
-                .iter()
-                .map(|s| {
-                    AvmString::new(self.context.gc_context, s.to_string_lossy(self.encoding()))
-                        .into()
-                })
-                .collect(),
-        );
-        self.set_constant_pool(self.context.avm1.constant_pool);
+        self.context.avm1.constant_pool = constant_pool;
+        self.set_constant_pool(constant_pool);
 
         Ok(FrameControl::Continue)
     }
@@ -912,20 +916,15 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
     fn action_define_function(
         &mut self,
-        name: &'_ SwfStr,
-        params: &[&'_ SwfStr],
-        actions: SwfSlice,
+        function: &FunctionDefinition,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        let name = name.to_str_lossy(self.encoding());
-        let name = name.as_ref();
+        let name = function.name.as_str();
         let swf_version = self.swf_version();
         let scope = Scope::new_closure_scope(self.scope_cell(), self.context.gc_context);
         let constant_pool = self.constant_pool();
-        let func = Avm1Function::from_df1(
+        let func = Avm1Function::from_definition(
             swf_version,
-            actions,
-            name,
-            params,
+            function,
             scope,
             constant_pool,
             self.target_clip_or_root()?,
@@ -952,18 +951,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
     fn action_define_function_2(
         &mut self,
-        action_func: &Function,
-        parent_data: &SwfSlice,
+        action_func: &FunctionDefinition,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let swf_version = self.swf_version();
-        let func_data = parent_data
-            .to_unbounded_subslice(action_func.actions)
-            .unwrap();
         let scope = Scope::new_closure_scope(self.scope_cell(), self.context.gc_context);
         let constant_pool = self.constant_pool();
-        let func = Avm1Function::from_df2(
+        let func = Avm1Function::from_definition(
             swf_version,
-            func_data,
             action_func,
             scope,
             constant_pool,
@@ -983,10 +977,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         if action_func.name.is_empty() {
             self.context.avm1.push(func_obj);
         } else {
-            self.define_local(
-                &action_func.name.to_str_lossy(self.encoding()),
-                func_obj.into(),
-            )?;
+            self.define_local(&action_func.name, func_obj.into())?;
         }
 
         Ok(FrameControl::Continue)
@@ -1215,18 +1206,14 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn action_get_url(
-        &mut self,
-        url: &'_ SwfStr,
-        target: &'_ SwfStr,
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        let target = target.to_str_lossy(self.encoding());
-        let target = target.as_ref();
-        let url = url.to_string_lossy(self.encoding());
+    fn action_get_url(&mut self, url: &str, target: &str) -> Result<FrameControl<'gc>, Error<'gc>> {
         if target.starts_with("_level") && target.len() > 6 {
             match target[6..].parse::<i32>() {
                 Ok(level_id) => {
-                    let fetch = self.context.navigator.fetch(&url, RequestOptions::get());
+                    let fetch = self
+                        .context
+                        .navigator
+                        .fetch_body(url, RequestOptions::get());
                     let level = self.resolve_level(level_id);
 
                     if url.is_empty() {
@@ -1239,7 +1226,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                             self.context.player.clone().unwrap(),
                             level,
                             fetch,
-                            url,
+                            url.to_string(),
                             None,
                             None,
                         );
@@ -1256,7 +1243,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             return Ok(FrameControl::Continue);
         }
 
-        if let Some(fscommand) = fscommand::parse(&url) {
+        if let Some(fscommand) = fscommand::parse(url) {
             let fsargs = target;
             fscommand::handle(fscommand, fsargs, self)?;
         } else {
@@ -1333,7 +1320,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                         mc.replace_with_movie(self.context.gc_context, None)
                     }
                 } else {
-                    let fetch = self.context.navigator.fetch(&url, opts);
+                    let fetch = self.context.navigator.fetch_body(&url, opts);
                     let process = self.context.load_manager.load_movie_into_clip(
                         self.context.player.clone().unwrap(),
                         clip_target,
@@ -1350,7 +1337,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             // target of `_level#` indicates a `loadMovieNum` call.
             match window_target[6..].parse::<i32>() {
                 Ok(level_id) => {
-                    let fetch = self.context.navigator.fetch(&url, RequestOptions::get());
+                    let fetch = self
+                        .context
+                        .navigator
+                        .fetch_body(&url, RequestOptions::get());
                     let level = self.resolve_level(level_id);
 
                     let process = self.context.load_manager.load_movie_into_clip(
@@ -1427,12 +1417,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn action_goto_label(&mut self, label: &'_ SwfStr) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn action_goto_label(&mut self, label: &str) -> Result<FrameControl<'gc>, Error<'gc>> {
         if let Some(clip) = self.target_clip() {
             if let Some(clip) = clip.as_movie_clip() {
-                if let Some(frame) =
-                    clip.frame_label_to_number(&label.to_str_lossy(self.encoding()))
-                {
+                if let Some(frame) = clip.frame_label_to_number(label) {
                     clip.goto_frame(&mut self.context, frame, true);
                 } else {
                     avm_warn!(self, "GoToLabel: Frame label '{:?}' not found", label);
@@ -1446,15 +1434,14 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn action_if<'b>(
+    fn action_if(
         &mut self,
-        jump_offset: i16,
-        reader: &mut Reader<'b>,
-        data: &'b SwfSlice,
+        target: usize,
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let val = self.context.avm1.pop();
         if val.as_bool(self.swf_version()) {
-            reader.seek(data.movie.data(), jump_offset);
+            *ip = target;
         }
         Ok(FrameControl::Continue)
     }
@@ -1549,13 +1536,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn action_jump<'b>(
+    fn action_jump(
         &mut self,
-        jump_offset: i16,
-        reader: &mut Reader<'b>,
-        data: &'b SwfSlice,
+        target: usize,
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        reader.seek(data.movie.data(), jump_offset);
+        *ip = target;
         Ok(FrameControl::Continue)
     }
 
@@ -1801,25 +1787,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn action_push(
-        &mut self,
-        values: &[swf::avm1::types::Value],
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn action_push(&mut self, values: &[PushValue<'gc>]) -> Result<FrameControl<'gc>, Error<'gc>> {
         for value in values {
-            use swf::avm1::types::Value as SwfValue;
             let value = match value {
-                SwfValue::Undefined => Value::Undefined,
-                SwfValue::Null => Value::Null,
-                SwfValue::Bool(v) => (*v).into(),
-                SwfValue::Int(v) => (*v).into(),
-                SwfValue::Float(v) => (*v).into(),
-                SwfValue::Double(v) => (*v).into(),
-                SwfValue::Str(v) => {
-                    AvmString::new(self.context.gc_context, v.to_string_lossy(self.encoding()))
-                        .into()
-                }
-                SwfValue::Register(v) => self.current_register(*v),
-                SwfValue::ConstantPool(i) => {
+                PushValue::Value(v) => *v,
+                PushValue::Register(v) => self.current_register(*v),
+                PushValue::ConstantPool(i) => {
                     if let Some(value) = self.constant_pool().read().get(*i as usize) {
                         *value
                     } else {
@@ -2238,36 +2211,39 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
     fn action_wait_for_frame(
         &mut self,
-        _frame: u16,
-        num_actions_to_skip: u8,
-        r: &mut Reader<'_>,
+        frame: u16,
+        skip_target: usize,
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        // TODO(Herschel): Always true for now.
-        let loaded = true;
+        // The frame in the action is 0-based, not 1-based.
+        let loaded = self.is_frame_loaded(frame.saturating_add(1));
         if !loaded {
-            // Note that the offset is given in # of actions, NOT in bytes.
-            // Read the actions and toss them away.
-            skip_actions(r, num_actions_to_skip);
+            *ip = skip_target;
         }
         Ok(FrameControl::Continue)
     }
 
     fn action_wait_for_frame_2(
         &mut self,
-        num_actions_to_skip: u8,
-        r: &mut Reader<'_>,
+        skip_target: usize,
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        // TODO(Herschel): Always true for now.
-        let _frame_num = self.context.avm1.pop().coerce_to_f64(self)? as u16;
-        let loaded = true;
+        let frame_num = self.context.avm1.pop().coerce_to_f64(self)? as u16;
+        let loaded = self.is_frame_loaded(frame_num);
         if !loaded {
-            // Note that the offset is given in # of actions, NOT in bytes.
-            // Read the actions and toss them away.
-            skip_actions(r, num_actions_to_skip);
+            *ip = skip_target;
         }
         Ok(FrameControl::Continue)
     }
 
+    /// Whether the given 1-based frame of the target clip has been preloaded.
+    fn is_frame_loaded(&self, frame: u16) -> bool {
+        match self.target_clip().and_then(|clip| clip.as_movie_clip()) {
+            Some(clip) => frame <= clip.frames_loaded(),
+            None => true,
+        }
+    }
+
     fn action_throw(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm1.pop();
         avm_debug!(
@@ -2307,16 +2283,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         }
     }
 
-    fn action_try(
-        &mut self,
-        try_block: &TryBlock,
-        parent_data: &SwfSlice,
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        let mut result = self.run_actions(
-            parent_data
-                .to_unbounded_subslice(try_block.try_actions)
-                .unwrap(),
-        );
+    fn action_try(&mut self, try_block: &TryBlock) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let mut result = self.run_actions(try_block.try_actions.clone());
 
         if let Some((catch_vars, actions)) = &try_block.catch {
             if let Err(Error::ThrownValue(value)) = &result {
@@ -2333,24 +2301,18 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 );
 
                 match catch_vars {
-                    CatchVar::Var(name) => activation.set_variable(
-                        &name.to_str_lossy(activation.encoding()),
-                        value.to_owned(),
-                    )?,
+                    CatchVar::Var(name) => activation.set_variable(name, value.to_owned())?,
                     CatchVar::Register(id) => {
                         activation.set_current_register(*id, value.to_owned())
                     }
                 }
 
-                result =
-                    activation.run_actions(parent_data.to_unbounded_subslice(actions).unwrap());
+                result = activation.run_actions(actions.clone());
             }
         }
 
-        if let Some(actions) = try_block.finally {
-            if let ReturnType::Explicit(value) =
-                self.run_actions(parent_data.to_unbounded_subslice(actions).unwrap())?
-            {
+        if let Some(actions) = &try_block.finally {
+            if let ReturnType::Explicit(value) = self.run_actions(actions.clone())? {
                 return Ok(FrameControl::Return(ReturnType::Explicit(value)));
             }
         }
@@ -3012,6 +2974,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         };
     }
 
+    /// Returns how many local registers this activation has.
+    pub fn local_register_count(&self) -> u8 {
+        self.local_registers.map(|rs| rs.read().len()).unwrap_or(0)
+    }
+
     /// Retrieve a local register.
     pub fn local_register(&self, id: u8) -> Option<Value<'gc>> {
         if let Some(local_registers) = self.local_registers {
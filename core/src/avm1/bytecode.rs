@@ -0,0 +1,809 @@
+//! Pre-decoded action lists
+
+use crate::avm1::{skip_actions, AvmString, Value};
+use crate::tag_utils::SwfSlice;
+use fnv::FnvHashMap;
+use gc_arena::{Collect, GcCell, MutationContext};
+use swf::avm1::read::Reader;
+use swf::avm1::types::{Action, FunctionFlags, SendVarsMethod};
+use swf::extensions::ReadSwfExt;
+use swf::SwfStr;
+
+/// An action list that has been decoded ahead of its execution.
+///
+/// Decoding happens once per action list, so that running it again does not
+/// need to re-read its actions or re-intern its strings.
+#[derive(Collect, Debug)]
+#[collect(no_drop)]
+pub struct DecodedActions<'gc> {
+    ops: Vec<Op<'gc>>,
+
+    /// The offset in the movie of each action.
+    offsets: Vec<usize>,
+}
+
+impl<'gc> DecodedActions<'gc> {
+    /// Decode the action list in a slice of a movie.
+    ///
+    /// Actions are decoded in runs, starting from the start of the slice and
+    /// from every jump target. A run that reaches an already decoded action
+    /// ends with a jump to it, so jumps into the middle of another action
+    /// still execute the bytes they point to. Jumps may leave the slice in
+    /// either direction, exactly as they could when actions were read on the
+    /// fly; execution only stops at or beyond the end of the slice.
+    pub fn decode(code: &SwfSlice, swf_version: u8, mc: MutationContext<'gc, '_>) -> Self {
+        let data = code.movie.data();
+        let mut ops = Vec::new();
+        let mut offsets = Vec::new();
+        let mut op_indices = FnvHashMap::default();
+        let mut pending = vec![code.start.min(data.len())];
+
+        while let Some(start) = pending.pop() {
+            if op_indices.contains_key(&start) {
+                continue;
+            }
+
+            let mut reader = Reader::new(&data[start..], swf_version);
+            loop {
+                let offset = reader.pos(data);
+                offsets.push(offset);
+                if offset != start && op_indices.contains_key(&offset) {
+                    ops.push(Op::Jump { target: offset });
+                    break;
+                }
+
+                op_indices.insert(offset, ops.len());
+
+                if offset >= code.end {
+                    // Executing beyond the end of the slice constitutes an
+                    // implicit return.
+                    ops.push(Op::End);
+                    break;
+                }
+
+                let action = match reader.read_action() {
+                    Ok(Some(action)) => action,
+                    Ok(None) => {
+                        ops.push(Op::End);
+                        break;
+                    }
+                    Err(_) => {
+                        ops.push(Op::Undecodable { offset });
+                        break;
+                    }
+                };
+
+                let end = reader.pos(data);
+                ops.push(Op::decode(code, action, end, swf_version, &mut pending, mc));
+            }
+        }
+
+        // Jump targets were decoded as byte offsets; every one of them has
+        // been decoded by now, so they can be pointed at their actions.
+        for op in ops.iter_mut() {
+            op.for_each_target(|target| *target = op_indices[&*target]);
+        }
+
+        Self { ops, offsets }
+    }
+
+    /// The actions of the list.
+    pub fn ops(&self) -> &[Op<'gc>] {
+        &self.ops
+    }
+
+    /// The offset in the movie of the action at the given index.
+    pub fn offset(&self, index: usize) -> Option<usize> {
+        self.offsets.get(index).copied()
+    }
+}
+
+/// A value pushed by a `Push` action.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub enum PushValue<'gc> {
+    /// A constant, with any string already interned.
+    Value(Value<'gc>),
+
+    /// The value of a register at the time of the push.
+    Register(u8),
+
+    /// An entry of the constant pool in use at the time of the push.
+    ConstantPool(u16),
+}
+
+/// A function defined by a `DefineFunction` or `DefineFunction2` action.
+#[derive(Clone, Collect, Debug)]
+#[collect(require_static)]
+pub struct FunctionDefinition {
+    /// The name of the function, empty if it is anonymous.
+    pub name: String,
+
+    /// The number of registers the function allocates.
+    pub register_count: u8,
+
+    /// The names of the parameters and the registers they are stored in.
+    pub params: Vec<(Option<u8>, String)>,
+
+    /// The flags that define the preloaded registers of the function.
+    pub flags: FunctionFlags,
+
+    /// The body of the function.
+    pub actions: SwfSlice,
+}
+
+/// Where a `Try` action stores the value it caught.
+#[derive(Clone, Collect, Debug)]
+#[collect(require_static)]
+pub enum CatchVar {
+    Var(String),
+    Register(u8),
+}
+
+/// The blocks of a `Try` action.
+#[derive(Clone, Collect, Debug)]
+#[collect(require_static)]
+pub struct TryBlock {
+    pub try_actions: SwfSlice,
+    pub catch: Option<(CatchVar, SwfSlice)>,
+    pub finally: Option<SwfSlice>,
+}
+
+/// A single decoded action.
+///
+/// Jump targets are indices into the action list the action belongs to.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub enum Op<'gc> {
+    Add,
+    Add2,
+    And,
+    AsciiToChar,
+    BitAnd,
+    BitLShift,
+    BitOr,
+    BitRShift,
+    BitURShift,
+    BitXor,
+    Call,
+    CallFunction,
+    CallMethod,
+    CastOp,
+    CharToAscii,
+    CloneSprite,
+    ConstantPool {
+        pool: GcCell<'gc, Vec<Value<'gc>>>,
+    },
+    Decrement,
+    DefineFunction {
+        function: Box<FunctionDefinition>,
+    },
+    DefineFunction2 {
+        function: Box<FunctionDefinition>,
+    },
+    DefineLocal,
+    DefineLocal2,
+    Delete,
+    Delete2,
+    Divide,
+    EndDrag,
+    Enumerate,
+    Enumerate2,
+    Equals,
+    Equals2,
+    Extends,
+    GetMember,
+    GetProperty,
+    GetTime,
+    GetUrl {
+        url: String,
+        target: String,
+    },
+    GetUrl2 {
+        #[collect(require_static)]
+        send_vars_method: SendVarsMethod,
+        is_target_sprite: bool,
+        is_load_vars: bool,
+    },
+    GetVariable,
+    GotoFrame {
+        frame: u16,
+    },
+    GotoFrame2 {
+        set_playing: bool,
+        scene_offset: u16,
+    },
+    GotoLabel {
+        label: String,
+    },
+    Greater,
+    If {
+        target: usize,
+    },
+    ImplementsOp,
+    Increment,
+    InitArray,
+    InitObject,
+    InstanceOf,
+    Jump {
+        target: usize,
+    },
+    Less,
+    Less2,
+    MBAsciiToChar,
+    MBCharToAscii,
+    MBStringExtract,
+    MBStringLength,
+    Modulo,
+    Multiply,
+    NewMethod,
+    NewObject,
+    NextFrame,
+    Not,
+    Or,
+    Play,
+    Pop,
+    PreviousFrame,
+    Push {
+        values: Vec<PushValue<'gc>>,
+    },
+    PushDuplicate,
+    RandomNumber,
+    RemoveSprite,
+    Return,
+    SetMember,
+    SetProperty,
+    SetTarget {
+        target: String,
+    },
+    SetTarget2,
+    SetVariable,
+    StackSwap,
+    StartDrag,
+    Stop,
+    StopSounds,
+    StoreRegister {
+        register: u8,
+    },
+    StrictEquals,
+    StringAdd,
+    StringEquals,
+    StringExtract,
+    StringGreater,
+    StringLength,
+    StringLess,
+    Subtract,
+    TargetPath,
+    Throw,
+    ToInteger,
+    ToNumber,
+    ToString,
+    ToggleQuality,
+    Trace,
+    Try {
+        block: Box<TryBlock>,
+    },
+    TypeOf,
+    WaitForFrame {
+        frame: u16,
+        skip_target: usize,
+    },
+    WaitForFrame2 {
+        skip_target: usize,
+    },
+    With {
+        actions: SwfSlice,
+    },
+    Unknown {
+        opcode: u8,
+    },
+
+    /// The end of the action list, either through an explicit `End` action
+    /// or by running past the end of the slice.
+    End,
+
+    /// The bytes at this offset of the movie could not be read as an action.
+    Undecodable {
+        offset: usize,
+    },
+}
+
+impl<'gc> Op<'gc> {
+    /// Decode a single action, interning its strings.
+    ///
+    /// Jump targets are returned as offsets into the movie, and are also
+    /// added to `pending` so that they get decoded.
+    fn decode(
+        code: &SwfSlice,
+        action: Action<'_>,
+        end: usize,
+        swf_version: u8,
+        pending: &mut Vec<usize>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Self {
+        let data = code.movie.data();
+        let encoding = SwfStr::encoding_for_version(swf_version);
+        let string = |s: &SwfStr| s.to_string_lossy(encoding);
+        let subslice = |actions: &[u8]| {
+            code.to_unbounded_subslice(actions)
+                .unwrap_or_else(|| SwfSlice::empty(code.movie.clone()))
+        };
+        let mut target = |offset: isize| {
+            // Jumps out of the movie land at its end, which stops execution.
+            let target = end as isize + offset;
+            let target = if target < 0 {
+                data.len()
+            } else {
+                (target as usize).min(data.len())
+            };
+            pending.push(target);
+            target
+        };
+
+        match action {
+            Action::Add => Op::Add,
+            Action::Add2 => Op::Add2,
+            Action::And => Op::And,
+            Action::AsciiToChar => Op::AsciiToChar,
+            Action::BitAnd => Op::BitAnd,
+            Action::BitLShift => Op::BitLShift,
+            Action::BitOr => Op::BitOr,
+            Action::BitRShift => Op::BitRShift,
+            Action::BitURShift => Op::BitURShift,
+            Action::BitXor => Op::BitXor,
+            Action::Call => Op::Call,
+            Action::CallFunction => Op::CallFunction,
+            Action::CallMethod => Op::CallMethod,
+            Action::CastOp => Op::CastOp,
+            Action::CharToAscii => Op::CharToAscii,
+            Action::CloneSprite => Op::CloneSprite,
+            Action::ConstantPool(constant_pool) => Op::ConstantPool {
+                pool: GcCell::allocate(
+                    mc,
+                    constant_pool
+                        .iter()
+                        .map(|&s| AvmString::new(mc, string(s)).into())
+                        .collect(),
+                ),
+            },
+            Action::Decrement => Op::Decrement,
+            Action::DefineFunction {
+                name,
+                params,
+                actions,
+            } => Op::DefineFunction {
+                function: Box::new(FunctionDefinition {
+                    name: string(name),
+                    register_count: 0,
+                    params: params.iter().map(|&s| (None, string(s))).collect(),
+                    flags: FunctionFlags::empty(),
+                    actions: subslice(actions),
+                }),
+            },
+            Action::DefineFunction2(function) => Op::DefineFunction2 {
+                function: Box::new(FunctionDefinition {
+                    name: string(function.name),
+                    register_count: function.register_count,
+                    params: function
+                        .params
+                        .iter()
+                        .map(|p| (p.register_index, string(p.name)))
+                        .collect(),
+                    flags: function.flags,
+                    actions: subslice(function.actions),
+                }),
+            },
+            Action::DefineLocal => Op::DefineLocal,
+            Action::DefineLocal2 => Op::DefineLocal2,
+            Action::Delete => Op::Delete,
+            Action::Delete2 => Op::Delete2,
+            Action::Divide => Op::Divide,
+            Action::EndDrag => Op::EndDrag,
+            Action::Enumerate => Op::Enumerate,
+            Action::Enumerate2 => Op::Enumerate2,
+            Action::Equals => Op::Equals,
+            Action::Equals2 => Op::Equals2,
+            Action::Extends => Op::Extends,
+            Action::GetMember => Op::GetMember,
+            Action::GetProperty => Op::GetProperty,
+            Action::GetTime => Op::GetTime,
+            Action::GetUrl { url, target } => Op::GetUrl {
+                url: string(url),
+                target: string(target),
+            },
+            Action::GetUrl2 {
+                send_vars_method,
+                is_target_sprite,
+                is_load_vars,
+            } => Op::GetUrl2 {
+                send_vars_method,
+                is_target_sprite,
+                is_load_vars,
+            },
+            Action::GetVariable => Op::GetVariable,
+            Action::GotoFrame(frame) => Op::GotoFrame { frame },
+            Action::GotoFrame2 {
+                set_playing,
+                scene_offset,
+            } => Op::GotoFrame2 {
+                set_playing,
+                scene_offset,
+            },
+            Action::GotoLabel(label) => Op::GotoLabel {
+                label: string(label),
+            },
+            Action::Greater => Op::Greater,
+            Action::If { offset } => Op::If {
+                target: target(offset.into()),
+            },
+            Action::ImplementsOp => Op::ImplementsOp,
+            Action::Increment => Op::Increment,
+            Action::InitArray => Op::InitArray,
+            Action::InitObject => Op::InitObject,
+            Action::InstanceOf => Op::InstanceOf,
+            Action::Jump { offset } => Op::Jump {
+                target: target(offset.into()),
+            },
+            Action::Less => Op::Less,
+            Action::Less2 => Op::Less2,
+            Action::MBAsciiToChar => Op::MBAsciiToChar,
+            Action::MBCharToAscii => Op::MBCharToAscii,
+            Action::MBStringExtract => Op::MBStringExtract,
+            Action::MBStringLength => Op::MBStringLength,
+            Action::Modulo => Op::Modulo,
+            Action::Multiply => Op::Multiply,
+            Action::NewMethod => Op::NewMethod,
+            Action::NewObject => Op::NewObject,
+            Action::NextFrame => Op::NextFrame,
+            Action::Not => Op::Not,
+            Action::Or => Op::Or,
+            Action::Play => Op::Play,
+            Action::Pop => Op::Pop,
+            Action::PreviousFrame => Op::PreviousFrame,
+            Action::Push(values) => Op::Push {
+                values: values
+                    .iter()
+                    .map(|value| {
+                        use swf::avm1::types::Value as SwfValue;
+                        match value {
+                            SwfValue::Undefined => PushValue::Value(Value::Undefined),
+                            SwfValue::Null => PushValue::Value(Value::Null),
+                            SwfValue::Bool(v) => PushValue::Value((*v).into()),
+                            SwfValue::Int(v) => PushValue::Value((*v).into()),
+                            SwfValue::Float(v) => PushValue::Value((*v).into()),
+                            SwfValue::Double(v) => PushValue::Value((*v).into()),
+                            SwfValue::Str(v) => {
+                                PushValue::Value(AvmString::new(mc, string(*v)).into())
+                            }
+                            SwfValue::Register(v) => PushValue::Register(*v),
+                            SwfValue::ConstantPool(i) => PushValue::ConstantPool(*i),
+                        }
+                    })
+                    .collect(),
+            },
+            Action::PushDuplicate => Op::PushDuplicate,
+            Action::RandomNumber => Op::RandomNumber,
+            Action::RemoveSprite => Op::RemoveSprite,
+            Action::Return => Op::Return,
+            Action::SetMember => Op::SetMember,
+            Action::SetProperty => Op::SetProperty,
+            Action::SetTarget(target) => Op::SetTarget {
+                target: string(target),
+            },
+            Action::SetTarget2 => Op::SetTarget2,
+            Action::SetVariable => Op::SetVariable,
+            Action::StackSwap => Op::StackSwap,
+            Action::StartDrag => Op::StartDrag,
+            Action::Stop => Op::Stop,
+            Action::StopSounds => Op::StopSounds,
+            Action::StoreRegister(register) => Op::StoreRegister { register },
+            Action::StrictEquals => Op::StrictEquals,
+            Action::StringAdd => Op::StringAdd,
+            Action::StringEquals => Op::StringEquals,
+            Action::StringExtract => Op::StringExtract,
+            Action::StringGreater => Op::StringGreater,
+            Action::StringLength => Op::StringLength,
+            Action::StringLess => Op::StringLess,
+            Action::Subtract => Op::Subtract,
+            Action::TargetPath => Op::TargetPath,
+            Action::Throw => Op::Throw,
+            Action::ToInteger => Op::ToInteger,
+            Action::ToNumber => Op::ToNumber,
+            Action::ToString => Op::ToString,
+            Action::ToggleQuality => Op::ToggleQuality,
+            Action::Trace => Op::Trace,
+            Action::Try(try_block) => {
+                use swf::avm1::types::CatchVar as SwfCatchVar;
+                Op::Try {
+                    block: Box::new(TryBlock {
+                        try_actions: subslice(try_block.try_actions),
+                        catch: try_block.catch.map(|(catch_var, actions)| {
+                            let catch_var = match catch_var {
+                                SwfCatchVar::Var(name) => CatchVar::Var(string(name)),
+                                SwfCatchVar::Register(id) => CatchVar::Register(id),
+                            };
+                            (catch_var, subslice(actions))
+                        }),
+                        finally: try_block.finally.map(subslice),
+                    }),
+                }
+            }
+            Action::TypeOf => Op::TypeOf,
+            Action::WaitForFrame {
+                frame,
+                num_actions_to_skip,
+            } => Op::WaitForFrame {
+                frame,
+                skip_target: skip_target(data, end, swf_version, num_actions_to_skip, pending),
+            },
+            Action::WaitForFrame2 {
+                num_actions_to_skip,
+            } => Op::WaitForFrame2 {
+                skip_target: skip_target(data, end, swf_version, num_actions_to_skip, pending),
+            },
+            Action::With { actions } => Op::With {
+                actions: subslice(actions),
+            },
+            Action::Unknown { opcode, .. } => Op::Unknown { opcode },
+        }
+    }
+
+    /// Call a function with every jump target of this action.
+    fn for_each_target(&mut self, mut f: impl FnMut(&mut usize)) {
+        match self {
+            Op::If { target } | Op::Jump { target } => f(target),
+            Op::WaitForFrame { skip_target, .. } | Op::WaitForFrame2 { skip_target } => {
+                f(skip_target)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Find where execution continues when a `WaitForFrame` action skips the
+/// actions that follow it.
+///
+/// The skip is given in number of actions, not bytes, so the actions are read
+/// and tossed away.
+fn skip_target(
+    data: &[u8],
+    end: usize,
+    swf_version: u8,
+    num_actions_to_skip: u8,
+    pending: &mut Vec<usize>,
+) -> usize {
+    let mut reader = Reader::new(&data[end..], swf_version);
+    skip_actions(&mut reader, num_actions_to_skip);
+
+    let target = reader.pos(data);
+    pending.push(target);
+    target
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::activation::ReturnType;
+    use crate::avm1::error::Error;
+    use crate::avm1::test_utils::with_avm;
+    use crate::tag_utils::SwfMovie;
+    use std::sync::Arc;
+
+    /// A slice over a movie whose tag data is nothing but `actions`.
+    fn movie_with_actions(actions: &[&[u8]]) -> SwfSlice {
+        let actions = actions.concat();
+        let mut swf = b"FWS\x0a".to_vec();
+        swf.extend_from_slice(&(13 + actions.len() as u32).to_le_bytes());
+        // An empty stage, the frame rate and the frame count.
+        swf.extend_from_slice(&[0, 0, 12, 1, 0]);
+        swf.extend_from_slice(&actions);
+
+        let movie = SwfMovie::from_data(&swf, None, None).expect("Valid movie");
+        SwfSlice::from(Arc::new(movie))
+    }
+
+    fn push(value: i32) -> Vec<u8> {
+        let mut action = vec![0x96, 5, 0, 7];
+        action.extend_from_slice(&value.to_le_bytes());
+        action
+    }
+
+    fn jump(offset: i16) -> Vec<u8> {
+        let mut action = vec![0x99, 2, 0];
+        action.extend_from_slice(&offset.to_le_bytes());
+        action
+    }
+
+    const ADD2: &[u8] = &[0x47];
+    const RETURN: &[u8] = &[0x3e];
+
+    #[test]
+    fn jump_targets_are_action_indices() {
+        with_avm(10, |activation, _root| -> Result<(), Error> {
+            let code = movie_with_actions(&[&push(1), &jump(8), &push(2), &push(3), RETURN]);
+            let actions = DecodedActions::decode(&code, 10, activation.context.gc_context);
+
+            let ops = actions.ops();
+            assert_eq!(ops.len(), 6);
+            assert!(matches!(ops[0], Op::Push { .. }));
+            assert!(matches!(ops[1], Op::Jump { target: 3 }));
+            assert!(matches!(ops[4], Op::Return));
+            // Running off the end of the slice ends the list.
+            assert!(matches!(ops[5], Op::End));
+
+            let offsets: Vec<_> = (0..ops.len()).map(|i| actions.offset(i)).collect();
+            assert_eq!(
+                offsets,
+                [Some(0), Some(8), Some(13), Some(21), Some(29), Some(30)]
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn actions_are_decoded_once_per_slice_and_version() {
+        with_avm(10, |activation, _root| -> Result<(), Error> {
+            let code = movie_with_actions(&[&push(1), RETURN]);
+            let gc_context = activation.context.gc_context;
+            let library = activation
+                .context
+                .library
+                .library_for_movie_mut(code.movie.clone());
+
+            let first = library.avm1_actions(&code, 10, gc_context);
+            let second = library.avm1_actions(&code, 10, gc_context);
+            assert!(std::ptr::eq(&*first, &*second));
+
+            let other_version = library.avm1_actions(&code, 5, gc_context);
+            assert!(!std::ptr::eq(&*first, &*other_version));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn constant_pool_is_shared_across_runs() {
+        with_avm(10, |activation, _root| -> Result<(), Error> {
+            // ConstantPool ["hello"]; Push pool[0]; Return
+            let code = movie_with_actions(&[
+                &[0x88, 8, 0, 1, 0, b'h', b'e', b'l', b'l', b'o', 0],
+                &[0x96, 2, 0, 8, 0],
+                RETURN,
+            ]);
+
+            for _ in 0..2 {
+                let result = activation.run_actions(code.clone())?;
+                assert_eq!(result.value(), "hello".into());
+            }
+
+            let gc_context = activation.context.gc_context;
+            let swf_version = activation.swf_version();
+            let actions = activation
+                .context
+                .library
+                .library_for_movie_mut(code.movie.clone())
+                .avm1_actions(&code, swf_version, gc_context);
+            match &actions.ops()[0] {
+                Op::ConstantPool { pool } => {
+                    assert!(GcCell::ptr_eq(*pool, activation.context.avm1.constant_pool))
+                }
+                op => panic!("Expected a constant pool, got {:?}", op),
+            }
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn jumps_out_of_with_blocks_end_the_block() {
+        with_avm(10, |activation, _root| -> Result<(), Error> {
+            // Push 5; With { Push 1; Jump +100; Push 99 }; Push 2; Add2; Return
+            let block = [push(1), jump(100), push(99)].concat();
+            let code = movie_with_actions(&[
+                &push(5),
+                &[0x94, 2, 0, block.len() as u8, 0],
+                &block,
+                &push(2),
+                ADD2,
+                RETURN,
+            ]);
+
+            let result = activation.run_actions(code)?;
+            assert_eq!(result.value(), Value::Number(3.0));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn jumps_into_with_blocks_run_their_actions_in_place() {
+        with_avm(10, |activation, _root| -> Result<(), Error> {
+            // Push 1; Jump +5; With { Push 2; Add2; Return }
+            let block = [push(2), ADD2.to_vec(), RETURN.to_vec()].concat();
+            let code = movie_with_actions(&[
+                &push(1),
+                &jump(5),
+                &[0x94, 2, 0, block.len() as u8, 0],
+                &block,
+            ]);
+
+            let result = activation.run_actions(code)?;
+            assert_eq!(result.value(), Value::Number(3.0));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn jumps_out_of_try_blocks_end_the_block() {
+        with_avm(10, |activation, _root| -> Result<(), Error> {
+            // try { Push 1; Jump +100; Push 99 } catch (e) { Return e }
+            // Push 2; Add2; Return
+            let try_block = [push(1), jump(100), push(99)].concat();
+            let catch_block = [0x96, 3, 0, 0, b'e', 0, 0x1c, 0x3e];
+            let code = movie_with_actions(&[
+                &[0x8f, 9, 0, 1, try_block.len() as u8, 0],
+                &[catch_block.len() as u8, 0, 0, 0, b'e', 0],
+                &try_block,
+                &catch_block,
+                &push(2),
+                ADD2,
+                RETURN,
+            ]);
+
+            let result = activation.run_actions(code)?;
+            assert_eq!(result.value(), Value::Number(3.0));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn values_thrown_in_try_blocks_are_caught() {
+        with_avm(10, |activation, _root| -> Result<(), Error> {
+            // try { Push 7; Throw } catch (e) { Return e }
+            let try_block = [push(7), vec![0x2a]].concat();
+            let catch_block = [0x96, 3, 0, 0, b'e', 0, 0x1c, 0x3e];
+            let code = movie_with_actions(&[
+                &[0x8f, 9, 0, 1, try_block.len() as u8, 0],
+                &[catch_block.len() as u8, 0, 0, 0, b'e', 0],
+                &try_block,
+                &catch_block,
+            ]);
+
+            let actions = DecodedActions::decode(&code, 10, activation.context.gc_context);
+            match &actions.ops()[0] {
+                Op::Try { block } => {
+                    assert_eq!(block.try_actions.as_ref(), &try_block[..]);
+                    assert!(
+                        matches!(block.catch, Some((CatchVar::Var(ref name), _)) if name == "e")
+                    );
+                    assert!(block.finally.is_none());
+                }
+                op => panic!("Expected a try block, got {:?}", op),
+            }
+
+            let result = activation.run_actions(code)?;
+            assert_eq!(result.value(), Value::Number(7.0));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn wait_for_frame_skips_by_action_count() {
+        with_avm(10, |activation, _root| -> Result<(), Error> {
+            // WaitForFrame 0, skip 1; Push 1; Push 2; Add2; Return
+            let code =
+                movie_with_actions(&[&[0x8a, 3, 0, 0, 0, 1], &push(1), &push(2), ADD2, RETURN]);
+
+            let actions = DecodedActions::decode(&code, 10, activation.context.gc_context);
+            assert!(matches!(
+                actions.ops()[0],
+                Op::WaitForFrame {
+                    frame: 0,
+                    skip_target: 2
+                }
+            ));
+
+            // The frame counts as loaded, so nothing is skipped.
+            let result = activation.run_actions(code)?;
+            assert_eq!(result.value(), Value::Number(3.0));
+            Ok(())
+        })
+    }
+}
@@ -1,5 +1,11 @@
 use crate::avm1::activation::Activation;
+use crate::avm1::scope::ScopeClass;
 use crate::avm1::{Object, ObjectPtr, TObject, Value};
+use crate::debugger::DebugFrame;
+use crate::display_object::TDisplayObject;
+
+/// The number of global registers, used by code outside of `DefineFunction2` functions.
+const GLOBAL_REGISTER_COUNT: u8 = 4;
 
 #[allow(dead_code)]
 pub struct VariableDumper<'a> {
@@ -186,6 +192,73 @@ impl<'a> VariableDumper<'a> {
     }
 }
 
+/// Describes a value for the debugger, without listing the properties of objects.
+fn describe_value<'gc>(value: &Value<'gc>, activation: &mut Activation<'_, 'gc, '_>) -> String {
+    match value {
+        Value::Object(object) => describe_object(*object),
+        value => VariableDumper::dump(value, "", activation),
+    }
+}
+
+fn describe_object(object: Object<'_>) -> String {
+    if let Some(display_object) = object.as_display_object() {
+        format!("[object {}]", display_object.path())
+    } else {
+        "[object]".to_string()
+    }
+}
+
+impl<'gc> DebugFrame for Activation<'_, 'gc, '_> {
+    fn locals(&mut self) -> Vec<(String, String)> {
+        let locals = *self.scope().locals();
+        let mut variables = Vec::new();
+        for key in locals.get_keys(self) {
+            let value = locals.get(&key, self).unwrap_or(Value::Undefined);
+            variables.push((key.to_string(), describe_value(&value, self)));
+        }
+        variables
+    }
+
+    fn registers(&mut self) -> Vec<(String, String)> {
+        let count = match self.local_register_count() {
+            0 => GLOBAL_REGISTER_COUNT,
+            count => count,
+        };
+        let mut registers = Vec::with_capacity(count.into());
+        for id in 0..count {
+            let value = self.current_register(id);
+            registers.push((format!("r{}", id), describe_value(&value, self)));
+        }
+        registers
+    }
+
+    fn scope_chain(&mut self) -> Vec<String> {
+        let mut chain = Vec::new();
+        let mut scope = Some(self.scope_cell());
+        while let Some(cell) = scope {
+            let scope_ref = cell.read();
+            let class = match scope_ref.class() {
+                ScopeClass::Global => "global",
+                ScopeClass::Target => "target",
+                ScopeClass::Local => "local",
+                ScopeClass::With => "with",
+            };
+            chain.push(format!(
+                "{} {}",
+                class,
+                describe_object(*scope_ref.locals())
+            ));
+            scope = scope_ref.parent_cell();
+        }
+        chain
+    }
+
+    fn inspect(&mut self, path: &str) -> Result<String, String> {
+        let value: Value<'gc> = self.get_variable(path).map_err(|e| e.to_string())?.into();
+        Ok(VariableDumper::dump(&value, "  ", self))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
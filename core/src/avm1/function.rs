@@ -1,6 +1,7 @@
 //! Code relating to executable functions + calling conventions.
 
 use crate::avm1::activation::Activation;
+use crate::avm1::bytecode::FunctionDefinition;
 use crate::avm1::error::Error;
 use crate::avm1::object::super_object::SuperObject;
 use crate::avm1::property::Attribute;
@@ -8,11 +9,12 @@ use crate::avm1::scope::Scope;
 use crate::avm1::value::Value;
 use crate::avm1::{ArrayObject, Object, ObjectPtr, ScriptObject, TObject};
 use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::profiler::ProfileCategory;
 use crate::tag_utils::SwfSlice;
 use gc_arena::{Collect, CollectionContext, Gc, GcCell, MutationContext};
 use std::borrow::Cow;
 use std::fmt;
-use swf::{avm1::types::FunctionFlags, SwfStr};
+use swf::avm1::types::FunctionFlags;
 
 /// Represents a function defined in Ruffle's code.
 ///
@@ -82,87 +84,30 @@ pub struct Avm1Function<'gc> {
 }
 
 impl<'gc> Avm1Function<'gc> {
-    /// Construct a function from a DefineFunction action.
-    ///
-    /// Parameters not specified in DefineFunction are filled with reasonable
-    /// defaults.
-    pub fn from_df1(
+    /// Construct a function from a DefineFunction or DefineFunction2 action.
+    pub fn from_definition(
         swf_version: u8,
-        actions: SwfSlice,
-        name: &str,
-        params: &[&'_ SwfStr],
+        definition: &FunctionDefinition,
         scope: GcCell<'gc, Scope<'gc>>,
         constant_pool: GcCell<'gc, Vec<Value<'gc>>>,
         base_clip: DisplayObject<'gc>,
     ) -> Self {
-        let name = if name.is_empty() {
+        let name = if definition.name.is_empty() {
             None
         } else {
-            Some(name.to_string())
+            Some(definition.name.clone())
         };
 
         Avm1Function {
             swf_version,
-            data: actions,
-            name,
-            register_count: 0,
-            params: params
-                .iter()
-                .map(|&s| {
-                    (
-                        None,
-                        s.to_string_lossy(SwfStr::encoding_for_version(swf_version)),
-                    )
-                })
-                .collect(),
-            scope,
-            constant_pool,
-            base_clip,
-            flags: FunctionFlags::empty(),
-        }
-    }
-
-    /// Construct a function from a DefineFunction2 action.
-    pub fn from_df2(
-        swf_version: u8,
-        actions: SwfSlice,
-        swf_function: &swf::avm1::types::Function,
-        scope: GcCell<'gc, Scope<'gc>>,
-        constant_pool: GcCell<'gc, Vec<Value<'gc>>>,
-        base_clip: DisplayObject<'gc>,
-    ) -> Self {
-        let name = if swf_function.name.is_empty() {
-            None
-        } else {
-            Some(
-                swf_function
-                    .name
-                    .to_string_lossy(SwfStr::encoding_for_version(swf_version)),
-            )
-        };
-
-        let params = swf_function
-            .params
-            .iter()
-            .map(|p| {
-                (
-                    p.register_index,
-                    p.name
-                        .to_string_lossy(SwfStr::encoding_for_version(swf_version)),
-                )
-            })
-            .collect();
-
-        Avm1Function {
-            swf_version,
-            data: actions,
+            data: definition.actions.clone(),
             name,
-            register_count: swf_function.register_count,
-            params,
+            register_count: definition.register_count,
+            params: definition.params.clone(),
             scope,
             constant_pool,
             base_clip,
-            flags: swf_function.flags,
+            flags: definition.flags,
         }
     }
 
@@ -280,6 +225,7 @@ impl<'gc> Executable<'gc> {
                         .unwrap_or(activation.context.player_version)
                 };
 
+                let function_name = af.name.as_deref().unwrap_or(name);
                 let name = if cfg!(feature = "avm_debug") {
                     let mut result = match &af.name {
                         None => name.to_string(),
@@ -386,7 +332,13 @@ impl<'gc> Executable<'gc> {
                     }
                 }
 
-                Ok(frame.run_actions(af.data.clone())?.value())
+                let span = frame.context.profiler.start();
+                let result = frame.run_actions(af.data.clone());
+                frame
+                    .context
+                    .profiler
+                    .finish(span, ProfileCategory::Avm1Function, || function_name);
+                Ok(result?.value())
             }
         }
     }
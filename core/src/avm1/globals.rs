@@ -49,6 +49,7 @@ pub(crate) mod shared_object;
 mod sound;
 mod stage;
 pub(crate) mod string;
+mod style_sheet;
 pub(crate) mod system;
 pub(crate) mod system_capabilities;
 pub(crate) mod system_ime;
@@ -563,6 +564,7 @@ pub fn create_globals<'gc>(
 
     let text_field_proto = text_field::create_proto(gc_context, object_proto, function_proto);
     let text_format_proto = text_format::create_proto(gc_context, object_proto, function_proto);
+    let style_sheet_proto = style_sheet::create_proto(gc_context, object_proto, function_proto);
 
     let array_proto = array::create_proto(gc_context, object_proto, function_proto);
 
@@ -680,6 +682,19 @@ pub fn create_globals<'gc>(
         Some(function_proto),
         text_format_proto,
     );
+    let style_sheet = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(style_sheet::constructor),
+        constructor_to_fn!(style_sheet::constructor),
+        Some(function_proto),
+        style_sheet_proto,
+    );
+    text_field.define_value(
+        gc_context,
+        "StyleSheet",
+        style_sheet.into(),
+        Attribute::DONT_ENUM,
+    );
     let array = array::create_array_object(gc_context, array_proto, function_proto);
     let xmlnode = FunctionObject::constructor(
         gc_context,
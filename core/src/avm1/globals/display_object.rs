@@ -28,6 +28,8 @@ const OBJECT_DECLS: &[Declaration] = declare_properties! {
     "_global" => property(get_global, overwrite_global; DONT_ENUM | DONT_DELETE | READ_ONLY);
     "_root" => property(get_root, overwrite_root; DONT_ENUM | DONT_DELETE | READ_ONLY);
     "_parent" => property(get_parent, overwrite_parent; DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "tabEnabled" => property(get_tab_enabled, set_tab_enabled; DONT_ENUM | DONT_DELETE);
+    "tabIndex" => property(get_tab_index, set_tab_index; DONT_ENUM | DONT_DELETE);
 };
 
 /// Add common display object prototype methods to the given prototype.
@@ -68,6 +70,58 @@ pub fn get_parent<'gc>(
         .unwrap_or(Value::Undefined))
 }
 
+pub fn get_tab_enabled<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_display_object()
+        .and_then(|dobj| dobj.tab_enabled_override())
+        .map_or(Value::Undefined, Value::from))
+}
+
+pub fn set_tab_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(display_object) = this.as_display_object() {
+        let value = match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            value => Some(value.as_bool(activation.swf_version())),
+        };
+        display_object.set_tab_enabled(activation.context.gc_context, value);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_tab_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_display_object()
+        .and_then(|dobj| dobj.tab_index())
+        .map_or(Value::Undefined, Value::from))
+}
+
+pub fn set_tab_index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(display_object) = this.as_display_object() {
+        let value = match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            value => Some(value.coerce_to_i32(activation)?),
+        };
+        display_object.set_tab_index(activation.context.gc_context, value);
+    }
+    Ok(Value::Undefined)
+}
+
 pub fn get_depth<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
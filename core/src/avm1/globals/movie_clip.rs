@@ -107,6 +107,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "enabled" => property(mc_getter!(enabled), mc_setter!(set_enabled); DONT_DELETE | DONT_ENUM);
     "focusEnabled" => property(mc_getter!(focus_enabled), mc_setter!(set_focus_enabled); DONT_DELETE | DONT_ENUM);
     "_lockroot" => property(mc_getter!(lock_root), mc_setter!(set_lock_root); DONT_DELETE | DONT_ENUM);
+    "tabChildren" => property(mc_getter!(tab_children), mc_setter!(set_tab_children); DONT_DELETE | DONT_ENUM);
     "useHandCursor" => property(mc_getter!(use_hand_cursor), mc_setter!(set_use_hand_cursor); DONT_DELETE | DONT_ENUM);
 };
 
@@ -860,10 +861,12 @@ fn get_bytes_loaded<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    // For a loaded SWF, returns how much of the uncompressed SWF has been downloaded so far.
+    // Otherwise, returns the size of the tag list in the clip's DefineSprite tag.
     let bytes_loaded = if movie_clip.is_root() {
         movie_clip
             .movie()
-            .map(|mv| mv.uncompressed_len())
+            .map(|mv| mv.uncompressed_loaded())
             .unwrap_or_default()
     } else {
         movie_clip.tag_stream_len() as u32
@@ -1335,7 +1338,7 @@ fn load_movie<'gc>(
     let method = args.get(1).cloned().unwrap_or(Value::Undefined);
     let method = NavigationMethod::from_method_str(&method.coerce_to_string(activation)?);
     let (url, opts) = activation.locals_into_request_options(Cow::Borrowed(&url), method);
-    let fetch = activation.context.navigator.fetch(&url, opts);
+    let fetch = activation.context.navigator.fetch_body(&url, opts);
     let process = activation.context.load_manager.load_movie_into_clip(
         activation.context.player.clone().unwrap(),
         DisplayObject::MovieClip(target),
@@ -1456,6 +1459,23 @@ fn set_lock_root<'gc>(
     Ok(())
 }
 
+fn tab_children<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.tab_children().into())
+}
+
+fn set_tab_children<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let tab_children = value.as_bool(activation.swf_version());
+    this.set_tab_children(activation.context.gc_context, tab_children);
+    Ok(())
+}
+
 fn use_hand_cursor<'gc>(
     this: MovieClip<'gc>,
     _activation: &mut Activation<'_, 'gc, '_>,
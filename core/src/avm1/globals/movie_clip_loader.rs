@@ -54,7 +54,7 @@ pub fn load_clip<'gc>(
             let fetch = activation
                 .context
                 .navigator
-                .fetch(&url, RequestOptions::get());
+                .fetch_body(&url, RequestOptions::get());
             let process = activation.context.load_manager.load_movie_into_clip(
                 activation.context.player.clone().unwrap(),
                 DisplayObject::MovieClip(mc),
@@ -112,7 +112,7 @@ pub fn get_progress<'gc>(
                 activation.context.gc_context,
                 "bytesLoaded",
                 mc.movie()
-                    .map(|mv| (mv.uncompressed_len()).into())
+                    .map(|mv| (mv.uncompressed_loaded()).into())
                     .unwrap_or(Value::Undefined),
                 Attribute::empty(),
             );
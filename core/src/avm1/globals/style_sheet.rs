@@ -0,0 +1,181 @@
+//! `TextField.StyleSheet` impl
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::object::style_sheet_object::StyleSheetObject;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{ArrayObject, AvmString, Object, ScriptObject, TObject, Value};
+use crate::html::{apply_css_style, CssStyle, TextFormat};
+use gc_arena::MutationContext;
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "getStyle" => method(get_style; DONT_ENUM | DONT_DELETE);
+    "setStyle" => method(set_style; DONT_ENUM | DONT_DELETE);
+    "getStyleNames" => method(get_style_names; DONT_ENUM | DONT_DELETE);
+    "clear" => method(clear; DONT_ENUM | DONT_DELETE);
+    "parseCSS" => method(parse_css; DONT_ENUM | DONT_DELETE);
+    "transform" => method(transform; DONT_ENUM | DONT_DELETE);
+};
+
+/// Implements `TextField.StyleSheet`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.into())
+}
+
+/// Reads the properties of a style object as CSS declarations.
+fn style_from_object<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<CssStyle, Error<'gc>> {
+    let mut style = CssStyle::new();
+    for name in object.get_keys(activation) {
+        let value = object.get(&name, activation)?;
+        if !matches!(value, Value::Undefined | Value::Null) {
+            let value = value.coerce_to_string(activation)?.to_string();
+            style.insert(name, value);
+        }
+    }
+    Ok(style)
+}
+
+fn get_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.as_style_sheet_object() {
+        let name = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_string(activation)?;
+        let style = style_sheet.style_sheet().style(&name).cloned();
+
+        // A copy is returned, so changing it doesn't affect the stylesheet.
+        if let Some(style) = style {
+            let object = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes().object),
+            );
+            for (name, value) in style {
+                let value = AvmString::new(activation.context.gc_context, value);
+                object.set(&name, value.into(), activation)?;
+            }
+            return Ok(object.into());
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn set_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.as_style_sheet_object() {
+        let name = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        // Setting a style to `null` removes it.
+        let style = match args.get(1) {
+            Some(Value::Object(object)) => Some(style_from_object(*object, activation)?),
+            _ => None,
+        };
+
+        style_sheet
+            .style_sheet_mut(activation.context.gc_context)
+            .set_style(&name, style);
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_style_names<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.as_style_sheet_object() {
+        let names: Vec<Value<'gc>> = style_sheet
+            .style_sheet()
+            .style_names()
+            .map(|name| AvmString::new(activation.context.gc_context, name.to_string()).into())
+            .collect();
+
+        return Ok(ArrayObject::new(
+            activation.context.gc_context,
+            activation.context.avm1.prototypes().array,
+            names,
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.as_style_sheet_object() {
+        style_sheet
+            .style_sheet_mut(activation.context.gc_context)
+            .clear();
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn parse_css<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.as_style_sheet_object() {
+        let css = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        return Ok(style_sheet
+            .style_sheet_mut(activation.context.gc_context)
+            .parse_css(&css)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(Value::Object(object)) = args.get(0) {
+        let style = style_from_object(*object, activation)?;
+        let tf = apply_css_style(&style, TextFormat::default());
+        return Ok(tf.as_avm1_object(activation)?.into());
+    }
+
+    Ok(Value::Null)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let style_sheet = StyleSheetObject::empty(gc_context, Some(proto));
+    let object = style_sheet.as_script_object().unwrap();
+    define_properties_on(PROTO_DECLS, gc_context, object, fn_proto);
+    style_sheet.into()
+}
@@ -284,6 +284,8 @@ pub struct SystemProperties {
     pub cpu_architecture: CpuArchitecture,
     /// The highest supported h264 decoder level
     pub idc_level: String,
+    /// Whether text can be entered with an input method editor (IME)
+    pub ime_enabled: bool,
 }
 
 impl SystemProperties {
@@ -408,6 +410,7 @@ impl Default for SystemProperties {
             sandbox_type: SandboxType::LocalTrusted,
             cpu_architecture: CpuArchitecture::X86,
             idc_level: "5.1".into(),
+            ime_enabled: true,
         }
     }
 }
@@ -4,6 +4,7 @@ use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
 use crate::avm1::object::Object;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{ScriptObject, Value};
+use crate::display_object::TDisplayObject;
 use gc_arena::MutationContext;
 use std::convert::Into;
 
@@ -49,18 +50,34 @@ fn get_conversion_mode<'gc>(
 }
 
 fn get_enabled<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(false.into())
+    Ok(activation.context.system.ime_enabled.into())
 }
 
 fn set_composition_string<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    let composition = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    let text = activation
+        .context
+        .focus_tracker
+        .get()
+        .and_then(|focus| focus.as_edit_text());
+    if let Some(text) = text {
+        return Ok(text
+            .set_ime_composition(&composition, &mut activation.context)
+            .into());
+    }
+
     Ok(false.into())
 }
 
@@ -73,11 +90,16 @@ fn set_conversion_mode<'gc>(
 }
 
 fn set_enabled<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(false.into())
+    let enabled = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .as_bool(activation.swf_version());
+    activation.context.system.ime_enabled = enabled;
+    Ok(true.into())
 }
 
 pub fn create<'gc>(
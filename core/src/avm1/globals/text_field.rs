@@ -7,7 +7,9 @@ use crate::avm_error;
 use crate::display_object::{AutoSizeMode, EditText, TDisplayObject, TextSelection};
 use crate::font::round_down_to_pixel;
 use crate::html::TextFormat;
+use crate::vminterface::AvmObject;
 use gc_arena::MutationContext;
+use swf::Twips;
 
 macro_rules! tf_method {
     ($fn:expr) => {
@@ -57,6 +59,15 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "replaceSel" => method(tf_method!(replace_sel); DONT_ENUM | DONT_DELETE | READ_ONLY);
     "replaceText" => method(tf_method!(replace_text); DONT_ENUM | DONT_DELETE | READ_ONLY);
     "removeTextField" => method(tf_method!(remove_text_field); DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "getCharBoundaries" => method(tf_method!(get_char_boundaries); DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "getCharIndexAtPoint" => method(tf_method!(get_char_index_at_point); DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "getFirstCharInParagraph" => method(tf_method!(get_first_char_in_paragraph); DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "getLineIndexAtPoint" => method(tf_method!(get_line_index_at_point); DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "getLineIndexOfChar" => method(tf_method!(get_line_index_of_char); DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "getLineLength" => method(tf_method!(get_line_length); DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "getLineMetrics" => method(tf_method!(get_line_metrics); DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "getLineOffset" => method(tf_method!(get_line_offset); DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "getLineText" => method(tf_method!(get_line_text); DONT_ENUM | DONT_DELETE | READ_ONLY);
     "autoSize" => property(tf_getter!(auto_size), tf_setter!(set_auto_size); DONT_DELETE);
     "background" => property(tf_getter!(background), tf_setter!(set_background); DONT_DELETE);
     "backgroundColor" => property(tf_getter!(background_color), tf_setter!(set_background_color); DONT_DELETE);
@@ -68,12 +79,16 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "html" => property(tf_getter!(html), tf_setter!(set_html); DONT_DELETE);
     "htmlText" => property(tf_getter!(html_text), tf_setter!(set_html_text); DONT_DELETE);
     "length" => property(tf_getter!(length); DONT_DELETE | READ_ONLY);
+    "maxChars" => property(tf_getter!(max_chars), tf_setter!(set_max_chars); DONT_DELETE);
     "maxhscroll" => property(tf_getter!(maxhscroll); DONT_DELETE | READ_ONLY);
     "maxscroll" => property(tf_getter!(maxscroll); DONT_DELETE | READ_ONLY);
     "multiline" => property(tf_getter!(multiline), tf_setter!(set_multiline); DONT_DELETE);
+    "numLines" => property(tf_getter!(num_lines); DONT_DELETE | READ_ONLY);
     "password" => property(tf_getter!(password), tf_setter!(set_password); DONT_DELETE);
+    "restrict" => property(tf_getter!(restrict), tf_setter!(set_restrict); DONT_DELETE);
     "scroll" => property(tf_getter!(scroll), tf_setter!(set_scroll); DONT_DELETE);
     "selectable" => property(tf_getter!(selectable), tf_setter!(set_selectable); DONT_DELETE);
+    "styleSheet" => property(tf_getter!(style_sheet), tf_setter!(set_style_sheet); DONT_DELETE);
     "text" => property(tf_getter!(text), tf_setter!(set_text); DONT_DELETE);
     "textColor" => property(tf_getter!(text_color), tf_setter!(set_text_color); DONT_DELETE);
     "textHeight" => property(tf_getter!(text_height); DONT_DELETE);
@@ -213,6 +228,7 @@ fn replace_sel<'gc>(
     let selection = text_field
         .selection()
         .unwrap_or_else(|| TextSelection::for_position(0));
+    let text = text_field.filter_input(&text, selection);
     text_field.replace_text(
         selection.start(),
         selection.end(),
@@ -265,6 +281,181 @@ pub fn remove_text_field<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Coerce an argument to a character or line index.
+///
+/// Negative indices are never valid, and are returned as `None`.
+fn index_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    i: usize,
+) -> Result<Option<usize>, Error<'gc>> {
+    let index = args
+        .get(i)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    Ok(if index >= 0 {
+        Some(index as usize)
+    } else {
+        None
+    })
+}
+
+/// Coerce the first two arguments to a point, in twips.
+fn point_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<(Twips, Twips), Error<'gc>> {
+    let x = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_f64(activation)?;
+    let y = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_f64(activation)?;
+
+    Ok((Twips::from_pixels(x), Twips::from_pixels(y)))
+}
+
+fn get_char_boundaries<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let bounds = match index_arg(activation, args, 0)?.and_then(|i| text_field.char_boundaries(i)) {
+        Some(bounds) => bounds,
+        None => return Ok(Value::Null),
+    };
+
+    let constructor = activation.context.avm1.prototypes.rectangle_constructor;
+    let result = constructor.construct(
+        activation,
+        &[
+            bounds.offset_x().to_pixels().into(),
+            bounds.offset_y().to_pixels().into(),
+            bounds.width().to_pixels().into(),
+            bounds.height().to_pixels().into(),
+        ],
+    )?;
+    Ok(result)
+}
+
+fn get_char_index_at_point<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let point = point_args(activation, args)?;
+    Ok(text_field
+        .char_index_at_point(point)
+        .map_or(-1.0, |index| index as f64)
+        .into())
+}
+
+fn get_first_char_in_paragraph<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(index_arg(activation, args, 0)?
+        .and_then(|index| text_field.first_char_in_paragraph(index))
+        .map_or(-1.0, |index| index as f64)
+        .into())
+}
+
+fn get_line_index_at_point<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let point = point_args(activation, args)?;
+    Ok(text_field
+        .line_index_at_point(point)
+        .map_or(-1.0, |line| line as f64)
+        .into())
+}
+
+fn get_line_index_of_char<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(index_arg(activation, args, 0)?
+        .and_then(|index| text_field.line_index_of_char(index))
+        .map_or(-1.0, |line| line as f64)
+        .into())
+}
+
+fn get_line_length<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(index_arg(activation, args, 0)?
+        .and_then(|line| text_field.line_length(line))
+        .map_or(-1.0, |length| length as f64)
+        .into())
+}
+
+fn get_line_metrics<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let metrics =
+        match index_arg(activation, args, 0)?.and_then(|line| text_field.line_metrics(line)) {
+            Some(metrics) => metrics,
+            None => return Ok(Value::Undefined),
+        };
+
+    let object = ScriptObject::object(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes.object),
+    );
+    object.set("x", metrics.x.to_pixels().into(), activation)?;
+    object.set("width", metrics.width.to_pixels().into(), activation)?;
+    object.set("height", metrics.height.to_pixels().into(), activation)?;
+    object.set("ascent", metrics.ascent.to_pixels().into(), activation)?;
+    object.set("descent", metrics.descent.to_pixels().into(), activation)?;
+    object.set("leading", metrics.leading.to_pixels().into(), activation)?;
+    Ok(object.into())
+}
+
+fn get_line_offset<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(index_arg(activation, args, 0)?
+        .and_then(|line| text_field.line_offset(line))
+        .map_or(-1.0, |offset| offset as f64)
+        .into())
+}
+
+fn get_line_text<'gc>(
+    text_field: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(
+        match index_arg(activation, args, 0)?.and_then(|line| text_field.line_text(line)) {
+            Some(text) => AvmString::new(activation.context.gc_context, text).into(),
+            None => Value::Undefined,
+        },
+    )
+}
+
+pub fn num_lines<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.num_lines().into())
+}
+
 pub fn text<'gc>(
     this: EditText<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
@@ -519,6 +710,81 @@ fn set_variable<'gc>(
     Ok(())
 }
 
+fn style_sheet<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(AvmObject::Avm1(style_sheet)) = this.style_sheet() {
+        return Ok(style_sheet.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn set_style_sheet<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let style_sheet = match value {
+        Value::Object(object) if object.as_style_sheet_object().is_some() => {
+            Some(AvmObject::Avm1(object))
+        }
+        _ => None,
+    };
+    this.set_style_sheet(style_sheet, &mut activation.context);
+    Ok(())
+}
+
+fn restrict<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(restrict) = this.restrict() {
+        return Ok(AvmString::new(activation.context.gc_context, restrict).into());
+    }
+
+    // Unset `restrict` returns null, not undefined
+    Ok(Value::Null)
+}
+
+fn set_restrict<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let restrict = match value {
+        Value::Undefined | Value::Null => None,
+        v => Some(v.coerce_to_string(activation)?),
+    };
+    this.set_restrict(restrict.as_deref(), activation.context.gc_context);
+    Ok(())
+}
+
+fn max_chars<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    match this.max_chars() {
+        // An unlimited `maxChars` returns null, not 0
+        0 => Ok(Value::Null),
+        max_chars => Ok(max_chars.into()),
+    }
+}
+
+fn set_max_chars<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let max_chars = match value {
+        Value::Undefined | Value::Null => 0,
+        v => v.coerce_to_i32(activation)?,
+    };
+    this.set_max_chars(max_chars, activation.context.gc_context);
+    Ok(())
+}
+
 pub fn word_wrap<'gc>(
     this: EditText<'gc>,
     _activation: &mut Activation<'_, 'gc, '_>,
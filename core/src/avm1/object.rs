@@ -21,6 +21,7 @@ use crate::avm1::object::drop_shadow_filter::DropShadowFilterObject;
 use crate::avm1::object::glow_filter::GlowFilterObject;
 use crate::avm1::object::gradient_bevel_filter::GradientBevelFilterObject;
 use crate::avm1::object::gradient_glow_filter::GradientGlowFilterObject;
+use crate::avm1::object::style_sheet_object::StyleSheetObject;
 use crate::avm1::object::transform_object::TransformObject;
 use crate::avm1::object::xml_attributes_object::XmlAttributesObject;
 use crate::avm1::object::xml_idmap_object::XmlIdMapObject;
@@ -52,6 +53,7 @@ pub mod script_object;
 pub mod shared_object;
 pub mod sound_object;
 pub mod stage_object;
+pub mod style_sheet_object;
 pub mod super_object;
 pub mod transform_object;
 pub mod value_object;
@@ -89,6 +91,7 @@ pub mod xml_object;
         GradientBevelFilterObject(GradientBevelFilterObject<'gc>),
         GradientGlowFilterObject(GradientGlowFilterObject<'gc>),
         DateObject(DateObject<'gc>),
+        StyleSheetObject(StyleSheetObject<'gc>),
         BitmapData(BitmapDataObject<'gc>),
     }
 )]
@@ -468,6 +471,11 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         None
     }
 
+    /// Get the underlying `StyleSheetObject`, if it exists
+    fn as_style_sheet_object(&self) -> Option<StyleSheetObject<'gc>> {
+        None
+    }
+
     /// Get the underlying `ColorTransformObject`, if it exists
     fn as_color_transform_object(&self) -> Option<ColorTransformObject<'gc>> {
         None
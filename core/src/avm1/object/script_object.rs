@@ -557,11 +557,13 @@ mod tests {
     use crate::backend::ui::NullUiBackend;
     use crate::backend::video::NullVideoBackend;
     use crate::context::UpdateContext;
+    use crate::debugger::Debugger;
     use crate::display_object::{MovieClip, Stage};
     use crate::focus_tracker::FocusTracker;
     use crate::library::Library;
     use crate::loader::LoadManager;
     use crate::prelude::*;
+    use crate::profiler::Profiler;
     use crate::tag_utils::{SwfMovie, SwfSlice};
     use crate::vminterface::Instantiator;
     use gc_arena::rootless_arena;
@@ -585,6 +587,8 @@ mod tests {
 
             let stage = Stage::empty(gc_context, 550, 400);
             let mut frame_rate = 12.0;
+            let mut debugger = Debugger::new();
+            let mut profiler = Profiler::new();
 
             let object = ScriptObject::object(gc_context, Some(avm1.prototypes().object)).into();
             let globals = avm1.global_object_cell();
@@ -607,6 +611,7 @@ mod tests {
                 video: &mut NullVideoBackend::new(),
                 mouse_over_object: None,
                 mouse_down_object: None,
+                last_click: None,
                 mouse_position: &(Twips::ZERO, Twips::ZERO),
                 drag_object: &mut None,
                 player: None,
@@ -628,6 +633,8 @@ mod tests {
                 times_get_time_called: 0,
                 time_offset: &mut 0,
                 frame_rate: &mut frame_rate,
+                debugger: &mut debugger,
+                profiler: &mut profiler,
             };
             context.stage.replace_at_depth(&mut context, root, 0);
 
@@ -182,8 +182,14 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
             .display_object
             .as_container()
             .and_then(|o| o.child_by_name(name, case_sensitive))
+            .or_else(|| {
+                obj.display_object
+                    .as_edit_text()
+                    .and_then(|o| o.image_by_id(name, case_sensitive))
+            })
         {
-            // 3) Child display objects with the given instance name
+            // 3) Child display objects with the given instance name, or the
+            //    images of a text field with the given `id`
             Some(Ok(child.object()))
         } else if let Some(property) = props.read().get_by_name(name) {
             // 4) Display object properties such as _x, _y
@@ -368,6 +374,15 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
             return true;
         }
 
+        if obj
+            .display_object
+            .as_edit_text()
+            .and_then(|o| o.image_by_id(name, case_sensitive))
+            .is_some()
+        {
+            return true;
+        }
+
         if Self::get_level_by_path(name, &mut activation.context, case_sensitive).is_some() {
             return true;
         }
@@ -836,18 +851,33 @@ fn set_high_quality<'gc>(
 
 fn focus_rect<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: DisplayObject<'gc>,
+    this: DisplayObject<'gc>,
 ) -> Value<'gc> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
-    Value::Null
+    // Root clips control the stage-wide focus rectangle setting.
+    if this.is_root() {
+        return activation.context.stage.stage_focus_rect().into();
+    }
+    this.focus_rect().map_or(Value::Null, Value::from)
 }
 
 fn set_focus_rect<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: DisplayObject<'gc>,
-    _val: Value<'gc>,
+    this: DisplayObject<'gc>,
+    val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
+    if this.is_root() {
+        let value = val.as_bool(activation.swf_version());
+        activation
+            .context
+            .stage
+            .set_stage_focus_rect(activation.context.gc_context, value);
+    } else {
+        let value = match val {
+            Value::Undefined | Value::Null => None,
+            _ => Some(val.as_bool(activation.swf_version())),
+        };
+        this.set_focus_rect(activation.context.gc_context, value);
+    }
     Ok(())
 }
 
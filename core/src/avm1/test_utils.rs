@@ -12,11 +12,13 @@ use crate::backend::storage::MemoryStorageBackend;
 use crate::backend::ui::NullUiBackend;
 use crate::backend::video::NullVideoBackend;
 use crate::context::ActionQueue;
+use crate::debugger::Debugger;
 use crate::display_object::{MovieClip, Stage, TDisplayObject};
 use crate::focus_tracker::FocusTracker;
 use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::prelude::*;
+use crate::profiler::Profiler;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::vminterface::Instantiator;
 use gc_arena::{rootless_arena, MutationContext};
@@ -42,6 +44,8 @@ where
         root.set_depth(gc_context, 0);
         let stage = Stage::empty(gc_context, 550, 400);
         let mut frame_rate = 12.0;
+        let mut debugger = Debugger::new();
+        let mut profiler = Profiler::new();
         let globals = avm1.global_object_cell();
 
         let mut context = UpdateContext {
@@ -61,6 +65,7 @@ where
             video: &mut NullVideoBackend::new(),
             mouse_over_object: None,
             mouse_down_object: None,
+            last_click: None,
             mouse_position: &(Twips::ZERO, Twips::ZERO),
             drag_object: &mut None,
             player: None,
@@ -83,6 +88,8 @@ where
             time_offset: &mut 0,
             audio_manager: &mut AudioManager::new(),
             frame_rate: &mut frame_rate,
+            debugger: &mut debugger,
+            profiler: &mut profiler,
         };
         context.stage.replace_at_depth(&mut context, root, 0);
 
@@ -4,8 +4,9 @@ use crate::avm2::globals::{SystemClasses, SystemPrototypes};
 use crate::avm2::method::Method;
 use crate::avm2::object::EventObject;
 use crate::avm2::script::{Script, TranslationUnit};
-use crate::avm2::string::AvmString;
 use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::events::KeyCode;
 use crate::tag_utils::SwfSlice;
 use gc_arena::{Collect, MutationContext};
 use std::collections::HashMap;
@@ -21,9 +22,13 @@ macro_rules! avm_debug {
     )
 }
 
+#[cfg(test)]
+mod test_utils;
+
 mod activation;
 mod array;
 mod bytearray;
+mod bytecode;
 mod class;
 mod domain;
 mod events;
@@ -50,6 +55,7 @@ pub use crate::avm2::domain::Domain;
 pub use crate::avm2::events::Event;
 pub use crate::avm2::names::{Namespace, QName};
 pub use crate::avm2::object::{ArrayObject, Object, ScriptObject, StageObject, TObject};
+pub use crate::avm2::string::AvmString;
 pub use crate::avm2::value::Value;
 
 const BROADCAST_WHITELIST: [&str; 3] = ["enterFrame", "exitFrame", "frameConstructed"];
@@ -169,6 +175,169 @@ impl<'gc> Avm2<'gc> {
         dispatch_event(&mut activation, target, event_object)
     }
 
+    /// Dispatch a `FocusEvent` on an object.
+    ///
+    /// `related_object` is the object gaining focus for `focusOut` and
+    /// `keyFocusChange` events, and the object losing focus for `focusIn`
+    /// events. The `bool` parameter reads false if the event was cancelled.
+    pub fn dispatch_focus_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &'static str,
+        cancelable: bool,
+        target: Object<'gc>,
+        related_object: Option<Object<'gc>>,
+    ) -> Result<bool, Error> {
+        use crate::avm2::events::dispatch_event;
+
+        let mut event = Event::new(event_type);
+        event.set_bubbles(true);
+        event.set_cancelable(cancelable);
+
+        let event_constr = context.avm2.classes().focusevent;
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        let mut event_object = EventObject::from_event(&mut activation, event_constr, event)?;
+        event_object.set_property(
+            event_object,
+            &QName::new(Namespace::public(), "relatedObject"),
+            related_object.map(Value::Object).unwrap_or(Value::Null),
+            &mut activation,
+        )?;
+
+        dispatch_event(&mut activation, target, event_object)
+    }
+
+    /// Dispatch a `MouseEvent` on a display object, at the current position
+    /// of the mouse.
+    ///
+    /// `related_object` is the object the mouse moved from or to for hover
+    /// events, and `delta` is the number of lines scrolled for `mouseWheel`
+    /// events. `rollOver` and `rollOut` events don't bubble. Display objects
+    /// without an AVM2 side are ignored.
+    ///
+    /// The `bool` parameter reads false if the event was cancelled.
+    pub fn dispatch_mouse_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &'static str,
+        target: DisplayObject<'gc>,
+        related_object: Option<DisplayObject<'gc>>,
+        button_down: bool,
+        delta: i32,
+    ) -> Result<bool, Error> {
+        use crate::avm2::events::dispatch_event;
+
+        let target_object = match target.object2() {
+            Value::Object(object) => object,
+            _ => return Ok(true),
+        };
+        let related_object = match related_object.map(|o| o.object2()) {
+            Some(object @ Value::Object(_)) => object,
+            _ => Value::Null,
+        };
+
+        let stage_position = *context.mouse_position;
+        let local_position = target.global_to_local(stage_position);
+        let ctrl_key = context.ui.is_key_down(KeyCode::Control);
+        let alt_key = context.ui.is_key_down(KeyCode::Alt);
+        let shift_key = context.ui.is_key_down(KeyCode::Shift);
+
+        let mut event = Event::new(event_type);
+        event.set_bubbles(!matches!(event_type, "rollOver" | "rollOut"));
+        event.set_cancelable(false);
+
+        let event_constr = context.avm2.classes().mouseevent;
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        let mut event_object = EventObject::from_event(&mut activation, event_constr, event)?;
+        let properties = [
+            ("localX", local_position.0.to_pixels().into()),
+            ("localY", local_position.1.to_pixels().into()),
+            ("stageX", stage_position.0.to_pixels().into()),
+            ("stageY", stage_position.1.to_pixels().into()),
+            ("relatedObject", related_object),
+            ("ctrlKey", ctrl_key.into()),
+            ("altKey", alt_key.into()),
+            ("shiftKey", shift_key.into()),
+            ("buttonDown", button_down.into()),
+            ("delta", delta.into()),
+        ];
+        for (name, value) in properties.iter() {
+            event_object.set_property(
+                event_object,
+                &QName::new(Namespace::public(), *name),
+                value.clone(),
+                &mut activation,
+            )?;
+        }
+
+        dispatch_event(&mut activation, target_object, event_object)
+    }
+
+    /// Dispatch an `IMEEvent` on an object.
+    ///
+    /// `text` is the text composed with the IME, if any.
+    /// The `bool` parameter reads false if the event was cancelled.
+    pub fn dispatch_ime_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &'static str,
+        target: Object<'gc>,
+        text: &str,
+    ) -> Result<bool, Error> {
+        use crate::avm2::events::dispatch_event;
+
+        let mut event = Event::new(event_type);
+        event.set_bubbles(true);
+        event.set_cancelable(false);
+
+        let event_constr = context.avm2.classes().imeevent;
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        let mut event_object = EventObject::from_event(&mut activation, event_constr, event)?;
+        let text = AvmString::new(activation.context.gc_context, text);
+        event_object.set_property(
+            event_object,
+            &QName::new(Namespace::public(), "text"),
+            text.into(),
+            &mut activation,
+        )?;
+
+        dispatch_event(&mut activation, target, event_object)
+    }
+
+    /// Dispatch a `ProgressEvent` on an object.
+    ///
+    /// The `bool` parameter reads false if the event was cancelled.
+    pub fn dispatch_progress_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &'static str,
+        target: Object<'gc>,
+        bytes_loaded: usize,
+        bytes_total: usize,
+    ) -> Result<bool, Error> {
+        use crate::avm2::events::dispatch_event;
+
+        let event = Event::new(event_type);
+
+        let event_constr = context.avm2.classes().progressevent;
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        let mut event_object = EventObject::from_event(&mut activation, event_constr, event)?;
+        event_object.set_property(
+            event_object,
+            &QName::new(Namespace::public(), "bytesLoaded"),
+            (bytes_loaded as f64).into(),
+            &mut activation,
+        )?;
+        event_object.set_property(
+            event_object,
+            &QName::new(Namespace::public(), "bytesTotal"),
+            (bytes_total as f64).into(),
+            &mut activation,
+        )?;
+
+        dispatch_event(&mut activation, target, event_object)
+    }
+
     /// Add an object to the broadcast list.
     ///
     /// Each broadcastable event contains it's own broadcast list. You must
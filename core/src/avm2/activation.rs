@@ -1,6 +1,7 @@
 //! Activation frames
 
 use crate::avm2::array::ArrayStorage;
+use crate::avm2::bytecode::{CachedTrait, MultinameOperand, Op, PropertyCache};
 use crate::avm2::class::Class;
 use crate::avm2::method::{BytecodeMethod, Method, ParamConfig};
 use crate::avm2::names::{Multiname, Namespace, QName};
@@ -12,19 +13,16 @@ use crate::avm2::scope::Scope;
 use crate::avm2::script::Script;
 use crate::avm2::string::AvmString;
 use crate::avm2::value::Value;
-use crate::avm2::{value, Avm2, Error};
+use crate::avm2::{Avm2, Error};
 use crate::context::UpdateContext;
-use crate::swf::extensions::ReadSwfExt;
+use crate::debugger::{DebugFrame, Location};
 use gc_arena::{Gc, GcCell, MutationContext};
+use instant::Instant;
 use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::cmp::{min, Ordering};
 use std::convert::{TryFrom, TryInto};
-use swf::avm2::read::Reader;
-use swf::avm2::types::{
-    Class as AbcClass, Index, Method as AbcMethod, Multiname as AbcMultiname,
-    Namespace as AbcNamespace, Op,
-};
+use swf::avm2::types::{Class as AbcClass, Index, Method as AbcMethod, Multiname as AbcMultiname};
 
 /// Represents a particular register set.
 ///
@@ -203,7 +201,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     ///
     /// This returns an error if a type is named but does not exist; or if the
     /// typed named is not a class object.
-    fn resolve_type(&mut self, type_name: Multiname<'gc>) -> Result<Option<Object<'gc>>, Error> {
+    fn resolve_type(&mut self, type_name: &Multiname<'gc>) -> Result<Option<Object<'gc>>, Error> {
         if type_name.is_any() {
             return Ok(None);
         }
@@ -212,7 +210,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             .scope()
             .ok_or("Cannot resolve parameter types without a scope stack")?
             .read()
-            .resolve(&type_name, self)?
+            .resolve(type_name, self)?
             .ok_or_else(|| format!("Could not resolve parameter type {:?}", type_name))?
             .coerce_to_object(self)?;
 
@@ -227,7 +225,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             let mut param_types = Vec::with_capacity(type_name.params().len());
 
             for param in type_name.params() {
-                param_types.push(match self.resolve_type(param.clone())? {
+                param_types.push(match self.resolve_type(param)? {
                     Some(o) => Value::Object(o),
                     None => Value::Null,
                 });
@@ -266,8 +264,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             .into());
         };
 
-        let type_name = param_config.param_type_name.clone();
-        let param_type = self.resolve_type(type_name)?;
+        let param_type = self.resolve_type(&param_config.param_type_name)?;
 
         if let Some(param_type) = param_type {
             arg.coerce_to_type(self, param_type)
@@ -584,37 +581,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         self.subclass_object
     }
 
-    /// Retrieve a int from the current constant pool.
-    fn pool_int(
-        &self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<i32>,
-    ) -> Result<i32, Error> {
-        value::abc_int(method.translation_unit(), index)
-    }
-
-    /// Retrieve a int from the current constant pool.
-    fn pool_uint(
-        &self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<u32>,
-    ) -> Result<u32, Error> {
-        value::abc_uint(method.translation_unit(), index)
-    }
-
-    /// Retrieve a double from the current constant pool.
-    fn pool_double(
-        &self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<f64>,
-    ) -> Result<f64, Error> {
-        value::abc_double(method.translation_unit(), index)
-    }
-
     /// Retrieve a string from the current constant pool.
-    fn pool_string<'b>(
+    #[cfg(avm_debug)]
+    fn pool_string(
         &self,
-        method: &'b BytecodeMethod<'gc>,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<String>,
     ) -> Result<AvmString<'gc>, Error> {
         method
@@ -622,15 +593,6 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             .pool_string(index.0, self.context.gc_context)
     }
 
-    /// Retrieve a namespace from the current constant pool.
-    fn pool_namespace(
-        &self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcNamespace>,
-    ) -> Result<Namespace<'gc>, Error> {
-        Namespace::from_abc_namespace(method.translation_unit(), index, self.context.gc_context)
-    }
-
     /// Retrieve a multiname from the current constant pool.
     fn pool_multiname(
         &mut self,
@@ -640,39 +602,20 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Multiname::from_abc_multiname(method.translation_unit(), index, self)
     }
 
-    /// Retrieve a static, or non-runtime, multiname from the current constant
-    /// pool.
-    ///
-    /// This version of the function treats index 0 as an error condition.
-    fn pool_multiname_static(
-        &mut self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
-    ) -> Result<Multiname<'gc>, Error> {
-        Multiname::from_abc_multiname_static(
-            method.translation_unit(),
-            index,
-            self.context.gc_context,
-        )
-    }
-
-    /// Retrieve a static, or non-runtime, multiname from the current constant
-    /// pool.
+    /// Retrieve the multiname an instruction operates on.
     ///
-    /// This version of the function treats index 0 as the any-type `*`.
-    fn pool_multiname_static_any(
+    /// Multinames that were not resolved when the method body was decoded
+    /// take the rest of their name from the stack.
+    fn resolve_multiname_operand<'b>(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
-    ) -> Result<Multiname<'gc>, Error> {
-        if index.0 == 0 {
-            Ok(Multiname::any())
-        } else {
-            Multiname::from_abc_multiname_static(
-                method.translation_unit(),
-                index,
-                self.context.gc_context,
-            )
+        name: &'b MultinameOperand<'gc>,
+    ) -> Result<Cow<'b, Multiname<'gc>>, Error> {
+        match name {
+            MultinameOperand::Static(multiname) => Ok(Cow::Borrowed(&**multiname)),
+            MultinameOperand::Runtime { index } => {
+                Ok(Cow::Owned(self.pool_multiname(method, index.clone())?))
+            }
         }
     }
 
@@ -699,28 +642,60 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
     ) -> Result<Value<'gc>, Error> {
-        let body: Result<_, Error> = method
-            .body()
+        let body_index: Result<_, Error> = method
+            .abc_method_body
             .ok_or_else(|| "Cannot execute non-native method without body".into());
-        let body = body?;
-        let mut reader = Reader::new(&body.code);
+        let body = method
+            .translation_unit()
+            .load_method_body(body_index?, self.context.gc_context)?;
+        let mut ip = 0;
 
-        loop {
-            let result = self.do_next_opcode(method, &mut reader, &body.code);
+        let name = match method.method_name() {
+            "" => "[Anonymous]",
+            name => name,
+        };
+        self.context.debugger.enter_frame(name);
+        let result = loop {
+            if self.context.debugger.is_attached() {
+                if let Some(offset) = body.offset(ip) {
+                    self.check_breakpoint(Location::Avm2 {
+                        method: method.abc_method,
+                        offset,
+                    });
+                }
+            }
+
+            let result = self.do_next_opcode(method, body.ops(), &mut ip);
             match result {
                 Ok(FrameControl::Return(value)) => break Ok(value),
                 Ok(FrameControl::Continue) => {}
                 Err(e) => break Err(e),
             }
+        };
+        self.context.debugger.exit_frame();
+
+        result
+    }
+
+    /// Stop in the debugger if it wants to break before the code at `location`.
+    fn check_breakpoint(&mut self, location: Location) {
+        if self.context.debugger.should_break(location) {
+            let mut debugger = std::mem::take(self.context.debugger);
+            debugger.pause(location, self);
+            *self.context.debugger = debugger;
+
+            // Time spent paused doesn't count towards the script timeout.
+            self.context.update_start = Instant::now();
         }
     }
 
-    /// Run a single action from a given action reader.
-    fn do_next_opcode<'b>(
+    /// Run the instruction at `ip` and advance `ip` to the next instruction
+    /// to run.
+    fn do_next_opcode(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
+        ops: &[Op<'gc>],
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error> {
         self.actions_since_timeout_check += 1;
         if self.actions_since_timeout_check >= 2000 {
@@ -733,201 +708,195 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             }
         }
 
-        let instruction_start = reader.pos(full_data);
-        let op = reader.read_op();
-        if let Ok(Some(op)) = op {
-            avm_debug!(self.avm2(), "Opcode: {:?}", op);
-
-            let result = match op {
-                Op::PushByte { value } => self.op_push_byte(value),
-                Op::PushDouble { value } => self.op_push_double(method, value),
-                Op::PushFalse => self.op_push_false(),
-                Op::PushInt { value } => self.op_push_int(method, value),
-                Op::PushNamespace { value } => self.op_push_namespace(method, value),
-                Op::PushNaN => self.op_push_nan(),
-                Op::PushNull => self.op_push_null(),
-                Op::PushShort { value } => self.op_push_short(value),
-                Op::PushString { value } => self.op_push_string(method, value),
-                Op::PushTrue => self.op_push_true(),
-                Op::PushUint { value } => self.op_push_uint(method, value),
-                Op::PushUndefined => self.op_push_undefined(),
-                Op::Pop => self.op_pop(),
-                Op::Dup => self.op_dup(),
-                Op::GetLocal { index } => self.op_get_local(index),
-                Op::SetLocal { index } => self.op_set_local(index),
-                Op::Kill { index } => self.op_kill(index),
-                Op::Call { num_args } => self.op_call(num_args),
-                Op::CallMethod { index, num_args } => self.op_call_method(index, num_args),
-                Op::CallProperty { index, num_args } => {
-                    self.op_call_property(method, index, num_args)
-                }
-                Op::CallPropLex { index, num_args } => {
-                    self.op_call_prop_lex(method, index, num_args)
-                }
-                Op::CallPropVoid { index, num_args } => {
-                    self.op_call_prop_void(method, index, num_args)
-                }
-                Op::CallStatic { index, num_args } => self.op_call_static(method, index, num_args),
-                Op::CallSuper { index, num_args } => self.op_call_super(method, index, num_args),
-                Op::CallSuperVoid { index, num_args } => {
-                    self.op_call_super_void(method, index, num_args)
-                }
-                Op::ReturnValue => self.op_return_value(),
-                Op::ReturnVoid => self.op_return_void(),
-                Op::GetProperty { index } => self.op_get_property(method, index),
-                Op::SetProperty { index } => self.op_set_property(method, index),
-                Op::InitProperty { index } => self.op_init_property(method, index),
-                Op::DeleteProperty { index } => self.op_delete_property(method, index),
-                Op::GetSuper { index } => self.op_get_super(method, index),
-                Op::SetSuper { index } => self.op_set_super(method, index),
-                Op::In => self.op_in(),
-                Op::PushScope => self.op_push_scope(),
-                Op::PushWith => self.op_push_with(),
-                Op::PopScope => self.op_pop_scope(),
-                Op::GetScopeObject { index } => self.op_get_scope_object(index),
-                Op::GetGlobalScope => self.op_get_global_scope(),
-                Op::FindProperty { index } => self.op_find_property(method, index),
-                Op::FindPropStrict { index } => self.op_find_prop_strict(method, index),
-                Op::GetLex { index } => self.op_get_lex(method, index),
-                Op::GetSlot { index } => self.op_get_slot(index),
-                Op::SetSlot { index } => self.op_set_slot(index),
-                Op::GetGlobalSlot { index } => self.op_get_global_slot(index),
-                Op::SetGlobalSlot { index } => self.op_set_global_slot(index),
-                Op::Construct { num_args } => self.op_construct(num_args),
-                Op::ConstructProp { index, num_args } => {
-                    self.op_construct_prop(method, index, num_args)
-                }
-                Op::ConstructSuper { num_args } => self.op_construct_super(num_args),
-                Op::NewActivation => self.op_new_activation(),
-                Op::NewObject { num_args } => self.op_new_object(num_args),
-                Op::NewFunction { index } => self.op_new_function(method, index),
-                Op::NewClass { index } => self.op_new_class(method, index),
-                Op::ApplyType { num_types } => self.op_apply_type(num_types),
-                Op::NewArray { num_args } => self.op_new_array(num_args),
-                Op::CoerceA => self.op_coerce_a(),
-                Op::CoerceS => self.op_coerce_s(),
-                Op::ConvertB => self.op_convert_b(),
-                Op::ConvertI => self.op_convert_i(),
-                Op::ConvertD => self.op_convert_d(),
-                Op::ConvertO => self.op_convert_o(),
-                Op::ConvertU => self.op_convert_u(),
-                Op::ConvertS => self.op_convert_s(),
-                Op::Add => self.op_add(),
-                Op::AddI => self.op_add_i(),
-                Op::BitAnd => self.op_bitand(),
-                Op::BitNot => self.op_bitnot(),
-                Op::BitOr => self.op_bitor(),
-                Op::BitXor => self.op_bitxor(),
-                Op::DecLocal { index } => self.op_declocal(index),
-                Op::DecLocalI { index } => self.op_declocal_i(index),
-                Op::Decrement => self.op_decrement(),
-                Op::DecrementI => self.op_decrement_i(),
-                Op::Divide => self.op_divide(),
-                Op::IncLocal { index } => self.op_inclocal(index),
-                Op::IncLocalI { index } => self.op_inclocal_i(index),
-                Op::Increment => self.op_increment(),
-                Op::IncrementI => self.op_increment_i(),
-                Op::LShift => self.op_lshift(),
-                Op::Modulo => self.op_modulo(),
-                Op::Multiply => self.op_multiply(),
-                Op::MultiplyI => self.op_multiply_i(),
-                Op::Negate => self.op_negate(),
-                Op::NegateI => self.op_negate_i(),
-                Op::RShift => self.op_rshift(),
-                Op::Subtract => self.op_subtract(),
-                Op::SubtractI => self.op_subtract_i(),
-                Op::Swap => self.op_swap(),
-                Op::URShift => self.op_urshift(),
-                Op::Jump { offset } => self.op_jump(offset, reader, full_data),
-                Op::IfTrue { offset } => self.op_if_true(offset, reader, full_data),
-                Op::IfFalse { offset } => self.op_if_false(offset, reader, full_data),
-                Op::IfStrictEq { offset } => self.op_if_strict_eq(offset, reader, full_data),
-                Op::IfStrictNe { offset } => self.op_if_strict_ne(offset, reader, full_data),
-                Op::IfEq { offset } => self.op_if_eq(offset, reader, full_data),
-                Op::IfNe { offset } => self.op_if_ne(offset, reader, full_data),
-                Op::IfGe { offset } => self.op_if_ge(offset, reader, full_data),
-                Op::IfGt { offset } => self.op_if_gt(offset, reader, full_data),
-                Op::IfLe { offset } => self.op_if_le(offset, reader, full_data),
-                Op::IfLt { offset } => self.op_if_lt(offset, reader, full_data),
-                Op::IfNge { offset } => self.op_if_nge(offset, reader, full_data),
-                Op::IfNgt { offset } => self.op_if_ngt(offset, reader, full_data),
-                Op::IfNle { offset } => self.op_if_nle(offset, reader, full_data),
-                Op::IfNlt { offset } => self.op_if_nlt(offset, reader, full_data),
-                Op::StrictEquals => self.op_strict_equals(),
-                Op::Equals => self.op_equals(),
-                Op::GreaterEquals => self.op_greater_equals(),
-                Op::GreaterThan => self.op_greater_than(),
-                Op::LessEquals => self.op_less_equals(),
-                Op::LessThan => self.op_less_than(),
-                Op::Nop => self.op_nop(),
-                Op::Not => self.op_not(),
-                Op::HasNext => self.op_has_next(),
-                Op::HasNext2 {
-                    object_register,
-                    index_register,
-                } => self.op_has_next_2(object_register, index_register),
-                Op::NextName => self.op_next_name(),
-                Op::NextValue => self.op_next_value(),
-                Op::IsType { index } => self.op_is_type(method, index),
-                Op::IsTypeLate => self.op_is_type_late(),
-                Op::AsType { type_name } => self.op_as_type(method, type_name),
-                Op::AsTypeLate => self.op_as_type_late(),
-                Op::InstanceOf => self.op_instance_of(),
-                Op::Label => Ok(FrameControl::Continue),
-                Op::Debug {
-                    is_local_register,
-                    register_name,
-                    register,
-                } => self.op_debug(method, is_local_register, register_name, register),
-                Op::DebugFile { file_name } => self.op_debug_file(method, file_name),
-                Op::DebugLine { line_num } => self.op_debug_line(line_num),
-                Op::TypeOf => self.op_type_of(),
-                Op::EscXAttr => self.op_esc_xattr(),
-                Op::EscXElem => self.op_esc_elem(),
-                Op::LookupSwitch {
-                    default_offset,
-                    case_offsets,
-                } => self.op_lookup_switch(
-                    default_offset,
-                    &case_offsets,
-                    instruction_start,
-                    reader,
-                    full_data,
-                ),
-                Op::Coerce { index } => self.op_coerce(method, index),
-                Op::Si8 => self.op_si8(),
-                Op::Si16 => self.op_si16(),
-                Op::Si32 => self.op_si32(),
-                Op::Sf32 => self.op_sf32(),
-                Op::Sf64 => self.op_sf64(),
-                Op::Li8 => self.op_li8(),
-                Op::Li16 => self.op_li16(),
-                Op::Li32 => self.op_li32(),
-                Op::Lf32 => self.op_lf32(),
-                Op::Lf64 => self.op_lf64(),
-                Op::Sxi1 => self.op_sxi1(),
-                Op::Sxi8 => self.op_sxi8(),
-                Op::Sxi16 => self.op_sxi16(),
-                _ => self.unknown_op(op),
-            };
-
-            if let Err(e) = result {
-                log::error!("AVM2 error: {}", e);
-                return Err(e);
+        let op: Result<_, Error> = ops
+            .get(*ip)
+            .ok_or_else(|| "Instruction index out of bounds".into());
+        let op = op?;
+        *ip += 1;
+
+        avm_debug!(self.avm2(), "Opcode: {:?}", op);
+
+        let result = match op {
+            Op::PushByte { value } => self.op_push_byte(*value),
+            Op::PushDouble { value } => self.op_push_double(*value),
+            Op::PushFalse => self.op_push_false(),
+            Op::PushInt { value } => self.op_push_int(*value),
+            Op::PushNamespace { value } => self.op_push_namespace(value.clone()),
+            Op::PushNaN => self.op_push_nan(),
+            Op::PushNull => self.op_push_null(),
+            Op::PushShort { value } => self.op_push_short(*value),
+            Op::PushString { value } => self.op_push_string(*value),
+            Op::PushTrue => self.op_push_true(),
+            Op::PushUint { value } => self.op_push_uint(*value),
+            Op::PushUndefined => self.op_push_undefined(),
+            Op::Pop => self.op_pop(),
+            Op::Dup => self.op_dup(),
+            Op::GetLocal { index } => self.op_get_local(*index),
+            Op::SetLocal { index } => self.op_set_local(*index),
+            Op::Kill { index } => self.op_kill(*index),
+            Op::Call { num_args } => self.op_call(*num_args),
+            Op::CallMethod { index, num_args } => self.op_call_method(index.clone(), *num_args),
+            Op::CallProperty {
+                name,
+                num_args,
+                cache,
+            } => self.op_call_property(method, name, *num_args, *cache),
+            Op::CallPropLex { name, num_args } => self.op_call_prop_lex(method, name, *num_args),
+            Op::CallPropVoid {
+                name,
+                num_args,
+                cache,
+            } => self.op_call_prop_void(method, name, *num_args, *cache),
+            Op::CallStatic { index, num_args } => {
+                self.op_call_static(method, index.clone(), *num_args)
             }
-            result
-        } else if let Ok(None) = op {
-            log::error!("Unknown opcode!");
-            Err("Unknown opcode!".into())
-        } else if let Err(e) = op {
-            log::error!("Parse error: {:?}", e);
-            Err(e.into())
-        } else {
-            unreachable!();
+            Op::CallSuper { name, num_args } => self.op_call_super(method, name, *num_args),
+            Op::CallSuperVoid { name, num_args } => {
+                self.op_call_super_void(method, name, *num_args)
+            }
+            Op::ReturnValue => self.op_return_value(),
+            Op::ReturnVoid => self.op_return_void(),
+            Op::GetProperty { name, cache } => self.op_get_property(method, name, *cache),
+            Op::SetProperty { name, cache } => self.op_set_property(method, name, *cache),
+            Op::InitProperty { name } => self.op_init_property(method, name),
+            Op::DeleteProperty { name } => self.op_delete_property(method, name),
+            Op::GetSuper { name } => self.op_get_super(method, name),
+            Op::SetSuper { name } => self.op_set_super(method, name),
+            Op::In => self.op_in(),
+            Op::PushScope => self.op_push_scope(),
+            Op::PushWith => self.op_push_with(),
+            Op::PopScope => self.op_pop_scope(),
+            Op::GetScopeObject { index } => self.op_get_scope_object(*index),
+            Op::GetGlobalScope => self.op_get_global_scope(),
+            Op::FindProperty { name } => self.op_find_property(method, name),
+            Op::FindPropStrict { name } => self.op_find_prop_strict(method, name),
+            Op::GetLex { name } => self.op_get_lex(name),
+            Op::GetSlot { index } => self.op_get_slot(*index),
+            Op::SetSlot { index } => self.op_set_slot(*index),
+            Op::GetGlobalSlot { index } => self.op_get_global_slot(*index),
+            Op::SetGlobalSlot { index } => self.op_set_global_slot(*index),
+            Op::Construct { num_args } => self.op_construct(*num_args),
+            Op::ConstructProp { name, num_args } => self.op_construct_prop(method, name, *num_args),
+            Op::ConstructSuper { num_args } => self.op_construct_super(*num_args),
+            Op::NewActivation => self.op_new_activation(),
+            Op::NewObject { num_args } => self.op_new_object(*num_args),
+            Op::NewFunction { index } => self.op_new_function(method, index.clone()),
+            Op::NewClass { index } => self.op_new_class(method, index.clone()),
+            Op::ApplyType { num_types } => self.op_apply_type(*num_types),
+            Op::NewArray { num_args } => self.op_new_array(*num_args),
+            Op::CoerceA => self.op_coerce_a(),
+            Op::CoerceS => self.op_coerce_s(),
+            Op::ConvertB => self.op_convert_b(),
+            Op::ConvertI => self.op_convert_i(),
+            Op::ConvertD => self.op_convert_d(),
+            Op::ConvertO => self.op_convert_o(),
+            Op::ConvertU => self.op_convert_u(),
+            Op::ConvertS => self.op_convert_s(),
+            Op::Add => self.op_add(),
+            Op::AddI => self.op_add_i(),
+            Op::BitAnd => self.op_bitand(),
+            Op::BitNot => self.op_bitnot(),
+            Op::BitOr => self.op_bitor(),
+            Op::BitXor => self.op_bitxor(),
+            Op::DecLocal { index } => self.op_declocal(*index),
+            Op::DecLocalI { index } => self.op_declocal_i(*index),
+            Op::Decrement => self.op_decrement(),
+            Op::DecrementI => self.op_decrement_i(),
+            Op::Divide => self.op_divide(),
+            Op::IncLocal { index } => self.op_inclocal(*index),
+            Op::IncLocalI { index } => self.op_inclocal_i(*index),
+            Op::Increment => self.op_increment(),
+            Op::IncrementI => self.op_increment_i(),
+            Op::LShift => self.op_lshift(),
+            Op::Modulo => self.op_modulo(),
+            Op::Multiply => self.op_multiply(),
+            Op::MultiplyI => self.op_multiply_i(),
+            Op::Negate => self.op_negate(),
+            Op::NegateI => self.op_negate_i(),
+            Op::RShift => self.op_rshift(),
+            Op::Subtract => self.op_subtract(),
+            Op::SubtractI => self.op_subtract_i(),
+            Op::Swap => self.op_swap(),
+            Op::URShift => self.op_urshift(),
+            Op::Jump { target } => self.op_jump(*target, ip),
+            Op::IfTrue { target } => self.op_if_true(*target, ip),
+            Op::IfFalse { target } => self.op_if_false(*target, ip),
+            Op::IfStrictEq { target } => self.op_if_strict_eq(*target, ip),
+            Op::IfStrictNe { target } => self.op_if_strict_ne(*target, ip),
+            Op::IfEq { target } => self.op_if_eq(*target, ip),
+            Op::IfNe { target } => self.op_if_ne(*target, ip),
+            Op::IfGe { target } => self.op_if_ge(*target, ip),
+            Op::IfGt { target } => self.op_if_gt(*target, ip),
+            Op::IfLe { target } => self.op_if_le(*target, ip),
+            Op::IfLt { target } => self.op_if_lt(*target, ip),
+            Op::IfNge { target } => self.op_if_nge(*target, ip),
+            Op::IfNgt { target } => self.op_if_ngt(*target, ip),
+            Op::IfNle { target } => self.op_if_nle(*target, ip),
+            Op::IfNlt { target } => self.op_if_nlt(*target, ip),
+            Op::StrictEquals => self.op_strict_equals(),
+            Op::Equals => self.op_equals(),
+            Op::GreaterEquals => self.op_greater_equals(),
+            Op::GreaterThan => self.op_greater_than(),
+            Op::LessEquals => self.op_less_equals(),
+            Op::LessThan => self.op_less_than(),
+            Op::Nop => self.op_nop(),
+            Op::Not => self.op_not(),
+            Op::HasNext => self.op_has_next(),
+            Op::HasNext2 {
+                object_register,
+                index_register,
+            } => self.op_has_next_2(*object_register, *index_register),
+            Op::NextName => self.op_next_name(),
+            Op::NextValue => self.op_next_value(),
+            Op::IsType { type_name } => self.op_is_type(type_name),
+            Op::IsTypeLate => self.op_is_type_late(),
+            Op::AsType { type_name } => self.op_as_type(type_name),
+            Op::AsTypeLate => self.op_as_type_late(),
+            Op::InstanceOf => self.op_instance_of(),
+            Op::Label => Ok(FrameControl::Continue),
+            Op::Debug {
+                is_local_register,
+                register_name,
+                register,
+            } => self.op_debug(method, *is_local_register, register_name.clone(), *register),
+            Op::DebugFile { file_name } => self.op_debug_file(method, file_name.clone()),
+            Op::DebugLine { line_num } => self.op_debug_line(*line_num),
+            Op::TypeOf => self.op_type_of(),
+            Op::EscXAttr => self.op_esc_xattr(),
+            Op::EscXElem => self.op_esc_elem(),
+            Op::LookupSwitch {
+                default_target,
+                case_targets,
+            } => self.op_lookup_switch(*default_target, case_targets, ip),
+            Op::Coerce { type_name } => self.op_coerce(type_name),
+            Op::Si8 => self.op_si8(),
+            Op::Si16 => self.op_si16(),
+            Op::Si32 => self.op_si32(),
+            Op::Sf32 => self.op_sf32(),
+            Op::Sf64 => self.op_sf64(),
+            Op::Li8 => self.op_li8(),
+            Op::Li16 => self.op_li16(),
+            Op::Li32 => self.op_li32(),
+            Op::Lf32 => self.op_lf32(),
+            Op::Lf64 => self.op_lf64(),
+            Op::Sxi1 => self.op_sxi1(),
+            Op::Sxi8 => self.op_sxi8(),
+            Op::Sxi16 => self.op_sxi16(),
+            Op::Unsupported { op } => self.unknown_op(op),
+            Op::Undecodable { error } => {
+                log::error!("Parse error: {}", error);
+                return Err(error.clone().into());
+            }
+        };
+
+        if let Err(e) = result {
+            log::error!("AVM2 error: {}", e);
+            return Err(e);
         }
+        result
     }
 
-    fn unknown_op(&mut self, op: swf::avm2::types::Op) -> Result<FrameControl<'gc>, Error> {
+    fn unknown_op(&mut self, op: &swf::avm2::types::Op) -> Result<FrameControl<'gc>, Error> {
         log::error!("Unknown AVM2 opcode: {:?}", op);
         Err("Unknown op".into())
     }
@@ -939,12 +908,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_double(
-        &mut self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
-        value: Index<f64>,
-    ) -> Result<FrameControl<'gc>, Error> {
-        self.context.avm2.push(self.pool_double(method, value)?);
+    fn op_push_double(&mut self, value: f64) -> Result<FrameControl<'gc>, Error> {
+        self.context.avm2.push(value);
         Ok(FrameControl::Continue)
     }
 
@@ -953,22 +918,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_int(
-        &mut self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
-        value: Index<i32>,
-    ) -> Result<FrameControl<'gc>, Error> {
-        self.context.avm2.push(self.pool_int(method, value)?);
+    fn op_push_int(&mut self, value: i32) -> Result<FrameControl<'gc>, Error> {
+        self.context.avm2.push(value);
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_namespace(
-        &mut self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
-        value: Index<AbcNamespace>,
-    ) -> Result<FrameControl<'gc>, Error> {
-        let ns = self.pool_namespace(method, value)?;
-        let ns_object = NamespaceObject::from_namespace(self, ns)?;
+    fn op_push_namespace(&mut self, value: Namespace<'gc>) -> Result<FrameControl<'gc>, Error> {
+        let ns_object = NamespaceObject::from_namespace(self, value)?;
 
         self.context.avm2.push(ns_object);
         Ok(FrameControl::Continue)
@@ -989,12 +945,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_string(
-        &mut self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
-        value: Index<String>,
-    ) -> Result<FrameControl<'gc>, Error> {
-        self.context.avm2.push(self.pool_string(&method, value)?);
+    fn op_push_string(&mut self, value: AvmString<'gc>) -> Result<FrameControl<'gc>, Error> {
+        self.context.avm2.push(value);
         Ok(FrameControl::Continue)
     }
 
@@ -1003,12 +955,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_uint(
-        &mut self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
-        value: Index<u32>,
-    ) -> Result<FrameControl<'gc>, Error> {
-        self.context.avm2.push(self.pool_uint(method, value)?);
+    fn op_push_uint(&mut self, value: u32) -> Result<FrameControl<'gc>, Error> {
+        self.context.avm2.push(value);
         Ok(FrameControl::Continue)
     }
 
@@ -1085,15 +1033,24 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_call_property(
+    /// Pop the receiver of a property call and find the method it names,
+    /// along with the class that declares it.
+    ///
+    /// Resolutions against instances of sealed classes are remembered in the
+    /// instruction's property cache.
+    fn resolve_method(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
-        arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
-        let args = self.context.avm2.pop_args(arg_count);
-        let multiname = self.pool_multiname(method, index)?;
+        name: &MultinameOperand<'gc>,
+        cache: Option<GcCell<'gc, PropertyCache<'gc>>>,
+    ) -> Result<(QName<'gc>, Option<Object<'gc>>, Object<'gc>), Error> {
+        let multiname = self.resolve_multiname_operand(method, name)?;
         let receiver = self.context.avm2.pop().coerce_to_object(self)?;
+
+        if let Some(entry) = PropertyCache::lookup(cache, receiver) {
+            return Ok((entry.name, entry.trait_class, receiver));
+        }
+
         let name: Result<QName, Error> = receiver
             .resolve_multiname(&multiname)?
             .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
@@ -1103,6 +1060,25 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         } else {
             None
         };
+
+        if let Some(cache) = cache {
+            cache
+                .write(self.context.gc_context)
+                .fill(receiver, &name, superclass_object)?;
+        }
+
+        Ok((name, superclass_object, receiver))
+    }
+
+    fn op_call_property(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        name: &MultinameOperand<'gc>,
+        arg_count: u32,
+        cache: Option<GcCell<'gc, PropertyCache<'gc>>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let args = self.context.avm2.pop_args(arg_count);
+        let (name, superclass_object, receiver) = self.resolve_method(method, name, cache)?;
         let function = receiver
             .get_property(receiver, &name, self)?
             .coerce_to_object(self)?;
@@ -1116,11 +1092,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_call_prop_lex(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
+        name: &MultinameOperand<'gc>,
         arg_count: u32,
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
-        let multiname = self.pool_multiname(method, index)?;
+        let multiname = self.resolve_multiname_operand(method, name)?;
         let receiver = self.context.avm2.pop().coerce_to_object(self)?;
         let name: Result<QName, Error> = receiver
             .resolve_multiname(&multiname)?
@@ -1138,21 +1114,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_call_prop_void(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
+        name: &MultinameOperand<'gc>,
         arg_count: u32,
+        cache: Option<GcCell<'gc, PropertyCache<'gc>>>,
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
-        let multiname = self.pool_multiname(method, index)?;
-        let receiver = self.context.avm2.pop().coerce_to_object(self)?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
-        let name = name?;
-        let superclass_object = if let Some(c) = receiver.as_class_object() {
-            c.find_class_for_trait(&name)?
-        } else {
-            None
-        };
+        let (name, superclass_object, receiver) = self.resolve_method(method, name, cache)?;
         let function = receiver
             .get_property(receiver, &name, self)?
             .coerce_to_object(self)?;
@@ -1183,11 +1150,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_call_super(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
+        name: &MultinameOperand<'gc>,
         arg_count: u32,
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
-        let multiname = self.pool_multiname(method, index)?;
+        let multiname = self.resolve_multiname_operand(method, name)?;
         let receiver = self.context.avm2.pop().coerce_to_object(self)?;
 
         let name: Result<QName, Error> = receiver
@@ -1215,11 +1182,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_call_super_void(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
+        name: &MultinameOperand<'gc>,
         arg_count: u32,
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
-        let multiname = self.pool_multiname(method, index)?;
+        let multiname = self.resolve_multiname_operand(method, name)?;
         let receiver = self.context.avm2.pop().coerce_to_object(self)?;
 
         let name: Result<QName, Error> = receiver
@@ -1255,11 +1222,24 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_get_property(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
+        name: &MultinameOperand<'gc>,
+        cache: Option<GcCell<'gc, PropertyCache<'gc>>>,
     ) -> Result<FrameControl<'gc>, Error> {
-        let multiname = self.pool_multiname(method, index)?;
+        let multiname = self.resolve_multiname_operand(method, name)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
 
+        if let Some(entry) = PropertyCache::lookup(cache, object) {
+            let value = match entry.kind {
+                CachedTrait::Slot { slot_id, .. } | CachedTrait::Const { slot_id } => {
+                    object.get_slot(slot_id)?
+                }
+                CachedTrait::Other => object.get_property(object, &entry.name, self)?,
+            };
+            self.context.avm2.push(value);
+
+            return Ok(FrameControl::Continue);
+        }
+
         let name: Result<QName, Error> = object.resolve_multiname(&multiname)?.ok_or_else(|| {
             format!("Could not resolve property {:?}", multiname.local_name()).into()
         });
@@ -1276,7 +1256,14 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             return Ok(FrameControl::Continue);
         }
 
-        let value = object.get_property(object, &name?, self)?;
+        let name = name?;
+        if let Some(cache) = cache {
+            cache
+                .write(self.context.gc_context)
+                .fill(object, &name, None)?;
+        }
+
+        let value = object.get_property(object, &name, self)?;
         self.context.avm2.push(value);
 
         Ok(FrameControl::Continue)
@@ -1285,34 +1272,93 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_set_property(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
+        name: &MultinameOperand<'gc>,
+        cache: Option<GcCell<'gc, PropertyCache<'gc>>>,
     ) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop();
-        let multiname = self.pool_multiname(method, index)?;
+        let multiname = self.resolve_multiname_operand(method, name)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        if let Some(name) = object.resolve_multiname(&multiname)? {
-            object.set_property(object, &name, value, self)?;
-        } else {
-            //TODO: Non-dynamic objects should fail
-            //TODO: This should only work if the public namespace is present
-            let local_name: Result<AvmString<'gc>, Error> = multiname
-                .local_name()
-                .ok_or_else(|| "Cannot set property using any name".into());
-            let name = QName::dynamic_name(local_name?);
-            object.set_property(object, &name, value, self)?;
+        let (name, kind) = match PropertyCache::lookup(cache, object) {
+            Some(entry) => (entry.name, entry.kind),
+            None => {
+                let name = match object.resolve_multiname(&multiname)? {
+                    Some(name) => name,
+                    None => {
+                        //TODO: Non-dynamic objects should fail
+                        //TODO: This should only work if the public namespace is present
+                        let local_name: Result<AvmString<'gc>, Error> = multiname
+                            .local_name()
+                            .ok_or_else(|| "Cannot set property using any name".into());
+                        let name = QName::dynamic_name(local_name?);
+                        object.set_property(object, &name, value, self)?;
+
+                        return Ok(FrameControl::Continue);
+                    }
+                };
+
+                let entry = match cache {
+                    Some(cache) => cache
+                        .write(self.context.gc_context)
+                        .fill(object, &name, None)?,
+                    None => None,
+                };
+                let kind = match entry {
+                    Some(entry) => entry.kind,
+                    None => CachedTrait::of(object, &name)?,
+                };
+
+                (name, kind)
+            }
+        };
+
+        match kind {
+            CachedTrait::Slot { slot_id, type_name } => {
+                let value = self.coerce_to_slot_type(value, &type_name)?;
+                object.set_slot(slot_id, value, self.context.gc_context)?;
+            }
+            // Constants are written through `set_property` so that the write fails.
+            CachedTrait::Const { .. } | CachedTrait::Other => {
+                object.set_property(object, &name, value, self)?
+            }
         }
 
         Ok(FrameControl::Continue)
     }
 
+    /// Coerce a value written to a slot to the slot's declared type.
+    ///
+    /// This fails with a `TypeError` if the value can't be coerced, or if the
+    /// declared type can't be resolved. Slots typed as `Object` hold any
+    /// value other than `undefined`, which becomes `null`.
+    fn coerce_to_slot_type(
+        &mut self,
+        value: Value<'gc>,
+        type_name: &Multiname<'gc>,
+    ) -> Result<Value<'gc>, Error> {
+        let class = match self.resolve_type(type_name) {
+            Ok(Some(class)) => class,
+            Ok(None) => return Ok(value),
+            Err(e) => return Err(format!("TypeError: {}", e).into()),
+        };
+
+        if Object::ptr_eq(class, self.avm2().classes().object) {
+            return Ok(match value {
+                Value::Undefined => Value::Null,
+                value => value,
+            });
+        }
+
+        value.coerce_to_type(self, class)
+    }
+
     fn op_init_property(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
+        name: &MultinameOperand<'gc>,
     ) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop();
-        let multiname = self.pool_multiname(method, index)?;
+        let multiname = self.resolve_multiname_operand(method, name)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
 
         if let Some(name) = object.resolve_multiname(&multiname)? {
@@ -1333,9 +1379,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_delete_property(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
+        name: &MultinameOperand<'gc>,
     ) -> Result<FrameControl<'gc>, Error> {
-        let multiname = self.pool_multiname(method, index)?;
+        let multiname = self.resolve_multiname_operand(method, name)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
 
         if let Some(name) = object.resolve_multiname(&multiname)? {
@@ -1358,9 +1404,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_get_super(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
+        name: &MultinameOperand<'gc>,
     ) -> Result<FrameControl<'gc>, Error> {
-        let multiname = self.pool_multiname(method, index)?;
+        let multiname = self.resolve_multiname_operand(method, name)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
 
         let name: Result<QName, Error> = object
@@ -1388,10 +1434,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_set_super(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
+        name: &MultinameOperand<'gc>,
     ) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop();
-        let multiname = self.pool_multiname(method, index)?;
+        let multiname = self.resolve_multiname_operand(method, name)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
 
         let name: Result<QName, Error> = object
@@ -1484,9 +1530,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_find_property(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
+        name: &MultinameOperand<'gc>,
     ) -> Result<FrameControl<'gc>, Error> {
-        let multiname = self.pool_multiname(method, index)?;
+        let multiname = self.resolve_multiname_operand(method, name)?;
         avm_debug!(self.context.avm2, "Resolving {:?}", multiname);
         let result = if let Some(scope) = self.scope() {
             scope.read().find(&multiname, self)?
@@ -1504,9 +1550,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_find_prop_strict(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
+        name: &MultinameOperand<'gc>,
     ) -> Result<FrameControl<'gc>, Error> {
-        let multiname = self.pool_multiname(method, index)?;
+        let multiname = self.resolve_multiname_operand(method, name)?;
         avm_debug!(self.context.avm2, "Resolving {:?}", multiname);
         let found: Result<Object<'gc>, Error> = if let Some(scope) = self.scope() {
             scope.read().find(&multiname, self)?
@@ -1521,15 +1567,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_get_lex(
-        &mut self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
-        let multiname = self.pool_multiname_static(method, index)?;
+    fn op_get_lex(&mut self, multiname: &Multiname<'gc>) -> Result<FrameControl<'gc>, Error> {
         avm_debug!(self.avm2(), "Resolving {:?}", multiname);
         let found: Result<Value<'gc>, Error> = if let Some(scope) = self.scope() {
-            scope.read().resolve(&multiname, self)?
+            scope.read().resolve(multiname, self)?
         } else {
             None
         }
@@ -1593,11 +1634,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_construct_prop(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
+        name: &MultinameOperand<'gc>,
         arg_count: u32,
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
-        let multiname = self.pool_multiname(method, index)?;
+        let multiname = self.resolve_multiname_operand(method, name)?;
         let source = self.context.avm2.pop().coerce_to_object(self)?;
 
         let ctor_name: Result<QName, Error> =
@@ -2050,234 +2091,167 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_jump<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
-        reader.seek(full_data, offset);
+    fn op_jump(&mut self, target: usize, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
+        *ip = target;
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_true<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_true(&mut self, target: usize, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop().coerce_to_boolean();
 
         if value {
-            reader.seek(full_data, offset);
+            *ip = target;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_false<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_false(&mut self, target: usize, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop().coerce_to_boolean();
 
         if !value {
-            reader.seek(full_data, offset);
+            *ip = target;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_strict_eq<'b>(
+    fn op_if_strict_eq(
         &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
+        target: usize,
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1 == value2 {
-            reader.seek(full_data, offset);
+            *ip = target;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_strict_ne<'b>(
+    fn op_if_strict_ne(
         &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
+        target: usize,
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1 != value2 {
-            reader.seek(full_data, offset);
+            *ip = target;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_eq<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_eq(&mut self, target: usize, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1.abstract_eq(&value2, self)? {
-            reader.seek(full_data, offset);
+            *ip = target;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_ne<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_ne(&mut self, target: usize, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if !value1.abstract_eq(&value2, self)? {
-            reader.seek(full_data, offset);
+            *ip = target;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_ge<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_ge(&mut self, target: usize, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1.abstract_lt(&value2, self)? == Some(false) {
-            reader.seek(full_data, offset);
+            *ip = target;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_gt<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_gt(&mut self, target: usize, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value2.abstract_lt(&value1, self)? == Some(true) {
-            reader.seek(full_data, offset);
+            *ip = target;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_le<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_le(&mut self, target: usize, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value2.abstract_lt(&value1, self)? == Some(false) {
-            reader.seek(full_data, offset);
+            *ip = target;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_lt<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_lt(&mut self, target: usize, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1.abstract_lt(&value2, self)? == Some(true) {
-            reader.seek(full_data, offset);
+            *ip = target;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_nge<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_nge(&mut self, target: usize, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1.abstract_lt(&value2, self)?.unwrap_or(true) {
-            reader.seek(full_data, offset);
+            *ip = target;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_ngt<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_ngt(&mut self, target: usize, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if !value2.abstract_lt(&value1, self)?.unwrap_or(false) {
-            reader.seek(full_data, offset);
+            *ip = target;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_nle<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_nle(&mut self, target: usize, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value2.abstract_lt(&value1, self)?.unwrap_or(true) {
-            reader.seek(full_data, offset);
+            *ip = target;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_nlt<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_nlt(&mut self, target: usize, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if !value1.abstract_lt(&value2, self)?.unwrap_or(false) {
-            reader.seek(full_data, offset);
+            *ip = target;
         }
 
         Ok(FrameControl::Continue)
@@ -2440,16 +2414,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_is_type(
-        &mut self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
-        type_name_index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_is_type(&mut self, multiname: &Multiname<'gc>) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop();
 
-        let multiname = self.pool_multiname_static(method, type_name_index)?;
         let found: Result<Value<'gc>, Error> = if let Some(scope) = self.scope() {
-            scope.read().resolve(&multiname, self)?
+            scope.read().resolve(multiname, self)?
         } else {
             None
         }
@@ -2479,16 +2448,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_as_type(
-        &mut self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
-        type_name_index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_as_type(&mut self, multiname: &Multiname<'gc>) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let multiname = self.pool_multiname_static(method, type_name_index)?;
         let found: Result<Value<'gc>, Error> = if let Some(scope) = self.scope() {
-            scope.read().resolve(&multiname, self)?
+            scope.read().resolve(multiname, self)?
         } else {
             None
         }
@@ -2637,35 +2601,25 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     /// Implements `Op::LookupSwitch`
-    fn op_lookup_switch<'b>(
+    fn op_lookup_switch(
         &mut self,
-        default_offset: i32,
-        case_offsets: &[i32],
-        instruction_start: usize,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
+        default_target: usize,
+        case_targets: &[usize],
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error> {
         let index = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        let offset = case_offsets
+        *ip = case_targets
             .get(index as usize)
             .copied()
-            .unwrap_or(default_offset)
-            + instruction_start as i32
-            - reader.pos(full_data) as i32;
+            .unwrap_or(default_target);
 
-        reader.seek(full_data, offset);
         Ok(FrameControl::Continue)
     }
 
     /// Implements `Op::Coerce`
-    fn op_coerce(
-        &mut self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
-        index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_coerce(&mut self, type_name: &Multiname<'gc>) -> Result<FrameControl<'gc>, Error> {
         let val = self.context.avm2.pop();
-        let type_name = self.pool_multiname_static_any(method, index)?;
         let param_type = self.resolve_type(type_name)?;
 
         let x = if let Some(param_type) = param_type {
@@ -2944,3 +2898,112 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 }
+
+/// Describes a value for the debugger, without listing the properties of objects.
+fn describe_value(value: &Value<'_>) -> String {
+    match value {
+        Value::Undefined => "undefined".to_string(),
+        Value::Null => "null".to_string(),
+        Value::Bool(value) => value.to_string(),
+        Value::Number(value) => value.to_string(),
+        Value::Unsigned(value) => value.to_string(),
+        Value::Integer(value) => value.to_string(),
+        Value::String(value) => format!("{:?}", value.as_str()),
+        Value::Object(object) => {
+            let class_name = object
+                .as_class()
+                .map(|class| class.read().name().to_qualified_name())
+                .unwrap_or_else(|| "Object".to_string());
+            format!("[object {}]", class_name)
+        }
+    }
+}
+
+impl<'a, 'gc, 'gc_context> DebugFrame for Activation<'a, 'gc, 'gc_context> {
+    fn locals(&mut self) -> Vec<(String, String)> {
+        let mut locals = vec![(
+            "this".to_string(),
+            describe_value(&self.this.map(Value::Object).unwrap_or(Value::Undefined)),
+        )];
+
+        let local_scope = self.local_scope;
+        let mut index = 1;
+        while let Some(name) = local_scope.get_enumerant_name(index) {
+            let value = local_scope
+                .get_property(local_scope, &name, self)
+                .unwrap_or(Value::Undefined);
+            locals.push((name.local_name().to_string(), describe_value(&value)));
+            index += 1;
+        }
+
+        locals
+    }
+
+    fn registers(&mut self) -> Vec<(String, String)> {
+        self.local_registers
+            .read()
+            .0
+            .iter()
+            .enumerate()
+            .map(|(id, value)| (format!("r{}", id), describe_value(value)))
+            .collect()
+    }
+
+    fn scope_chain(&mut self) -> Vec<String> {
+        let mut chain = Vec::new();
+        let mut scope = self.scope;
+        while let Some(cell) = scope {
+            let scope_ref = cell.read();
+            chain.push(describe_value(&Value::Object(*scope_ref.locals())));
+            scope = scope_ref.parent_cell();
+        }
+        chain
+    }
+
+    fn inspect(&mut self, path: &str) -> Result<String, String> {
+        let mut segments = path.split('.');
+        let first = segments.next().unwrap_or_default();
+        let mut value = if first == "this" {
+            self.this.map(Value::Object).unwrap_or(Value::Undefined)
+        } else if let Some(id) = first.strip_prefix('r').and_then(|id| id.parse().ok()) {
+            self.local_register(id).map_err(|e| e.to_string())?
+        } else {
+            let name = QName::dynamic_name(AvmString::new(self.context.gc_context, first));
+            let scope = self.scope.ok_or("The scope stack is empty")?;
+            let value = scope
+                .read()
+                .resolve(&name.into(), self)
+                .map_err(|e| e.to_string())?;
+            value.ok_or_else(|| format!("{} is not defined", first))?
+        };
+
+        for segment in segments {
+            let object = match value {
+                Value::Object(object) => object,
+                _ => return Err(format!("Cannot read {} of a primitive", segment)),
+            };
+            let name = QName::dynamic_name(AvmString::new(self.context.gc_context, segment));
+            value = object
+                .get_property(object, &name, self)
+                .map_err(|e| e.to_string())?;
+        }
+
+        let mut output = describe_value(&value);
+        if let Value::Object(object) = value {
+            let mut index = 1;
+            while let Some(name) = object.get_enumerant_name(index) {
+                let property = object
+                    .get_property(object, &name, self)
+                    .unwrap_or(Value::Undefined);
+                output.push_str(&format!(
+                    "\n  {}: {}",
+                    name.local_name(),
+                    describe_value(&property)
+                ));
+                index += 1;
+            }
+        }
+
+        Ok(output)
+    }
+}
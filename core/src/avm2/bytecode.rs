@@ -0,0 +1,1208 @@
+//! Pre-decoded method bodies
+//!
+//! Method bodies are decoded from ABC bytecode the first time they run. Each
+//! instruction has its constant pool operands resolved and its branch
+//! offsets turned into instruction indices, so the interpreter never has to
+//! re-read the bytecode or the constant pool while executing.
+
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::script::TranslationUnit;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::{Trait, TraitKind};
+use crate::avm2::{value, Error};
+use crate::swf::extensions::ReadSwfExt;
+use fnv::FnvHashMap;
+use gc_arena::{Collect, Gc, GcCell, MutationContext};
+use swf::avm2::read::Reader;
+use swf::avm2::types::{
+    Class as AbcClass, Index, Method as AbcMethod, MethodBody as AbcMethodBody,
+    Multiname as AbcMultiname, Op as AbcOp,
+};
+
+/// A method body that has been decoded into instructions.
+#[derive(Collect, Debug)]
+#[collect(no_drop)]
+pub struct DecodedBody<'gc> {
+    /// The instructions of the method body.
+    ///
+    /// Execution starts at the first instruction and proceeds to the next one
+    /// unless a branch is taken.
+    ops: Vec<Op<'gc>>,
+
+    /// The byte offset in the method body of each instruction.
+    offsets: Vec<usize>,
+}
+
+/// A multiname operand of an instruction.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub enum MultinameOperand<'gc> {
+    /// A multiname whose namespaces and name are all known ahead of time.
+    Static(Gc<'gc, Multiname<'gc>>),
+
+    /// A multiname that takes part of itself from the stack when executed.
+    Runtime {
+        #[collect(require_static)]
+        index: Index<AbcMultiname>,
+    },
+}
+
+/// An inline cache for instructions that access properties by name.
+///
+/// Instances of sealed classes all carry the same traits, so the result of
+/// resolving a multiname against one instance holds for every instance of
+/// that class. The cache remembers the resolution for the last class seen by
+/// the instruction.
+#[derive(Clone, Collect, Debug, Default)]
+#[collect(no_drop)]
+pub struct PropertyCache<'gc> {
+    entry: Option<PropertyCacheEntry<'gc>>,
+}
+
+/// The resolution of a multiname against instances of a single class.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct PropertyCacheEntry<'gc> {
+    /// The class object of the instances this entry applies to.
+    class: Object<'gc>,
+
+    /// The name the multiname resolved to.
+    pub name: QName<'gc>,
+
+    /// How the property can be accessed.
+    pub kind: CachedTrait<'gc>,
+
+    /// The class that declares the trait.
+    pub trait_class: Option<Object<'gc>>,
+}
+
+/// The kind of trait a cached property resolved to.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub enum CachedTrait<'gc> {
+    /// A variable stored in a slot.
+    ///
+    /// Values written to it must be coerced to its declared type first.
+    Slot {
+        slot_id: u32,
+        type_name: Multiname<'gc>,
+    },
+
+    /// A constant stored in a slot. It can be read from the slot, but writes
+    /// must go through `set_property` so that they fail.
+    Const { slot_id: u32 },
+
+    /// Any other trait, such as a method or a getter and setter pair. These
+    /// are always accessed through the object's property methods.
+    Other,
+}
+
+impl<'gc> PropertyCache<'gc> {
+    /// Retrieve the cached resolution for an object, if the object is an
+    /// instance of the class that was last cached.
+    pub fn get(&self, object: Object<'gc>) -> Option<&PropertyCacheEntry<'gc>> {
+        let entry = self.entry.as_ref()?;
+        let class = object.as_class_object()?;
+
+        if Object::ptr_eq(class, entry.class) {
+            Some(entry)
+        } else {
+            None
+        }
+    }
+
+    /// Look up an instruction's cached resolution for an object.
+    pub fn lookup(
+        cache: Option<GcCell<'gc, Self>>,
+        object: Object<'gc>,
+    ) -> Option<PropertyCacheEntry<'gc>> {
+        let cache = cache?;
+        let cache = cache.read();
+
+        cache.get(object).cloned()
+    }
+
+    /// Remember how a multiname resolved on an object, and return the new
+    /// cache entry.
+    ///
+    /// Nothing is cached unless the object is an instance of a sealed class
+    /// and the name refers to one of that class's traits.
+    pub fn fill(
+        &mut self,
+        object: Object<'gc>,
+        name: &QName<'gc>,
+        trait_class: Option<Object<'gc>>,
+    ) -> Result<Option<PropertyCacheEntry<'gc>>, Error> {
+        let class = match object.as_class_object() {
+            Some(class) => class,
+            None => return Ok(None),
+        };
+        let is_sealed = class
+            .as_class()
+            .map(|c| c.read().is_sealed())
+            .unwrap_or(false);
+
+        if !is_sealed || !object.has_trait(name)? {
+            return Ok(None);
+        }
+
+        let kind = CachedTrait::of(object, name)?;
+
+        let entry = PropertyCacheEntry {
+            class,
+            name: name.clone(),
+            kind,
+            trait_class,
+        };
+        self.entry = Some(entry.clone());
+
+        Ok(Some(entry))
+    }
+}
+
+impl<'gc> CachedTrait<'gc> {
+    /// Determine how a resolved property of an object is accessed.
+    ///
+    /// This is the same whether or not the resolution ends up cached, so that
+    /// cached and uncached accesses behave alike.
+    pub fn of(object: Object<'gc>, name: &QName<'gc>) -> Result<Self, Error> {
+        let slot_id = match object.get_slot_id(name) {
+            Some(slot_id) => slot_id,
+            None => return Ok(CachedTrait::Other),
+        };
+        let class = match object.as_class_object() {
+            Some(class) => class,
+            None => return Ok(CachedTrait::Other),
+        };
+
+        let declared = Self::declared_trait(class, name)?;
+        Ok(match declared.map(|t| t.kind().clone()) {
+            Some(TraitKind::Slot { type_name, .. }) => CachedTrait::Slot { slot_id, type_name },
+            Some(TraitKind::Const { .. }) => CachedTrait::Const { slot_id },
+            _ => CachedTrait::Other,
+        })
+    }
+
+    /// Find the trait that declares a property on instances of a class.
+    fn declared_trait(class: Object<'gc>, name: &QName<'gc>) -> Result<Option<Trait<'gc>>, Error> {
+        let declaring_class = match class.find_class_for_trait(name)? {
+            Some(declaring_class) => declaring_class,
+            None => return Ok(None),
+        };
+        let mut traits = Vec::new();
+        if let Some(declaring_class) = declaring_class.as_class() {
+            declaring_class
+                .read()
+                .lookup_instance_traits(name, &mut traits)?;
+        }
+
+        Ok(traits.pop())
+    }
+}
+
+/// A single decoded instruction.
+///
+/// Branch targets are indices into the instruction list of the method body
+/// the instruction belongs to.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub enum Op<'gc> {
+    Add,
+    AddI,
+    ApplyType {
+        num_types: u32,
+    },
+    AsType {
+        type_name: Gc<'gc, Multiname<'gc>>,
+    },
+    AsTypeLate,
+    BitAnd,
+    BitNot,
+    BitOr,
+    BitXor,
+    Call {
+        num_args: u32,
+    },
+    CallMethod {
+        #[collect(require_static)]
+        index: Index<AbcMethod>,
+        num_args: u32,
+    },
+    CallProperty {
+        name: MultinameOperand<'gc>,
+        num_args: u32,
+        cache: Option<GcCell<'gc, PropertyCache<'gc>>>,
+    },
+    CallPropLex {
+        name: MultinameOperand<'gc>,
+        num_args: u32,
+    },
+    CallPropVoid {
+        name: MultinameOperand<'gc>,
+        num_args: u32,
+        cache: Option<GcCell<'gc, PropertyCache<'gc>>>,
+    },
+    CallStatic {
+        #[collect(require_static)]
+        index: Index<AbcMethod>,
+        num_args: u32,
+    },
+    CallSuper {
+        name: MultinameOperand<'gc>,
+        num_args: u32,
+    },
+    CallSuperVoid {
+        name: MultinameOperand<'gc>,
+        num_args: u32,
+    },
+    Coerce {
+        type_name: Gc<'gc, Multiname<'gc>>,
+    },
+    CoerceA,
+    CoerceS,
+    Construct {
+        num_args: u32,
+    },
+    ConstructProp {
+        name: MultinameOperand<'gc>,
+        num_args: u32,
+    },
+    ConstructSuper {
+        num_args: u32,
+    },
+    ConvertB,
+    ConvertD,
+    ConvertI,
+    ConvertO,
+    ConvertS,
+    ConvertU,
+    Debug {
+        is_local_register: bool,
+        #[collect(require_static)]
+        register_name: Index<String>,
+        register: u8,
+    },
+    DebugFile {
+        #[collect(require_static)]
+        file_name: Index<String>,
+    },
+    DebugLine {
+        line_num: u32,
+    },
+    DecLocal {
+        index: u32,
+    },
+    DecLocalI {
+        index: u32,
+    },
+    Decrement,
+    DecrementI,
+    DeleteProperty {
+        name: MultinameOperand<'gc>,
+    },
+    Divide,
+    Dup,
+    Equals,
+    EscXAttr,
+    EscXElem,
+    FindProperty {
+        name: MultinameOperand<'gc>,
+    },
+    FindPropStrict {
+        name: MultinameOperand<'gc>,
+    },
+    GetGlobalScope,
+    GetGlobalSlot {
+        index: u32,
+    },
+    GetLex {
+        name: Gc<'gc, Multiname<'gc>>,
+    },
+    GetLocal {
+        index: u32,
+    },
+    GetProperty {
+        name: MultinameOperand<'gc>,
+        cache: Option<GcCell<'gc, PropertyCache<'gc>>>,
+    },
+    GetScopeObject {
+        index: u8,
+    },
+    GetSlot {
+        index: u32,
+    },
+    GetSuper {
+        name: MultinameOperand<'gc>,
+    },
+    GreaterEquals,
+    GreaterThan,
+    HasNext,
+    HasNext2 {
+        object_register: u32,
+        index_register: u32,
+    },
+    IfEq {
+        target: usize,
+    },
+    IfFalse {
+        target: usize,
+    },
+    IfGe {
+        target: usize,
+    },
+    IfGt {
+        target: usize,
+    },
+    IfLe {
+        target: usize,
+    },
+    IfLt {
+        target: usize,
+    },
+    IfNge {
+        target: usize,
+    },
+    IfNgt {
+        target: usize,
+    },
+    IfNle {
+        target: usize,
+    },
+    IfNlt {
+        target: usize,
+    },
+    IfNe {
+        target: usize,
+    },
+    IfStrictEq {
+        target: usize,
+    },
+    IfStrictNe {
+        target: usize,
+    },
+    IfTrue {
+        target: usize,
+    },
+    In,
+    IncLocal {
+        index: u32,
+    },
+    IncLocalI {
+        index: u32,
+    },
+    Increment,
+    IncrementI,
+    InitProperty {
+        name: MultinameOperand<'gc>,
+    },
+    InstanceOf,
+    IsType {
+        type_name: Gc<'gc, Multiname<'gc>>,
+    },
+    IsTypeLate,
+    Jump {
+        target: usize,
+    },
+    Kill {
+        index: u32,
+    },
+    Label,
+    LessEquals,
+    LessThan,
+    Lf32,
+    Lf64,
+    Li16,
+    Li32,
+    Li8,
+    LookupSwitch {
+        default_target: usize,
+        case_targets: Vec<usize>,
+    },
+    LShift,
+    Modulo,
+    Multiply,
+    MultiplyI,
+    Negate,
+    NegateI,
+    NewActivation,
+    NewArray {
+        num_args: u32,
+    },
+    NewClass {
+        #[collect(require_static)]
+        index: Index<AbcClass>,
+    },
+    NewFunction {
+        #[collect(require_static)]
+        index: Index<AbcMethod>,
+    },
+    NewObject {
+        num_args: u32,
+    },
+    NextName,
+    NextValue,
+    Nop,
+    Not,
+    Pop,
+    PopScope,
+    PushByte {
+        value: u8,
+    },
+    PushDouble {
+        value: f64,
+    },
+    PushFalse,
+    PushInt {
+        value: i32,
+    },
+    PushNamespace {
+        value: Namespace<'gc>,
+    },
+    PushNaN,
+    PushNull,
+    PushScope,
+    PushShort {
+        value: i16,
+    },
+    PushString {
+        value: AvmString<'gc>,
+    },
+    PushTrue,
+    PushUint {
+        value: u32,
+    },
+    PushUndefined,
+    PushWith,
+    ReturnValue,
+    ReturnVoid,
+    RShift,
+    SetGlobalSlot {
+        index: u32,
+    },
+    SetLocal {
+        index: u32,
+    },
+    SetProperty {
+        name: MultinameOperand<'gc>,
+        cache: Option<GcCell<'gc, PropertyCache<'gc>>>,
+    },
+    SetSlot {
+        index: u32,
+    },
+    SetSuper {
+        name: MultinameOperand<'gc>,
+    },
+    Sf32,
+    Sf64,
+    Si16,
+    Si32,
+    Si8,
+    StrictEquals,
+    Subtract,
+    SubtractI,
+    Swap,
+    Sxi1,
+    Sxi16,
+    Sxi8,
+    TypeOf,
+    URShift,
+
+    /// An instruction the interpreter does not implement.
+    Unsupported {
+        #[collect(require_static)]
+        op: AbcOp,
+    },
+
+    /// An instruction that could not be decoded.
+    ///
+    /// Bytecode is only rejected when it is executed, so a method body with
+    /// garbage after its last reachable instruction still runs.
+    Undecodable {
+        error: String,
+    },
+}
+
+impl<'gc> DecodedBody<'gc> {
+    /// Decode a method body of a translation unit.
+    ///
+    /// Bytes are decoded in runs, starting from the start of the body and
+    /// from every branch target. A run that reaches an already decoded
+    /// instruction ends with a jump to it, which means that branches into the
+    /// middle of another instruction still execute the bytes they point to.
+    pub fn decode(
+        txunit: TranslationUnit<'gc>,
+        body: &AbcMethodBody,
+        mc: MutationContext<'gc, '_>,
+    ) -> Self {
+        let code = &body.code[..];
+        let mut ops = Vec::new();
+        let mut offsets = Vec::new();
+        let mut op_indices = FnvHashMap::default();
+        let mut pending = vec![0];
+
+        while let Some(start) = pending.pop() {
+            if op_indices.contains_key(&start) {
+                continue;
+            }
+
+            let mut reader = Reader::new(&code[start..]);
+            loop {
+                let offset = reader.pos(code);
+                offsets.push(offset);
+                if offset != start && op_indices.contains_key(&offset) {
+                    ops.push(Op::Jump { target: offset });
+                    break;
+                }
+
+                op_indices.insert(offset, ops.len());
+
+                let op = match reader.read_op() {
+                    Ok(Some(op)) => op,
+                    Ok(None) => {
+                        ops.push(Op::Undecodable {
+                            error: "Unknown opcode!".to_string(),
+                        });
+                        break;
+                    }
+                    Err(e) => {
+                        ops.push(Op::Undecodable {
+                            error: e.to_string(),
+                        });
+                        break;
+                    }
+                };
+
+                let end = reader.pos(code);
+                let op = Op::decode(txunit, op, offset, end, code.len(), &mut pending, mc)
+                    .unwrap_or_else(|e| Op::Undecodable {
+                        error: e.to_string(),
+                    });
+                ops.push(op);
+            }
+        }
+
+        // Branch targets were decoded as byte offsets; every one of them has
+        // been decoded by now, so they can be pointed at their instructions.
+        for op in ops.iter_mut() {
+            op.for_each_target(|target| *target = op_indices[&*target]);
+        }
+
+        Self { ops, offsets }
+    }
+
+    /// The instructions of the method body.
+    pub fn ops(&self) -> &[Op<'gc>] {
+        &self.ops
+    }
+
+    /// The byte offset in the method body of the instruction at the given index.
+    pub fn offset(&self, index: usize) -> Option<usize> {
+        self.offsets.get(index).copied()
+    }
+}
+
+impl<'gc> Op<'gc> {
+    /// Decode a single instruction, resolving its constant pool operands.
+    ///
+    /// Branch targets are returned as byte offsets into the method body, and
+    /// are also added to `pending` so that they get decoded.
+    fn decode(
+        txunit: TranslationUnit<'gc>,
+        op: AbcOp,
+        start: usize,
+        end: usize,
+        code_len: usize,
+        pending: &mut Vec<usize>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Result<Self, Error> {
+        let mut target = |base: usize, offset: i32| {
+            // Branches out of the method body land at its end, which fails to
+            // decode when executed.
+            let target = base as i64 + i64::from(offset);
+            let target = if target < 0 || target > code_len as i64 {
+                code_len
+            } else {
+                target as usize
+            };
+            pending.push(target);
+            target
+        };
+
+        Ok(match op {
+            AbcOp::Add => Op::Add,
+            AbcOp::AddI => Op::AddI,
+            AbcOp::ApplyType { num_types } => Op::ApplyType { num_types },
+            AbcOp::AsType { type_name } => Op::AsType {
+                type_name: static_multiname(txunit, type_name, mc)?,
+            },
+            AbcOp::AsTypeLate => Op::AsTypeLate,
+            AbcOp::BitAnd => Op::BitAnd,
+            AbcOp::BitNot => Op::BitNot,
+            AbcOp::BitOr => Op::BitOr,
+            AbcOp::BitXor => Op::BitXor,
+            AbcOp::Call { num_args } => Op::Call { num_args },
+            AbcOp::CallMethod { index, num_args } => Op::CallMethod { index, num_args },
+            AbcOp::CallProperty { index, num_args } => {
+                let name = MultinameOperand::decode(txunit, index, mc)?;
+                let cache = name.property_cache(mc);
+                Op::CallProperty {
+                    name,
+                    num_args,
+                    cache,
+                }
+            }
+            AbcOp::CallPropLex { index, num_args } => Op::CallPropLex {
+                name: MultinameOperand::decode(txunit, index, mc)?,
+                num_args,
+            },
+            AbcOp::CallPropVoid { index, num_args } => {
+                let name = MultinameOperand::decode(txunit, index, mc)?;
+                let cache = name.property_cache(mc);
+                Op::CallPropVoid {
+                    name,
+                    num_args,
+                    cache,
+                }
+            }
+            AbcOp::CallStatic { index, num_args } => Op::CallStatic { index, num_args },
+            AbcOp::CallSuper { index, num_args } => Op::CallSuper {
+                name: MultinameOperand::decode(txunit, index, mc)?,
+                num_args,
+            },
+            AbcOp::CallSuperVoid { index, num_args } => Op::CallSuperVoid {
+                name: MultinameOperand::decode(txunit, index, mc)?,
+                num_args,
+            },
+            AbcOp::Coerce { index } => Op::Coerce {
+                type_name: if index.0 == 0 {
+                    Gc::allocate(mc, Multiname::any())
+                } else {
+                    static_multiname(txunit, index, mc)?
+                },
+            },
+            AbcOp::CoerceA => Op::CoerceA,
+            AbcOp::CoerceS => Op::CoerceS,
+            AbcOp::Construct { num_args } => Op::Construct { num_args },
+            AbcOp::ConstructProp { index, num_args } => Op::ConstructProp {
+                name: MultinameOperand::decode(txunit, index, mc)?,
+                num_args,
+            },
+            AbcOp::ConstructSuper { num_args } => Op::ConstructSuper { num_args },
+            AbcOp::ConvertB => Op::ConvertB,
+            AbcOp::ConvertD => Op::ConvertD,
+            AbcOp::ConvertI => Op::ConvertI,
+            AbcOp::ConvertO => Op::ConvertO,
+            AbcOp::ConvertS => Op::ConvertS,
+            AbcOp::ConvertU => Op::ConvertU,
+            AbcOp::Debug {
+                is_local_register,
+                register_name,
+                register,
+            } => Op::Debug {
+                is_local_register,
+                register_name,
+                register,
+            },
+            AbcOp::DebugFile { file_name } => Op::DebugFile { file_name },
+            AbcOp::DebugLine { line_num } => Op::DebugLine { line_num },
+            AbcOp::DecLocal { index } => Op::DecLocal { index },
+            AbcOp::DecLocalI { index } => Op::DecLocalI { index },
+            AbcOp::Decrement => Op::Decrement,
+            AbcOp::DecrementI => Op::DecrementI,
+            AbcOp::DeleteProperty { index } => Op::DeleteProperty {
+                name: MultinameOperand::decode(txunit, index, mc)?,
+            },
+            AbcOp::Divide => Op::Divide,
+            AbcOp::Dup => Op::Dup,
+            AbcOp::Equals => Op::Equals,
+            AbcOp::EscXAttr => Op::EscXAttr,
+            AbcOp::EscXElem => Op::EscXElem,
+            AbcOp::FindProperty { index } => Op::FindProperty {
+                name: MultinameOperand::decode(txunit, index, mc)?,
+            },
+            AbcOp::FindPropStrict { index } => Op::FindPropStrict {
+                name: MultinameOperand::decode(txunit, index, mc)?,
+            },
+            AbcOp::GetGlobalScope => Op::GetGlobalScope,
+            AbcOp::GetGlobalSlot { index } => Op::GetGlobalSlot { index },
+            AbcOp::GetLex { index } => Op::GetLex {
+                name: static_multiname(txunit, index, mc)?,
+            },
+            AbcOp::GetLocal { index } => Op::GetLocal { index },
+            AbcOp::GetProperty { index } => {
+                let name = MultinameOperand::decode(txunit, index, mc)?;
+                let cache = name.property_cache(mc);
+                Op::GetProperty { name, cache }
+            }
+            AbcOp::GetScopeObject { index } => Op::GetScopeObject { index },
+            AbcOp::GetSlot { index } => Op::GetSlot { index },
+            AbcOp::GetSuper { index } => Op::GetSuper {
+                name: MultinameOperand::decode(txunit, index, mc)?,
+            },
+            AbcOp::GreaterEquals => Op::GreaterEquals,
+            AbcOp::GreaterThan => Op::GreaterThan,
+            AbcOp::HasNext => Op::HasNext,
+            AbcOp::HasNext2 {
+                object_register,
+                index_register,
+            } => Op::HasNext2 {
+                object_register,
+                index_register,
+            },
+            AbcOp::IfEq { offset } => Op::IfEq {
+                target: target(end, offset),
+            },
+            AbcOp::IfFalse { offset } => Op::IfFalse {
+                target: target(end, offset),
+            },
+            AbcOp::IfGe { offset } => Op::IfGe {
+                target: target(end, offset),
+            },
+            AbcOp::IfGt { offset } => Op::IfGt {
+                target: target(end, offset),
+            },
+            AbcOp::IfLe { offset } => Op::IfLe {
+                target: target(end, offset),
+            },
+            AbcOp::IfLt { offset } => Op::IfLt {
+                target: target(end, offset),
+            },
+            AbcOp::IfNge { offset } => Op::IfNge {
+                target: target(end, offset),
+            },
+            AbcOp::IfNgt { offset } => Op::IfNgt {
+                target: target(end, offset),
+            },
+            AbcOp::IfNle { offset } => Op::IfNle {
+                target: target(end, offset),
+            },
+            AbcOp::IfNlt { offset } => Op::IfNlt {
+                target: target(end, offset),
+            },
+            AbcOp::IfNe { offset } => Op::IfNe {
+                target: target(end, offset),
+            },
+            AbcOp::IfStrictEq { offset } => Op::IfStrictEq {
+                target: target(end, offset),
+            },
+            AbcOp::IfStrictNe { offset } => Op::IfStrictNe {
+                target: target(end, offset),
+            },
+            AbcOp::IfTrue { offset } => Op::IfTrue {
+                target: target(end, offset),
+            },
+            AbcOp::In => Op::In,
+            AbcOp::IncLocal { index } => Op::IncLocal { index },
+            AbcOp::IncLocalI { index } => Op::IncLocalI { index },
+            AbcOp::Increment => Op::Increment,
+            AbcOp::IncrementI => Op::IncrementI,
+            AbcOp::InitProperty { index } => Op::InitProperty {
+                name: MultinameOperand::decode(txunit, index, mc)?,
+            },
+            AbcOp::InstanceOf => Op::InstanceOf,
+            AbcOp::IsType { index } => Op::IsType {
+                type_name: static_multiname(txunit, index, mc)?,
+            },
+            AbcOp::IsTypeLate => Op::IsTypeLate,
+            AbcOp::Jump { offset } => Op::Jump {
+                target: target(end, offset),
+            },
+            AbcOp::Kill { index } => Op::Kill { index },
+            AbcOp::Label => Op::Label,
+            AbcOp::LessEquals => Op::LessEquals,
+            AbcOp::LessThan => Op::LessThan,
+            AbcOp::Lf32 => Op::Lf32,
+            AbcOp::Lf64 => Op::Lf64,
+            AbcOp::Li16 => Op::Li16,
+            AbcOp::Li32 => Op::Li32,
+            AbcOp::Li8 => Op::Li8,
+            // Switch offsets are relative to the start of the instruction.
+            AbcOp::LookupSwitch {
+                default_offset,
+                case_offsets,
+            } => Op::LookupSwitch {
+                default_target: target(start, default_offset),
+                case_targets: case_offsets
+                    .iter()
+                    .map(|offset| target(start, *offset))
+                    .collect(),
+            },
+            AbcOp::LShift => Op::LShift,
+            AbcOp::Modulo => Op::Modulo,
+            AbcOp::Multiply => Op::Multiply,
+            AbcOp::MultiplyI => Op::MultiplyI,
+            AbcOp::Negate => Op::Negate,
+            AbcOp::NegateI => Op::NegateI,
+            AbcOp::NewActivation => Op::NewActivation,
+            AbcOp::NewArray { num_args } => Op::NewArray { num_args },
+            AbcOp::NewClass { index } => Op::NewClass { index },
+            AbcOp::NewFunction { index } => Op::NewFunction { index },
+            AbcOp::NewObject { num_args } => Op::NewObject { num_args },
+            AbcOp::NextName => Op::NextName,
+            AbcOp::NextValue => Op::NextValue,
+            AbcOp::Nop => Op::Nop,
+            AbcOp::Not => Op::Not,
+            AbcOp::Pop => Op::Pop,
+            AbcOp::PopScope => Op::PopScope,
+            AbcOp::PushByte { value } => Op::PushByte { value },
+            AbcOp::PushDouble { value } => Op::PushDouble {
+                value: value::abc_double(txunit, value)?,
+            },
+            AbcOp::PushFalse => Op::PushFalse,
+            AbcOp::PushInt { value } => Op::PushInt {
+                value: value::abc_int(txunit, value)?,
+            },
+            AbcOp::PushNamespace { value } => Op::PushNamespace {
+                value: Namespace::from_abc_namespace(txunit, value, mc)?,
+            },
+            AbcOp::PushNaN => Op::PushNaN,
+            AbcOp::PushNull => Op::PushNull,
+            AbcOp::PushScope => Op::PushScope,
+            AbcOp::PushShort { value } => Op::PushShort { value },
+            AbcOp::PushString { value } => Op::PushString {
+                value: txunit.pool_string(value.0, mc)?,
+            },
+            AbcOp::PushTrue => Op::PushTrue,
+            AbcOp::PushUint { value } => Op::PushUint {
+                value: value::abc_uint(txunit, value)?,
+            },
+            AbcOp::PushUndefined => Op::PushUndefined,
+            AbcOp::PushWith => Op::PushWith,
+            AbcOp::ReturnValue => Op::ReturnValue,
+            AbcOp::ReturnVoid => Op::ReturnVoid,
+            AbcOp::RShift => Op::RShift,
+            AbcOp::SetGlobalSlot { index } => Op::SetGlobalSlot { index },
+            AbcOp::SetLocal { index } => Op::SetLocal { index },
+            AbcOp::SetProperty { index } => {
+                let name = MultinameOperand::decode(txunit, index, mc)?;
+                let cache = name.property_cache(mc);
+                Op::SetProperty { name, cache }
+            }
+            AbcOp::SetSlot { index } => Op::SetSlot { index },
+            AbcOp::SetSuper { index } => Op::SetSuper {
+                name: MultinameOperand::decode(txunit, index, mc)?,
+            },
+            AbcOp::Sf32 => Op::Sf32,
+            AbcOp::Sf64 => Op::Sf64,
+            AbcOp::Si16 => Op::Si16,
+            AbcOp::Si32 => Op::Si32,
+            AbcOp::Si8 => Op::Si8,
+            AbcOp::StrictEquals => Op::StrictEquals,
+            AbcOp::Subtract => Op::Subtract,
+            AbcOp::SubtractI => Op::SubtractI,
+            AbcOp::Swap => Op::Swap,
+            AbcOp::Sxi1 => Op::Sxi1,
+            AbcOp::Sxi16 => Op::Sxi16,
+            AbcOp::Sxi8 => Op::Sxi8,
+            AbcOp::TypeOf => Op::TypeOf,
+            AbcOp::URShift => Op::URShift,
+            op => Op::Unsupported { op },
+        })
+    }
+
+    /// Call a function on every branch target of this instruction.
+    fn for_each_target(&mut self, mut f: impl FnMut(&mut usize)) {
+        match self {
+            Op::IfEq { target }
+            | Op::IfFalse { target }
+            | Op::IfGe { target }
+            | Op::IfGt { target }
+            | Op::IfLe { target }
+            | Op::IfLt { target }
+            | Op::IfNge { target }
+            | Op::IfNgt { target }
+            | Op::IfNle { target }
+            | Op::IfNlt { target }
+            | Op::IfNe { target }
+            | Op::IfStrictEq { target }
+            | Op::IfStrictNe { target }
+            | Op::IfTrue { target }
+            | Op::Jump { target } => f(target),
+            Op::LookupSwitch {
+                default_target,
+                case_targets,
+            } => {
+                f(default_target);
+                case_targets.iter_mut().for_each(f);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'gc> MultinameOperand<'gc> {
+    /// Decode a multiname operand, resolving it now if it does not depend on
+    /// the stack.
+    fn decode(
+        txunit: TranslationUnit<'gc>,
+        index: Index<AbcMultiname>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Result<Self, Error> {
+        let abc = txunit.abc();
+        let abc_multiname = Multiname::resolve_multiname_index(&abc, index.clone())?;
+
+        Ok(match abc_multiname {
+            AbcMultiname::QName { .. }
+            | AbcMultiname::QNameA { .. }
+            | AbcMultiname::Multiname { .. }
+            | AbcMultiname::MultinameA { .. } => {
+                MultinameOperand::Static(static_multiname(txunit, index, mc)?)
+            }
+            _ => MultinameOperand::Runtime { index },
+        })
+    }
+
+    /// Allocate an inline cache for an instruction using this name.
+    ///
+    /// Names read from the stack can differ on every execution, so they are
+    /// never cached.
+    fn property_cache(
+        &self,
+        mc: MutationContext<'gc, '_>,
+    ) -> Option<GcCell<'gc, PropertyCache<'gc>>> {
+        match self {
+            MultinameOperand::Static(_) => Some(GcCell::allocate(mc, PropertyCache::default())),
+            MultinameOperand::Runtime { .. } => None,
+        }
+    }
+}
+
+fn static_multiname<'gc>(
+    txunit: TranslationUnit<'gc>,
+    index: Index<AbcMultiname>,
+    mc: MutationContext<'gc, '_>,
+) -> Result<Gc<'gc, Multiname<'gc>>, Error> {
+    Ok(Gc::allocate(
+        mc,
+        Multiname::from_abc_multiname_static(txunit, index, mc)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::activation::Activation;
+    use crate::avm2::test_utils::with_avm2;
+    use std::rc::Rc;
+    use swf::avm2::types::{AbcFile, ConstantPool, Namespace as AbcNamespace};
+
+    /// Build a translation unit whose constant pool has a static name and a
+    /// runtime name for `leading`.
+    fn translation_unit<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> TranslationUnit<'gc> {
+        let abc = AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![],
+                uints: vec![],
+                doubles: vec![],
+                strings: vec!["".to_string(), "leading".to_string()],
+                namespaces: vec![AbcNamespace::Package(Index::new(1))],
+                namespace_sets: vec![vec![Index::new(1)]],
+                multinames: vec![
+                    AbcMultiname::QName {
+                        namespace: Index::new(1),
+                        name: Index::new(2),
+                    },
+                    AbcMultiname::MultinameL {
+                        namespace_set: Index::new(1),
+                    },
+                ],
+            },
+            methods: vec![],
+            metadata: vec![],
+            instances: vec![],
+            classes: vec![],
+            scripts: vec![],
+            method_bodies: vec![],
+        };
+        let domain = activation.avm2().global_domain();
+
+        TranslationUnit::from_abc(Rc::new(abc), domain, activation.context.gc_context)
+    }
+
+    fn decode<'gc>(activation: &mut Activation<'_, 'gc, '_>, code: &[u8]) -> DecodedBody<'gc> {
+        let body = AbcMethodBody {
+            method: Index::new(0),
+            max_stack: 2,
+            num_locals: 1,
+            init_scope_depth: 0,
+            max_scope_depth: 0,
+            code: code.to_vec(),
+            exceptions: vec![],
+            traits: vec![],
+        };
+        let txunit = translation_unit(activation);
+
+        DecodedBody::decode(txunit, &body, activation.context.gc_context)
+    }
+
+    #[test]
+    fn branch_targets_are_instruction_indices() {
+        with_avm2(|activation| {
+            // pushbyte 1; iftrue +2; pushbyte 2; returnvalue
+            let body = decode(activation, &[0x24, 1, 0x11, 2, 0, 0, 0x24, 2, 0x48]);
+
+            let ops = body.ops();
+            assert_eq!(ops.len(), 5);
+            assert!(matches!(ops[0], Op::PushByte { value: 1 }));
+            assert!(matches!(ops[1], Op::IfTrue { target: 3 }));
+            assert!(matches!(ops[2], Op::PushByte { value: 2 }));
+            assert!(matches!(ops[3], Op::ReturnValue));
+            // Running off the end of the body fails when executed.
+            assert!(matches!(ops[4], Op::Undecodable { .. }));
+
+            let offsets: Vec<_> = (0..ops.len()).map(|i| body.offset(i)).collect();
+            assert_eq!(offsets, [Some(0), Some(2), Some(6), Some(8), Some(9)]);
+            assert_eq!(body.offset(ops.len()), None);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn branches_into_an_instruction_decode_its_bytes() {
+        with_avm2(|activation| {
+            // jump +1 lands on the operand of `pushbyte 0x48`, which is a
+            // `returnvalue`.
+            let body = decode(activation, &[0x10, 1, 0, 0, 0x24, 0x48, 0x48]);
+
+            let ops = body.ops();
+            assert_eq!(ops.len(), 6);
+            assert!(matches!(ops[0], Op::Jump { target: 4 }));
+            assert!(matches!(ops[1], Op::PushByte { value: 0x48 }));
+            assert!(matches!(ops[2], Op::ReturnValue));
+            assert!(matches!(ops[3], Op::Undecodable { .. }));
+            assert!(matches!(ops[4], Op::ReturnValue));
+            // The second run joins the first one where they overlap.
+            assert!(matches!(ops[5], Op::Jump { target: 2 }));
+
+            assert_eq!(body.offset(4), Some(5));
+            assert_eq!(body.offset(5), Some(6));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn branches_out_of_the_body_land_at_its_end() {
+        with_avm2(|activation| {
+            // jump +16
+            let body = decode(activation, &[0x10, 16, 0, 0]);
+
+            let ops = body.ops();
+            assert_eq!(ops.len(), 2);
+            assert!(matches!(ops[0], Op::Jump { target: 1 }));
+            assert!(matches!(ops[1], Op::Undecodable { .. }));
+            assert_eq!(body.offset(1), Some(4));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn only_static_names_are_cached() {
+        with_avm2(|activation| {
+            // setproperty leading; setproperty [runtime name]
+            let body = decode(activation, &[0x61, 1, 0x61, 2]);
+
+            let ops = body.ops();
+            assert!(matches!(
+                ops[0],
+                Op::SetProperty {
+                    name: MultinameOperand::Static(_),
+                    cache: Some(_),
+                }
+            ));
+            assert!(matches!(
+                ops[1],
+                Op::SetProperty {
+                    name: MultinameOperand::Runtime { .. },
+                    cache: None,
+                }
+            ));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn property_cache_hits_for_instances_of_the_cached_class() {
+        with_avm2(|activation| {
+            let metrics_class = activation.avm2().classes().textlinemetrics;
+            let metrics = metrics_class.construct(activation, &[])?;
+            let other_metrics = metrics_class.construct(activation, &[])?;
+            let name = QName::new(Namespace::public(), "leading");
+
+            let cache = GcCell::allocate(activation.context.gc_context, PropertyCache::default());
+            assert!(PropertyCache::lookup(Some(cache), metrics).is_none());
+            assert!(PropertyCache::lookup(None, metrics).is_none());
+
+            let entry = cache
+                .write(activation.context.gc_context)
+                .fill(metrics, &name, None)?
+                .expect("TextLineMetrics is sealed");
+            assert_eq!(entry.name, name);
+            match entry.kind {
+                CachedTrait::Slot { type_name, .. } => {
+                    assert_eq!(type_name.local_name().as_deref(), Some("Number"))
+                }
+                kind => panic!("Expected a slot, got {:?}", kind),
+            }
+
+            let hit = PropertyCache::lookup(Some(cache), other_metrics)
+                .expect("Instances of the same class share the cache entry");
+            assert_eq!(hit.name, name);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn property_cache_misses_after_another_class_is_cached() {
+        with_avm2(|activation| {
+            let metrics_class = activation.avm2().classes().textlinemetrics;
+            let format_class = activation.avm2().classes().textformat;
+            let metrics = metrics_class.construct(activation, &[])?;
+            let format = format_class.construct(activation, &[])?;
+            let name = QName::new(Namespace::public(), "leading");
+
+            let mut cache = PropertyCache::default();
+            cache.fill(metrics, &name, None)?;
+            assert!(cache.get(metrics).is_some());
+            assert!(cache.get(format).is_none());
+
+            // Both classes have a `leading` slot, with different types.
+            let entry = cache
+                .fill(format, &name, None)?
+                .expect("TextFormat is sealed");
+            match entry.kind {
+                CachedTrait::Slot { type_name, .. } => {
+                    assert_eq!(type_name.local_name().as_deref(), Some("Object"))
+                }
+                kind => panic!("Expected a slot, got {:?}", kind),
+            }
+            assert!(cache.get(format).is_some());
+            assert!(cache.get(metrics).is_none());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn property_cache_skips_dynamic_objects() {
+        with_avm2(|activation| {
+            let object_class = activation.avm2().classes().object;
+            let mut object = object_class.construct(activation, &[])?;
+            let name = QName::dynamic_name("leading");
+            object.set_property(object, &name, "value".into(), activation)?;
+
+            let mut cache = PropertyCache::default();
+            assert!(cache.fill(object, &name, None)?.is_none());
+            assert!(cache.get(object).is_none());
+
+            Ok(())
+        });
+    }
+}
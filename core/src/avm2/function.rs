@@ -2,10 +2,11 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::method::{BytecodeMethod, Method, NativeMethod};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
 use crate::avm2::scope::Scope;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::profiler::ProfileCategory;
 use gc_arena::{Collect, CollectionContext, Gc, GcCell, MutationContext};
 use std::fmt;
 
@@ -139,6 +140,7 @@ impl<'gc> Executable<'gc> {
                 }
 
                 let receiver = bm.receiver.or(unbound_receiver);
+                let class = subclass_object.and_then(|class| class.as_class());
                 let mut activation = Activation::from_method(
                     activation.context.reborrow(),
                     bm.method,
@@ -149,7 +151,30 @@ impl<'gc> Executable<'gc> {
                     callee,
                 )?;
 
-                activation.run_actions(bm.method)
+                let span = activation.context.profiler.start();
+                let result = activation.run_actions(bm.method);
+                activation
+                    .context
+                    .profiler
+                    .finish(span, ProfileCategory::Avm2Function, || {
+                        // A method is always found on the same class, so its
+                        // name only needs to be built once.
+                        bm.method.profile_name(|| {
+                            let method_name = match bm.method.method_name() {
+                                "" => "[Anonymous]",
+                                name => name,
+                            };
+                            match class {
+                                Some(class) => format!(
+                                    "{}/{}",
+                                    class.read().name().to_qualified_name(),
+                                    method_name
+                                ),
+                                None => method_name.to_string(),
+                            }
+                        })
+                    });
+                result
             }
         }
     }
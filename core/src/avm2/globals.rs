@@ -99,6 +99,10 @@ pub struct SystemPrototypes<'gc> {
     pub scene: Object<'gc>,
     pub application_domain: Object<'gc>,
     pub event: Object<'gc>,
+    pub focusevent: Object<'gc>,
+    pub mouseevent: Object<'gc>,
+    pub imeevent: Object<'gc>,
+    pub progressevent: Object<'gc>,
     pub video: Object<'gc>,
     pub xml: Object<'gc>,
     pub xml_list: Object<'gc>,
@@ -108,6 +112,7 @@ pub struct SystemPrototypes<'gc> {
     pub rectangle: Object<'gc>,
     pub textfield: Object<'gc>,
     pub textformat: Object<'gc>,
+    pub textlinemetrics: Object<'gc>,
     pub graphics: Object<'gc>,
     pub loaderinfo: Object<'gc>,
     pub bytearray: Object<'gc>,
@@ -151,6 +156,10 @@ impl<'gc> SystemPrototypes<'gc> {
             scene: empty,
             application_domain: empty,
             event: empty,
+            focusevent: empty,
+            mouseevent: empty,
+            imeevent: empty,
+            progressevent: empty,
             video: empty,
             xml: empty,
             xml_list: empty,
@@ -160,6 +169,7 @@ impl<'gc> SystemPrototypes<'gc> {
             rectangle: empty,
             textfield: empty,
             textformat: empty,
+            textlinemetrics: empty,
             graphics: empty,
             loaderinfo: empty,
             bytearray: empty,
@@ -194,6 +204,10 @@ pub struct SystemClasses<'gc> {
     pub scene: Object<'gc>,
     pub application_domain: Object<'gc>,
     pub event: Object<'gc>,
+    pub focusevent: Object<'gc>,
+    pub mouseevent: Object<'gc>,
+    pub imeevent: Object<'gc>,
+    pub progressevent: Object<'gc>,
     pub video: Object<'gc>,
     pub xml: Object<'gc>,
     pub xml_list: Object<'gc>,
@@ -203,6 +217,7 @@ pub struct SystemClasses<'gc> {
     pub rectangle: Object<'gc>,
     pub textfield: Object<'gc>,
     pub textformat: Object<'gc>,
+    pub textlinemetrics: Object<'gc>,
     pub graphics: Object<'gc>,
     pub loaderinfo: Object<'gc>,
     pub bytearray: Object<'gc>,
@@ -246,6 +261,10 @@ impl<'gc> SystemClasses<'gc> {
             scene: empty,
             application_domain: empty,
             event: empty,
+            focusevent: empty,
+            mouseevent: empty,
+            imeevent: empty,
+            progressevent: empty,
             video: empty,
             xml: empty,
             xml_list: empty,
@@ -255,6 +274,7 @@ impl<'gc> SystemClasses<'gc> {
             rectangle: empty,
             textfield: empty,
             textformat: empty,
+            textlinemetrics: empty,
             graphics: empty,
             loaderinfo: empty,
             bytearray: empty,
@@ -548,6 +568,14 @@ pub fn load_player_globals<'gc>(
         script,
     )?;
 
+    // package `flash.external`
+    class(
+        activation,
+        flash::external::external_interface::create_class(mc),
+        domain,
+        script,
+    )?;
+
     // package `flash.events`
     avm2_system_class!(
         event,
@@ -568,12 +596,40 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
-    class(
+    avm2_system_class!(
+        mouseevent,
         activation,
         flash::events::mouseevent::create_class(mc),
         domain,
+        script
+    );
+    avm2_system_class!(
+        focusevent,
+        activation,
+        flash::events::focusevent::create_class(mc),
+        domain,
+        script
+    );
+    class(
+        activation,
+        flash::events::textevent::create_class(mc),
+        domain,
         script,
     )?;
+    avm2_system_class!(
+        imeevent,
+        activation,
+        flash::events::imeevent::create_class(mc),
+        domain,
+        script
+    );
+    avm2_system_class!(
+        progressevent,
+        activation,
+        flash::events::progressevent::create_class(mc),
+        domain,
+        script
+    );
     // package `flash.utils`
     avm2_system_class!(
         bytearray,
@@ -846,6 +902,13 @@ pub fn load_player_globals<'gc>(
         domain,
         script
     );
+    avm2_system_class!(
+        textlinemetrics,
+        activation,
+        flash::text::textlinemetrics::create_class(mc),
+        domain,
+        script
+    );
     class(
         activation,
         flash::text::textfieldautosize::create_class(mc),
@@ -870,6 +933,12 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    class(
+        activation,
+        flash::text::stylesheet::create_class(mc),
+        domain,
+        script,
+    )?;
 
     // package `flash.crypto`
     function(
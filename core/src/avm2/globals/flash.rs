@@ -3,6 +3,7 @@
 pub mod crypto;
 pub mod display;
 pub mod events;
+pub mod external;
 pub mod geom;
 pub mod media;
 pub mod system;
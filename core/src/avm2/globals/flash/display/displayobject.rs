@@ -567,10 +567,21 @@ pub fn loader_info<'gc>(
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         if let Some(root) = dobj.avm2_root(&mut activation.context) {
             if DisplayObject::ptr_eq(root, dobj) {
+                let stage = activation.context.stage;
+                let is_root_movie = DisplayObject::ptr_eq(root, stage.root_clip());
+                if is_root_movie {
+                    if let Some(obj) = stage.root_loader_info() {
+                        return Ok(obj.into());
+                    }
+                }
+
                 let movie = dobj.movie();
 
                 if let Some(movie) = movie {
                     let obj = LoaderInfoObject::from_movie(activation, movie, root)?;
+                    if is_root_movie {
+                        stage.set_root_loader_info(activation.context.gc_context, Some(obj));
+                    }
 
                     return Ok(obj.into());
                 }
@@ -277,6 +277,68 @@ pub fn num_children<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `DisplayObjectContainer.tabChildren`'s getter.
+pub fn tab_children<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.tab_children().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObjectContainer.tabChildren`'s setter.
+pub fn set_tab_children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+        dobj.set_tab_children(activation.context.gc_context, value);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObjectContainer.mouseChildren`'s getter.
+pub fn mouse_children<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.mouse_children().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObjectContainer.mouseChildren`'s setter.
+pub fn set_mouse_children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+        dobj.set_mouse_children(activation.context.gc_context, value);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `DisplayObjectContainer.contains`
 pub fn contains<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -608,7 +670,15 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         &str,
         Option<NativeMethodImpl>,
         Option<NativeMethodImpl>,
-    )] = &[("numChildren", Some(num_children), None)];
+    )] = &[
+        ("numChildren", Some(num_children), None),
+        ("tabChildren", Some(tab_children), Some(set_tab_children)),
+        (
+            "mouseChildren",
+            Some(mouse_children),
+            Some(set_mouse_children),
+        ),
+    ];
     write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
 
     const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
@@ -2,11 +2,12 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
-use crate::avm2::method::Method;
+use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `flash.display.InteractiveObject`'s instance constructor.
@@ -40,6 +41,164 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `InteractiveObject.tabEnabled`'s getter.
+pub fn tab_enabled<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.is_tab_enabled().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `InteractiveObject.tabEnabled`'s setter.
+pub fn set_tab_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+        dobj.set_tab_enabled(activation.context.gc_context, Some(value));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `InteractiveObject.tabIndex`'s getter.
+pub fn tab_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.tab_index().unwrap_or(-1).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `InteractiveObject.tabIndex`'s setter.
+pub fn set_tab_index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        if value < -1 {
+            return Err("RangeError: Parameter tabIndex must be a non-negative number.".into());
+        }
+        let value = if value == -1 { None } else { Some(value) };
+        dobj.set_tab_index(activation.context.gc_context, value);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `InteractiveObject.focusRect`'s getter.
+pub fn focus_rect<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.focus_rect().map_or(Value::Null, Value::from));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `InteractiveObject.focusRect`'s setter.
+pub fn set_focus_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let value = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            value => Some(value.coerce_to_boolean()),
+        };
+        dobj.set_focus_rect(activation.context.gc_context, value);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `InteractiveObject.mouseEnabled`'s getter.
+pub fn mouse_enabled<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.mouse_enabled().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `InteractiveObject.mouseEnabled`'s setter.
+pub fn set_mouse_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+        dobj.set_mouse_enabled(activation.context.gc_context, value);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `InteractiveObject.doubleClickEnabled`'s getter.
+pub fn double_click_enabled<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.double_click_enabled().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `InteractiveObject.doubleClickEnabled`'s setter.
+pub fn set_double_click_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+        dobj.set_double_click_enabled(activation.context.gc_context, value);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `InteractiveObject`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -62,5 +221,22 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         mc,
     ));
 
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[
+        ("tabEnabled", Some(tab_enabled), Some(set_tab_enabled)),
+        ("tabIndex", Some(tab_index), Some(set_tab_index)),
+        ("focusRect", Some(focus_rect), Some(set_focus_rect)),
+        ("mouseEnabled", Some(mouse_enabled), Some(set_mouse_enabled)),
+        (
+            "doubleClickEnabled",
+            Some(double_click_enabled),
+            Some(set_double_click_enabled),
+        ),
+    ];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
     class
 }
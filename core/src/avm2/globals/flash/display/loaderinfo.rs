@@ -100,10 +100,29 @@ pub fn application_domain<'gc>(
     Ok(Value::Undefined)
 }
 
+/// `bytesLoaded` getter
+pub fn bytes_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(loader_stream) = this.as_loader_stream() {
+            match &*loader_stream {
+                LoaderStream::Stage => {
+                    return Ok(activation.context.swf.compressed_loaded().into())
+                }
+                LoaderStream::Swf(movie, _) => {
+                    return Ok(movie.compressed_loaded().into());
+                }
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// `bytesTotal` getter
-///
-/// TODO: This is also the getter for `bytesLoaded` as we don't yet support
-/// streaming loads yet. When we do, we'll need another property for this.
 pub fn bytes_total<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -430,7 +449,7 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
     )] = &[
         ("actionScriptVersion", Some(action_script_version), None),
         ("applicationDomain", Some(application_domain), None),
-        ("bytesLoaded", Some(bytes_total), None),
+        ("bytesLoaded", Some(bytes_loaded), None),
         ("bytesTotal", Some(bytes_total), None),
         ("content", Some(content), None),
         ("contentType", Some(content_type), None),
@@ -9,8 +9,13 @@ use crate::avm2::object::{Object, StageObject, TObject};
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
-use crate::display_object::{SoundTransform, TDisplayObject};
+use crate::bounding_box::BoundingBox;
+use crate::display_object::{MovieClip, SoundTransform, TDisplayObject};
+use crate::player::DragObject;
+use crate::tag_utils::{SwfMovie, SwfSlice};
 use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
+use swf::Twips;
 
 /// Implements `flash.display.Sprite`'s instance constructor.
 pub fn instance_init<'gc>(
@@ -20,6 +25,21 @@ pub fn instance_init<'gc>(
 ) -> Result<Value<'gc>, Error> {
     if let Some(this) = this {
         activation.super_init(this, &[])?;
+
+        if this.as_display_object().is_none() {
+            let class_object = this
+                .as_class_object()
+                .ok_or("Attempted to construct non-instance Sprite")?;
+            let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+            let new_do = MovieClip::new_with_avm2(
+                SwfSlice::empty(movie),
+                this,
+                class_object,
+                activation.context.gc_context,
+            );
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+        }
     }
 
     Ok(Value::Undefined)
@@ -102,6 +122,218 @@ pub fn set_sound_transform<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `buttonMode`'s getter
+pub fn button_mode<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|o| o.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        return Ok(mc.button_mode().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `buttonMode`'s setter
+pub fn set_button_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|o| o.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        let button_mode = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+        mc.set_button_mode(activation.context.gc_context, button_mode);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `useHandCursor`'s getter
+pub fn use_hand_cursor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|o| o.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        return Ok(mc.use_hand_cursor().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `useHandCursor`'s setter
+pub fn set_use_hand_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|o| o.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        let use_hand_cursor = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+        mc.set_use_hand_cursor(&mut activation.context, use_hand_cursor);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `hitArea`'s getter
+pub fn hit_area<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|o| o.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        return Ok(mc
+            .hit_area()
+            .map(|hit_area| hit_area.object2())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `hitArea`'s setter
+pub fn set_hit_area<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|o| o.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        let hit_area = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            hit_area => hit_area.coerce_to_object(activation)?.as_display_object(),
+        };
+        mc.set_hit_area(activation.context.gc_context, hit_area);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `dropTarget`'s getter
+pub fn drop_target<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|o| o.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        // Objects without an AVM2 side, such as timeline shapes, read as null.
+        return Ok(match mc.drop_target().map(|target| target.object2()) {
+            Some(Value::Object(target)) => target.into(),
+            _ => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Sprite.startDrag`
+pub fn start_drag<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|o| o.as_display_object()) {
+        let lock_center = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+
+        let offset = if lock_center {
+            // The object's origin point is locked to the mouse.
+            Default::default()
+        } else {
+            // The object moves relative to current mouse position.
+            // Calculate the offset from the mouse to the object in world space.
+            let obj_pos = dobj.local_to_global(Default::default());
+            (
+                obj_pos.0 - activation.context.mouse_position.0,
+                obj_pos.1 - activation.context.mouse_position.1,
+            )
+        };
+
+        let constraint = match args.get(1).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => Default::default(),
+            bounds => drag_constraint(activation, bounds.coerce_to_object(activation)?)?,
+        };
+
+        *activation.context.drag_object = Some(DragObject {
+            display_object: dobj,
+            offset,
+            constraint,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Converts the `Rectangle` passed to `startDrag` into the area the dragged
+/// object is kept in, in the coordinate space of its parent.
+fn drag_constraint<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    bounds: Object<'gc>,
+) -> Result<BoundingBox, Error> {
+    let mut values = [0.0; 4];
+    for (value, name) in values.iter_mut().zip(&["x", "y", "width", "height"]) {
+        let n = bounds
+            .get_property(bounds, &QName::new(Namespace::public(), *name), activation)?
+            .coerce_to_number(activation)?;
+        // Invalid values turn into 0.
+        *value = if n.is_finite() { n } else { 0.0 };
+    }
+    let [x, y, width, height] = values;
+
+    // A negative size extends the rectangle to the left or top.
+    Ok(BoundingBox {
+        valid: true,
+        x_min: Twips::from_pixels(x.min(x + width)),
+        y_min: Twips::from_pixels(y.min(y + height)),
+        x_max: Twips::from_pixels(x.max(x + width)),
+        y_max: Twips::from_pixels(y.max(y + height)),
+    })
+}
+
+/// Implements `Sprite.stopDrag`
+pub fn stop_drag<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    // It doesn't matter which sprite we call this on; it simply stops any active drag.
+    *activation.context.drag_object = None;
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `Sprite`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -133,9 +365,21 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
             Some(sound_transform),
             Some(set_sound_transform),
         ),
+        ("buttonMode", Some(button_mode), Some(set_button_mode)),
+        (
+            "useHandCursor",
+            Some(use_hand_cursor),
+            Some(set_use_hand_cursor),
+        ),
+        ("hitArea", Some(hit_area), Some(set_hit_area)),
+        ("dropTarget", Some(drop_target), None),
     ];
     write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
 
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] =
+        &[("startDrag", start_drag), ("stopDrag", stop_drag)];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
     // Slot for lazy-initialized Graphics object.
     write.define_instance_trait(Trait::from_slot(
         QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "graphics"),
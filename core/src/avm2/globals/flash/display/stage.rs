@@ -477,6 +477,29 @@ pub fn set_show_default_context_menu<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implement `stageFocusRect`'s getter
+pub fn stage_focus_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.stage.stage_focus_rect().into())
+}
+
+/// Implement `stageFocusRect`'s setter
+pub fn set_stage_focus_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let stage_focus_rect = args.get(0).unwrap_or(&Value::Undefined).coerce_to_boolean();
+    activation
+        .context
+        .stage
+        .set_stage_focus_rect(activation.context.gc_context, stage_focus_rect);
+    Ok(Value::Undefined)
+}
+
 /// Implement `scaleMode`'s getter
 pub fn scale_mode<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -717,6 +740,11 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
             Some(show_default_context_menu),
             Some(set_show_default_context_menu),
         ),
+        (
+            "stageFocusRect",
+            Some(stage_focus_rect),
+            Some(set_stage_focus_rect),
+        ),
         ("stageWidth", Some(stage_width), Some(set_stage_width)),
         ("stageHeight", Some(stage_height), Some(set_stage_height)),
         ("allowsFullScreen", Some(allows_full_screen), None),
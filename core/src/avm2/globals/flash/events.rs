@@ -2,5 +2,9 @@
 
 pub mod event;
 pub mod eventdispatcher;
+pub mod focusevent;
 pub mod ieventdispatcher;
+pub mod imeevent;
 pub mod mouseevent;
+pub mod progressevent;
+pub mod textevent;
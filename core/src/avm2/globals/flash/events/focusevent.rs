@@ -0,0 +1,97 @@
+//! `flash.events.FocusEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.FocusEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let event_args = [
+            args.get(0).cloned().unwrap_or(Value::Undefined),
+            args.get(1).cloned().unwrap_or(Value::Bool(true)),
+            args.get(2).cloned().unwrap_or(Value::Bool(false)),
+        ];
+        activation.super_init(this, &event_args)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "relatedObject"),
+            args.get(3).cloned().unwrap_or(Value::Null),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "shiftKey"),
+            args.get(4).cloned().unwrap_or(Value::Bool(false)),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "keyCode"),
+            args.get(5).cloned().unwrap_or(Value::Unsigned(0)),
+            activation,
+        )?;
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.FocusEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `FocusEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "FocusEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init, "<FocusEvent instance initializer>", mc),
+        Method::from_builtin(class_init, "<FocusEvent class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const CONSTANTS: &[(&str, &str)] = &[
+        ("FOCUS_IN", "focusIn"),
+        ("FOCUS_OUT", "focusOut"),
+        ("KEY_FOCUS_CHANGE", "keyFocusChange"),
+        ("MOUSE_FOCUS_CHANGE", "mouseFocusChange"),
+    ];
+    write.define_public_constant_string_class_traits(CONSTANTS);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "relatedObject"),
+        QName::new(Namespace::package("flash.display"), "InteractiveObject").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "shiftKey"),
+        QName::new(Namespace::public(), "Boolean").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "keyCode"),
+        QName::new(Namespace::public(), "uint").into(),
+        None,
+    ));
+
+    class
+}
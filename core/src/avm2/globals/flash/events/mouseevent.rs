@@ -1,8 +1,11 @@
+//! `flash.events.MouseEvent` builtin/prototype
+
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
-use crate::avm2::method::Method;
+use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use gc_arena::{GcCell, MutationContext};
@@ -13,8 +16,49 @@ pub fn instance_init<'gc>(
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    if let Some(this) = this {
-        activation.super_init(this, args)?; // Event uses the first three parameters
+    if let Some(mut this) = this {
+        let event_args = [
+            args.get(0).cloned().unwrap_or(Value::Undefined),
+            args.get(1).cloned().unwrap_or(Value::Bool(true)),
+            args.get(2).cloned().unwrap_or(Value::Bool(false)),
+        ];
+        activation.super_init(this, &event_args)?;
+
+        let properties = [
+            (
+                "localX",
+                args.get(3).cloned().unwrap_or(Value::Number(f64::NAN)),
+            ),
+            (
+                "localY",
+                args.get(4).cloned().unwrap_or(Value::Number(f64::NAN)),
+            ),
+            ("stageX", Value::Number(f64::NAN)),
+            ("stageY", Value::Number(f64::NAN)),
+            ("relatedObject", args.get(5).cloned().unwrap_or(Value::Null)),
+            (
+                "ctrlKey",
+                args.get(6).cloned().unwrap_or(Value::Bool(false)),
+            ),
+            ("altKey", args.get(7).cloned().unwrap_or(Value::Bool(false))),
+            (
+                "shiftKey",
+                args.get(8).cloned().unwrap_or(Value::Bool(false)),
+            ),
+            (
+                "buttonDown",
+                args.get(9).cloned().unwrap_or(Value::Bool(false)),
+            ),
+            ("delta", args.get(10).cloned().unwrap_or(Value::Integer(0))),
+        ];
+        for (name, value) in properties.iter() {
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                value.clone(),
+                activation,
+            )?;
+        }
     }
     Ok(Value::Undefined)
 }
@@ -28,6 +72,17 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `MouseEvent.updateAfterEvent`.
+pub fn update_after_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    *activation.context.needs_render = true;
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `MouseEvent`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -42,8 +97,43 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     write.set_attributes(ClassAttributes::SEALED);
 
-    const CONSTANTS: &[(&str, &str)] = &[("CLICK", "click")];
+    const CONSTANTS: &[(&str, &str)] = &[
+        ("CLICK", "click"),
+        ("DOUBLE_CLICK", "doubleClick"),
+        ("MOUSE_DOWN", "mouseDown"),
+        ("MOUSE_MOVE", "mouseMove"),
+        ("MOUSE_OUT", "mouseOut"),
+        ("MOUSE_OVER", "mouseOver"),
+        ("MOUSE_UP", "mouseUp"),
+        ("MOUSE_WHEEL", "mouseWheel"),
+        ("ROLL_OUT", "rollOut"),
+        ("ROLL_OVER", "rollOver"),
+    ];
     write.define_public_constant_string_class_traits(CONSTANTS);
 
+    const SLOTS: &[(&str, &str, &str)] = &[
+        ("localX", "", "Number"),
+        ("localY", "", "Number"),
+        ("stageX", "", "Number"),
+        ("stageY", "", "Number"),
+        ("relatedObject", "flash.display", "InteractiveObject"),
+        ("ctrlKey", "", "Boolean"),
+        ("altKey", "", "Boolean"),
+        ("shiftKey", "", "Boolean"),
+        ("buttonDown", "", "Boolean"),
+        ("delta", "", "int"),
+    ];
+    for &(name, type_ns, type_name) in SLOTS {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), name),
+            QName::new(Namespace::package(type_ns), type_name).into(),
+            None,
+        ));
+    }
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] =
+        &[("updateAfterEvent", update_after_event)];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
     class
 }
@@ -0,0 +1,69 @@
+//! `flash.events.TextEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.TextEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, args.get(..3).unwrap_or(args))?; // Event uses the first three parameters
+
+        let text = match args.get(3) {
+            Some(text) => text.coerce_to_string(activation)?.into(),
+            None => "".into(),
+        };
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "text"),
+            text,
+            activation,
+        )?;
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.TextEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `TextEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "TextEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init, "<TextEvent instance initializer>", mc),
+        Method::from_builtin(class_init, "<TextEvent class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const CONSTANTS: &[(&str, &str)] = &[("LINK", "link"), ("TEXT_INPUT", "textInput")];
+    write.define_public_constant_string_class_traits(CONSTANTS);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "text"),
+        QName::new(Namespace::public(), "String").into(),
+        None,
+    ));
+
+    class
+}
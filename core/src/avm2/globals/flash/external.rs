@@ -0,0 +1,3 @@
+//! `flash.external` namespace
+
+pub mod external_interface;
@@ -0,0 +1,137 @@
+//! `flash.external.ExternalInterface` class
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::AvmString;
+use crate::avm2::Error;
+use crate::external::{Callback, Value as ExternalValue};
+use gc_arena::{GcCell, MutationContext};
+
+const UNAVAILABLE_ERROR: &str = "Error: Error #2067: The ExternalInterface is not available in this container. ExternalInterface requires Internet Explorer ActiveX, Firefox, Mozilla 1.7.5 and greater, or other browsers that support NPRuntime.";
+
+/// Implements `flash.external.ExternalInterface`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("The ExternalInterface class cannot be constructed.".into())
+}
+
+/// Implements `flash.external.ExternalInterface`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// `available` static property.
+pub fn available<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.external_interface.available().into())
+}
+
+/// `objectID` static property.
+pub fn object_id<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(match activation.context.external_interface.object_id() {
+        Some(id) => AvmString::new(activation.context.gc_context, id).into(),
+        None => Value::Null,
+    })
+}
+
+/// Implements `ExternalInterface.addCallback`.
+pub fn add_callback<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if !activation.context.external_interface.available() {
+        return Err(UNAVAILABLE_ERROR.into());
+    }
+
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    if let Some(Value::Object(method)) = args.get(1) {
+        activation
+            .context
+            .external_interface
+            .add_callback(name.to_string(), Callback::Avm2 { method: *method });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ExternalInterface.call`.
+pub fn call<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if !activation.context.external_interface.available() {
+        return Err(UNAVAILABLE_ERROR.into());
+    }
+
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    if let Some(method) = activation.context.external_interface.get_method_for(&name) {
+        let mut external_args = Vec::with_capacity(args.len().saturating_sub(1));
+        for arg in args.iter().skip(1) {
+            external_args.push(ExternalValue::from_avm2(activation, arg.to_owned())?);
+        }
+        Ok(method
+            .call(&mut activation.context, &external_args)
+            .into_avm2(activation))
+    } else {
+        Ok(Value::Null)
+    }
+}
+
+/// Construct `ExternalInterface`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.external"), "ExternalInterface"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(
+            instance_init,
+            "<ExternalInterface instance initializer>",
+            mc,
+        ),
+        Method::from_builtin(class_init, "<ExternalInterface class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    const PUBLIC_CLASS_TRAITS: &[(&str, Option<NativeMethodImpl>, Option<NativeMethodImpl>)] = &[
+        ("available", Some(available), None),
+        ("objectID", Some(object_id), None),
+    ];
+    write.define_public_builtin_class_properties(mc, PUBLIC_CLASS_TRAITS);
+
+    const PUBLIC_CLASS_METHODS: &[(&str, NativeMethodImpl)] =
+        &[("addCallback", add_callback), ("call", call)];
+    write.define_public_builtin_class_methods(mc, PUBLIC_CLASS_METHODS);
+
+    class
+}
@@ -0,0 +1,230 @@
+//! `flash.text.StyleSheet` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{stylesheet_allocator, ArrayObject, Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::html::{apply_css_style, CssStyle, TextFormat};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.text.StyleSheet`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.text.StyleSheet`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Reads the enumerable properties of a style object as CSS declarations.
+fn style_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) -> Result<CssStyle, Error> {
+    let mut style = CssStyle::new();
+    let mut index = 1;
+    while let Some(name) = object.get_enumerant_name(index) {
+        let value = object.get_property(object, &name, activation)?;
+        if !matches!(value, Value::Undefined | Value::Null) {
+            let value = value.coerce_to_string(activation)?.to_string();
+            style.insert(name.local_name().to_string(), value);
+        }
+        index += 1;
+    }
+    Ok(style)
+}
+
+/// Implements `StyleSheet.styleNames`.
+pub fn style_names<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let names: Option<Vec<String>> = this
+            .as_style_sheet()
+            .map(|sheet| sheet.style_names().map(str::to_string).collect());
+
+        if let Some(names) = names {
+            let storage: ArrayStorage<'gc> = names
+                .into_iter()
+                .map(|name| AvmString::new(activation.context.gc_context, name).into())
+                .collect();
+            return Ok(ArrayObject::from_storage(activation, storage)?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `StyleSheet.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut sheet) =
+        this.and_then(|this| this.as_style_sheet_mut(activation.context.gc_context))
+    {
+        sheet.clear();
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `StyleSheet.getStyle`.
+pub fn get_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let style = match this.as_style_sheet() {
+            Some(sheet) => sheet.style(&name).cloned(),
+            None => return Ok(Value::Undefined),
+        };
+
+        // A copy is returned, so changing it doesn't affect the stylesheet.
+        if let Some(style) = style {
+            let mut object = ScriptObject::object(
+                activation.context.gc_context,
+                activation.avm2().prototypes().object,
+            );
+            for (name, value) in style {
+                let name = QName::new(
+                    Namespace::public(),
+                    AvmString::new(activation.context.gc_context, name),
+                );
+                let value = AvmString::new(activation.context.gc_context, value);
+                object.set_property(object, &name, value.into(), activation)?;
+            }
+            return Ok(object.into());
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `StyleSheet.setStyle`.
+pub fn set_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        // Setting a style to `null` removes it.
+        let style = match args.get(1) {
+            Some(Value::Object(object)) => Some(style_from_object(activation, *object)?),
+            _ => None,
+        };
+
+        if let Some(mut sheet) = this.as_style_sheet_mut(activation.context.gc_context) {
+            sheet.set_style(&name, style);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `StyleSheet.parseCSS`.
+///
+/// Unlike AVM1, CSS that can't be parsed is silently ignored.
+pub fn parse_css<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let css = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if let Some(mut sheet) = this.as_style_sheet_mut(activation.context.gc_context) {
+            sheet.parse_css(&css);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `StyleSheet.transform`.
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(Value::Object(object)) = args.get(0) {
+        let style = style_from_object(activation, *object)?;
+        let tf = apply_css_style(&style, TextFormat::default());
+        return Ok(tf.as_avm2_object(activation)?.into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Construct `StyleSheet`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.text"), "StyleSheet"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init, "<StyleSheet instance initializer>", mc),
+        Method::from_builtin(class_init, "<StyleSheet class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+    write.set_instance_allocator(stylesheet_allocator);
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[("styleNames", Some(style_names), None)];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("clear", clear),
+        ("getStyle", get_style),
+        ("parseCSS", parse_css),
+        ("setStyle", set_style),
+        ("transform", transform),
+    ];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    class
+}
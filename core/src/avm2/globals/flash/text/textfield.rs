@@ -2,6 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::geom::rectangle::create_rectangle;
 use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{Object, TObject};
@@ -11,9 +12,10 @@ use crate::avm2::Error;
 use crate::display_object::{AutoSizeMode, EditText, TDisplayObject, TextSelection};
 use crate::html::TextFormat;
 use crate::tag_utils::SwfMovie;
-use crate::vminterface::AvmType;
+use crate::vminterface::{AvmObject, AvmType};
 use gc_arena::{GcCell, MutationContext};
 use std::sync::Arc;
+use swf::Twips;
 
 /// Implements `flash.text.TextField`'s instance constructor.
 pub fn instance_init<'gc>(
@@ -405,6 +407,80 @@ pub fn set_multiline<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn max_chars<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.max_chars().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_max_chars<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let max_chars = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        this.set_max_chars(max_chars, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn restrict<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(match this.restrict() {
+            Some(restrict) => AvmString::new(activation.context.gc_context, restrict).into(),
+            None => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_restrict<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let restrict = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            value => Some(value.coerce_to_string(activation)?),
+        };
+
+        this.set_restrict(restrict.as_deref(), activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn selectable<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -441,6 +517,46 @@ pub fn set_selectable<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn style_sheet<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(match this.style_sheet() {
+            Some(AvmObject::Avm2(style_sheet)) => style_sheet.into(),
+            _ => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_style_sheet<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let style_sheet = match args.get(0) {
+            Some(Value::Object(object)) if object.as_style_sheet().is_some() => {
+                Some(AvmObject::Avm2(*object))
+            }
+            _ => None,
+        };
+
+        this.set_style_sheet(style_sheet, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn text<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -666,6 +782,30 @@ pub fn append_text<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_image_reference<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let id = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        return Ok(match this.image_by_id(&id, true) {
+            Some(image) => image.object2(),
+            None => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_text_format<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -718,6 +858,7 @@ pub fn replace_selected_text<'gc>(
         let selection = this
             .selection()
             .unwrap_or_else(|| TextSelection::for_position(0));
+        let value = this.filter_input(&value, selection);
 
         this.replace_text(
             selection.start(),
@@ -851,6 +992,365 @@ pub fn set_text_format<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn bottom_scroll_v<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.bottom_scroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn max_scroll_h<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.maxhscroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn max_scroll_v<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.maxscroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn num_lines<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.num_lines().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn scroll_h<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.hscroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_scroll_h<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let hscroll = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)? as f64;
+        let clamped = hscroll.clamp(0.0, this.maxhscroll());
+        this.set_hscroll(clamped, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn scroll_v<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.scroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_scroll_v<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let scroll = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        this.set_scroll(scroll, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Coerce an argument to a character or line index.
+///
+/// Negative indices are never valid, and are returned as `None`.
+fn index_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    i: usize,
+) -> Result<Option<usize>, Error> {
+    let index = args
+        .get(i)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    Ok(if index >= 0 {
+        Some(index as usize)
+    } else {
+        None
+    })
+}
+
+/// Coerce the first two arguments to a point, in twips.
+fn point_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<(Twips, Twips), Error> {
+    let x = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+    let y = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+
+    Ok((Twips::from_pixels(x), Twips::from_pixels(y)))
+}
+
+pub fn get_char_boundaries<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let bounds = index_arg(activation, args, 0)?.and_then(|index| this.char_boundaries(index));
+
+        return match bounds {
+            Some(bounds) => create_rectangle(
+                activation,
+                (
+                    bounds.offset_x().to_pixels(),
+                    bounds.offset_y().to_pixels(),
+                    bounds.width().to_pixels(),
+                    bounds.height().to_pixels(),
+                ),
+            ),
+            None => Ok(Value::Null),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_char_index_at_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let point = point_args(activation, args)?;
+
+        return Ok(this
+            .char_index_at_point(point)
+            .map_or(-1, |index| index as i32)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_first_char_in_paragraph<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(index_arg(activation, args, 0)?
+            .and_then(|index| this.first_char_in_paragraph(index))
+            .map_or(-1, |index| index as i32)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_index_at_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let point = point_args(activation, args)?;
+
+        return Ok(this
+            .line_index_at_point(point)
+            .map_or(-1, |line| line as i32)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_index_of_char<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(index_arg(activation, args, 0)?
+            .and_then(|index| this.line_index_of_char(index))
+            .map_or(-1, |line| line as i32)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return match index_arg(activation, args, 0)?.and_then(|line| this.line_length(line)) {
+            Some(length) => Ok(length.into()),
+            None => Err("RangeError: The supplied index is out of bounds.".into()),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_metrics<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let metrics = match index_arg(activation, args, 0)?.and_then(|line| this.line_metrics(line))
+        {
+            Some(metrics) => metrics,
+            None => return Err("RangeError: The supplied index is out of bounds.".into()),
+        };
+
+        let constr = activation.context.avm2.classes().textlinemetrics;
+        let args = [
+            metrics.x.to_pixels().into(),
+            metrics.width.to_pixels().into(),
+            metrics.height.to_pixels().into(),
+            metrics.ascent.to_pixels().into(),
+            metrics.descent.to_pixels().into(),
+            metrics.leading.to_pixels().into(),
+        ];
+        return Ok(constr.construct(activation, &args)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_offset<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return match index_arg(activation, args, 0)?.and_then(|line| this.line_offset(line)) {
+            Some(offset) => Ok(offset.into()),
+            None => Err("RangeError: The supplied index is out of bounds.".into()),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return match index_arg(activation, args, 0)?.and_then(|line| this.line_text(line)) {
+            Some(text) => Ok(AvmString::new(activation.context.gc_context, text).into()),
+            None => Err("RangeError: The supplied index is out of bounds.".into()),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `TextField`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -877,6 +1377,7 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
             Some(set_background_color),
         ),
         ("border", Some(border), Some(set_border)),
+        ("bottomScrollV", Some(bottom_scroll_v), None),
         ("borderColor", Some(border_color), Some(set_border_color)),
         (
             "defaultTextFormat",
@@ -891,8 +1392,16 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ("embedFonts", Some(embed_fonts), Some(set_embed_fonts)),
         ("htmlText", Some(html_text), Some(set_html_text)),
         ("length", Some(length), None),
+        ("maxChars", Some(max_chars), Some(set_max_chars)),
+        ("maxScrollH", Some(max_scroll_h), None),
+        ("maxScrollV", Some(max_scroll_v), None),
         ("multiline", Some(multiline), Some(set_multiline)),
+        ("numLines", Some(num_lines), None),
+        ("restrict", Some(restrict), Some(set_restrict)),
+        ("scrollH", Some(scroll_h), Some(set_scroll_h)),
+        ("scrollV", Some(scroll_v), Some(set_scroll_v)),
         ("selectable", Some(selectable), Some(set_selectable)),
+        ("styleSheet", Some(style_sheet), Some(set_style_sheet)),
         ("text", Some(text), Some(set_text)),
         ("textColor", Some(text_color), Some(set_text_color)),
         ("textHeight", Some(text_height), None),
@@ -904,6 +1413,16 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
         ("appendText", append_text),
+        ("getCharBoundaries", get_char_boundaries),
+        ("getCharIndexAtPoint", get_char_index_at_point),
+        ("getFirstCharInParagraph", get_first_char_in_paragraph),
+        ("getImageReference", get_image_reference),
+        ("getLineIndexAtPoint", get_line_index_at_point),
+        ("getLineIndexOfChar", get_line_index_of_char),
+        ("getLineLength", get_line_length),
+        ("getLineMetrics", get_line_metrics),
+        ("getLineOffset", get_line_offset),
+        ("getLineText", get_line_text),
         ("getTextFormat", get_text_format),
         ("replaceSelectedText", replace_selected_text),
         ("replaceText", replace_text),
@@ -0,0 +1,69 @@
+//! `flash.text.TextLineMetrics` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The properties of a `TextLineMetrics`, in constructor argument order.
+const PROPERTIES: &[&str] = &["x", "width", "height", "ascent", "descent", "leading"];
+
+/// Implements `flash.text.TextLineMetrics`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        for (i, name) in PROPERTIES.iter().enumerate() {
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                args.get(i).cloned().unwrap_or(Value::Number(0.0)),
+                activation,
+            )?;
+        }
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.text.TextLineMetrics`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `TextLineMetrics`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.text"), "TextLineMetrics"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init, "<TextLineMetrics instance initializer>", mc),
+        Method::from_builtin(class_init, "<TextLineMetrics class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    for &name in PROPERTIES {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), name),
+            QName::new(Namespace::public(), "Number").into(),
+            None,
+        ));
+    }
+
+    class
+}
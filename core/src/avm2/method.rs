@@ -8,6 +8,7 @@ use crate::avm2::string::AvmString;
 use crate::avm2::value::{abc_default_value, Value};
 use crate::avm2::Error;
 use gc_arena::{Collect, CollectionContext, Gc, MutationContext};
+use std::cell::RefCell;
 use std::fmt;
 use std::rc::Rc;
 use swf::avm2::types::{
@@ -130,6 +131,11 @@ pub struct BytecodeMethod<'gc> {
     /// A free-standing function corresponds to the `Function` trait type, and
     /// is instantiated with the `newfunction` opcode.
     pub is_function: bool,
+
+    /// The name calls to this method are profiled under, built the first time
+    /// the method is profiled.
+    #[collect(require_static)]
+    profile_name: RefCell<Option<Rc<str>>>,
 }
 
 impl<'gc> BytecodeMethod<'gc> {
@@ -171,6 +177,7 @@ impl<'gc> BytecodeMethod<'gc> {
                             signature,
                             return_type,
                             is_function,
+                            profile_name: RefCell::new(None),
                         },
                     ));
                 }
@@ -187,6 +194,7 @@ impl<'gc> BytecodeMethod<'gc> {
                 signature,
                 return_type: Multiname::any(),
                 is_function,
+                profile_name: RefCell::new(None),
             },
         ))
     }
@@ -237,6 +245,16 @@ impl<'gc> BytecodeMethod<'gc> {
             .unwrap_or("")
     }
 
+    /// The name calls to this method are profiled under.
+    ///
+    /// The name is only built once; `name` is called the first time.
+    pub fn profile_name(&self, name: impl FnOnce() -> String) -> Rc<str> {
+        self.profile_name
+            .borrow_mut()
+            .get_or_insert_with(|| Rc::from(name()))
+            .clone()
+    }
+
     /// Determine if a given method is variadic.
     ///
     /// Variadic methods shove excess parameters into a final register.
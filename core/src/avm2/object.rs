@@ -17,6 +17,7 @@ use crate::avm2::vector::VectorStorage;
 use crate::avm2::Error;
 use crate::backend::audio::{SoundHandle, SoundInstanceHandle};
 use crate::display_object::DisplayObject;
+use crate::html::StyleSheet;
 use gc_arena::{Collect, GcCell, MutationContext};
 use ruffle_macros::enum_trait_object;
 use std::cell::{Ref, RefMut};
@@ -39,6 +40,7 @@ mod script_object;
 mod sound_object;
 mod soundchannel_object;
 mod stage_object;
+mod stylesheet_object;
 mod vector_object;
 mod xml_object;
 
@@ -59,6 +61,7 @@ pub use crate::avm2::object::script_object::ScriptObject;
 pub use crate::avm2::object::sound_object::{sound_allocator, SoundObject};
 pub use crate::avm2::object::soundchannel_object::{soundchannel_allocator, SoundChannelObject};
 pub use crate::avm2::object::stage_object::{stage_allocator, StageObject};
+pub use crate::avm2::object::stylesheet_object::{stylesheet_allocator, StyleSheetObject};
 pub use crate::avm2::object::vector_object::{vector_allocator, VectorObject};
 pub use crate::avm2::object::xml_object::{xml_allocator, XmlObject};
 
@@ -86,6 +89,7 @@ pub use crate::avm2::object::xml_object::{xml_allocator, XmlObject};
         VectorObject(VectorObject<'gc>),
         SoundObject(SoundObject<'gc>),
         SoundChannelObject(SoundChannelObject<'gc>),
+        StyleSheetObject(StyleSheetObject<'gc>),
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -219,6 +223,12 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// Retrieve a method by its index.
     fn get_method(self, id: u32) -> Option<Object<'gc>>;
 
+    /// Retrieve the index of the slot a property is stored in, if the
+    /// property is a slot of this object.
+    fn get_slot_id(self, _name: &QName<'gc>) -> Option<u32> {
+        None
+    }
+
     /// Retrieves the scope chain of the object at time of its creation.
     ///
     /// The scope chain is used to determine the starting scope stack when an
@@ -1239,6 +1249,16 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     ///
     /// This does nothing if the object is not a sound channel.
     fn set_sound_instance(self, _mc: MutationContext<'gc, '_>, _sound: SoundInstanceHandle) {}
+
+    /// Unwrap this object as a CSS stylesheet.
+    fn as_style_sheet(&self) -> Option<Ref<StyleSheet>> {
+        None
+    }
+
+    /// Unwrap this object as a mutable CSS stylesheet.
+    fn as_style_sheet_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<StyleSheet>> {
+        None
+    }
 }
 
 pub enum ObjectPtr {}
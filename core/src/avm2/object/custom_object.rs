@@ -84,6 +84,10 @@ macro_rules! impl_avm2_custom_object_properties {
             self.0.read().$field.has_own_property(name)
         }
 
+        fn get_slot_id(self, name: &QName<'gc>) -> Option<u32> {
+            self.0.read().$field.get_slot_id(name)
+        }
+
         fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error> {
             self.0.read().$field.resolve_any(local_name)
         }
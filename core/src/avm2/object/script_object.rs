@@ -173,6 +173,10 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         self.0.read().has_own_property(name)
     }
 
+    fn get_slot_id(self, name: &QName<'gc>) -> Option<u32> {
+        self.0.read().get_slot_id(name)
+    }
+
     fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error> {
         self.0.read().has_trait(name)
     }
@@ -524,6 +528,11 @@ impl<'gc> ScriptObjectData<'gc> {
         self.methods.get(id as usize).and_then(|v| *v)
     }
 
+    /// Retrieve the slot a property is stored in, if it is a slot property.
+    pub fn get_slot_id(&self, name: &QName<'gc>) -> Option<u32> {
+        self.values.get(name).and_then(|p| p.slot_id())
+    }
+
     pub fn has_trait(&self, name: &QName<'gc>) -> Result<bool, Error> {
         match self.instance_of {
             //Class instances have instance traits from any class in the base
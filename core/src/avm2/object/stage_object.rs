@@ -238,6 +238,10 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         self.0.read().base.has_own_property(name)
     }
 
+    fn get_slot_id(self, name: &QName<'gc>) -> Option<u32> {
+        self.0.read().base.get_slot_id(name)
+    }
+
     fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error> {
         self.0.read().base.has_trait(name)
     }
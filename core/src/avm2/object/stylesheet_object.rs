@@ -0,0 +1,80 @@
+//! Object representation for CSS stylesheets
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::html::StyleSheet;
+use crate::{
+    impl_avm2_custom_object, impl_avm2_custom_object_instance, impl_avm2_custom_object_properties,
+};
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// A class instance allocator that allocates StyleSheet objects.
+pub fn stylesheet_allocator<'gc>(
+    class: Object<'gc>,
+    proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    let base = ScriptObjectData::base_new(Some(proto), Some(class));
+
+    Ok(StyleSheetObject(GcCell::allocate(
+        activation.context.gc_context,
+        StyleSheetObjectData {
+            base,
+            style_sheet: StyleSheet::new(),
+        },
+    ))
+    .into())
+}
+
+#[derive(Clone, Collect, Debug, Copy)]
+#[collect(no_drop)]
+pub struct StyleSheetObject<'gc>(GcCell<'gc, StyleSheetObjectData<'gc>>);
+
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct StyleSheetObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The styles this object holds.
+    style_sheet: StyleSheet,
+}
+
+impl<'gc> TObject<'gc> for StyleSheetObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+    impl_avm2_custom_object_instance!(base);
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Object::from(*self).into())
+    }
+
+    fn derive(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(Some((*self).into()), None);
+
+        Ok(StyleSheetObject(GcCell::allocate(
+            activation.context.gc_context,
+            StyleSheetObjectData {
+                base,
+                style_sheet: StyleSheet::new(),
+            },
+        ))
+        .into())
+    }
+
+    fn as_style_sheet(&self) -> Option<Ref<StyleSheet>> {
+        Some(Ref::map(self.0.read(), |d| &d.style_sheet))
+    }
+
+    fn as_style_sheet_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<StyleSheet>> {
+        Some(RefMut::map(self.0.write(mc), |d| &mut d.style_sheet))
+    }
+}
@@ -1,6 +1,7 @@
 //! Whole script representation
 
 use crate::avm2::activation::Activation;
+use crate::avm2::bytecode::DecodedBody;
 use crate::avm2::class::Class;
 use crate::avm2::domain::Domain;
 use crate::avm2::method::{BytecodeMethod, Method};
@@ -49,6 +50,9 @@ pub struct TranslationUnitData<'gc> {
     /// All methods loaded from the ABC's method list.
     methods: FnvHashMap<u32, Method<'gc>>,
 
+    /// All method bodies decoded from the ABC's method body list.
+    method_bodies: FnvHashMap<u32, Gc<'gc, DecodedBody<'gc>>>,
+
     /// All scripts loaded from the ABC's scripts list.
     scripts: FnvHashMap<u32, Script<'gc>>,
 
@@ -67,6 +71,7 @@ impl<'gc> TranslationUnit<'gc> {
                 abc,
                 classes: FnvHashMap::default(),
                 methods: FnvHashMap::default(),
+                method_bodies: FnvHashMap::default(),
                 scripts: FnvHashMap::default(),
                 strings: FnvHashMap::default(),
             },
@@ -108,6 +113,32 @@ impl<'gc> TranslationUnit<'gc> {
         Ok(method)
     }
 
+    /// Decode a method body from the ABC file.
+    pub fn load_method_body(
+        self,
+        body_index: u32,
+        mc: MutationContext<'gc, '_>,
+    ) -> Result<Gc<'gc, DecodedBody<'gc>>, Error> {
+        let read = self.0.read();
+        if let Some(body) = read.method_bodies.get(&body_index) {
+            return Ok(*body);
+        }
+
+        let abc = read.abc.clone();
+
+        drop(read);
+
+        let body: Result<_, Error> = abc
+            .method_bodies
+            .get(body_index as usize)
+            .ok_or_else(|| format!("Unknown method body {}", body_index).into());
+        let body = Gc::allocate(mc, DecodedBody::decode(self, body?, mc));
+
+        self.0.write(mc).method_bodies.insert(body_index, body);
+
+        Ok(body)
+    }
+
     /// Load a class from the ABC file and return its class definition.
     pub fn load_class(
         self,
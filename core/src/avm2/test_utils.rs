@@ -0,0 +1,101 @@
+use crate::avm1::globals::system::SystemProperties;
+use crate::avm1::{Avm1, Timers};
+use crate::avm2::{Activation, Avm2, Error};
+use crate::backend::audio::{AudioManager, NullAudioBackend};
+use crate::backend::locale::NullLocaleBackend;
+use crate::backend::log::NullLogBackend;
+use crate::backend::navigator::NullNavigatorBackend;
+use crate::backend::render::NullRenderer;
+use crate::backend::storage::MemoryStorageBackend;
+use crate::backend::ui::NullUiBackend;
+use crate::backend::video::NullVideoBackend;
+use crate::context::{ActionQueue, UpdateContext};
+use crate::debugger::Debugger;
+use crate::display_object::Stage;
+use crate::focus_tracker::FocusTracker;
+use crate::library::Library;
+use crate::loader::LoadManager;
+use crate::prelude::*;
+use crate::profiler::Profiler;
+use crate::tag_utils::SwfMovie;
+use gc_arena::{rootless_arena, MutationContext};
+use instant::Instant;
+use rand::{rngs::SmallRng, SeedableRng};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Run a test with an AVM2 that has the player globals loaded.
+pub fn with_avm2<F>(test: F)
+where
+    F: for<'a, 'gc> FnOnce(&mut Activation<'_, 'gc, '_>) -> Result<(), Error>,
+{
+    fn in_the_arena<'a, 'gc: 'a, F>(test: F, gc_context: MutationContext<'gc, '_>)
+    where
+        F: FnOnce(&mut Activation<'_, 'gc, '_>) -> Result<(), Error>,
+    {
+        let swf_version = 10;
+        let mut avm1 = Avm1::new(gc_context, swf_version);
+        let mut avm2 = Avm2::new(gc_context);
+        let swf = Arc::new(SwfMovie::empty(swf_version));
+        let stage = Stage::empty(gc_context, 550, 400);
+        let mut frame_rate = 12.0;
+        let mut debugger = Debugger::new();
+        let mut profiler = Profiler::new();
+
+        let mut context = UpdateContext {
+            gc_context,
+            player_version: 32,
+            swf: &swf,
+            stage,
+            rng: &mut SmallRng::from_seed([0u8; 32]),
+            audio: &mut NullAudioBackend::new(),
+            ui: &mut NullUiBackend::new(),
+            action_queue: &mut ActionQueue::new(),
+            library: &mut Library::empty(gc_context),
+            navigator: &mut NullNavigatorBackend::new(),
+            renderer: &mut NullRenderer::new(),
+            locale: &mut NullLocaleBackend::new(),
+            log: &mut NullLogBackend::new(),
+            video: &mut NullVideoBackend::new(),
+            mouse_over_object: None,
+            mouse_down_object: None,
+            last_click: None,
+            mouse_position: &(Twips::ZERO, Twips::ZERO),
+            drag_object: &mut None,
+            player: None,
+            load_manager: &mut LoadManager::new(),
+            system: &mut SystemProperties::default(),
+            instance_counter: &mut 0,
+            storage: &mut MemoryStorageBackend::default(),
+            shared_objects: &mut HashMap::new(),
+            unbound_text_fields: &mut Vec::new(),
+            timers: &mut Timers::new(),
+            current_context_menu: &mut None,
+            needs_render: &mut false,
+            avm1: &mut avm1,
+            avm2: &mut avm2,
+            external_interface: &mut Default::default(),
+            update_start: Instant::now(),
+            max_execution_duration: Duration::from_secs(15),
+            focus_tracker: FocusTracker::new(gc_context),
+            times_get_time_called: 0,
+            time_offset: &mut 0,
+            audio_manager: &mut AudioManager::new(),
+            frame_rate: &mut frame_rate,
+            debugger: &mut debugger,
+            profiler: &mut profiler,
+        };
+
+        if let Err(e) = Avm2::load_player_globals(&mut context) {
+            panic!("Could not load the player globals: {}", e);
+        }
+
+        let mut activation = Activation::from_nothing(context);
+        if let Err(e) = test(&mut activation) {
+            panic!("Encountered exception during test: {}", e);
+        }
+    }
+
+    rootless_arena(|gc_context| in_the_arena(test, gc_context))
+}
@@ -613,13 +613,13 @@ impl<'gc> Value<'gc> {
 
         if let Some(static_class) = class.as_class() {
             return Err(format!(
-                "Cannot coerce {:?} to an {:?}",
+                "TypeError: Cannot coerce {:?} to an {:?}",
                 self,
                 static_class.read().name()
             )
             .into());
         } else {
-            return Err(format!("Cannot coerce {:?} to {:?}", self, class).into());
+            return Err(format!("TypeError: Cannot coerce {:?} to {:?}", self, class).into());
         }
     }
 
@@ -1,4 +1,5 @@
 pub mod audio;
+pub mod debugger;
 pub mod locale;
 pub mod log;
 pub mod navigator;
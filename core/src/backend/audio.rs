@@ -12,6 +12,7 @@ use gc_arena::Collect;
 use generational_arena::{Arena, Index};
 
 pub mod decoders;
+pub mod mixer;
 pub mod swf {
     pub use swf::{
         read, AudioCompression, CharacterId, Sound, SoundEnvelope, SoundEnvelopePoint, SoundEvent,
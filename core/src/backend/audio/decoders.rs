@@ -28,6 +28,18 @@ pub trait Decoder: Iterator<Item = [i16; 2]> {
     fn sample_rate(&self) -> u16;
 }
 
+impl<D: Decoder + ?Sized> Decoder for Box<D> {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        (**self).num_channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        (**self).sample_rate()
+    }
+}
+
 /// Instantiate a decoder for the compression that the sound data uses.
 pub fn make_decoder<'a, R: 'a + Send + Read>(
     format: &SoundFormat,
@@ -0,0 +1,540 @@
+//! A software audio mixer.
+//!
+//! Backends that can't decode and mix sounds natively use this to produce their
+//! output. The exporter's `RecordingAudioBackend` mixes into a buffer with it, so
+//! that recorded audio stays in sync with recorded frames.
+
+use super::decoders::{self, Decoder};
+use super::{swf, SoundHandle, SoundInstanceHandle, SoundTransform};
+use crate::tag_utils::SwfSlice;
+use generational_arena::Arena;
+use std::io::Cursor;
+use std::sync::Arc;
+
+type Error = Box<dyn std::error::Error>;
+
+type Signal = Box<dyn Iterator<Item = [i16; 2]> + Send>;
+
+/// The sample rate of sound envelopes and in/out points, regardless of the sound's own rate.
+const SWF_SAMPLE_RATE: u32 = 44100;
+
+/// Decodes, resamples and mixes all playing sounds to a single stereo output.
+pub struct AudioMixer {
+    sounds: Arena<Sound>,
+    sound_instances: Arena<SoundInstance>,
+    output_sample_rate: u32,
+}
+
+/// Contains the data and metadata for a sound in an SWF file.
+/// A `Sound` is defined by the `DefineSound` SWF tags.
+struct Sound {
+    format: swf::SoundFormat,
+
+    data: Arc<[u8]>,
+
+    /// Number of samples in this audio.
+    /// This does not include the skip_sample_frames.
+    num_sample_frames: u32,
+
+    /// Number of samples to skip encoder delay.
+    skip_sample_frames: u16,
+}
+
+/// An actively playing instance of a sound.
+/// This sound can be either an event sound (`StartSound`) or
+/// a stream sound (`SoundStreamBlock`).
+struct SoundInstance {
+    /// The audio resampled to the output sample rate.
+    signal: Signal,
+
+    /// Flag indicating whether this sound is still playing.
+    /// If this flag is false, the sound will be removed after the current mix.
+    active: bool,
+
+    /// The number of sample frames mixed so far.
+    position: u32,
+
+    /// The transform for this sound instance.
+    left_transform: [f32; 2],
+    right_transform: [f32; 2],
+}
+
+impl AudioMixer {
+    pub fn new(output_sample_rate: u32) -> Self {
+        Self {
+            sounds: Arena::new(),
+            sound_instances: Arena::new(),
+            output_sample_rate,
+        }
+    }
+
+    pub fn output_sample_rate(&self) -> u32 {
+        self.output_sample_rate
+    }
+
+    pub fn register_sound(&mut self, swf_sound: &swf::Sound) -> Result<SoundHandle, Error> {
+        // Slice off latency seek for MP3 data.
+        let (skip_sample_frames, data) =
+            if swf_sound.format.compression == swf::AudioCompression::Mp3 {
+                let skip_sample_frames = u16::from_le_bytes([swf_sound.data[0], swf_sound.data[1]]);
+                (skip_sample_frames, &swf_sound.data[2..])
+            } else {
+                (0, swf_sound.data)
+            };
+
+        Ok(self.sounds.insert(Sound {
+            format: swf_sound.format.clone(),
+            data: Arc::from(data),
+            num_sample_frames: swf_sound.num_samples,
+            skip_sample_frames,
+        }))
+    }
+
+    pub fn start_sound(
+        &mut self,
+        sound_handle: SoundHandle,
+        settings: &swf::SoundInfo,
+    ) -> Result<SoundInstanceHandle, Error> {
+        let sound = self
+            .sounds
+            .get(sound_handle)
+            .ok_or("start_sound: Sound not registered")?;
+        let signal = Resampler::new(
+            EventSoundSignal::new(sound, settings)?,
+            self.output_sample_rate,
+        );
+        let signal: Signal = if let Some(envelope) = &settings.envelope {
+            Box::new(Envelope::new(signal, envelope, self.output_sample_rate))
+        } else {
+            Box::new(signal)
+        };
+        Ok(self.add_instance(signal))
+    }
+
+    /// Starts a "stream" sound, whose data is distributed among the frames of a movie clip.
+    pub fn start_stream(
+        &mut self,
+        clip_data: SwfSlice,
+        stream_info: &swf::SoundStreamHead,
+    ) -> Result<SoundInstanceHandle, Error> {
+        let decoder = decoders::make_stream_decoder(&stream_info.stream_format, clip_data)?;
+        let signal = Resampler::new(decoder, self.output_sample_rate);
+        Ok(self.add_instance(Box::new(signal)))
+    }
+
+    pub fn stop_sound(&mut self, sound: SoundInstanceHandle) {
+        self.sound_instances.remove(sound);
+    }
+
+    pub fn stop_all_sounds(&mut self) {
+        // This is a workaround for a bug in generational-arena:
+        // Arena::clear does not properly bump the generational index, allowing for stale references
+        // to continue to work (this caused #1315). Arena::remove will force a generation bump.
+        // See https://github.com/fitzgen/generational-arena/issues/30
+        if let Some((i, _)) = self.sound_instances.iter().next() {
+            self.sound_instances.remove(i);
+        }
+        self.sound_instances.clear();
+    }
+
+    /// The position of a playing sound instance in milliseconds.
+    pub fn get_sound_position(&self, instance: SoundInstanceHandle) -> Option<u32> {
+        self.sound_instances.get(instance).map(|instance| {
+            (u64::from(instance.position) * 1000 / u64::from(self.output_sample_rate)) as u32
+        })
+    }
+
+    pub fn get_sound_duration(&self, sound: SoundHandle) -> Option<f64> {
+        if let Some(sound) = self.sounds.get(sound) {
+            // AS duration does not subtract `skip_sample_frames`.
+            let num_sample_frames: f64 = sound.num_sample_frames.into();
+            let sample_rate: f64 = sound.format.sample_rate.into();
+            Some(num_sample_frames * 1000.0 / sample_rate)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_sound_size(&self, sound: SoundHandle) -> Option<u32> {
+        self.sounds.get(sound).map(|s| s.data.len() as u32)
+    }
+
+    pub fn get_sound_format(&self, sound: SoundHandle) -> Option<&swf::SoundFormat> {
+        self.sounds.get(sound).map(|s| &s.format)
+    }
+
+    pub fn set_sound_transform(
+        &mut self,
+        instance: SoundInstanceHandle,
+        transform: SoundTransform,
+    ) {
+        if let Some(instance) = self.sound_instances.get_mut(instance) {
+            instance.left_transform = [transform.left_to_left, transform.right_to_left];
+            instance.right_transform = [transform.left_to_right, transform.right_to_right];
+        }
+    }
+
+    /// Mixes all playing sounds into an interleaved output buffer with `num_channels` channels.
+    ///
+    /// Only the first two channels of each output frame are written; a mono buffer only
+    /// receives the left channel. Sounds that finish playing are removed afterwards.
+    pub fn mix<T>(&mut self, output: &mut [T], num_channels: usize, convert: impl Fn(i16) -> T) {
+        for output_frame in output.chunks_exact_mut(num_channels.max(1)) {
+            let mixed = self.mix_sample_frame();
+            for (output_sample, sample) in output_frame.iter_mut().zip(&mixed) {
+                *output_sample = convert(*sample);
+            }
+        }
+
+        // Remove all dead sounds.
+        self.sound_instances.retain(|_, sound| sound.active);
+    }
+
+    /// Mixes the next sample frame of all active sound instances.
+    fn mix_sample_frame(&mut self) -> [i16; 2] {
+        let mut output = [0.0f32; 2];
+        for (_, sound) in self.sound_instances.iter_mut() {
+            if !sound.active {
+                continue;
+            }
+
+            if let Some([left, right]) = sound.signal.next() {
+                let (left, right) = (f32::from(left), f32::from(right));
+                output[0] += left * sound.left_transform[0] + right * sound.left_transform[1];
+                output[1] += left * sound.right_transform[0] + right * sound.right_transform[1];
+                sound.position += 1;
+            } else {
+                sound.active = false;
+            }
+        }
+
+        [
+            output[0].clamp(i16::MIN.into(), i16::MAX.into()) as i16,
+            output[1].clamp(i16::MIN.into(), i16::MAX.into()) as i16,
+        ]
+    }
+
+    fn add_instance(&mut self, signal: Signal) -> SoundInstanceHandle {
+        self.sound_instances.insert(SoundInstance {
+            signal,
+            active: true,
+            position: 0,
+            left_transform: [1.0, 0.0],
+            right_transform: [0.0, 1.0],
+        })
+    }
+}
+
+/// A dummy wrapper struct to implement `AsRef<[u8]>` for `Arc<[u8]>`.
+/// Not having this trait causes problems when trying to use `Cursor<Arc<[u8]>>`.
+struct ArcAsRef(Arc<[u8]>);
+
+impl AsRef<[u8]> for ArcAsRef {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// A decoded event sound, applying the start/end points and loop count of its sound settings.
+struct EventSoundSignal {
+    format: swf::SoundFormat,
+    data: Arc<[u8]>,
+    decoder: Box<dyn Decoder + Send>,
+    num_loops: u16,
+    start_sample_frame: u32,
+    end_sample_frame: u32,
+    cur_sample_frame: u32,
+}
+
+impl EventSoundSignal {
+    fn new(sound: &Sound, settings: &swf::SoundInfo) -> Result<Self, Error> {
+        let decoder = decoders::make_decoder(
+            &sound.format,
+            Cursor::new(ArcAsRef(Arc::clone(&sound.data))),
+        )?;
+
+        // In and out points are always specified at 44.1KHz.
+        let skip_sample_frames: u32 = sound.skip_sample_frames.into();
+        let sample_divisor = (SWF_SAMPLE_RATE / u32::from(decoder.sample_rate().max(1))).max(1);
+        let start_sample_frame =
+            settings.in_sample.unwrap_or(0) / sample_divisor + skip_sample_frames;
+        let end_sample_frame = settings
+            .out_sample
+            .map(|n| n / sample_divisor)
+            .unwrap_or(sound.num_sample_frames)
+            + skip_sample_frames;
+
+        let mut signal = Self {
+            format: sound.format.clone(),
+            data: Arc::clone(&sound.data),
+            decoder,
+            num_loops: settings.num_loops.max(1),
+            start_sample_frame,
+            end_sample_frame,
+            cur_sample_frame: 0,
+        };
+        signal.skip_to_start();
+        Ok(signal)
+    }
+
+    /// Skips the decoder ahead to the start point of the sound.
+    fn skip_to_start(&mut self) {
+        while self.cur_sample_frame < self.start_sample_frame && self.decoder.next().is_some() {
+            self.cur_sample_frame += 1;
+        }
+    }
+
+    /// Restarts the sound for the next loop, returning false if there are no loops left.
+    fn next_loop(&mut self) -> bool {
+        self.num_loops = self.num_loops.saturating_sub(1);
+        if self.num_loops == 0 {
+            return false;
+        }
+
+        match decoders::make_decoder(&self.format, Cursor::new(ArcAsRef(Arc::clone(&self.data)))) {
+            Ok(decoder) => {
+                self.decoder = decoder;
+                self.cur_sample_frame = 0;
+                self.skip_to_start();
+                true
+            }
+            Err(e) => {
+                log::error!("Unable to loop sound: {}", e);
+                false
+            }
+        }
+    }
+}
+
+impl Decoder for EventSoundSignal {
+    fn num_channels(&self) -> u8 {
+        self.decoder.num_channels()
+    }
+
+    fn sample_rate(&self) -> u16 {
+        self.decoder.sample_rate()
+    }
+}
+
+impl Iterator for EventSoundSignal {
+    type Item = [i16; 2];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.cur_sample_frame < self.end_sample_frame {
+                if let Some(frame) = self.decoder.next() {
+                    self.cur_sample_frame += 1;
+                    return Some(frame);
+                }
+            }
+
+            if !self.next_loop() {
+                return None;
+            }
+        }
+    }
+}
+
+/// Converts a decoder's output to the output sample rate using linear interpolation.
+struct Resampler<D: Decoder> {
+    decoder: D,
+    /// The distance between output samples, in input samples.
+    step: f64,
+    /// The position between `prev` and `next`.
+    position: f64,
+    prev: [i16; 2],
+    next: Option<[i16; 2]>,
+}
+
+impl<D: Decoder> Resampler<D> {
+    fn new(mut decoder: D, output_sample_rate: u32) -> Self {
+        let step = f64::from(decoder.sample_rate()) / f64::from(output_sample_rate.max(1));
+        let prev = decoder.next();
+        let next = decoder.next();
+        Self {
+            decoder,
+            step,
+            position: 0.0,
+            prev: prev.unwrap_or([0, 0]),
+            next: if prev.is_some() { next } else { None },
+        }
+    }
+}
+
+impl<D: Decoder> Iterator for Resampler<D> {
+    type Item = [i16; 2];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.position >= 1.0 {
+            self.prev = self.next?;
+            self.next = self.decoder.next();
+            self.position -= 1.0;
+        }
+
+        // Hold the last sample frame until the position moves past it.
+        let next = self.next.unwrap_or(self.prev);
+        let a = self.position;
+        let lerp =
+            |prev: i16, next: i16| (f64::from(prev) * (1.0 - a) + f64::from(next) * a) as i16;
+        self.position += self.step;
+        Some([lerp(self.prev[0], next[0]), lerp(self.prev[1], next[1])])
+    }
+}
+
+/// Applies the volume envelope of an event sound.
+struct Envelope<S: Iterator<Item = [i16; 2]>> {
+    signal: S,
+    points: Vec<swf::SoundEnvelopePoint>,
+    /// The current position in the envelope, in 44.1KHz samples.
+    cur_sample: f64,
+    /// The number of envelope samples per output sample.
+    step: f64,
+}
+
+impl<S: Iterator<Item = [i16; 2]>> Envelope<S> {
+    fn new(signal: S, envelope: &[swf::SoundEnvelopePoint], output_sample_rate: u32) -> Self {
+        Self {
+            signal,
+            points: envelope.to_vec(),
+            cur_sample: 0.0,
+            step: f64::from(SWF_SAMPLE_RATE) / f64::from(output_sample_rate.max(1)),
+        }
+    }
+
+    /// The left and right volume at the current sample.
+    fn volume(&self) -> [f32; 2] {
+        let cur_sample = self.cur_sample as u32;
+        let next_index = self
+            .points
+            .iter()
+            .position(|point| point.sample > cur_sample);
+        match next_index {
+            Some(0) => [self.points[0].left_volume, self.points[0].right_volume],
+            Some(i) => {
+                let prev = &self.points[i - 1];
+                let next = &self.points[i];
+                let a = (cur_sample - prev.sample) as f32 / (next.sample - prev.sample) as f32;
+                [
+                    prev.left_volume + (next.left_volume - prev.left_volume) * a,
+                    prev.right_volume + (next.right_volume - prev.right_volume) * a,
+                ]
+            }
+            None => self
+                .points
+                .last()
+                .map(|point| [point.left_volume, point.right_volume])
+                .unwrap_or([1.0, 1.0]),
+        }
+    }
+}
+
+impl<S: Iterator<Item = [i16; 2]>> Iterator for Envelope<S> {
+    type Item = [i16; 2];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let [left, right] = self.signal.next()?;
+        let [left_volume, right_volume] = self.volume();
+        self.cur_sample += self.step;
+        Some([
+            (f32::from(left) * left_volume) as i16,
+            (f32::from(right) * right_volume) as i16,
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Registers a 44.1KHz 16-bit stereo PCM sound with the given sample frames.
+    fn register_pcm(mixer: &mut AudioMixer, frames: &[[i16; 2]]) -> SoundHandle {
+        let data: Vec<u8> = frames
+            .iter()
+            .flat_map(|frame| frame.iter().flat_map(|sample| sample.to_le_bytes()))
+            .collect();
+        mixer
+            .register_sound(&swf::Sound {
+                id: 1,
+                format: swf::SoundFormat {
+                    compression: swf::AudioCompression::Uncompressed,
+                    sample_rate: 44100,
+                    is_stereo: true,
+                    is_16_bit: true,
+                },
+                num_samples: frames.len() as u32,
+                data: &data,
+            })
+            .unwrap()
+    }
+
+    fn sound_info(num_loops: u16) -> swf::SoundInfo {
+        swf::SoundInfo {
+            event: swf::SoundEvent::Event,
+            in_sample: None,
+            out_sample: None,
+            num_loops,
+            envelope: None,
+        }
+    }
+
+    #[test]
+    fn mix_plays_sound_once() {
+        let mut mixer = AudioMixer::new(44100);
+        let sound = register_pcm(&mut mixer, &[[100, -100], [200, -200], [300, -300]]);
+        let instance = mixer.start_sound(sound, &sound_info(1)).unwrap();
+
+        let mut output = [0i16; 8];
+        mixer.mix(&mut output, 2, |sample| sample);
+        assert_eq!(output, [100, -100, 200, -200, 300, -300, 0, 0]);
+
+        // The finished sound is removed.
+        assert_eq!(mixer.get_sound_position(instance), None);
+    }
+
+    #[test]
+    fn mix_sums_sounds_and_applies_transforms() {
+        let mut mixer = AudioMixer::new(44100);
+        let sound = register_pcm(&mut mixer, &[[1000, 2000], [1000, 2000]]);
+        mixer.start_sound(sound, &sound_info(1)).unwrap();
+        let swapped = mixer.start_sound(sound, &sound_info(1)).unwrap();
+        mixer.set_sound_transform(
+            swapped,
+            SoundTransform {
+                left_to_left: 0.0,
+                left_to_right: 0.5,
+                right_to_left: 0.5,
+                right_to_right: 0.0,
+            },
+        );
+
+        let mut output = [0i16; 2];
+        mixer.mix(&mut output, 2, |sample| sample);
+        assert_eq!(output, [2000, 2500]);
+        assert_eq!(mixer.get_sound_position(swapped), Some(0));
+    }
+
+    #[test]
+    fn mix_loops_sound() {
+        let mut mixer = AudioMixer::new(44100);
+        let sound = register_pcm(&mut mixer, &[[1, 1], [2, 2]]);
+        mixer.start_sound(sound, &sound_info(2)).unwrap();
+
+        let mut output = [0i16; 5];
+        mixer.mix(&mut output, 1, |sample| sample);
+        assert_eq!(output, [1, 2, 1, 2, 0]);
+    }
+
+    #[test]
+    fn stop_all_sounds_silences_output() {
+        let mut mixer = AudioMixer::new(44100);
+        let sound = register_pcm(&mut mixer, &[[100, 100]; 4]);
+        let instance = mixer.start_sound(sound, &sound_info(1)).unwrap();
+        mixer.stop_all_sounds();
+
+        let mut output = [0i16; 4];
+        mixer.mix(&mut output, 2, |sample| sample);
+        assert_eq!(output, [0; 4]);
+        assert_eq!(mixer.get_sound_position(instance), None);
+    }
+}
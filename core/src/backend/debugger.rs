@@ -0,0 +1,21 @@
+use downcast_rs::Downcast;
+
+/// A connection to a debugger client.
+///
+/// The protocol is line-based: the client sends one command per line, and
+/// every message sent back to it is a line of human-readable text.
+pub trait DebuggerBackend: Downcast {
+    /// Returns the next command sent by the client, if one is waiting.
+    ///
+    /// This must not block; it is called while the movie is running.
+    fn poll_command(&mut self) -> Option<String>;
+
+    /// Blocks until the client sends a command.
+    ///
+    /// Returns `None` if the client has disconnected.
+    fn wait_command(&mut self) -> Option<String>;
+
+    /// Sends a line of output to the client.
+    fn send(&mut self, message: &str);
+}
+impl_downcast!(DebuggerBackend);
@@ -22,3 +22,56 @@ impl Default for NullLogBackend {
         NullLogBackend::new()
     }
 }
+
+/// Whether a log message is an error raised while running ActionScript.
+///
+/// Other parts of the player (such as loading fonts or decoding bitmaps) also log
+/// errors, so only errors logged by the AVMs themselves, or about their exceptions, count.
+pub fn is_avm_error(level: log::Level, target: &str, message: &str) -> bool {
+    level == log::Level::Error
+        && (target.starts_with("ruffle_core::avm1")
+            || target.starts_with("ruffle_core::avm2")
+            || message.contains("AVM1")
+            || message.contains("AVM2"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::Level;
+
+    #[test]
+    fn avm_errors() {
+        assert!(is_avm_error(
+            Level::Error,
+            "ruffle_core::avm1::activation",
+            "Unknown AVM1 opcode: 0x02"
+        ));
+        assert!(is_avm_error(
+            Level::Error,
+            "ruffle_core::avm2::activation",
+            "Cannot call null"
+        ));
+        assert!(is_avm_error(
+            Level::Error,
+            "ruffle_core::display_object::container",
+            "Encountered AVM2 error when dispatching event: Error"
+        ));
+
+        assert!(!is_avm_error(
+            Level::Warn,
+            "ruffle_core::avm1::activation",
+            "Unhandled FSCommand: quit"
+        ));
+        assert!(!is_avm_error(
+            Level::Error,
+            "ruffle_core::display_object::movie_clip",
+            "Unable to instantiate display node id 99, reason being: Character id doesn't exist"
+        ));
+        assert!(!is_avm_error(
+            Level::Error,
+            "ruffle_core::player",
+            "Unable to load device font: invalid data"
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 //! Browser-related platform functions
 
 use crate::loader::Error;
+use downcast_rs::Downcast;
 use indexmap::IndexMap;
 use std::borrow::Cow;
 use std::collections::VecDeque;
@@ -141,8 +142,44 @@ impl RequestOptions {
 /// result of type `Result<T, E>`.
 pub type OwnedFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + 'static>>;
 
+/// Type alias for pinned and boxed futures that borrow from their creator,
+/// and output a falliable result of type `Result<T, E>`.
+pub type BorrowedFuture<'a, T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + 'a>>;
+
+/// The body of a fetched resource, which arrives in chunks.
+pub trait ResponseBody {
+    /// The length of the entire body in bytes, if it is known up front.
+    fn total_len(&self) -> Option<usize>;
+
+    /// Wait for the next chunk of the body.
+    ///
+    /// Yields `None` once the entire body has been received.
+    fn next_chunk(&mut self) -> BorrowedFuture<'_, Option<Vec<u8>>, Error>;
+}
+
+/// A response body that has been received in full, and is yielded as a
+/// single chunk.
+pub struct WholeBody(Option<Vec<u8>>);
+
+impl WholeBody {
+    pub fn new(data: Vec<u8>) -> Self {
+        Self(Some(data))
+    }
+}
+
+impl ResponseBody for WholeBody {
+    fn total_len(&self) -> Option<usize> {
+        self.0.as_ref().map(|data| data.len())
+    }
+
+    fn next_chunk(&mut self) -> BorrowedFuture<'_, Option<Vec<u8>>, Error> {
+        let chunk = self.0.take();
+        Box::pin(async move { Ok(chunk) })
+    }
+}
+
 /// A backend interacting with a browser environment.
-pub trait NavigatorBackend {
+pub trait NavigatorBackend: Downcast {
     /// Cause a browser navigation to a given URL.
     ///
     /// The URL given may be any URL scheme a browser can support. This may not
@@ -175,6 +212,21 @@ pub trait NavigatorBackend {
     /// Fetch data at a given URL and return it some time in the future.
     fn fetch(&self, url: &str, request_options: RequestOptions) -> OwnedFuture<Vec<u8>, Error>;
 
+    /// Fetch data at a given URL, and return a body that yields the data in
+    /// chunks as it arrives.
+    ///
+    /// Movies are fetched this way, so that they can start playing before
+    /// they have been downloaded in full. The default implementation waits
+    /// for `fetch` and yields the entire response as a single chunk.
+    fn fetch_body(
+        &self,
+        url: &str,
+        request_options: RequestOptions,
+    ) -> OwnedFuture<Box<dyn ResponseBody>, Error> {
+        let fetch = self.fetch(url, request_options);
+        Box::pin(async move { Ok(Box::new(WholeBody::new(fetch.await?)) as Box<dyn ResponseBody>) })
+    }
+
     /// Get the amount of time since the SWF was launched.
     /// Used by the `getTimer` ActionScript call.
     fn time_since_launch(&mut self) -> Duration;
@@ -205,6 +257,7 @@ pub trait NavigatorBackend {
     /// URL (generally only if configured to do so by the user).
     fn pre_process_url(&self, url: Url) -> Url;
 }
+impl_downcast!(NavigatorBackend);
 
 /// A null implementation of an event loop that only supports blocking.
 pub struct NullExecutor {
@@ -316,6 +369,12 @@ pub struct NullNavigatorBackend {
 
     /// The base path for all relative fetches.
     relative_base_path: PathBuf,
+
+    /// The value returned by `time_since_launch`.
+    time_since_launch: Duration,
+
+    /// The size of the chunks that `fetch_body` splits files into, if any.
+    chunk_size: Option<usize>,
 }
 
 impl NullNavigatorBackend {
@@ -325,6 +384,8 @@ impl NullNavigatorBackend {
         NullNavigatorBackend {
             channel: None,
             relative_base_path: PathBuf::new(),
+            time_since_launch: Duration::from_millis(0),
+            chunk_size: None,
         }
     }
 
@@ -340,6 +401,68 @@ impl NullNavigatorBackend {
         NullNavigatorBackend {
             channel: Some(channel),
             relative_base_path,
+            time_since_launch: Duration::from_millis(0),
+            chunk_size: None,
+        }
+    }
+
+    /// Sets the time reported to `getTimer`.
+    ///
+    /// The clock of this backend never advances on its own, so headless
+    /// hosts can step it manually to get deterministic playback.
+    pub fn set_time_since_launch(&mut self, time_since_launch: Duration) {
+        self.time_since_launch = time_since_launch;
+    }
+
+    /// Makes `fetch_body` deliver files in chunks of the given size.
+    ///
+    /// Each chunk only arrives after the executor has polled the fetch once
+    /// more, so headless hosts can watch a movie stream in one chunk per
+    /// frame. By default, files are delivered whole.
+    pub fn set_chunk_size(&mut self, chunk_size: Option<usize>) {
+        self.chunk_size = chunk_size;
+    }
+}
+
+/// A file that is delivered in chunks by `NullNavigatorBackend::fetch_body`.
+struct ChunkedBody {
+    data: Vec<u8>,
+    position: usize,
+    chunk_size: usize,
+}
+
+impl ResponseBody for ChunkedBody {
+    fn total_len(&self) -> Option<usize> {
+        Some(self.data.len())
+    }
+
+    fn next_chunk(&mut self) -> BorrowedFuture<'_, Option<Vec<u8>>, Error> {
+        Box::pin(async move {
+            YieldNow(false).await;
+            if self.position >= self.data.len() {
+                return Ok(None);
+            }
+            let end = (self.position + self.chunk_size).min(self.data.len());
+            let chunk = self.data[self.position..end].to_vec();
+            self.position = end;
+            Ok(Some(chunk))
+        })
+    }
+}
+
+/// A future that returns to the executor once before it completes.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            context.waker().wake_by_ref();
+            Poll::Pending
         }
     }
 }
@@ -366,8 +489,28 @@ impl NavigatorBackend for NullNavigatorBackend {
         Box::pin(async move { fs::read(path).map_err(Error::NetworkError) })
     }
 
+    fn fetch_body(
+        &self,
+        url: &str,
+        request_options: RequestOptions,
+    ) -> OwnedFuture<Box<dyn ResponseBody>, Error> {
+        let fetch = self.fetch(url, request_options);
+        let chunk_size = self.chunk_size;
+        Box::pin(async move {
+            let data = fetch.await?;
+            Ok(match chunk_size {
+                Some(chunk_size) => Box::new(ChunkedBody {
+                    data,
+                    position: 0,
+                    chunk_size: chunk_size.max(1),
+                }) as Box<dyn ResponseBody>,
+                None => Box::new(WholeBody::new(data)),
+            })
+        })
+    }
+
     fn time_since_launch(&mut self) -> Duration {
-        Duration::from_millis(0)
+        self.time_since_launch
     }
 
     fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
@@ -18,8 +18,15 @@ pub trait UiBackend: Downcast {
     /// Set the clipboard to the given content
     fn set_clipboard_content(&mut self, content: String);
 
+    /// Get the current content of the clipboard, or an empty string if it holds no text.
+    fn get_clipboard_content(&mut self) -> String;
+
     fn is_fullscreen(&self) -> bool;
 
+    /// Tells the host where the text being composed with an input method
+    /// editor (IME) is, so that the candidate window can be placed next to it.
+    fn set_ime_cursor_area(&mut self, area: ImeCursorArea);
+
     /// Displays a warning about unsupported content in Ruffle.
     /// The user can still click an "OK" or "run anyway" message to dismiss the warning.
     fn display_unsupported_message(&self);
@@ -55,6 +62,18 @@ pub enum MouseCursor {
     Grab,
 }
 
+/// The screen area of the text cursor while composing text with an input method editor (IME).
+/// Communicated from the core to the UI backend via `UiBackend::set_ime_cursor_area`.
+///
+/// All values are in viewport pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ImeCursorArea {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
 /// UiBackend that does nothing.
 pub struct NullUiBackend {}
 
@@ -87,10 +106,16 @@ impl UiBackend for NullUiBackend {
 
     fn set_clipboard_content(&mut self, _content: String) {}
 
+    fn get_clipboard_content(&mut self) -> String {
+        String::new()
+    }
+
     fn is_fullscreen(&self) -> bool {
         false
     }
 
+    fn set_ime_cursor_area(&mut self, _area: ImeCursorArea) {}
+
     fn display_unsupported_message(&self) {}
 
     fn display_root_movie_download_failed_message(&self) {}
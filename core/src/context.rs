@@ -14,6 +14,7 @@ use crate::backend::{
     video::VideoBackend,
 };
 use crate::context_menu::ContextMenuState;
+use crate::debugger::Debugger;
 use crate::display_object::{EditText, MovieClip, SoundTransform, Stage};
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
@@ -21,6 +22,7 @@ use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::player::Player;
 use crate::prelude::*;
+use crate::profiler::Profiler;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::transform::TransformStack;
 use core::fmt;
@@ -98,6 +100,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// If the mouse is down, the display object that the mouse is currently pressing.
     pub mouse_down_object: Option<DisplayObject<'gc>>,
 
+    /// The last click of the mouse, used to detect double clicks.
+    pub last_click: Option<crate::player::MouseClick<'gc>>,
+
     /// The location of the mouse when it was last over the player.
     pub mouse_position: &'a (Twips, Twips),
 
@@ -160,6 +165,12 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
 
     /// The current stage frame rate.
     pub frame_rate: &'a mut f64,
+
+    /// The step debugger.
+    pub debugger: &'a mut Debugger,
+
+    /// The profiler, which times scripts and tessellation while enabled.
+    pub profiler: &'a mut Profiler,
 }
 
 /// Convenience methods for controlling audio.
@@ -294,6 +305,7 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             stage: self.stage,
             mouse_over_object: self.mouse_over_object,
             mouse_down_object: self.mouse_down_object,
+            last_click: self.last_click,
             mouse_position: self.mouse_position,
             drag_object: self.drag_object,
             player: self.player.clone(),
@@ -313,6 +325,8 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             times_get_time_called: self.times_get_time_called,
             time_offset: self.time_offset,
             frame_rate: self.frame_rate,
+            debugger: self.debugger,
+            profiler: self.profiler,
         }
     }
 }
@@ -409,12 +423,18 @@ pub struct RenderContext<'a, 'gc> {
     /// The current player's stage (including all loaded levels)
     pub stage: Stage<'gc>,
 
+    /// The focus tracker, used to draw the focus rectangle.
+    pub focus_tracker: FocusTracker<'gc>,
+
     /// The stack of clip depths, used in masking.
     pub clip_depth_stack: Vec<Depth>,
 
     /// Whether to allow pushing a new mask. A masker-inside-a-masker does not work in Flash, instead
     /// causing the inner mask to be included as part of the outer mask. Maskee-inside-a-maskee works as one expects.
     pub allow_mask: bool,
+
+    /// The profiler, which times tessellation of dynamically drawn shapes while enabled.
+    pub profiler: &'a mut Profiler,
 }
 
 /// The type of action being run.
@@ -0,0 +1,645 @@
+//! Step debugger for AVM1 and AVM2 code.
+//!
+//! The debugger is driven by a client connected through a `DebuggerBackend`.
+//! While the movie runs, commands are polled once per tick; once execution
+//! stops at a breakpoint or after a step, the player blocks until the client
+//! tells it to continue, answering inspection commands in the meantime.
+
+use crate::backend::debugger::DebuggerBackend;
+use std::fmt;
+
+const HELP: &str = "\
+Commands:
+  break <offset>          Break at the AVM1 action at this offset in the movie
+  break <method>:<offset> Break at the AVM2 instruction at this hex offset in a method body
+  break <name>            Break when a function with this name is entered
+  delete <n>              Remove breakpoint number n
+  breakpoints             List all breakpoints
+  pause                   Break at the next instruction
+  continue, c             Resume execution
+  step, s                 Step to the next instruction, entering calls
+  next, n                 Step to the next instruction in this function
+  finish, f               Run until this function returns
+  backtrace, bt           Print the call stack
+  locals                  Print the local variables of the current function
+  registers               Print the registers of the current function
+  scope                   Print the scope chain of the current function
+  print <path>, p <path>  Print a variable and its properties
+  help                    Print this message";
+
+/// A position in a script at which execution can be stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    /// An AVM1 action, identified by its offset in the movie data.
+    Avm1 { offset: usize },
+
+    /// An AVM2 instruction, identified by the index of its method in the ABC
+    /// file and its byte offset within that method's body, as listed by
+    /// `ruffle_dump abc`.
+    Avm2 { method: u32, offset: usize },
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Location::Avm1 { offset } => write!(f, "AVM1 action at {:#x}", offset),
+            Location::Avm2 { method, offset } => {
+                write!(f, "AVM2 method {} at {:04x}", method, offset)
+            }
+        }
+    }
+}
+
+/// A condition under which execution is stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Stop before executing the code at a location.
+    Location(Location),
+
+    /// Stop when a function with the given name is entered.
+    Function(String),
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Breakpoint::Location(location) => location.fmt(f),
+            Breakpoint::Function(name) => write!(f, "function {}", name),
+        }
+    }
+}
+
+/// When execution should next be stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StepMode {
+    /// Only stop at breakpoints.
+    Run,
+
+    /// Stop at the next instruction.
+    Into,
+
+    /// Stop at the next instruction in a frame at or above this call depth.
+    Over(usize),
+
+    /// Stop at the next instruction in a frame above this call depth.
+    Out(usize),
+}
+
+/// A command sent by the client.
+enum Command<'a> {
+    Break(Breakpoint),
+    Delete(usize),
+    Breakpoints,
+    Pause,
+    Continue,
+    Step,
+    Next,
+    Finish,
+    Backtrace,
+    Locals,
+    Registers,
+    Scope,
+    Print(&'a str),
+    Help,
+}
+
+impl<'a> Command<'a> {
+    fn parse(line: &'a str) -> Result<Self, String> {
+        let line = line.trim();
+        let (name, argument) = match line.find(char::is_whitespace) {
+            Some(i) => (&line[..i], line[i..].trim()),
+            None => (line, ""),
+        };
+
+        let command = match name {
+            "break" | "b" => Command::Break(Self::parse_breakpoint(argument)?),
+            "delete" | "d" => Command::Delete(
+                argument
+                    .parse()
+                    .map_err(|_| format!("Invalid breakpoint number: {}", argument))?,
+            ),
+            "breakpoints" => Command::Breakpoints,
+            "pause" => Command::Pause,
+            "continue" | "c" => Command::Continue,
+            "step" | "s" => Command::Step,
+            "next" | "n" => Command::Next,
+            "finish" | "f" => Command::Finish,
+            "backtrace" | "bt" => Command::Backtrace,
+            "locals" => Command::Locals,
+            "registers" => Command::Registers,
+            "scope" => Command::Scope,
+            "print" | "p" if !argument.is_empty() => Command::Print(argument),
+            "help" => Command::Help,
+            _ => return Err(format!("Unknown command: {}", line)),
+        };
+
+        Ok(command)
+    }
+
+    fn parse_breakpoint(argument: &str) -> Result<Breakpoint, String> {
+        if argument.is_empty() {
+            return Err("Expected a breakpoint".to_string());
+        }
+
+        if let Some((method, offset)) = argument.split_once(':') {
+            // AVM2 offsets are hexadecimal, as they are in disassembled method bodies.
+            let offset = offset.strip_prefix("0x").unwrap_or(offset);
+            if let (Ok(method), Ok(offset)) = (method.parse(), usize::from_str_radix(offset, 16)) {
+                return Ok(Breakpoint::Location(Location::Avm2 { method, offset }));
+            }
+        }
+
+        let offset = if let Some(hex) = argument.strip_prefix("0x") {
+            usize::from_str_radix(hex, 16).ok()
+        } else {
+            argument.parse().ok()
+        };
+
+        Ok(match offset {
+            Some(offset) => Breakpoint::Location(Location::Avm1 { offset }),
+            None => Breakpoint::Function(argument.to_string()),
+        })
+    }
+}
+
+/// The view a debugger has of a paused activation.
+///
+/// Values are returned already formatted for display.
+pub trait DebugFrame {
+    /// The local variables of the function being executed.
+    fn locals(&mut self) -> Vec<(String, String)>;
+
+    /// The registers of the function being executed.
+    fn registers(&mut self) -> Vec<(String, String)>;
+
+    /// A description of each entry of the scope chain, innermost first.
+    fn scope_chain(&mut self) -> Vec<String>;
+
+    /// Resolves a dot-separated variable path, and describes the resulting
+    /// value along with its properties.
+    fn inspect(&mut self, path: &str) -> Result<String, String>;
+}
+
+/// The state of the debugger.
+pub struct Debugger {
+    backend: Option<Box<dyn DebuggerBackend>>,
+    breakpoints: Vec<Breakpoint>,
+    step: StepMode,
+
+    /// The names of all functions currently being executed, outermost first.
+    ///
+    /// Frames entered while no client was attached have no name.
+    frames: Vec<Option<String>>,
+}
+
+impl Debugger {
+    pub fn new() -> Self {
+        Self {
+            backend: None,
+            breakpoints: Vec::new(),
+            step: StepMode::Run,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Connects a client to this debugger, replacing any previous one.
+    pub fn attach(&mut self, backend: Box<dyn DebuggerBackend>) {
+        self.backend = Some(backend);
+        self.step = StepMode::Run;
+    }
+
+    /// Disconnects the current client, and drops all breakpoints.
+    pub fn detach(&mut self) -> Option<Box<dyn DebuggerBackend>> {
+        self.breakpoints.clear();
+        self.step = StepMode::Run;
+        self.backend.take()
+    }
+
+    pub fn is_attached(&self) -> bool {
+        self.backend.is_some()
+    }
+
+    pub fn backend(&self) -> Option<&dyn DebuggerBackend> {
+        self.backend.as_deref()
+    }
+
+    pub fn backend_mut(&mut self) -> Option<&mut dyn DebuggerBackend> {
+        match &mut self.backend {
+            Some(backend) => Some(backend.as_mut()),
+            None => None,
+        }
+    }
+
+    /// Handles any commands the client sent while the movie was running.
+    pub fn poll(&mut self) {
+        while let Some(line) = self.backend.as_mut().and_then(|b| b.poll_command()) {
+            match Command::parse(&line) {
+                Ok(Command::Pause) => {
+                    self.step = StepMode::Into;
+                    self.send("Pausing at the next instruction.");
+                }
+                Ok(Command::Continue) => self.send("Already running."),
+                Ok(
+                    Command::Step
+                    | Command::Next
+                    | Command::Finish
+                    | Command::Backtrace
+                    | Command::Locals
+                    | Command::Registers
+                    | Command::Scope
+                    | Command::Print(_),
+                ) => self.send("Not paused."),
+                Ok(command) => self.handle_breakpoint_command(command),
+                Err(e) => self.send(&e),
+            }
+        }
+    }
+
+    /// Called when an activation starts executing a function.
+    ///
+    /// Every call must be paired with a call to `exit_frame`, whether or not a
+    /// client is attached.
+    pub fn enter_frame(&mut self, name: &str) {
+        if self.backend.is_none() {
+            self.frames.push(None);
+            return;
+        }
+
+        self.frames.push(Some(name.to_string()));
+        let is_breakpoint = self
+            .breakpoints
+            .iter()
+            .any(|b| matches!(b, Breakpoint::Function(f) if f == name));
+        if is_breakpoint {
+            self.step = StepMode::Into;
+        }
+    }
+
+    /// Called when an activation is done executing a function.
+    pub fn exit_frame(&mut self) {
+        self.frames.pop();
+    }
+
+    /// Whether execution should stop before running the code at `location`.
+    pub fn should_break(&self, location: Location) -> bool {
+        if self.backend.is_none() {
+            return false;
+        }
+
+        match self.step {
+            StepMode::Into => true,
+            StepMode::Over(depth) if self.frames.len() <= depth => true,
+            StepMode::Out(depth) if self.frames.len() < depth => true,
+            _ => self
+                .breakpoints
+                .iter()
+                .any(|b| *b == Breakpoint::Location(location)),
+        }
+    }
+
+    /// Stops execution at `location`, and blocks until the client resumes it.
+    pub fn pause(&mut self, location: Location, frame: &mut dyn DebugFrame) {
+        let function = self.frames.last().and_then(Option::as_deref).unwrap_or("?");
+        let message = format!("Paused at {} in {}", location, function);
+        self.send(&message);
+
+        loop {
+            let line = match self.backend.as_mut().and_then(|b| b.wait_command()) {
+                Some(line) => line,
+                None => {
+                    // The client went away; let the movie run freely until
+                    // another one connects.
+                    self.breakpoints.clear();
+                    self.step = StepMode::Run;
+                    return;
+                }
+            };
+
+            match Command::parse(&line) {
+                Ok(Command::Pause) => self.send("Already paused."),
+                Ok(Command::Continue) => {
+                    self.step = StepMode::Run;
+                    return;
+                }
+                Ok(Command::Step) => {
+                    self.step = StepMode::Into;
+                    return;
+                }
+                Ok(Command::Next) => {
+                    self.step = StepMode::Over(self.frames.len());
+                    return;
+                }
+                Ok(Command::Finish) => {
+                    self.step = StepMode::Out(self.frames.len());
+                    return;
+                }
+                Ok(Command::Backtrace) => {
+                    let backtrace: Vec<_> = self
+                        .frames
+                        .iter()
+                        .rev()
+                        .enumerate()
+                        .map(|(i, name)| format!("#{} {}", i, name.as_deref().unwrap_or("?")))
+                        .collect();
+                    self.send(&backtrace.join("\n"));
+                }
+                Ok(Command::Locals) => {
+                    let locals = frame.locals();
+                    self.send_variables(&locals);
+                }
+                Ok(Command::Registers) => {
+                    let registers = frame.registers();
+                    self.send_variables(&registers);
+                }
+                Ok(Command::Scope) => {
+                    let scope_chain: Vec<_> = frame
+                        .scope_chain()
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, scope)| format!("#{} {}", i, scope))
+                        .collect();
+                    self.send(&scope_chain.join("\n"));
+                }
+                Ok(Command::Print(path)) => match frame.inspect(path) {
+                    Ok(value) => self.send(&format!("{} = {}", path, value)),
+                    Err(e) => self.send(&e),
+                },
+                Ok(command) => self.handle_breakpoint_command(command),
+                Err(e) => self.send(&e),
+            }
+        }
+    }
+
+    /// Handles the commands that behave the same whether or not execution is paused.
+    fn handle_breakpoint_command(&mut self, command: Command) {
+        match command {
+            Command::Break(breakpoint) => {
+                let message = format!(
+                    "Breakpoint {} set at {}",
+                    self.breakpoints.len(),
+                    breakpoint
+                );
+                self.breakpoints.push(breakpoint);
+                self.send(&message);
+            }
+            Command::Delete(index) if index < self.breakpoints.len() => {
+                let breakpoint = self.breakpoints.remove(index);
+                self.send(&format!("Deleted breakpoint at {}", breakpoint));
+            }
+            Command::Delete(index) => self.send(&format!("No breakpoint number {}", index)),
+            Command::Breakpoints if self.breakpoints.is_empty() => self.send("No breakpoints."),
+            Command::Breakpoints => {
+                let breakpoints: Vec<_> = self
+                    .breakpoints
+                    .iter()
+                    .enumerate()
+                    .map(|(i, breakpoint)| format!("{}: {}", i, breakpoint))
+                    .collect();
+                self.send(&breakpoints.join("\n"));
+            }
+            _ => self.send(HELP),
+        }
+    }
+
+    fn send_variables(&mut self, variables: &[(String, String)]) {
+        if variables.is_empty() {
+            self.send("(none)");
+        } else {
+            let lines: Vec<_> = variables
+                .iter()
+                .map(|(name, value)| format!("{} = {}", name, value))
+                .collect();
+            self.send(&lines.join("\n"));
+        }
+    }
+
+    fn send(&mut self, message: &str) {
+        if let Some(backend) = &mut self.backend {
+            backend.send(message);
+        }
+    }
+}
+
+impl Default for Debugger {
+    fn default() -> Self {
+        Debugger::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    /// A client that sends a fixed list of commands, and records every message.
+    struct ScriptedBackend {
+        commands: VecDeque<String>,
+        output: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl DebuggerBackend for ScriptedBackend {
+        fn poll_command(&mut self) -> Option<String> {
+            self.commands.pop_front()
+        }
+
+        fn wait_command(&mut self) -> Option<String> {
+            self.commands.pop_front()
+        }
+
+        fn send(&mut self, message: &str) {
+            self.output.borrow_mut().push(message.to_string());
+        }
+    }
+
+    struct EmptyFrame;
+
+    impl DebugFrame for EmptyFrame {
+        fn locals(&mut self) -> Vec<(String, String)> {
+            Vec::new()
+        }
+
+        fn registers(&mut self) -> Vec<(String, String)> {
+            Vec::new()
+        }
+
+        fn scope_chain(&mut self) -> Vec<String> {
+            Vec::new()
+        }
+
+        fn inspect(&mut self, path: &str) -> Result<String, String> {
+            Err(format!("No variable {}", path))
+        }
+    }
+
+    fn attached(commands: &[&str]) -> (Debugger, Rc<RefCell<Vec<String>>>) {
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let mut debugger = Debugger::new();
+        debugger.attach(Box::new(ScriptedBackend {
+            commands: commands.iter().map(|c| c.to_string()).collect(),
+            output: output.clone(),
+        }));
+        (debugger, output)
+    }
+
+    /// Feeds more commands to the client of an attached debugger.
+    fn send_commands(debugger: &mut Debugger, commands: &[&str]) {
+        let backend = debugger
+            .backend_mut()
+            .unwrap()
+            .downcast_mut::<ScriptedBackend>()
+            .unwrap();
+        backend
+            .commands
+            .extend(commands.iter().map(|c| c.to_string()));
+    }
+
+    const AVM1_START: Location = Location::Avm1 { offset: 0x10 };
+    const AVM1_NEXT: Location = Location::Avm1 { offset: 0x14 };
+
+    #[test]
+    fn parse_breakpoints() {
+        let parse = |argument| Command::parse_breakpoint(argument).unwrap();
+        assert_eq!(
+            parse("0x1f"),
+            Breakpoint::Location(Location::Avm1 { offset: 0x1f })
+        );
+        assert_eq!(
+            parse("31"),
+            Breakpoint::Location(Location::Avm1 { offset: 31 })
+        );
+        assert_eq!(
+            parse("3:001a"),
+            Breakpoint::Location(Location::Avm2 {
+                method: 3,
+                offset: 0x1a
+            })
+        );
+        assert_eq!(
+            parse("3:0x1a"),
+            Breakpoint::Location(Location::Avm2 {
+                method: 3,
+                offset: 0x1a
+            })
+        );
+        assert_eq!(
+            parse("onEnterFrame"),
+            Breakpoint::Function("onEnterFrame".to_string())
+        );
+        assert!(Command::parse_breakpoint("").is_err());
+    }
+
+    #[test]
+    fn location_breakpoints() {
+        let avm2 = Location::Avm2 {
+            method: 2,
+            offset: 0x0c,
+        };
+        let (mut debugger, output) = attached(&["break 0x10", "break 2:000c"]);
+        debugger.poll();
+        assert_eq!(
+            *output.borrow(),
+            [
+                "Breakpoint 0 set at AVM1 action at 0x10",
+                "Breakpoint 1 set at AVM2 method 2 at 000c"
+            ]
+        );
+
+        assert!(debugger.should_break(AVM1_START));
+        assert!(!debugger.should_break(AVM1_NEXT));
+        assert!(debugger.should_break(avm2));
+        assert!(!debugger.should_break(Location::Avm2 {
+            method: 2,
+            offset: 0x0d
+        }));
+
+        // Breakpoints are dropped along with the client.
+        debugger.detach();
+        assert!(!debugger.should_break(AVM1_START));
+    }
+
+    #[test]
+    fn function_breakpoint_stops_on_entry() {
+        let (mut debugger, output) = attached(&["break onLoad"]);
+        debugger.poll();
+
+        debugger.enter_frame("onEnterFrame");
+        assert!(!debugger.should_break(AVM1_START));
+        debugger.exit_frame();
+
+        debugger.enter_frame("onLoad");
+        assert!(debugger.should_break(AVM1_START));
+        send_commands(&mut debugger, &["bt", "continue"]);
+        debugger.pause(AVM1_START, &mut EmptyFrame);
+        assert!(!debugger.should_break(AVM1_NEXT));
+        debugger.exit_frame();
+
+        assert_eq!(
+            output.borrow()[1..],
+            ["Paused at AVM1 action at 0x10 in onLoad", "#0 onLoad"]
+        );
+    }
+
+    #[test]
+    fn stepping() {
+        let (mut debugger, _output) = attached(&["pause"]);
+        debugger.poll();
+
+        debugger.enter_frame("outer");
+        assert!(debugger.should_break(AVM1_START));
+
+        // `next` steps over calls made by the current function.
+        send_commands(&mut debugger, &["next"]);
+        debugger.pause(AVM1_START, &mut EmptyFrame);
+        debugger.enter_frame("inner");
+        assert!(!debugger.should_break(AVM1_START));
+        debugger.exit_frame();
+        assert!(debugger.should_break(AVM1_NEXT));
+
+        // `step` enters them.
+        send_commands(&mut debugger, &["step"]);
+        debugger.pause(AVM1_NEXT, &mut EmptyFrame);
+        debugger.enter_frame("inner");
+        assert!(debugger.should_break(AVM1_START));
+
+        // `finish` runs until the current function returns.
+        send_commands(&mut debugger, &["finish"]);
+        debugger.pause(AVM1_START, &mut EmptyFrame);
+        assert!(!debugger.should_break(AVM1_NEXT));
+        debugger.exit_frame();
+        assert!(debugger.should_break(AVM1_NEXT));
+
+        // `continue` only stops at breakpoints again.
+        send_commands(&mut debugger, &["continue"]);
+        debugger.pause(AVM1_NEXT, &mut EmptyFrame);
+        assert!(!debugger.should_break(AVM1_NEXT));
+        debugger.exit_frame();
+    }
+
+    #[test]
+    fn frames_entered_before_attaching() {
+        let mut debugger = Debugger::new();
+        debugger.enter_frame("outer");
+        assert!(!debugger.should_break(AVM1_START));
+
+        let (attached, output) = attached(&["pause"]);
+        debugger.attach(attached.backend.unwrap());
+        debugger.poll();
+        debugger.enter_frame("inner");
+        send_commands(&mut debugger, &["bt", "finish"]);
+        debugger.pause(AVM1_START, &mut EmptyFrame);
+        debugger.exit_frame();
+
+        // Stepping out of `inner` stops in `outer`, even though it was
+        // entered before the client attached.
+        assert!(debugger.should_break(AVM1_NEXT));
+        debugger.exit_frame();
+        assert!(debugger.frames.is_empty());
+
+        assert_eq!(
+            output.borrow()[1..],
+            ["Paused at AVM1 action at 0x10 in inner", "#0 inner\n#1 ?"]
+        );
+    }
+}
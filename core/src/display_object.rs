@@ -43,7 +43,7 @@ use crate::events::{ClipEvent, ClipEventResult};
 pub use avm1_button::Avm1Button;
 pub use avm2_button::{Avm2Button, ButtonTracking};
 pub use bitmap::Bitmap;
-pub use edit_text::{AutoSizeMode, EditText, TextSelection};
+pub use edit_text::{AutoSizeMode, EditText, LineMetrics, TextSelection};
 pub use graphic::Graphic;
 pub use morph_shape::{MorphShape, MorphShapeStatic};
 pub use movie_clip::{MovieClip, Scene};
@@ -92,6 +92,34 @@ pub struct DisplayObjectBase<'gc> {
     /// The display object we are currently masking.
     maskee: Option<DisplayObject<'gc>>,
 
+    /// The explicit tab order of this display object (`tabIndex` property).
+    tab_index: Option<i32>,
+
+    /// Whether this display object takes part in tab navigation (`tabEnabled` property).
+    /// `None` means the default for this type of display object is used.
+    tab_enabled: Option<bool>,
+
+    /// Whether the children of this display object take part in tab navigation
+    /// (`tabChildren` property).
+    tab_children: bool,
+
+    /// Whether a yellow focus rectangle is drawn around this display object
+    /// (`_focusrect` property).
+    /// `None` means the stage-wide setting is used.
+    focus_rect: Option<bool>,
+
+    /// Whether this display object can be the target of mouse events
+    /// (`mouseEnabled` property).
+    mouse_enabled: bool,
+
+    /// Whether the children of this display object can be the target of mouse events
+    /// (`mouseChildren` property).
+    mouse_children: bool,
+
+    /// Whether this display object receives `doubleClick` events
+    /// (`doubleClickEnabled` property).
+    double_click_enabled: bool,
+
     /// Bit flags for various display object properties.
     flags: DisplayObjectFlags,
 }
@@ -114,6 +142,13 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             masker: None,
             maskee: None,
             sound_transform: Default::default(),
+            tab_index: None,
+            tab_enabled: None,
+            tab_children: true,
+            focus_rect: None,
+            mouse_enabled: true,
+            mouse_children: true,
+            double_click_enabled: false,
             flags: DisplayObjectFlags::VISIBLE,
         }
     }
@@ -390,6 +425,62 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.flags.set(DisplayObjectFlags::LOCK_ROOT, value);
     }
 
+    fn tab_index(&self) -> Option<i32> {
+        self.tab_index
+    }
+
+    fn set_tab_index(&mut self, value: Option<i32>) {
+        self.tab_index = value;
+    }
+
+    fn tab_enabled_override(&self) -> Option<bool> {
+        self.tab_enabled
+    }
+
+    fn set_tab_enabled(&mut self, value: Option<bool>) {
+        self.tab_enabled = value;
+    }
+
+    fn tab_children(&self) -> bool {
+        self.tab_children
+    }
+
+    fn set_tab_children(&mut self, value: bool) {
+        self.tab_children = value;
+    }
+
+    fn focus_rect(&self) -> Option<bool> {
+        self.focus_rect
+    }
+
+    fn set_focus_rect(&mut self, value: Option<bool>) {
+        self.focus_rect = value;
+    }
+
+    fn mouse_enabled(&self) -> bool {
+        self.mouse_enabled
+    }
+
+    fn set_mouse_enabled(&mut self, value: bool) {
+        self.mouse_enabled = value;
+    }
+
+    fn mouse_children(&self) -> bool {
+        self.mouse_children
+    }
+
+    fn set_mouse_children(&mut self, value: bool) {
+        self.mouse_children = value;
+    }
+
+    fn double_click_enabled(&self) -> bool {
+        self.double_click_enabled
+    }
+
+    fn set_double_click_enabled(&mut self, value: bool) {
+        self.double_click_enabled = value;
+    }
+
     fn transformed_by_script(&self) -> bool {
         self.flags
             .contains(DisplayObjectFlags::TRANSFORMED_BY_SCRIPT)
@@ -879,6 +970,70 @@ pub trait TDisplayObject<'gc>:
     /// Returned by the `_lockroot` ActionScript property.
     fn set_lock_root(&self, gc_context: MutationContext<'gc, '_>, value: bool);
 
+    /// The explicit tab order of this display object.
+    /// Returned by the `tabIndex` ActionScript property.
+    fn tab_index(&self) -> Option<i32>;
+
+    /// Sets the explicit tab order of this display object.
+    /// Set by the `tabIndex` ActionScript property or the `SetTabIndex` tag.
+    fn set_tab_index(&self, gc_context: MutationContext<'gc, '_>, value: Option<i32>);
+
+    /// The value of `tabEnabled` explicitly set by ActionScript, if any.
+    fn tab_enabled_override(&self) -> Option<bool>;
+
+    /// Sets whether this display object takes part in tab navigation.
+    /// `None` restores the default for this type of display object.
+    fn set_tab_enabled(&self, gc_context: MutationContext<'gc, '_>, value: Option<bool>);
+
+    /// Whether this display object takes part in tab navigation.
+    /// Returned by the `tabEnabled` ActionScript property.
+    fn is_tab_enabled(&self) -> bool {
+        self.tab_enabled_override()
+            .unwrap_or_else(|| self.is_tab_enabled_by_default())
+    }
+
+    /// Whether this display object takes part in tab navigation when
+    /// `tabEnabled` has not been set by ActionScript.
+    fn is_tab_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    /// Whether the children of this display object take part in tab navigation.
+    /// Returned by the `tabChildren` ActionScript property.
+    fn tab_children(&self) -> bool;
+
+    /// Sets whether the children of this display object take part in tab navigation.
+    fn set_tab_children(&self, gc_context: MutationContext<'gc, '_>, value: bool);
+
+    /// Whether a focus rectangle is drawn around this display object when it is focused.
+    /// `None` means the stage-wide setting is used.
+    /// Returned by the `_focusrect` ActionScript property.
+    fn focus_rect(&self) -> Option<bool>;
+
+    /// Sets whether a focus rectangle is drawn around this display object when it is focused.
+    fn set_focus_rect(&self, gc_context: MutationContext<'gc, '_>, value: Option<bool>);
+
+    /// Whether this display object can be the target of mouse events.
+    /// Returned by the `mouseEnabled` ActionScript property.
+    fn mouse_enabled(&self) -> bool;
+
+    /// Sets whether this display object can be the target of mouse events.
+    fn set_mouse_enabled(&self, gc_context: MutationContext<'gc, '_>, value: bool);
+
+    /// Whether the children of this display object can be the target of mouse events.
+    /// Returned by the `mouseChildren` ActionScript property.
+    fn mouse_children(&self) -> bool;
+
+    /// Sets whether the children of this display object can be the target of mouse events.
+    fn set_mouse_children(&self, gc_context: MutationContext<'gc, '_>, value: bool);
+
+    /// Whether this display object receives `doubleClick` events.
+    /// Returned by the `doubleClickEnabled` ActionScript property.
+    fn double_click_enabled(&self) -> bool;
+
+    /// Sets whether this display object receives `doubleClick` events.
+    fn set_double_click_enabled(&self, gc_context: MutationContext<'gc, '_>, value: bool);
+
     /// Whether this display object has been transformed by ActionScript.
     /// When this flag is set, changes from SWF `PlaceObject` tags are ignored.
     fn transformed_by_script(&self) -> bool;
@@ -1562,6 +1717,56 @@ macro_rules! impl_display_object_sansbounds {
         fn set_lock_root(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
             self.0.write(context).$field.set_lock_root(value);
         }
+        fn tab_index(&self) -> Option<i32> {
+            self.0.read().$field.tab_index()
+        }
+        fn set_tab_index(&self, context: gc_arena::MutationContext<'gc, '_>, value: Option<i32>) {
+            self.0.write(context).$field.set_tab_index(value);
+        }
+        fn tab_enabled_override(&self) -> Option<bool> {
+            self.0.read().$field.tab_enabled_override()
+        }
+        fn set_tab_enabled(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            value: Option<bool>,
+        ) {
+            self.0.write(context).$field.set_tab_enabled(value);
+        }
+        fn tab_children(&self) -> bool {
+            self.0.read().$field.tab_children()
+        }
+        fn set_tab_children(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_tab_children(value);
+        }
+        fn focus_rect(&self) -> Option<bool> {
+            self.0.read().$field.focus_rect()
+        }
+        fn set_focus_rect(&self, context: gc_arena::MutationContext<'gc, '_>, value: Option<bool>) {
+            self.0.write(context).$field.set_focus_rect(value);
+        }
+        fn mouse_enabled(&self) -> bool {
+            self.0.read().$field.mouse_enabled()
+        }
+        fn set_mouse_enabled(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_mouse_enabled(value);
+        }
+        fn mouse_children(&self) -> bool {
+            self.0.read().$field.mouse_children()
+        }
+        fn set_mouse_children(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_mouse_children(value);
+        }
+        fn double_click_enabled(&self) -> bool {
+            self.0.read().$field.double_click_enabled()
+        }
+        fn set_double_click_enabled(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            value: bool,
+        ) {
+            self.0.write(context).$field.set_double_click_enabled(value);
+        }
         fn transformed_by_script(&self) -> bool {
             self.0.read().$field.transformed_by_script()
         }
@@ -506,6 +506,10 @@ impl<'gc> TDisplayObject<'gc> for Avm1Button<'gc> {
         true
     }
 
+    fn is_tab_enabled_by_default(&self) -> bool {
+        true
+    }
+
     fn on_focus_changed(&self, gc_context: MutationContext<'gc, '_>, focused: bool) {
         self.0.write(gc_context).has_focus = focused;
     }
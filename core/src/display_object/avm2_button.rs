@@ -663,7 +663,7 @@ impl<'gc> TDisplayObject<'gc> for Avm2Button<'gc> {
         require_button_mode: bool,
     ) -> Option<DisplayObject<'gc>> {
         // The button is hovered if the mouse is over any child nodes.
-        if self.visible() {
+        if self.visible() && self.mouse_enabled() {
             let state = self.0.read().state;
             let state_child = self.get_state_child(state.into());
 
@@ -807,6 +807,10 @@ impl<'gc> TDisplayObject<'gc> for Avm2Button<'gc> {
         true
     }
 
+    fn is_tab_enabled_by_default(&self) -> bool {
+        true
+    }
+
     fn on_focus_changed(&self, gc_context: MutationContext<'gc, '_>, focused: bool) {
         self.0.write(gc_context).has_focus = focused;
     }
@@ -6,31 +6,43 @@ use crate::avm1::{
     Value as Avm1Value,
 };
 use crate::avm2::{
-    Activation as Avm2Activation, Object as Avm2Object, StageObject as Avm2StageObject,
+    Activation as Avm2Activation, Avm2, Event as Avm2Event, Object as Avm2Object,
+    StageObject as Avm2StageObject,
 };
-use crate::backend::ui::MouseCursor;
+use crate::backend::navigator::RequestOptions;
+use crate::backend::ui::{ImeCursorArea, MouseCursor};
 use crate::context::{RenderContext, UpdateContext};
-use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::display_object::{DisplayObjectBase, MovieClip, TDisplayObject};
 use crate::drawing::Drawing;
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode};
 use crate::font::{round_down_to_pixel, Glyph, TextRenderSettings};
-use crate::html::{BoxBounds, FormatSpans, LayoutBox, LayoutContent, TextFormat};
+use crate::html::{
+    BoxBounds, FormatSpans, LayoutBox, LayoutContent, Position, Size, StyleSheet, TextFormat,
+};
 use crate::prelude::*;
 use crate::shape_utils::DrawCommand;
 use crate::string_utils;
-use crate::tag_utils::SwfMovie;
+use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::transform::Transform;
 use crate::types::{Degrees, Percent};
 use crate::vminterface::{AvmObject, AvmType, Instantiator};
 use crate::xml::XmlDocument;
 use chrono::Utc;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
+use std::ops::Range;
+use std::time::Duration;
 use std::{cell::Ref, sync::Arc};
 use swf::Twips;
 
 /// Boxed error type.
 pub type Error = Box<dyn std::error::Error>;
 
+/// The maximum number of user edits that can be undone.
+const MAX_UNDO_LEVELS: usize = 100;
+
+/// The longest time between two clicks on the same character that selects its word.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
 /// The kind of autosizing behavior an `EditText` should have, if any
 #[derive(Copy, Clone, Debug, Collect)]
 #[collect(no_drop)]
@@ -160,56 +172,179 @@ pub struct EditTextData<'gc> {
 
     /// How many lines down the text is offset by. 1-based index.
     scroll: usize,
+
+    /// The edits made by the user that can be undone and redone.
+    undo_history: UndoHistory,
+
+    /// The time and text position of the last click, used to detect double-clicks.
+    #[collect(require_static)]
+    last_click: Option<(Duration, usize)>,
+
+    /// The characters the user is allowed to enter.
+    restrict: TextRestrict,
+
+    /// The maximum number of characters the user can enter, or 0 for no limit.
+    max_chars: i32,
+
+    /// The `StyleSheet` object that styles this field's HTML, if any.
+    style_sheet: Option<AvmObject<'gc>>,
+
+    /// The clips holding the images embedded in the HTML, in the same order
+    /// as the images in `text_spans`.
+    images: Vec<MovieClip<'gc>>,
+
+    /// The size each image was displayed at in the last layout.
+    #[collect(require_static)]
+    image_sizes: Vec<Size<Twips>>,
+
+    /// The text being composed with an input method editor (IME), if any.
+    composition: Option<ImeComposition>,
 }
 
-// TODO: would be nicer to compute (and return) this during layout, instead of afterwards
-/// Compute line (index, offset, extent) from the layout data.
-fn get_line_data(layout: &[LayoutBox]) -> Vec<LineData> {
-    // if there are no boxes, there are no lines
-    if layout.is_empty() {
-        return Vec::new();
+impl<'gc> EditTextData<'gc> {
+    /// The text that the layout was computed from, including any text
+    /// being composed with an IME.
+    fn layout_text(&self) -> &str {
+        match &self.composition {
+            Some(composition) => &composition.layout_text,
+            None => self.text_spans.displayed_text(),
+        }
     }
+}
 
-    let first_box = &layout[0];
+/// A line of laid-out text, before blank lines have been added.
+#[derive(Clone, Debug)]
+struct LaidOutLine {
+    /// The position in the text of the line's first character, if it has any text.
+    start: Option<usize>,
+    offset: Twips,
+    extent: Twips,
+    boxes: Range<usize>,
+}
 
-    let mut index = 1;
-    let mut offset = first_box.bounds().offset_y();
-    let mut extent = first_box.bounds().extent_y();
+// TODO: would be nicer to compute (and return) this during layout, instead of afterwards
+/// Compute line (index, offset, extent) from the layout data.
+fn get_line_data(layout: &[LayoutBox], text: &str) -> Vec<LineData> {
+    let mut lines: Vec<LaidOutLine> = Vec::new();
 
-    let mut line_data = Vec::new();
+    for (i, layout_box) in layout.iter().enumerate() {
+        // Images float beside the lines rather than being part of them.
+        if layout_box.as_image().is_some() {
+            continue;
+        }
 
-    for layout_box in layout.get(1..).unwrap() {
         let bounds = layout_box.bounds();
+        let start = match layout_box.content() {
+            LayoutContent::Text { start, .. } => Some(*start),
+            _ => None,
+        };
 
-        // if the top of the new box is lower than the bottom of the old box, it's a new line
-        if bounds.offset_y() > extent {
-            // save old line and reset
-            line_data.push(LineData {
-                index,
-                offset,
-                extent,
-            });
+        match lines.last_mut() {
+            // if the top of the new box is lower than the bottom of the old box, it's a new line
+            Some(line) if bounds.offset_y() <= line.extent => {
+                // otherwise we continue from the previous box
+                line.offset = line.offset.min(bounds.offset_y());
+                line.extent = line.extent.max(bounds.extent_y());
+                line.start = match (line.start, start) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+                line.boxes.end = i + 1;
+            }
+            _ => lines.push(LaidOutLine {
+                start,
+                offset: bounds.offset_y(),
+                extent: bounds.extent_y(),
+                boxes: i..i + 1,
+            }),
+        }
+    }
 
-            index += 1;
-            offset = bounds.offset_y();
-            extent = bounds.extent_y();
-        } else {
-            // otherwise we continue from the previous box
-            offset = offset.min(bounds.offset_y());
-            extent = extent.max(bounds.extent_y());
+    add_blank_lines(text, lines)
+}
+
+/// Add the blank lines between paragraphs to a list of laid-out lines.
+///
+/// Flash counts a line after every newline, even if there's no text on it,
+/// but such lines have no layout boxes of their own. They're spread evenly
+/// over the space between the lines around them.
+fn add_blank_lines(text: &str, lines: Vec<LaidOutLine>) -> Vec<LineData> {
+    let mut paragraph_starts = std::iter::once(0)
+        .chain(text.match_indices(&['\n', '\r'][..]).map(|(i, _)| i + 1))
+        .peekable();
+    let is_blank = |from: usize, to: usize| {
+        text.get(from..to)
+            .map_or(false, |between| between.contains(&['\n', '\r'][..]))
+    };
+
+    // Every line's start, and its bounds if it was laid out.
+    let mut merged: Vec<(usize, Option<(Twips, Twips)>, Range<usize>)> = Vec::new();
+    for line in lines {
+        let mut start = match line.start {
+            Some(start) => start,
+            // A line without text, such as a lone bullet, takes the next paragraph.
+            None => paragraph_starts.next().unwrap_or_else(|| text.len()),
+        };
+
+        while let Some(&paragraph_start) = paragraph_starts.peek() {
+            if paragraph_start > start {
+                break;
+            }
+            paragraph_starts.next();
+            if is_blank(paragraph_start, start) {
+                merged.push((paragraph_start, None, line.boxes.start..line.boxes.start));
+            } else {
+                // The line starts with something that isn't laid out, such as a tab.
+                start = paragraph_start;
+            }
         }
+
+        merged.push((start, Some((line.offset, line.extent)), line.boxes));
+    }
+    for paragraph_start in paragraph_starts {
+        merged.push((paragraph_start, None, layout_end(&merged)));
     }
 
-    // save the final line
-    line_data.push(LineData {
-        index,
-        offset,
-        extent,
-    });
+    let mut line_data: Vec<LineData> = Vec::with_capacity(merged.len());
+    let mut last_height = Twips::ZERO;
+    for (i, (start, bounds, boxes)) in merged.iter().enumerate() {
+        let (offset, extent) = match bounds {
+            Some((offset, extent)) => {
+                last_height = *extent - *offset;
+                (*offset, *extent)
+            }
+            None => {
+                let top = line_data.last().map_or(Twips::ZERO, |l| l.extent);
+                let next_laid_out = merged[i..]
+                    .iter()
+                    .enumerate()
+                    .find_map(|(count, (_, bounds, _))| bounds.map(|(offset, _)| (count, offset)));
+                let height = match next_laid_out {
+                    Some((count, bottom)) => ((bottom - top) / count as i32).max(Twips::ZERO),
+                    None => last_height,
+                };
+                (top, top + height)
+            }
+        };
+
+        line_data.push(LineData {
+            index: i + 1,
+            offset,
+            extent,
+            start: *start,
+            boxes: boxes.clone(),
+        });
+    }
 
     line_data
 }
 
+/// An empty range of layout boxes after all of the given lines.
+fn layout_end(lines: &[(usize, Option<(Twips, Twips)>, Range<usize>)]) -> Range<usize> {
+    let end = lines.last().map_or(0, |(_, _, boxes)| boxes.end);
+    end..end
+}
+
 impl<'gc> EditText<'gc> {
     /// Creates a new `EditText` from an SWF `DefineEditText` tag.
     pub fn from_swf_tag(
@@ -259,8 +394,9 @@ impl<'gc> EditText<'gc> {
             bounds.width() - Twips::from_pixels(Self::INTERNAL_PADDING * 2.0),
             swf_tag.is_word_wrap,
             swf_tag.is_device_font,
+            &[],
         );
-        let line_data = get_line_data(&layout);
+        let line_data = get_line_data(&layout, text_spans.displayed_text());
 
         let has_background = swf_tag.has_border;
         let background_color = 0xFFFFFF; // Default is white
@@ -341,6 +477,14 @@ impl<'gc> EditText<'gc> {
                 hscroll: 0.0,
                 line_data,
                 scroll: 1,
+                undo_history: UndoHistory::default(),
+                last_click: None,
+                restrict: TextRestrict::default(),
+                max_chars: swf_tag.max_length.map_or(0, i32::from),
+                style_sheet: None,
+                images: Vec::new(),
+                image_sizes: Vec::new(),
+                composition: None,
             },
         ));
 
@@ -375,7 +519,7 @@ impl<'gc> EditText<'gc> {
                 b: 0,
                 a: 0xFF,
             }),
-            max_length: Some(width as u16),
+            max_length: None,
             layout: Some(swf::TextLayout {
                 align: swf::TextAlign::Left,
                 left_margin: Twips::from_pixels(0.0),
@@ -422,9 +566,15 @@ impl<'gc> EditText<'gc> {
         let tf = edit_text.text_spans.default_format().clone();
 
         edit_text.text_spans.replace_text(0, len, &text, Some(&tf));
+        let had_images = !edit_text.text_spans.images().is_empty();
+        edit_text.text_spans.clear_images();
 
         drop(edit_text);
 
+        if had_images {
+            self.load_images(context);
+        }
+
         self.relayout(context);
 
         Ok(())
@@ -474,7 +624,12 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn html_tree(self, context: &mut UpdateContext<'_, 'gc, '_>) -> XmlDocument<'gc> {
-        self.0.read().text_spans.raise_to_html(context.gc_context)
+        let edit_text = self.0.read();
+        if edit_text.style_sheet.is_some() {
+            edit_text.document
+        } else {
+            edit_text.text_spans.raise_to_html(context.gc_context)
+        }
     }
 
     /// Set the HTML tree for the given display object.
@@ -488,16 +643,160 @@ impl<'gc> EditText<'gc> {
     /// intermediate, user-facing text span APIs don't work, and the document
     /// is retained.
     pub fn set_html_tree(self, doc: XmlDocument<'gc>, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let style_sheet = self.style_sheet_data();
         let mut write = self.0.write(context.gc_context);
 
         write.document = doc;
-        write.text_spans.lower_from_html(doc);
+        if let Some(style_sheet) = style_sheet {
+            write.text_spans.lower_from_css(doc, &style_sheet);
+        } else {
+            write.text_spans.lower_from_html(doc);
+        }
 
         drop(write);
 
+        self.load_images(context);
         self.relayout(context);
     }
 
+    /// Create the clips that hold the images embedded in the HTML, replacing
+    /// any previous ones, and start loading them.
+    ///
+    /// An image's `src` is either the export name of a library symbol, or
+    /// the URL of an image or SWF to load.
+    fn load_images(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let old_images = std::mem::take(&mut self.0.write(context.gc_context).images);
+        for image in old_images {
+            image.unload(context);
+        }
+
+        let movie = self.0.read().static_data.swf.clone();
+        let text_images = self.0.read().text_spans.images().to_vec();
+        let mut images = Vec::with_capacity(text_images.len());
+
+        for text_image in text_images {
+            let image = MovieClip::new(SwfSlice::empty(movie.clone()), context.gc_context);
+            image.set_parent(context.gc_context, Some(self.into()));
+            if let Some(id) = &text_image.id {
+                image.set_name(context.gc_context, id);
+            }
+            image.post_instantiation(context, image.into(), None, Instantiator::Movie, false);
+
+            let library = context.library.library_for_movie_mut(movie.clone());
+            if library.character_by_export_name(&text_image.src).is_some() {
+                if let Ok(symbol) =
+                    library.instantiate_by_export_name(&text_image.src, context.gc_context)
+                {
+                    image.replace_at_depth(context, symbol, 0);
+                    symbol.post_instantiation(context, symbol, None, Instantiator::Movie, false);
+                }
+            } else if let Some(player) = context.player.clone() {
+                let fetch = context
+                    .navigator
+                    .fetch_body(&text_image.src, RequestOptions::get());
+                let process = context.load_manager.load_movie_into_clip(
+                    player,
+                    image.into(),
+                    fetch,
+                    text_image.src.clone(),
+                    None,
+                    None,
+                );
+                context.navigator.spawn_future(process);
+            }
+
+            // `getImageReference` can return the image straight away, so its
+            // AVM2 object can't wait for the next frame to be constructed.
+            if image.avm_type() == AvmType::Avm2 {
+                image.construct_frame(context);
+            }
+
+            images.push(image);
+        }
+
+        self.0.write(context.gc_context).images = images;
+    }
+
+    /// The size to display each of the embedded images at.
+    ///
+    /// Images are displayed at the size given in the HTML, falling back on
+    /// their own size. Images that haven't loaded yet have no size of their
+    /// own.
+    fn image_display_sizes(self) -> Vec<Size<Twips>> {
+        let edit_text = self.0.read();
+        edit_text
+            .text_spans
+            .images()
+            .iter()
+            .zip(edit_text.images.iter())
+            .map(|(text_image, image)| {
+                let bounds = image.bounds_with_transform(&Matrix::default());
+                let width = text_image
+                    .width
+                    .map_or_else(|| bounds.width(), Twips::from_pixels);
+                let height = text_image
+                    .height
+                    .map_or_else(|| bounds.height(), Twips::from_pixels);
+                Size::from((width, height))
+            })
+            .collect()
+    }
+
+    /// The clip holding the embedded image with the given `id` attribute.
+    pub fn image_by_id(self, id: &str, case_sensitive: bool) -> Option<DisplayObject<'gc>> {
+        let edit_text = self.0.read();
+        edit_text
+            .text_spans
+            .images()
+            .iter()
+            .zip(edit_text.images.iter())
+            .find(|(text_image, _)| match &text_image.id {
+                Some(image_id) if case_sensitive => image_id == id,
+                Some(image_id) => image_id.eq_ignore_ascii_case(id),
+                None => false,
+            })
+            .map(|(_, image)| (*image).into())
+    }
+
+    /// The `StyleSheet` object that styles this field's HTML, if any.
+    pub fn style_sheet(self) -> Option<AvmObject<'gc>> {
+        self.0.read().style_sheet
+    }
+
+    /// Sets the `StyleSheet` object that styles this field's HTML.
+    ///
+    /// The current HTML is restyled straight away. Later changes to the
+    /// stylesheet only apply the next time the HTML is set.
+    pub fn set_style_sheet(
+        self,
+        style_sheet: Option<AvmObject<'gc>>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) {
+        self.0.write(context.gc_context).style_sheet = style_sheet;
+        if self.is_html() {
+            let document = self.0.read().document;
+            self.set_html_tree(document, context);
+        }
+    }
+
+    /// A copy of the styles in this field's stylesheet, if it has one.
+    fn style_sheet_data(self) -> Option<StyleSheet> {
+        match self.0.read().style_sheet? {
+            AvmObject::Avm1(object) => object
+                .as_style_sheet_object()
+                .map(|object| object.style_sheet().clone()),
+            AvmObject::Avm2(object) => object.as_style_sheet().map(|sheet| sheet.clone()),
+        }
+    }
+
+    /// Whether the user can edit the text.
+    ///
+    /// Fields styled by a stylesheet can't be edited, even if they are input fields.
+    fn is_user_editable(self) -> bool {
+        let edit_text = self.0.read();
+        edit_text.is_editable && edit_text.style_sheet.is_none()
+    }
+
     pub fn text_length(self) -> usize {
         self.0.read().text_spans.text().len()
     }
@@ -559,6 +858,24 @@ impl<'gc> EditText<'gc> {
         self.relayout(context);
     }
 
+    /// The pattern of characters the user may enter, or `None` if any character is allowed.
+    pub fn restrict(self) -> Option<String> {
+        self.0.read().restrict.pattern().map(str::to_string)
+    }
+
+    pub fn set_restrict(self, pattern: Option<&str>, gc_context: MutationContext<'gc, '_>) {
+        self.0.write(gc_context).restrict = TextRestrict::from_pattern(pattern);
+    }
+
+    /// The maximum number of characters the user may enter, or 0 if there is no limit.
+    pub fn max_chars(self) -> i32 {
+        self.0.read().max_chars
+    }
+
+    pub fn set_max_chars(self, max_chars: i32, gc_context: MutationContext<'gc, '_>) {
+        self.0.write(gc_context).max_chars = max_chars.max(0);
+    }
+
     pub fn is_selectable(self) -> bool {
         self.0.read().is_selectable
     }
@@ -803,6 +1120,7 @@ impl<'gc> EditText<'gc> {
     /// have already been calculated and applied to HTML trees lowered into the
     /// text-span representation.
     fn relayout(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let image_sizes = self.image_display_sizes();
         let mut edit_text = self.0.write(context.gc_context);
         let autosize = edit_text.autosize;
         let is_word_wrap = edit_text.is_word_wrap;
@@ -817,16 +1135,63 @@ impl<'gc> EditText<'gc> {
             edit_text.text_spans.clear_displayed_text();
         }
 
+        // Text being composed with an IME is laid out as if it was inserted at the caret.
+        let composed_spans = edit_text.composition.as_ref().map(|composition| {
+            let mut spans = edit_text.text_spans.clone();
+            let position = composition.position.min(spans.text().len());
+            spans.replace_text(position, position, &composition.text, None);
+            spans
+        });
+
         let (new_layout, intrinsic_bounds) = LayoutBox::lower_from_text_spans(
-            &edit_text.text_spans,
+            composed_spans.as_ref().unwrap_or(&edit_text.text_spans),
             context,
             movie,
             width,
             is_word_wrap,
             edit_text.is_device_font,
+            &image_sizes,
         );
 
-        edit_text.line_data = get_line_data(&new_layout);
+        // Scale and move each image's clip to fill its layout box.
+        for layout_box in new_layout.iter() {
+            let image = layout_box
+                .as_image()
+                .and_then(|index| edit_text.images.get(index));
+            if let Some(image) = image {
+                let bounds = image.bounds_with_transform(&Matrix::default());
+                let target = layout_box.bounds();
+                let scale = |target: Twips, size: Twips| {
+                    if size > Twips::ZERO {
+                        target.get() as f32 / size.get() as f32
+                    } else {
+                        1.0
+                    }
+                };
+                let scale_x = scale(target.width(), bounds.width());
+                let scale_y = scale(target.height(), bounds.height());
+                let offset = |target: Twips, min: Twips, scale: f32| {
+                    target - Twips::new((min.get() as f32 * scale) as i32)
+                };
+                image.set_matrix(
+                    context.gc_context,
+                    &Matrix {
+                        a: scale_x,
+                        d: scale_y,
+                        tx: offset(target.offset_x(), bounds.x_min, scale_x),
+                        ty: offset(target.offset_y(), bounds.y_min, scale_y),
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+
+        if let (Some(composition), Some(spans)) = (&mut edit_text.composition, composed_spans) {
+            composition.layout_text = spans.displayed_text().to_string();
+        }
+
+        edit_text.image_sizes = image_sizes;
+        edit_text.line_data = get_line_data(&new_layout, edit_text.layout_text());
         edit_text.layout = new_layout;
         edit_text.intrinsic_bounds = intrinsic_bounds;
         // reset scroll
@@ -921,7 +1286,7 @@ impl<'gc> EditText<'gc> {
         let target = line_data.last().unwrap().extent - edit_text.bounds.height();
 
         // minimum line n such that n.offset > max.extent - bounds.height()
-        let max_line = line_data.iter().find(|&&l| target < l.offset);
+        let max_line = line_data.iter().find(|l| target < l.offset);
         if let Some(line) = max_line {
             line.index
         } else {
@@ -946,7 +1311,7 @@ impl<'gc> EditText<'gc> {
         let target = edit_text.bounds.height() + scroll_offset;
 
         // Line before first line with extent greater than bounds.height() + line "scroll"'s offset
-        let too_far = line_data.iter().find(|&&l| l.extent > target);
+        let too_far = line_data.iter().find(|l| l.extent > target);
         if let Some(line) = too_far {
             line.index - 1
         } else {
@@ -961,7 +1326,16 @@ impl<'gc> EditText<'gc> {
         context.transform_stack.push(&box_transform);
 
         let edit_text = self.0.read();
-        let selection = edit_text.selection;
+        // While composing with an IME, the caret is shown after the composed text,
+        // which is underlined.
+        let composed = edit_text
+            .composition
+            .as_ref()
+            .map(|composition| composition.position..composition.position + composition.text.len());
+        let selection = match &composed {
+            Some(composed) => Some(TextSelection::for_position(composed.end)),
+            None => edit_text.selection,
+        };
 
         let caret = if let LayoutContent::Text { start, end, .. } = &lbox.content() {
             if let Some(selection) = selection {
@@ -993,7 +1367,7 @@ impl<'gc> EditText<'gc> {
         // Instead, we embed an SWF version of Noto Sans to use as the "device font", and render
         // it the same as any other SWF outline text.
         if let Some((text, _tf, font, params, color)) =
-            lbox.as_renderable_text(edit_text.text_spans.displayed_text())
+            lbox.as_renderable_text(edit_text.layout_text())
         {
             let baseline_adjustment =
                 font.get_baseline_for_height(params.height()) - params.height();
@@ -1035,6 +1409,18 @@ impl<'gc> EditText<'gc> {
                         .render_shape(glyph.shape_handle, context.transform_stack.transform());
                     context.transform_stack.pop();
 
+                    if matches!(&composed, Some(composed) if composed.contains(&(start + pos))) {
+                        let underline = context.transform_stack.transform().matrix
+                            * Matrix::create_box(
+                                advance.to_pixels() as f32,
+                                1.0,
+                                0.0,
+                                x,
+                                params.height() + Twips::from_pixels(1.0),
+                            );
+                        context.renderer.draw_rect(color.clone(), &underline);
+                    }
+
                     if let Some((caret_pos, length)) = caret {
                         if caret_pos == pos {
                             let caret = context.transform_stack.transform().matrix
@@ -1254,21 +1640,70 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn screen_position_to_index(self, position: (Twips, Twips)) -> Option<usize> {
-        let text = self.0.read();
         let position = self.global_to_local(position);
-        let position = (
-            position.0 + Twips::from_pixels(Self::INTERNAL_PADDING),
-            position.1 + Twips::from_pixels(Self::INTERNAL_PADDING),
-        );
+        self.position_to_index(position, true)
+    }
+
+    /// The index of the character under a point in this text field's own
+    /// coordinate space.
+    ///
+    /// Like all of the indices taken and returned by the text metrics
+    /// methods, this counts UTF-16 units, as ActionScript does.
+    pub fn char_index_at_point(self, position: (Twips, Twips)) -> Option<usize> {
+        self.position_to_index(position, false)
+            .map(|index| self.byte_to_utf16_index(index))
+    }
+
+    /// Convert a byte offset into the laid out text into the UTF-16 index
+    /// ActionScript uses for the same character.
+    fn byte_to_utf16_index(self, index: usize) -> usize {
+        string_utils::byte_to_utf16_index(self.0.read().layout_text(), index)
+    }
+
+    /// Convert a UTF-16 index from ActionScript into a byte offset into the
+    /// laid out text, if it is the index of a character.
+    fn utf16_to_byte_index(self, index: usize) -> Option<usize> {
+        string_utils::utf16_index_to_byte(self.0.read().layout_text(), index)
+    }
+
+    /// Convert a point in this text field's coordinate space to one in the
+    /// space its layout boxes are positioned in, taking scrolling into
+    /// account.
+    fn local_to_layout(self, position: (Twips, Twips)) -> (Twips, Twips) {
+        let edit_text = self.0.read();
+        let scroll_offset = edit_text
+            .line_data
+            .get(edit_text.scroll.saturating_sub(1))
+            .map_or(Twips::ZERO, |line| line.offset);
+
+        (
+            position.0 - Twips::from_pixels(Self::INTERNAL_PADDING)
+                + Twips::from_pixels(edit_text.hscroll),
+            position.1 - Twips::from_pixels(Self::INTERNAL_PADDING) + scroll_offset,
+        )
+    }
+
+    /// Find the character under a point in this text field's coordinate space.
+    ///
+    /// If `nearest_boundary` is set, the boundary between characters closest
+    /// to the point is returned instead, which is where a caret would be
+    /// placed.
+    fn position_to_index(self, position: (Twips, Twips), nearest_boundary: bool) -> Option<usize> {
+        let position = self.local_to_layout(position);
+        let text = self.0.read();
 
         for layout_box in text.layout.iter() {
+            let start = match layout_box.content() {
+                LayoutContent::Text { start, .. } => *start,
+                _ => continue,
+            };
             let transform: Transform = layout_box.bounds().origin().into();
             let mut matrix = transform.matrix;
             matrix.invert();
             let local_position = matrix * position;
 
             if let Some((text, _tf, font, params, color)) =
-                layout_box.as_renderable_text(text.text_spans.text())
+                layout_box.as_renderable_text(text.layout_text())
             {
                 let mut result = None;
                 let baseline_adjustment =
@@ -1283,7 +1718,7 @@ impl<'gc> EditText<'gc> {
                             && local_position.1 >= Twips::ZERO
                             && local_position.1 <= params.height()
                         {
-                            if local_position.0 >= x + (advance / 2) {
+                            if nearest_boundary && local_position.0 >= x + (advance / 2) {
                                 result = Some(string_utils::next_char_boundary(text, pos));
                             } else {
                                 result = Some(pos);
@@ -1291,8 +1726,8 @@ impl<'gc> EditText<'gc> {
                         }
                     },
                 );
-                if result.is_some() {
-                    return result;
+                if let Some(result) = result {
+                    return Some(start + result);
                 }
             }
         }
@@ -1300,81 +1735,379 @@ impl<'gc> EditText<'gc> {
         None
     }
 
-    pub fn text_input(self, character: char, context: &mut UpdateContext<'_, 'gc, '_>) {
-        if !self.0.read().is_editable {
-            return;
+    /// The number of lines of text, including empty ones.
+    pub fn num_lines(self) -> usize {
+        self.0.read().line_data.len()
+    }
+
+    /// The index of the line under a point in this text field's coordinate
+    /// space.
+    pub fn line_index_at_point(self, position: (Twips, Twips)) -> Option<usize> {
+        let (_, y) = self.local_to_layout(position);
+        self.0
+            .read()
+            .line_data
+            .iter()
+            .position(|line| line.offset <= y && y < line.extent)
+    }
+
+    /// The index of the line containing the character at `index`.
+    pub fn line_index_of_char(self, index: usize) -> Option<usize> {
+        let index = self.utf16_to_byte_index(index)?;
+        let edit_text = self.0.read();
+        if index >= edit_text.text_spans.text().len() {
+            return None;
         }
 
-        if let Some(selection) = self.selection() {
-            let mut changed = false;
-            match character as u8 {
-                8 | 127 if !selection.is_caret() => {
-                    // Backspace or delete with multiple characters selected
-                    self.replace_text(selection.start(), selection.end(), "", context);
-                    self.set_selection(
-                        Some(TextSelection::for_position(selection.start())),
-                        context.gc_context,
-                    );
-                    changed = true;
-                }
-                8 => {
-                    // Backspace with caret
-                    if selection.start() > 0 {
-                        // Delete previous character
-                        let text = self.text();
-                        let start = string_utils::prev_char_boundary(&text, selection.start());
-                        self.replace_text(start, selection.start(), "", context);
-                        self.set_selection(
-                            Some(TextSelection::for_position(start)),
-                            context.gc_context,
-                        );
-                        changed = true;
-                    }
+        edit_text
+            .line_data
+            .iter()
+            .rposition(|line| line.start <= index)
+    }
+
+    /// The range of text on a line, including the newline that ends it.
+    fn line_range(self, line: usize) -> Option<Range<usize>> {
+        let edit_text = self.0.read();
+        let start = edit_text.line_data.get(line)?.start;
+        let end = edit_text
+            .line_data
+            .get(line + 1)
+            .map_or_else(|| edit_text.text_spans.text().len(), |next| next.start);
+
+        Some(start..end.max(start))
+    }
+
+    /// The index of the first character on a line.
+    pub fn line_offset(self, line: usize) -> Option<usize> {
+        self.line_range(line)
+            .map(|range| self.byte_to_utf16_index(range.start))
+    }
+
+    /// The number of characters on a line, including the newline that ends it.
+    pub fn line_length(self, line: usize) -> Option<usize> {
+        self.line_range(line).map(|range| {
+            self.byte_to_utf16_index(range.end) - self.byte_to_utf16_index(range.start)
+        })
+    }
+
+    /// The text on a line, including the newline that ends it.
+    pub fn line_text(self, line: usize) -> Option<String> {
+        let range = self.line_range(line)?;
+        self.0
+            .read()
+            .text_spans
+            .text()
+            .get(range)
+            .map(|text| text.to_string())
+    }
+
+    /// The index of the first character of the paragraph containing the
+    /// character at `index`.
+    pub fn first_char_in_paragraph(self, index: usize) -> Option<usize> {
+        let edit_text = self.0.read();
+        let text = edit_text.text_spans.text();
+        let index = string_utils::utf16_index_to_byte(text, index)?;
+        if index >= text.len() {
+            return None;
+        }
+
+        let start = text[..index]
+            .rfind(&['\n', '\r'][..])
+            .map_or(0, |newline| newline + 1);
+        Some(string_utils::byte_to_utf16_index(text, start))
+    }
+
+    /// The metrics of a line of text.
+    ///
+    /// Positions are relative to the text field, ignoring scrolling.
+    pub fn line_metrics(self, line: usize) -> Option<LineMetrics> {
+        let edit_text = self.0.read();
+        let line = edit_text.line_data.get(line)?;
+        let text = edit_text.layout_text();
+
+        let mut bounds: Option<BoxBounds<Twips>> = None;
+        let mut ascent = Twips::ZERO;
+        let mut descent = Twips::ZERO;
+        let mut leading = Twips::ZERO;
+        for layout_box in edit_text.layout.get(line.boxes.clone())? {
+            if let Some((_text, tf, font, params, _color)) = layout_box.as_renderable_text(text) {
+                ascent = ascent.max(font.get_baseline_for_height(params.height()));
+                descent = descent.max(font.get_descent_for_height(params.height()));
+                leading = leading.max(Twips::from_pixels(tf.leading.unwrap_or(0.0)));
+
+                if let Some(bounds) = &mut bounds {
+                    *bounds += layout_box.bounds();
+                } else {
+                    bounds = Some(layout_box.bounds());
                 }
-                127 => {
-                    // Delete with caret
-                    if selection.end() < self.text_length() {
-                        // Delete next character
-                        let text = self.text();
-                        let end = string_utils::next_char_boundary(&text, selection.start());
-                        self.replace_text(selection.start(), end, "", context);
-                        // No need to change selection
-                        changed = true;
-                    }
+            }
+        }
+
+        // Blank lines have no text to measure.
+        if bounds.is_none() {
+            ascent = line.extent - line.offset;
+        }
+        let bounds = bounds.unwrap_or_default();
+
+        Some(LineMetrics {
+            x: bounds.offset_x() + Twips::from_pixels(Self::INTERNAL_PADDING),
+            width: bounds.width(),
+            height: ascent + descent + leading,
+            ascent,
+            descent,
+            leading,
+        })
+    }
+
+    /// The bounding box of the character at `index`, or `None` if that
+    /// character isn't displayed.
+    ///
+    /// Positions are relative to the text field, ignoring scrolling.
+    pub fn char_boundaries(self, index: usize) -> Option<BoxBounds<Twips>> {
+        self.char_bounds(self.utf16_to_byte_index(index)?)
+    }
+
+    /// The bounding box of the character at the byte offset `index` into the
+    /// laid out text.
+    fn char_bounds(self, index: usize) -> Option<BoxBounds<Twips>> {
+        let edit_text = self.0.read();
+        let text = edit_text.layout_text();
+
+        for layout_box in edit_text.layout.iter() {
+            if let LayoutContent::Text {
+                start,
+                end,
+                font,
+                params,
+                ..
+            } = layout_box.content()
+            {
+                if index < *start || index >= *end {
+                    continue;
                 }
-                code if !(code as char).is_control() => {
-                    self.replace_text(
-                        selection.start(),
-                        selection.end(),
-                        &character.to_string(),
-                        context,
-                    );
-                    let new_start = selection.start() + character.len_utf8();
+
+                let mut glyph = None;
+                font.evaluate(
+                    text.get(*start..*end)?,
+                    Default::default(),
+                    *params,
+                    |pos, _transform, _glyph: &Glyph, advance, x| {
+                        if start + pos == index {
+                            glyph = Some((x, advance));
+                        }
+                    },
+                );
+                let (x, advance) = glyph?;
+
+                let bounds = layout_box.bounds();
+                let padding = Twips::from_pixels(Self::INTERNAL_PADDING);
+                return Some(BoxBounds::from_position_and_size(
+                    Position::from((bounds.offset_x() + x + padding, bounds.offset_y() + padding)),
+                    Size::from((advance, bounds.height())),
+                ));
+            }
+        }
+
+        None
+    }
+
+    /// Handles a character typed by the user.
+    pub fn text_input(self, character: char, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if !self.is_user_editable() {
+            return;
+        }
+
+        let selection = match self.selection() {
+            Some(selection) => selection,
+            None => return,
+        };
+
+        let changed = match character {
+            '\u{8}' | '\u{7f}' if !selection.is_caret() => {
+                // Backspace or delete with multiple characters selected
+                self.insert_input("", context)
+            }
+            '\u{8}' | '\u{7f}' => {
+                // Backspace or delete with caret, removing a whole word if Ctrl is held.
+                // Some platforms report Ctrl+Backspace as a delete character,
+                // so prefer the key that was actually pressed.
+                let is_backspace = match context.ui.last_key_code() {
+                    KeyCode::Backspace => true,
+                    KeyCode::Delete => false,
+                    _ => character == '\u{8}',
+                };
+                let by_word = context.ui.is_key_down(KeyCode::Control);
+                let text = self.text();
+                let caret = selection.to();
+                let (start, end) = match (is_backspace, by_word) {
+                    (true, false) => (string_utils::prev_char_boundary(&text, caret), caret),
+                    (true, true) => (string_utils::prev_word_boundary(&text, caret), caret),
+                    (false, false) => (caret, string_utils::next_char_boundary(&text, caret)),
+                    (false, true) => (caret, string_utils::next_word_boundary(&text, caret)),
+                };
+                if start != end {
                     self.set_selection(
-                        Some(TextSelection::for_position(new_start)),
+                        Some(TextSelection::for_range(start, end)),
                         context.gc_context,
                     );
-                    changed = true;
+                    self.insert_input("", context)
+                } else {
+                    false
                 }
-                _ => {}
             }
+            // Characters typed with Ctrl held down are shortcuts, not text,
+            // unless Alt is also held (AltGr on some keyboard layouts).
+            _ if context.ui.is_key_down(KeyCode::Control)
+                && !context.ui.is_key_down(KeyCode::Alt) =>
+            {
+                false
+            }
+            c if !c.is_control() => self.insert_input(c.encode_utf8(&mut [0; 4]), context),
+            _ => false,
+        };
 
-            if changed {
-                let globals = context.avm1.global_object_cell();
-                let swf_version = context.swf.version();
-                let mut activation = Avm1Activation::from_nothing(
-                    context.reborrow(),
-                    ActivationIdentifier::root("[Propagate Text Binding]"),
-                    swf_version,
-                    globals,
-                    self.into(),
-                );
-                self.propagate_text_binding(&mut activation);
-                self.on_changed(&mut activation);
+        if changed {
+            self.on_user_change(context);
+        }
+    }
+
+    /// Starts composing text with an input method editor (IME) at the caret.
+    pub fn start_ime_composition(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        // Passwords can't be entered with an IME.
+        if !self.is_user_editable() || self.is_password() || self.0.read().composition.is_some() {
+            return;
+        }
+
+        let position = match self.selection() {
+            Some(selection) => selection.start(),
+            None => return,
+        };
+        self.0.write(context.gc_context).composition = Some(ImeComposition {
+            position,
+            text: String::new(),
+            layout_text: String::new(),
+        });
+        self.relayout(context);
+        self.update_ime_cursor_area(context);
+
+        if let Avm2Value::Object(object) = self.object2() {
+            if let Err(e) = Avm2::dispatch_ime_event(context, "imeStartComposition", object, "") {
+                log::error!("Encountered AVM2 error when dispatching event: {}", e);
             }
         }
     }
 
+    /// Replaces the text being composed with an IME, starting a composition if needed.
+    ///
+    /// Returns whether this text field accepts text from an IME.
+    pub fn set_ime_composition(self, text: &str, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        self.start_ime_composition(context);
+
+        match &mut self.0.write(context.gc_context).composition {
+            Some(composition) => composition.text = text.to_string(),
+            None => return false,
+        }
+        self.relayout(context);
+        self.update_ime_cursor_area(context);
+        true
+    }
+
+    /// Finishes composing text with an IME, entering `text` as if it was typed.
+    /// An empty `text` cancels the composition.
+    pub fn commit_ime_composition(self, text: &str, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if self
+            .0
+            .write(context.gc_context)
+            .composition
+            .take()
+            .is_some()
+        {
+            self.relayout(context);
+        }
+
+        if text.is_empty() || !self.is_user_editable() {
+            return;
+        }
+
+        if self.insert_input(text, context) {
+            self.on_user_change(context);
+        }
+
+        if let Avm2Value::Object(object) = self.object2() {
+            if let Err(e) = Avm2::dispatch_ime_event(context, "imeComposition", object, text) {
+                log::error!("Encountered AVM2 error when dispatching event: {}", e);
+            }
+        } else {
+            let level0 = context.stage.root_clip();
+            let text = AvmString::new(context.gc_context, text);
+            Avm1::notify_system_listeners(
+                level0,
+                context.swf.version(),
+                context,
+                "System.IME",
+                "onIMEComposition",
+                &[text.into()],
+            );
+        }
+    }
+
+    /// Tells the UI where the text being composed with an IME is shown,
+    /// so that the IME candidate window can be placed next to it.
+    fn update_ime_cursor_area(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let (end, last_char_len) = match &self.0.read().composition {
+            Some(composition) => (
+                composition.position + composition.text.len(),
+                composition
+                    .text
+                    .chars()
+                    .next_back()
+                    .map_or(0, char::len_utf8),
+            ),
+            None => return,
+        };
+
+        // The caret is after the last composed character, or before the
+        // character following it if nothing has been composed yet.
+        let padding = Twips::from_pixels(Self::INTERNAL_PADDING);
+        let (x, y, height) = if last_char_len > 0 {
+            self.char_bounds(end - last_char_len)
+                .map(|bounds| (bounds.extent_x(), bounds.offset_y(), bounds.height()))
+        } else {
+            self.char_bounds(end)
+                .map(|bounds| (bounds.offset_x(), bounds.offset_y(), bounds.height()))
+        }
+        .unwrap_or_else(|| {
+            let size = self.new_text_format().size.unwrap_or(12.0);
+            (padding, padding, Twips::from_pixels(size))
+        });
+
+        let (x, y) = {
+            let edit_text = self.0.read();
+            let scroll_offset = if edit_text.scroll > 1 {
+                edit_text
+                    .line_data
+                    .get(edit_text.scroll - 1)
+                    .map_or(Twips::ZERO, |line| line.offset)
+            } else {
+                Twips::ZERO
+            };
+            (
+                edit_text.bounds.x_min + x - Twips::from_pixels(edit_text.hscroll),
+                edit_text.bounds.y_min + y - scroll_offset,
+            )
+        };
+
+        let matrix = *context.stage.matrix() * self.local_to_global_matrix();
+        let (left, top) = matrix * (x, y);
+        let (_, bottom) = matrix * (x, y + height);
+        context.ui.set_ime_cursor_area(ImeCursorArea {
+            x: left.to_pixels(),
+            y: top.to_pixels(),
+            width: 1.0,
+            height: (bottom - top).to_pixels(),
+        });
+    }
+
     /// Listens for keyboard text control commands.
     ///
     /// TODO: Add explicit text control events (#4452).
@@ -1383,51 +2116,275 @@ impl<'gc> EditText<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         event: ClipEvent,
     ) -> ClipEventResult {
-        if let ClipEvent::KeyPress { key_code } = event {
-            let mut edit_text = self.0.write(context.gc_context);
-            let selection = edit_text.selection;
-            if let Some(mut selection) = selection {
-                let text = edit_text.text_spans.text();
-                let length = text.len();
-                match key_code {
-                    ButtonKeyCode::Left => {
-                        if (context.ui.is_key_down(KeyCode::Shift) || selection.is_caret())
-                            && selection.to > 0
-                        {
-                            selection.to = string_utils::prev_char_boundary(text, selection.to);
-                            if !context.ui.is_key_down(KeyCode::Shift) {
-                                selection.from = selection.to;
-                            }
-                        } else if !context.ui.is_key_down(KeyCode::Shift) {
-                            selection.to = selection.start();
-                            selection.from = selection.to;
-                        }
-                        selection.clamp(length);
-                        edit_text.selection = Some(selection);
-                        return ClipEventResult::Handled;
-                    }
-                    ButtonKeyCode::Right => {
-                        if (context.ui.is_key_down(KeyCode::Shift) || selection.is_caret())
-                            && selection.to < length
-                        {
-                            selection.to = string_utils::next_char_boundary(text, selection.to);
-                            if !context.ui.is_key_down(KeyCode::Shift) {
-                                selection.from = selection.to;
-                            }
-                        } else if !context.ui.is_key_down(KeyCode::Shift) {
-                            selection.to = selection.end();
-                            selection.from = selection.to;
-                        }
-                        selection.clamp(length);
-                        edit_text.selection = Some(selection);
-                        return ClipEventResult::Handled;
-                    }
-                    _ => (),
+        let key_code = match event {
+            ClipEvent::KeyPress { key_code } => key_code,
+            _ => return ClipEventResult::NotHandled,
+        };
+        let selection = match self.selection() {
+            Some(selection) => selection,
+            None => return ClipEventResult::NotHandled,
+        };
+
+        let shift = context.ui.is_key_down(KeyCode::Shift);
+        let by_word = context.ui.is_key_down(KeyCode::Control);
+        let is_multiline = self.is_multiline();
+        let text = self.text();
+        let caret = selection.to();
+
+        let new_position = match key_code {
+            ButtonKeyCode::Left if by_word => string_utils::prev_word_boundary(&text, caret),
+            ButtonKeyCode::Left if shift || selection.is_caret() => {
+                string_utils::prev_char_boundary(&text, caret)
+            }
+            ButtonKeyCode::Left => selection.start(),
+            ButtonKeyCode::Right if by_word => string_utils::next_word_boundary(&text, caret),
+            ButtonKeyCode::Right if shift || selection.is_caret() => {
+                string_utils::next_char_boundary(&text, caret)
+            }
+            ButtonKeyCode::Right => selection.end(),
+            ButtonKeyCode::Home if by_word || !is_multiline => 0,
+            ButtonKeyCode::Home => text[..caret]
+                .rfind(|c: char| c == '\r' || c == '\n')
+                .map_or(0, |i| i + 1),
+            ButtonKeyCode::End if by_word || !is_multiline => text.len(),
+            ButtonKeyCode::End => text[caret..]
+                .find(|c: char| c == '\r' || c == '\n')
+                .map_or(text.len(), |i| caret + i),
+            ButtonKeyCode::Return if is_multiline && self.is_user_editable() => {
+                if self.insert_input("\r", context) {
+                    self.on_user_change(context);
                 }
+                return ClipEventResult::Handled;
             }
+            _ => return ClipEventResult::NotHandled,
+        };
+
+        let selection = if shift {
+            TextSelection::for_range(selection.from(), new_position)
+        } else {
+            TextSelection::for_position(new_position)
+        };
+        self.set_selection(Some(selection), context.gc_context);
+        ClipEventResult::Handled
+    }
+
+    /// Handles an editing shortcut pressed while Ctrl is held down, such as
+    /// copy, cut, paste, undo and redo.
+    pub fn handle_shortcut(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        key_code: KeyCode,
+    ) -> ClipEventResult {
+        let selection = match self.selection() {
+            Some(selection) => selection,
+            None => return ClipEventResult::NotHandled,
+        };
+        let is_editable = self.is_user_editable();
+        // Password fields never give their text away.
+        let can_copy = !self.is_password() && !selection.is_caret();
+
+        let changed = match key_code {
+            KeyCode::A => {
+                self.set_selection(
+                    Some(TextSelection::for_range(0, self.text_length())),
+                    context.gc_context,
+                );
+                false
+            }
+            KeyCode::C => {
+                if can_copy {
+                    let text = self.text()[selection.start()..selection.end()].to_string();
+                    context.ui.set_clipboard_content(text);
+                }
+                false
+            }
+            KeyCode::X => {
+                if can_copy && is_editable {
+                    let text = self.text()[selection.start()..selection.end()].to_string();
+                    context.ui.set_clipboard_content(text);
+                    self.insert_input("", context)
+                } else {
+                    false
+                }
+            }
+            KeyCode::V => {
+                if is_editable {
+                    let text = context.ui.get_clipboard_content();
+                    self.insert_input(&text, context)
+                } else {
+                    false
+                }
+            }
+            KeyCode::Z if is_editable && context.ui.is_key_down(KeyCode::Shift) => {
+                self.redo(context)
+            }
+            KeyCode::Z if is_editable => self.undo(context),
+            KeyCode::Y if is_editable => self.redo(context),
+            _ => return ClipEventResult::NotHandled,
+        };
+
+        if changed {
+            self.on_user_change(context);
         }
+        ClipEventResult::Handled
+    }
 
-        ClipEventResult::NotHandled
+    /// Extends the selection to the character under the mouse, while the user
+    /// drags across the text.
+    pub fn drag_selection(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if !self.is_selectable() {
+            return;
+        }
+
+        if let (Some(selection), Some(position)) = (
+            self.selection(),
+            self.screen_position_to_index(*context.mouse_position),
+        ) {
+            self.set_selection(
+                Some(TextSelection::for_range(selection.from(), position)),
+                context.gc_context,
+            );
+        }
+    }
+
+    /// Replaces the selection with text entered by the user, and places the
+    /// caret after it. The edit can be undone.
+    ///
+    /// Returns whether the text changed.
+    fn insert_input(self, text: &str, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        let selection = match self.selection() {
+            Some(selection) => selection,
+            None => return false,
+        };
+        // Single-line fields only take the first line of the text.
+        let text = if self.is_multiline() {
+            text.replace("\r\n", "\r").replace('\n', "\r")
+        } else {
+            text.split(|c: char| c == '\r' || c == '\n')
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        };
+        let inserted = self.filter_input(&text, selection);
+        if inserted.is_empty() && selection.is_caret() {
+            return false;
+        }
+
+        let (start, end) = (selection.start(), selection.end());
+        let removed = self.text()[start..end].to_string();
+        self.replace_text(start, end, &inserted, context);
+        self.set_selection(
+            Some(TextSelection::for_position(start + inserted.len())),
+            context.gc_context,
+        );
+        self.record_edit(
+            TextEdit {
+                position: start,
+                removed,
+                inserted,
+                selection,
+            },
+            context.gc_context,
+        );
+        true
+    }
+
+    /// Filters text entered in place of the given selection down to what
+    /// this field accepts.
+    ///
+    /// Characters rejected by `restrict` are dropped, and the text is cut
+    /// short so the field never holds more than `maxChars` characters.
+    pub fn filter_input(self, text: &str, selection: TextSelection) -> String {
+        let edit_text = self.0.read();
+        let mut input: String = text
+            .chars()
+            .filter_map(|c| match c {
+                '\r' | '\n' => Some(c),
+                c => edit_text.restrict.filter(c),
+            })
+            .collect();
+
+        if edit_text.max_chars > 0 {
+            let current = edit_text.text_spans.text();
+            let start = selection.start().min(current.len());
+            let end = selection.end().min(current.len());
+            let kept = current.chars().count() - current[start..end].chars().count();
+            let available = (edit_text.max_chars as usize).saturating_sub(kept);
+            if let Some(end) = input.char_indices().nth(available).map(|(i, _)| i) {
+                input.truncate(end);
+            }
+        }
+
+        input
+    }
+
+    /// Adds a user edit to the undo history.
+    fn record_edit(self, edit: TextEdit, gc_context: MutationContext<'gc, '_>) {
+        self.0.write(gc_context).undo_history.record(edit);
+    }
+
+    /// Reverts the most recent user edit.
+    ///
+    /// Returns whether the text changed.
+    fn undo(self, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        let text = self.text();
+        let edit = match self.0.write(context.gc_context).undo_history.undo(&text) {
+            Some(edit) => edit,
+            None => return false,
+        };
+
+        let end = edit.position + edit.inserted.len();
+        self.replace_text(edit.position, end, &edit.removed, context);
+        self.set_selection(Some(edit.selection), context.gc_context);
+        true
+    }
+
+    /// Reapplies the most recently undone user edit.
+    ///
+    /// Returns whether the text changed.
+    fn redo(self, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        let text = self.text();
+        let edit = match self.0.write(context.gc_context).undo_history.redo(&text) {
+            Some(edit) => edit,
+            None => return false,
+        };
+
+        let end = edit.position + edit.removed.len();
+        self.replace_text(edit.position, end, &edit.inserted, context);
+        self.set_selection(
+            Some(TextSelection::for_position(
+                edit.position + edit.inserted.len(),
+            )),
+            context.gc_context,
+        );
+        true
+    }
+
+    /// Notifies scripts that the user has changed the text.
+    fn on_user_change(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if self.avm_type() == AvmType::Avm2 {
+            if let Avm2Value::Object(object) = self.object2() {
+                let mut change_evt = Avm2Event::new("change");
+                change_evt.set_bubbles(true);
+                change_evt.set_cancelable(false);
+
+                if let Err(e) = Avm2::dispatch_event(context, change_evt, object) {
+                    log::error!("Encountered AVM2 error when dispatching event: {}", e);
+                }
+            }
+        } else {
+            let globals = context.avm1.global_object_cell();
+            let swf_version = context.swf.version();
+            let mut activation = Avm1Activation::from_nothing(
+                context.reborrow(),
+                ActivationIdentifier::root("[Propagate Text Binding]"),
+                swf_version,
+                globals,
+                self.into(),
+            );
+            self.propagate_text_binding(&mut activation);
+            self.on_changed(&mut activation);
+        }
     }
 
     fn initialize_as_broadcaster(&self, activation: &mut Avm1Activation<'_, 'gc, '_>) {
@@ -1544,10 +2501,28 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         if self.avm_type() == AvmType::Avm2 && matches!(self.object2(), Avm2Value::Undefined) {
             self.construct_as_avm2_object(context, (*self).into());
         }
+
+        if self.avm_type() == AvmType::Avm2 {
+            let images = self.0.read().images.clone();
+            for image in images {
+                image.construct_frame(context);
+            }
+        }
     }
 
-    fn run_frame(&self, _context: &mut UpdateContext) {
-        // Noop
+    fn run_frame(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        // In AVM1, image clips run from the execution list like any other clip.
+        if self.avm_type() == AvmType::Avm2 {
+            let images = self.0.read().images.clone();
+            for image in images {
+                image.run_frame_avm2(context);
+            }
+        }
+
+        // Images that were still loading at the last layout may have a size now.
+        if self.image_display_sizes() != self.0.read().image_sizes {
+            self.relayout(context);
+        }
     }
 
     fn as_edit_text(&self) -> Option<EditText<'gc>> {
@@ -1576,8 +2551,15 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
             .as_node()
             .duplicate(context.gc_context, true)
             .document();
+        let has_images = !text.text_spans.images().is_empty();
         drop(text);
 
+        // Each instance of a library text field gets its own images.
+        if has_images {
+            self.load_images(context);
+            self.relayout(context);
+        }
+
         let movie = self.movie().unwrap();
         let library = context.library.library_for_movie_mut(movie);
         let vm_type = library.avm_type();
@@ -1758,7 +2740,14 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
             }
         } else {
             for layout_box in edit_text.layout.iter() {
-                self.render_layout_box(context, layout_box);
+                let image = layout_box
+                    .as_image()
+                    .and_then(|index| edit_text.images.get(index));
+                if let Some(image) = image {
+                    image.render(context);
+                } else {
+                    self.render_layout_box(context, layout_box);
+                }
             }
         }
 
@@ -1779,6 +2768,11 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
     }
 
     fn unload(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let images = self.0.read().images.clone();
+        for image in images {
+            image.unload(context);
+        }
+
         let had_focus = self.0.read().has_focus;
         if had_focus {
             let tracker = context.focus_tracker;
@@ -1820,6 +2814,7 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         // The button is hovered if the mouse is over any child nodes.
         if self.visible()
             && self.is_selectable()
+            && self.mouse_enabled()
             && self.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK)
         {
             Some((*self).into())
@@ -1845,6 +2840,10 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         true
     }
 
+    fn is_tab_enabled_by_default(&self) -> bool {
+        self.is_editable()
+    }
+
     fn handle_clip_event(
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -1853,15 +2852,41 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         if event == ClipEvent::Press {
             let tracker = context.focus_tracker;
             tracker.set(Some((*self).into()), context);
-            if let Some(position) = self
+            let position = self
                 .screen_position_to_index(*context.mouse_position)
-                .map(TextSelection::for_position)
-            {
-                self.0.write(context.gc_context).selection = Some(position);
+                .unwrap_or_else(|| self.text_length());
+            let now = context.navigator.time_since_launch();
+            let mut edit_text = self.0.write(context.gc_context);
+            let is_double_click = match edit_text.last_click {
+                Some((time, last_position)) => {
+                    last_position == position
+                        && now
+                            .checked_sub(time)
+                            .map_or(false, |t| t <= DOUBLE_CLICK_INTERVAL)
+                }
+                None => false,
+            };
+
+            let selection = match edit_text.selection {
+                // Double-clicking selects a whole word.
+                _ if is_double_click => {
+                    let text = edit_text.text_spans.text();
+                    let (start, end) = string_utils::word_at(text, position);
+                    TextSelection::for_range(start, end)
+                }
+                // Shift-clicking extends the existing selection.
+                Some(selection) if context.ui.is_key_down(KeyCode::Shift) => {
+                    TextSelection::for_range(selection.from(), position)
+                }
+                _ => TextSelection::for_position(position),
+            };
+            edit_text.selection = Some(selection);
+            // A third click starts over, rather than being another double-click.
+            edit_text.last_click = if is_double_click {
+                None
             } else {
-                self.0.write(context.gc_context).selection =
-                    Some(TextSelection::for_position(self.text_length()));
-            }
+                Some((now, position))
+            };
             return ClipEventResult::Handled;
         }
         ClipEventResult::NotHandled
@@ -1907,8 +2932,231 @@ pub struct TextSelection {
     to: usize,
 }
 
+/// The characters a user may enter into a text field, parsed from the
+/// `restrict` property.
+///
+/// The pattern lists the allowed characters, with `-` for ranges such as
+/// `A-Z`. A `^` switches between allowing and rejecting the characters that
+/// follow it, and a pattern starting with `^` allows every character not
+/// listed. A backslash escapes `-`, `^` and `\`.
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(require_static)]
+struct TextRestrict {
+    /// The pattern this was parsed from, or `None` if any character is allowed.
+    pattern: Option<String>,
+
+    /// The listed character ranges, and whether they are allowed.
+    /// Later ranges take precedence over earlier ones.
+    ranges: Vec<(char, char, bool)>,
+
+    /// Whether characters that aren't in any range are allowed.
+    allow_unlisted: bool,
+}
+
+impl TextRestrict {
+    pub fn from_pattern(pattern: Option<&str>) -> Self {
+        let pattern = match pattern {
+            Some(pattern) => pattern,
+            None => return Self::default(),
+        };
+
+        let mut ranges = Vec::new();
+        let mut allow = true;
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            let start = match c {
+                '^' => {
+                    allow = !allow;
+                    continue;
+                }
+                '\\' => match chars.next() {
+                    Some(c) => c,
+                    None => break,
+                },
+                c => c,
+            };
+
+            let mut end = start;
+            if chars.peek() == Some(&'-') {
+                chars.next();
+                end = match chars.next() {
+                    Some('\\') => chars.next().unwrap_or('-'),
+                    Some(c) => c,
+                    // A trailing `-` is just another character.
+                    None => {
+                        ranges.push(('-', '-', allow));
+                        start
+                    }
+                };
+            }
+            ranges.push((start.min(end), start.max(end), allow));
+        }
+
+        Self {
+            pattern: Some(pattern.to_string()),
+            ranges,
+            allow_unlisted: pattern.starts_with('^'),
+        }
+    }
+
+    pub fn pattern(&self) -> Option<&str> {
+        self.pattern.as_deref()
+    }
+
+    /// Whether `c` is explicitly allowed or rejected by a listed range.
+    fn listed(&self, c: char) -> Option<bool> {
+        self.ranges
+            .iter()
+            .rev()
+            .find(|(start, end, _)| (*start..=*end).contains(&c))
+            .map(|(_, _, allow)| *allow)
+    }
+
+    pub fn is_allowed(&self, c: char) -> bool {
+        self.pattern.is_none() || self.listed(c).unwrap_or(self.allow_unlisted)
+    }
+
+    /// Returns the character to enter in place of `c`, if any.
+    ///
+    /// Like Flash, a letter that isn't allowed is changed to the other case
+    /// if that case is listed as allowed, so `A-Z` turns typed lowercase
+    /// letters into uppercase.
+    pub fn filter(&self, c: char) -> Option<char> {
+        if self.is_allowed(c) {
+            return Some(c);
+        }
+
+        let other_case = if c.is_lowercase() {
+            string_utils::swf_char_to_uppercase(c)
+        } else {
+            string_utils::swf_char_to_lowercase(c)
+        };
+        if other_case != c && self.listed(other_case) == Some(true) {
+            Some(other_case)
+        } else {
+            None
+        }
+    }
+}
+
+/// Text being composed with an input method editor (IME).
+///
+/// It is laid out at the caret, but isn't part of the text until it is committed.
+#[derive(Clone, Debug, Collect)]
+#[collect(require_static)]
+struct ImeComposition {
+    /// The byte position in the text at which the composition is shown.
+    position: usize,
+
+    /// The text being composed.
+    text: String,
+
+    /// The displayed text with the composition inserted, as it was laid out.
+    layout_text: String,
+}
+
+/// A change made to the text by the user, which can be undone.
+#[derive(Clone, Debug, Collect)]
+#[collect(require_static)]
+struct TextEdit {
+    /// The byte position at which the text was replaced.
+    position: usize,
+
+    /// The text that was removed.
+    removed: String,
+
+    /// The text that was inserted in its place.
+    inserted: String,
+
+    /// The selection before the edit, restored when it is undone.
+    selection: TextSelection,
+}
+
+/// The user edits to a text field that can be undone and redone.
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(require_static)]
+struct UndoHistory {
+    /// The edits that can be undone, most recent last.
+    undo_stack: Vec<TextEdit>,
+
+    /// The edits that were undone and can be redone, most recently undone last.
+    redo_stack: Vec<TextEdit>,
+}
+
+impl UndoHistory {
+    /// Adds a user edit, forgetting any edits that were undone.
+    ///
+    /// Characters typed one after another are merged into a single edit, so
+    /// that they are undone together.
+    fn record(&mut self, edit: TextEdit) {
+        self.redo_stack.clear();
+
+        if let Some(last) = self.undo_stack.last_mut() {
+            if edit.removed.is_empty()
+                && edit.inserted.chars().count() == 1
+                && !last.inserted.is_empty()
+                && last.position + last.inserted.len() == edit.position
+            {
+                last.inserted.push_str(&edit.inserted);
+                return;
+            }
+        }
+
+        if self.undo_stack.len() >= MAX_UNDO_LEVELS {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(edit);
+    }
+
+    /// Takes the most recent edit so that it can be reverted in `text`.
+    ///
+    /// If `text` no longer holds what the edit inserted, a script has changed
+    /// the text since, so the history no longer applies and is dropped.
+    fn undo(&mut self, text: &str) -> Option<TextEdit> {
+        let edit = self.undo_stack.pop()?;
+        let end = edit.position + edit.inserted.len();
+        if text.get(edit.position..end) != Some(edit.inserted.as_str()) {
+            self.clear();
+            return None;
+        }
+
+        self.redo_stack.push(edit.clone());
+        Some(edit)
+    }
+
+    /// Takes the most recently undone edit so that it can be reapplied to `text`.
+    fn redo(&mut self, text: &str) -> Option<TextEdit> {
+        let edit = self.redo_stack.pop()?;
+        let end = edit.position + edit.removed.len();
+        if text.get(edit.position..end) != Some(edit.removed.as_str()) {
+            self.clear();
+            return None;
+        }
+
+        self.undo_stack.push(edit.clone());
+        Some(edit)
+    }
+
+    fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+}
+
+/// The measurements of a line of text, as given by `TextField.getLineMetrics`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LineMetrics {
+    /// The left edge of the line's text.
+    pub x: Twips,
+    pub width: Twips,
+    pub height: Twips,
+    pub ascent: Twips,
+    pub descent: Twips,
+    pub leading: Twips,
+}
+
 /// Information about the start and end y-coordinates of a given line of text
-#[derive(Copy, Clone, Debug, Collect)]
+#[derive(Clone, Debug, Collect)]
 #[collect(require_static)]
 pub struct LineData {
     index: usize,
@@ -1916,6 +3164,10 @@ pub struct LineData {
     offset: Twips,
     /// How many twips down the lowest point of the line is
     extent: Twips,
+    /// The position in the text of the line's first character
+    start: usize,
+    /// The layout boxes that make up the line
+    boxes: Range<usize>,
 }
 
 impl TextSelection {
@@ -1980,3 +3232,212 @@ impl TextSelection {
         self.to == self.from
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        add_blank_lines, LaidOutLine, TextEdit, TextRestrict, TextSelection, UndoHistory,
+        MAX_UNDO_LEVELS,
+    };
+    use swf::Twips;
+
+    fn allowed(pattern: Option<&str>, text: &str) -> String {
+        let restrict = TextRestrict::from_pattern(pattern);
+        text.chars().filter_map(|c| restrict.filter(c)).collect()
+    }
+
+    #[test]
+    fn restrict_none_allows_everything() {
+        assert_eq!(allowed(None, "aZ 9-^\\"), "aZ 9-^\\");
+    }
+
+    #[test]
+    fn restrict_empty_allows_nothing() {
+        assert_eq!(allowed(Some(""), "abc123"), "");
+    }
+
+    #[test]
+    fn restrict_ranges() {
+        assert_eq!(allowed(Some("0-9"), "a1b2c3"), "123");
+        assert_eq!(allowed(Some("A-Z 0-9"), "Hello 42!"), "HELLO 42");
+        assert_eq!(allowed(Some("a-z"), "ABC"), "abc");
+        assert_eq!(allowed(Some("z-a"), "mq"), "mq");
+    }
+
+    #[test]
+    fn restrict_negation() {
+        assert_eq!(allowed(Some("^0-9"), "a1b2"), "ab");
+        assert_eq!(allowed(Some("A-Z^Q"), "QUIZ"), "UIZ");
+        assert_eq!(allowed(Some("^a-z^m"), "ma1"), "m1");
+    }
+
+    #[test]
+    fn restrict_escapes() {
+        assert_eq!(allowed(Some("0-9\\-"), "-1a"), "-1");
+        assert_eq!(allowed(Some("\\^\\\\"), "^\\a"), "^\\");
+        assert_eq!(allowed(Some("a-"), "a-b"), "a-");
+    }
+
+    fn laid_out(start: usize, offset: i32, extent: i32, boxes: usize) -> LaidOutLine {
+        LaidOutLine {
+            start: Some(start),
+            offset: Twips::new(offset),
+            extent: Twips::new(extent),
+            boxes: boxes..boxes + 1,
+        }
+    }
+
+    fn lines(text: &str, lines: Vec<LaidOutLine>) -> Vec<(usize, usize, i32, i32)> {
+        add_blank_lines(text, lines)
+            .iter()
+            .map(|line| (line.index, line.start, line.offset.get(), line.extent.get()))
+            .collect()
+    }
+
+    #[test]
+    fn blank_lines_are_counted() {
+        assert_eq!(
+            lines(
+                "ab\n\ncd\n",
+                vec![laid_out(0, 0, 100, 0), laid_out(4, 300, 400, 1)]
+            ),
+            vec![
+                (1, 0, 0, 100),
+                (2, 3, 100, 300),
+                (3, 4, 300, 400),
+                (4, 7, 400, 500)
+            ]
+        );
+        assert_eq!(lines("", vec![]), vec![(1, 0, 0, 0)]);
+    }
+
+    #[test]
+    fn wrapped_lines_keep_their_start() {
+        assert_eq!(
+            lines(
+                "\tab cd",
+                vec![laid_out(1, 0, 100, 0), laid_out(4, 100, 200, 1)]
+            ),
+            vec![(1, 0, 0, 100), (2, 4, 100, 200)]
+        );
+    }
+
+    fn edit(position: usize, removed: &str, inserted: &str) -> TextEdit {
+        TextEdit {
+            position,
+            removed: removed.to_string(),
+            inserted: inserted.to_string(),
+            selection: TextSelection::for_range(position, position + removed.len()),
+        }
+    }
+
+    /// Types `text` into `field` one character at a time, at `position`.
+    fn type_text(history: &mut UndoHistory, field: &mut String, position: usize, text: &str) {
+        for (i, c) in text.char_indices() {
+            field.insert(position + i, c);
+            history.record(edit(position + i, "", &c.to_string()));
+        }
+    }
+
+    fn undo(history: &mut UndoHistory, field: &mut String) -> bool {
+        match history.undo(field) {
+            Some(edit) => {
+                let end = edit.position + edit.inserted.len();
+                field.replace_range(edit.position..end, &edit.removed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn redo(history: &mut UndoHistory, field: &mut String) -> bool {
+        match history.redo(field) {
+            Some(edit) => {
+                let end = edit.position + edit.removed.len();
+                field.replace_range(edit.position..end, &edit.inserted);
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[test]
+    fn typing_is_undone_together() {
+        let mut history = UndoHistory::default();
+        let mut field = String::new();
+        type_text(&mut history, &mut field, 0, "héllo");
+        assert_eq!(history.undo_stack.len(), 1);
+
+        assert!(undo(&mut history, &mut field));
+        assert_eq!(field, "");
+        assert!(!undo(&mut history, &mut field));
+
+        assert!(redo(&mut history, &mut field));
+        assert_eq!(field, "héllo");
+        assert!(!redo(&mut history, &mut field));
+    }
+
+    #[test]
+    fn separate_edits_are_undone_in_turn() {
+        let mut history = UndoHistory::default();
+        let mut field = String::new();
+        type_text(&mut history, &mut field, 0, "ac");
+        // Typing somewhere else starts a new edit.
+        type_text(&mut history, &mut field, 1, "b");
+        // So does replacing a selection.
+        field.replace_range(0..3, "xyz");
+        history.record(edit(0, "abc", "xyz"));
+        assert_eq!(history.undo_stack.len(), 3);
+
+        assert!(undo(&mut history, &mut field));
+        assert_eq!(field, "abc");
+        assert!(undo(&mut history, &mut field));
+        assert_eq!(field, "ac");
+        assert!(redo(&mut history, &mut field));
+        assert_eq!(field, "abc");
+        assert!(undo(&mut history, &mut field));
+        assert!(undo(&mut history, &mut field));
+        assert_eq!(field, "");
+    }
+
+    #[test]
+    fn new_edits_forget_undone_edits() {
+        let mut history = UndoHistory::default();
+        let mut field = String::new();
+        type_text(&mut history, &mut field, 0, "one");
+        assert!(undo(&mut history, &mut field));
+        type_text(&mut history, &mut field, 0, "two");
+        assert!(!redo(&mut history, &mut field));
+        assert_eq!(field, "two");
+    }
+
+    #[test]
+    fn script_changes_drop_the_history() {
+        let mut history = UndoHistory::default();
+        let mut field = String::new();
+        type_text(&mut history, &mut field, 0, "abc");
+        type_text(&mut history, &mut field, 0, "x");
+
+        field = "changed".to_string();
+        assert!(!undo(&mut history, &mut field));
+        assert!(history.undo_stack.is_empty());
+        assert!(history.redo_stack.is_empty());
+        assert_eq!(field, "changed");
+    }
+
+    #[test]
+    fn undo_levels_are_limited() {
+        let mut history = UndoHistory::default();
+        let mut field = String::new();
+        for i in 0..=MAX_UNDO_LEVELS {
+            // Replacing a character is never merged with the previous edit.
+            let removed = field.clone();
+            field = (i % 10).to_string();
+            history.record(edit(0, &removed, &field));
+        }
+        assert_eq!(history.undo_stack.len(), MAX_UNDO_LEVELS);
+
+        while undo(&mut history, &mut field) {}
+        assert_eq!(field, "0");
+    }
+}
@@ -7,6 +7,7 @@ use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
 use crate::drawing::Drawing;
 use crate::prelude::*;
+use crate::profiler::ProfileCategory;
 use crate::tag_utils::SwfMovie;
 use crate::types::{Degrees, Percent};
 use crate::vminterface::{AvmType, Instantiator};
@@ -35,14 +36,19 @@ impl<'gc> Graphic<'gc> {
         movie: Arc<SwfMovie>,
     ) -> Self {
         let library = context.library.library_for_movie(movie.clone()).unwrap();
+        let span = context.profiler.start();
+        let render_handle = context
+            .renderer
+            .register_shape((&swf_shape).into(), library);
+        context
+            .profiler
+            .finish(span, ProfileCategory::Tessellation, || {
+                format!("Shape {}", swf_shape.id)
+            });
         let static_data = GraphicStatic {
             id: swf_shape.id,
             bounds: swf_shape.shape_bounds.clone().into(),
-            render_handle: Some(
-                context
-                    .renderer
-                    .register_shape((&swf_shape).into(), library),
-            ),
+            render_handle: Some(render_handle),
             shape: swf_shape,
             movie: Some(movie),
         };
@@ -2,9 +2,11 @@ use crate::backend::render::ShapeHandle;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
 use crate::prelude::*;
+use crate::profiler::ProfileCategory;
 use crate::tag_utils::SwfMovie;
 use crate::types::{Degrees, Percent};
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
+use std::cell::RefCell;
 use std::sync::Arc;
 use swf::{Fixed16, Fixed8, Twips};
 
@@ -42,6 +44,12 @@ impl<'gc> MorphShape<'gc> {
     pub fn set_ratio(&mut self, gc_context: MutationContext<'gc, '_>, ratio: u16) {
         self.0.write(gc_context).ratio = ratio;
     }
+
+    /// Precalculates the frame for the given ratio, shared by all instances of this morph shape.
+    pub fn register_ratio(self, context: &mut UpdateContext<'_, 'gc, '_>, ratio: u16) {
+        let static_data = self.0.read().static_data;
+        static_data.register_ratio(context, ratio);
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
@@ -72,7 +80,7 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext) {
-        if let Some(frame) = self.0.read().static_data.frames.get(&self.ratio()) {
+        if let Some(frame) = self.0.read().static_data.frames.borrow().get(&self.ratio()) {
             context
                 .renderer
                 .render_shape(frame.shape_handle, context.transform_stack.transform());
@@ -83,7 +91,7 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
 
     fn self_bounds(&self) -> BoundingBox {
         // TODO: Use the bounds of the current ratio.
-        if let Some(frame) = self.0.read().static_data.frames.get(&self.ratio()) {
+        if let Some(frame) = self.0.read().static_data.frames.borrow().get(&self.ratio()) {
             frame.bounds.clone()
         } else {
             BoundingBox::default()
@@ -97,7 +105,7 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
         _options: HitTestOptions,
     ) -> bool {
         if self.world_bounds().contains(point) {
-            if let Some(frame) = self.0.read().static_data.frames.get(&self.ratio()) {
+            if let Some(frame) = self.0.read().static_data.frames.borrow().get(&self.ratio()) {
                 let local_matrix = self.global_to_local_matrix();
                 let point = local_matrix * point;
                 return crate::shape_utils::shape_hit_test(&frame.shape, point, &local_matrix);
@@ -125,7 +133,7 @@ pub struct MorphShapeStatic {
     id: CharacterId,
     start: swf::MorphShape,
     end: swf::MorphShape,
-    frames: fnv::FnvHashMap<u16, Frame>,
+    frames: RefCell<fnv::FnvHashMap<u16, Frame>>,
     movie: Arc<SwfMovie>,
 }
 
@@ -135,11 +143,11 @@ impl MorphShapeStatic {
         swf_tag: &swf::DefineMorphShape,
         movie: Arc<SwfMovie>,
     ) -> Self {
-        let mut morph_shape = Self {
+        let morph_shape = Self {
             id: swf_tag.id,
             start: swf_tag.start.clone(),
             end: swf_tag.end.clone(),
-            frames: fnv::FnvHashMap::default(),
+            frames: RefCell::new(fnv::FnvHashMap::default()),
             movie,
         };
         // Pre-register the start and end states.
@@ -148,8 +156,8 @@ impl MorphShapeStatic {
         morph_shape
     }
 
-    pub fn register_ratio(&mut self, context: &mut UpdateContext<'_, '_, '_>, ratio: u16) {
-        if self.frames.contains_key(&ratio) {
+    pub fn register_ratio(&self, context: &mut UpdateContext<'_, '_, '_>, ratio: u16) {
+        if self.frames.borrow().contains_key(&ratio) {
             // Already registered.
             return;
         }
@@ -284,13 +292,19 @@ impl MorphShapeStatic {
             shape,
         };
 
+        let span = context.profiler.start();
         let shape_handle = context.renderer.register_shape((&shape).into(), library);
+        context
+            .profiler
+            .finish(span, ProfileCategory::Tessellation, || {
+                format!("MorphShape {}", self.id)
+            });
         let frame = Frame {
             shape_handle,
             shape,
             bounds: bounds.into(),
         };
-        self.frames.insert(ratio, frame);
+        self.frames.borrow_mut().insert(ratio, frame);
     }
 
     fn update_pos(x: &mut Twips, y: &mut Twips, record: &swf::ShapeRecord) {
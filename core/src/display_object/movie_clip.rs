@@ -20,8 +20,8 @@ use crate::display_object::container::{
     ChildContainer, TDisplayObjectContainer,
 };
 use crate::display_object::{
-    Avm1Button, Avm2Button, Bitmap, DisplayObjectBase, EditText, Graphic, MorphShapeStatic,
-    TDisplayObject, Text, Video,
+    Avm1Button, Avm2Button, Bitmap, DisplayObjectBase, EditText, Graphic, MorphShape,
+    MorphShapeStatic, TDisplayObject, Text, Video,
 };
 use crate::drawing::Drawing;
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult};
@@ -30,9 +30,9 @@ use crate::prelude::*;
 use crate::tag_utils::{self, DecodeResult, SwfMovie, SwfSlice, SwfStream};
 use crate::types::{Degrees, Percent};
 use crate::vminterface::{AvmObject, AvmType, Instantiator};
-use gc_arena::{Collect, Gc, GcCell, MutationContext};
+use gc_arena::{Collect, GcCell, MutationContext};
 use smallvec::SmallVec;
-use std::cell::{Ref, RefCell, RefMut};
+use std::cell::{Ref, RefMut};
 use std::collections::HashMap;
 use std::sync::Arc;
 use swf::extensions::ReadSwfExt;
@@ -51,6 +51,9 @@ enum NextFrame {
 
     /// Do not construct or run any frames.
     Same,
+
+    /// Stay on the current frame until the next frame has been preloaded.
+    Wait,
 }
 
 /// A movie clip is a display object with its own timeline that runs independently of the root timeline.
@@ -66,7 +69,7 @@ pub struct MovieClip<'gc>(GcCell<'gc, MovieClipData<'gc>>);
 #[collect(no_drop)]
 pub struct MovieClipData<'gc> {
     base: DisplayObjectBase<'gc>,
-    static_data: Gc<'gc, MovieClipStatic>,
+    static_data: GcCell<'gc, MovieClipStatic>,
     tag_stream_pos: u64,
     current_frame: FrameNumber,
     #[collect(require_static)]
@@ -87,6 +90,13 @@ pub struct MovieClipData<'gc> {
     last_queued_script_frame: Option<FrameNumber>,
     queued_script_frame: Option<FrameNumber>,
     drop_target: Option<DisplayObject<'gc>>,
+
+    /// Whether this clip acts as a button in AVM2 (`buttonMode` property).
+    button_mode: bool,
+
+    /// The display object used instead of this clip's own shape for
+    /// mouse picking (`hitArea` property).
+    hit_area: Option<DisplayObject<'gc>>,
 }
 
 impl<'gc> MovieClip<'gc> {
@@ -96,7 +106,7 @@ impl<'gc> MovieClip<'gc> {
             gc_context,
             MovieClipData {
                 base: Default::default(),
-                static_data: Gc::allocate(gc_context, MovieClipStatic::empty(swf)),
+                static_data: GcCell::allocate(gc_context, MovieClipStatic::empty(swf)),
                 tag_stream_pos: 0,
                 current_frame: 0,
                 audio_stream: None,
@@ -115,6 +125,8 @@ impl<'gc> MovieClip<'gc> {
                 last_queued_script_frame: None,
                 queued_script_frame: None,
                 drop_target: None,
+                button_mode: false,
+                hit_area: None,
             },
         ))
     }
@@ -129,7 +141,7 @@ impl<'gc> MovieClip<'gc> {
             gc_context,
             MovieClipData {
                 base: Default::default(),
-                static_data: Gc::allocate(gc_context, MovieClipStatic::empty(swf)),
+                static_data: GcCell::allocate(gc_context, MovieClipStatic::empty(swf)),
                 tag_stream_pos: 0,
                 current_frame: 0,
                 audio_stream: None,
@@ -148,6 +160,8 @@ impl<'gc> MovieClip<'gc> {
                 last_queued_script_frame: None,
                 queued_script_frame: None,
                 drop_target: None,
+                button_mode: false,
+                hit_area: None,
             },
         ))
     }
@@ -162,7 +176,7 @@ impl<'gc> MovieClip<'gc> {
             gc_context,
             MovieClipData {
                 base: Default::default(),
-                static_data: Gc::allocate(
+                static_data: GcCell::allocate(
                     gc_context,
                     MovieClipStatic::with_data(id, swf, num_frames),
                 ),
@@ -184,6 +198,8 @@ impl<'gc> MovieClip<'gc> {
                 last_queued_script_frame: None,
                 queued_script_frame: None,
                 drop_target: None,
+                button_mode: false,
+                hit_area: None,
             },
         ))
     }
@@ -195,7 +211,7 @@ impl<'gc> MovieClip<'gc> {
             gc_context,
             MovieClipData {
                 base: Default::default(),
-                static_data: Gc::allocate(
+                static_data: GcCell::allocate(
                     gc_context,
                     MovieClipStatic::with_data(0, movie.into(), num_frames),
                 ),
@@ -217,6 +233,8 @@ impl<'gc> MovieClip<'gc> {
                 last_queued_script_frame: None,
                 queued_script_frame: None,
                 drop_target: None,
+                button_mode: false,
+                hit_area: None,
             },
         ));
         mc.set_is_root(gc_context, true);
@@ -238,251 +256,271 @@ impl<'gc> MovieClip<'gc> {
             .replace_with_movie(gc_context, movie)
     }
 
-    pub fn preload(
-        self,
-        context: &mut UpdateContext<'_, 'gc, '_>,
-        morph_shapes: &mut fnv::FnvHashMap<CharacterId, MorphShapeStatic>,
-    ) {
+    /// Preloads the tags of this clip that have been loaded so far.
+    ///
+    /// If the movie is still downloading, only the frames that have arrived
+    /// in full are preloaded, and each of them becomes playable straight
+    /// away. Calling this again once more of the movie has arrived picks up
+    /// where the last call left off.
+    ///
+    /// Returns `true` once the entire clip has been preloaded.
+    pub fn preload(self, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
         use swf::TagCode;
-        // TODO: Re-creating static data because preload step occurs after construction.
-        // Should be able to hoist this up somewhere, or use MaybeUninit.
-        let mut static_data = (&*self.0.read().static_data).clone();
-        let data = self.0.read().static_data.swf.clone();
-        let mut reader = data.read_from(0);
-        let mut cur_frame = 1;
-        let mut ids = fnv::FnvHashMap::default();
-        let mut preload_stream_handle = None;
-        let tag_callback = |reader: &mut SwfStream<'_>, tag_code, tag_len| match tag_code {
-            TagCode::CsmTextSettings => self
-                .0
-                .write(context.gc_context)
-                .csm_text_settings(context, reader),
-            TagCode::DefineBits => self
-                .0
-                .write(context.gc_context)
-                .define_bits(context, reader, tag_len),
-            TagCode::DefineBitsJpeg2 => self
-                .0
-                .write(context.gc_context)
-                .define_bits_jpeg_2(context, reader, tag_len),
-            TagCode::DefineBitsJpeg3 => self
-                .0
-                .write(context.gc_context)
-                .define_bits_jpeg_3(context, reader, tag_len),
-            TagCode::DefineBitsJpeg4 => self
-                .0
-                .write(context.gc_context)
-                .define_bits_jpeg_4(context, reader, tag_len),
-            TagCode::DefineBitsLossless => self
-                .0
-                .write(context.gc_context)
-                .define_bits_lossless(context, reader, 1),
-            TagCode::DefineBitsLossless2 => self
-                .0
-                .write(context.gc_context)
-                .define_bits_lossless(context, reader, 2),
-            TagCode::DefineButton => self
-                .0
-                .write(context.gc_context)
-                .define_button_1(context, reader),
-            TagCode::DefineButton2 => self
-                .0
-                .write(context.gc_context)
-                .define_button_2(context, reader),
-            TagCode::DefineButtonCxform => self
-                .0
-                .write(context.gc_context)
-                .define_button_cxform(context, reader, tag_len),
-            TagCode::DefineButtonSound => self
-                .0
-                .write(context.gc_context)
-                .define_button_sound(context, reader),
-            TagCode::DefineEditText => self
-                .0
-                .write(context.gc_context)
-                .define_edit_text(context, reader),
-            TagCode::DefineFont => self
-                .0
-                .write(context.gc_context)
-                .define_font_1(context, reader),
-            TagCode::DefineFont2 => self
-                .0
-                .write(context.gc_context)
-                .define_font_2(context, reader),
-            TagCode::DefineFont3 => self
-                .0
-                .write(context.gc_context)
-                .define_font_3(context, reader),
-            TagCode::DefineFont4 => self
-                .0
-                .write(context.gc_context)
-                .define_font_4(context, reader),
-            TagCode::DefineMorphShape => self.0.write(context.gc_context).define_morph_shape(
-                context,
-                reader,
-                morph_shapes,
-                1,
-            ),
-            TagCode::DefineMorphShape2 => self.0.write(context.gc_context).define_morph_shape(
-                context,
-                reader,
-                morph_shapes,
-                2,
-            ),
-            TagCode::DefineShape => self
-                .0
-                .write(context.gc_context)
-                .define_shape(context, reader, 1),
-            TagCode::DefineShape2 => self
-                .0
-                .write(context.gc_context)
-                .define_shape(context, reader, 2),
-            TagCode::DefineShape3 => self
-                .0
-                .write(context.gc_context)
-                .define_shape(context, reader, 3),
-            TagCode::DefineShape4 => self
-                .0
-                .write(context.gc_context)
-                .define_shape(context, reader, 4),
-            TagCode::DefineSound => self
-                .0
-                .write(context.gc_context)
-                .define_sound(context, reader),
-            TagCode::DefineVideoStream => self
-                .0
-                .write(context.gc_context)
-                .define_video_stream(context, reader),
-            TagCode::DefineSprite => self.0.write(context.gc_context).define_sprite(
-                context,
-                reader,
-                tag_len,
-                morph_shapes,
-            ),
-            TagCode::DefineText => self
-                .0
-                .write(context.gc_context)
-                .define_text(context, reader, 1),
-            TagCode::DefineText2 => self
-                .0
-                .write(context.gc_context)
-                .define_text(context, reader, 2),
-            TagCode::DoInitAction => self.do_init_action(context, reader, tag_len),
-            TagCode::DoAbc => self.do_abc(context, reader, tag_len),
-            TagCode::SymbolClass => self.symbol_class(context, reader),
-            TagCode::DefineSceneAndFrameLabelData => {
-                self.scene_and_frame_labels(reader, &mut static_data)
-            }
-            TagCode::ExportAssets => self
-                .0
-                .write(context.gc_context)
-                .export_assets(context, reader),
-            TagCode::FrameLabel => self.0.write(context.gc_context).frame_label(
-                context,
-                reader,
-                tag_len,
-                cur_frame,
-                &mut static_data,
-            ),
-            TagCode::JpegTables => self
-                .0
-                .write(context.gc_context)
-                .jpeg_tables(context, reader, tag_len),
-            TagCode::PlaceObject => self.0.write(context.gc_context).preload_place_object(
-                context,
-                reader,
-                tag_len,
-                &mut ids,
-                morph_shapes,
-                1,
-            ),
-            TagCode::PlaceObject2 => self.0.write(context.gc_context).preload_place_object(
-                context,
-                reader,
-                tag_len,
-                &mut ids,
-                morph_shapes,
-                2,
-            ),
-            TagCode::PlaceObject3 => self.0.write(context.gc_context).preload_place_object(
-                context,
-                reader,
-                tag_len,
-                &mut ids,
-                morph_shapes,
-                3,
-            ),
-            TagCode::PlaceObject4 => self.0.write(context.gc_context).preload_place_object(
-                context,
-                reader,
-                tag_len,
-                &mut ids,
-                morph_shapes,
-                4,
-            ),
-            TagCode::RemoveObject => self
-                .0
-                .write(context.gc_context)
-                .preload_remove_object(context, reader, &mut ids, 1),
-            TagCode::RemoveObject2 => self
-                .0
-                .write(context.gc_context)
-                .preload_remove_object(context, reader, &mut ids, 2),
-            TagCode::ShowFrame => {
-                self.0
-                    .write(context.gc_context)
-                    .preload_show_frame(context, reader, &mut cur_frame)
-            }
-            TagCode::ScriptLimits => self
-                .0
-                .write(context.gc_context)
-                .script_limits(reader, context.avm1),
-            TagCode::SoundStreamHead => self.0.write(context.gc_context).preload_sound_stream_head(
-                context,
-                reader,
-                &mut preload_stream_handle,
-                &mut static_data,
-                1,
-            ),
-            TagCode::VideoFrame => self
-                .0
-                .write(context.gc_context)
-                .preload_video_frame(context, reader),
-            TagCode::SoundStreamHead2 => {
-                self.0.write(context.gc_context).preload_sound_stream_head(
-                    context,
-                    reader,
-                    &mut preload_stream_handle,
-                    &mut static_data,
-                    2,
-                )
-            }
-            TagCode::SoundStreamBlock => {
-                self.0.write(context.gc_context).preload_sound_stream_block(
-                    context,
-                    reader,
-                    preload_stream_handle,
-                    cur_frame,
-                    tag_len,
-                )
-            }
-            TagCode::DefineBinaryData => self
-                .0
-                .write(context.gc_context)
-                .define_binary_data(context, reader),
-            _ => Ok(()),
+        let static_data = self.0.read().static_data;
+        let (data, mut progress, is_loaded) = {
+            let mut static_data = static_data.write(context.gc_context);
+            let progress = match static_data.preload_progress.take() {
+                Some(progress) => progress,
+                None => return true,
+            };
+            let is_loaded = static_data.grow_to_whole_frames(progress.next_preload_chunk as usize);
+            (static_data.swf.clone(), progress, is_loaded)
         };
-        let _ = tag_utils::decode_tags(&mut reader, tag_callback, TagCode::End);
 
-        // Finalize audio stream.
-        if let Some(stream) = preload_stream_handle {
-            if let Some(sound) = context.audio.preload_sound_stream_end(stream) {
-                static_data.audio_stream_handle = Some(sound);
+        let tag_stream_start = data.as_ref().as_ptr() as u64;
+        let mut reader = data.read_from(progress.next_preload_chunk);
+        let mut end_tag_found = false;
+        let mut is_complete = is_loaded;
+
+        while !reader.get_ref().is_empty() {
+            let tag_callback =
+                |reader: &mut SwfStream<'_>, tag_code, tag_len| match tag_code {
+                    TagCode::CsmTextSettings => self
+                        .0
+                        .write(context.gc_context)
+                        .csm_text_settings(context, reader),
+                    TagCode::DefineBits => self
+                        .0
+                        .write(context.gc_context)
+                        .define_bits(context, reader, tag_len),
+                    TagCode::DefineBitsJpeg2 => self
+                        .0
+                        .write(context.gc_context)
+                        .define_bits_jpeg_2(context, reader, tag_len),
+                    TagCode::DefineBitsJpeg3 => self
+                        .0
+                        .write(context.gc_context)
+                        .define_bits_jpeg_3(context, reader, tag_len),
+                    TagCode::DefineBitsJpeg4 => self
+                        .0
+                        .write(context.gc_context)
+                        .define_bits_jpeg_4(context, reader, tag_len),
+                    TagCode::DefineBitsLossless => self
+                        .0
+                        .write(context.gc_context)
+                        .define_bits_lossless(context, reader, 1),
+                    TagCode::DefineBitsLossless2 => self
+                        .0
+                        .write(context.gc_context)
+                        .define_bits_lossless(context, reader, 2),
+                    TagCode::DefineButton => self
+                        .0
+                        .write(context.gc_context)
+                        .define_button_1(context, reader),
+                    TagCode::DefineButton2 => self
+                        .0
+                        .write(context.gc_context)
+                        .define_button_2(context, reader),
+                    TagCode::DefineButtonCxform => self
+                        .0
+                        .write(context.gc_context)
+                        .define_button_cxform(context, reader, tag_len),
+                    TagCode::DefineButtonSound => self
+                        .0
+                        .write(context.gc_context)
+                        .define_button_sound(context, reader),
+                    TagCode::DefineEditText => self
+                        .0
+                        .write(context.gc_context)
+                        .define_edit_text(context, reader),
+                    TagCode::DefineFont => self
+                        .0
+                        .write(context.gc_context)
+                        .define_font_1(context, reader),
+                    TagCode::DefineFont2 => self
+                        .0
+                        .write(context.gc_context)
+                        .define_font_2(context, reader),
+                    TagCode::DefineFont3 => self
+                        .0
+                        .write(context.gc_context)
+                        .define_font_3(context, reader),
+                    TagCode::DefineFont4 => self
+                        .0
+                        .write(context.gc_context)
+                        .define_font_4(context, reader),
+                    TagCode::DefineMorphShape => self
+                        .0
+                        .write(context.gc_context)
+                        .define_morph_shape(context, reader, 1),
+                    TagCode::DefineMorphShape2 => self
+                        .0
+                        .write(context.gc_context)
+                        .define_morph_shape(context, reader, 2),
+                    TagCode::DefineShape => self
+                        .0
+                        .write(context.gc_context)
+                        .define_shape(context, reader, 1),
+                    TagCode::DefineShape2 => self
+                        .0
+                        .write(context.gc_context)
+                        .define_shape(context, reader, 2),
+                    TagCode::DefineShape3 => self
+                        .0
+                        .write(context.gc_context)
+                        .define_shape(context, reader, 3),
+                    TagCode::DefineShape4 => self
+                        .0
+                        .write(context.gc_context)
+                        .define_shape(context, reader, 4),
+                    TagCode::DefineSound => self
+                        .0
+                        .write(context.gc_context)
+                        .define_sound(context, reader),
+                    TagCode::DefineVideoStream => self
+                        .0
+                        .write(context.gc_context)
+                        .define_video_stream(context, reader),
+                    TagCode::DefineSprite => self
+                        .0
+                        .write(context.gc_context)
+                        .define_sprite(context, reader, tag_len),
+                    TagCode::DefineText => self
+                        .0
+                        .write(context.gc_context)
+                        .define_text(context, reader, 1),
+                    TagCode::DefineText2 => self
+                        .0
+                        .write(context.gc_context)
+                        .define_text(context, reader, 2),
+                    TagCode::DoInitAction => self.do_init_action(context, reader, tag_len),
+                    TagCode::DoAbc => self.do_abc(context, reader, tag_len),
+                    TagCode::SymbolClass => self.symbol_class(context, reader),
+                    TagCode::DefineSceneAndFrameLabelData => {
+                        self.scene_and_frame_labels(context, reader)
+                    }
+                    TagCode::ExportAssets => self
+                        .0
+                        .write(context.gc_context)
+                        .export_assets(context, reader),
+                    TagCode::FrameLabel => self.0.write(context.gc_context).frame_label(
+                        context,
+                        reader,
+                        tag_len,
+                        progress.cur_preload_frame,
+                    ),
+                    TagCode::JpegTables => self
+                        .0
+                        .write(context.gc_context)
+                        .jpeg_tables(context, reader, tag_len),
+                    TagCode::PlaceObject => self.0.write(context.gc_context).preload_place_object(
+                        context,
+                        reader,
+                        tag_len,
+                        &mut progress.ids,
+                        1,
+                    ),
+                    TagCode::PlaceObject2 => self.0.write(context.gc_context).preload_place_object(
+                        context,
+                        reader,
+                        tag_len,
+                        &mut progress.ids,
+                        2,
+                    ),
+                    TagCode::PlaceObject3 => self.0.write(context.gc_context).preload_place_object(
+                        context,
+                        reader,
+                        tag_len,
+                        &mut progress.ids,
+                        3,
+                    ),
+                    TagCode::PlaceObject4 => self.0.write(context.gc_context).preload_place_object(
+                        context,
+                        reader,
+                        tag_len,
+                        &mut progress.ids,
+                        4,
+                    ),
+                    TagCode::RemoveObject => self
+                        .0
+                        .write(context.gc_context)
+                        .preload_remove_object(context, reader, &mut progress.ids, 1),
+                    TagCode::RemoveObject2 => self
+                        .0
+                        .write(context.gc_context)
+                        .preload_remove_object(context, reader, &mut progress.ids, 2),
+                    TagCode::ShowFrame => self.0.write(context.gc_context).preload_show_frame(
+                        context,
+                        reader,
+                        &mut progress.cur_preload_frame,
+                    ),
+                    TagCode::ScriptLimits => self
+                        .0
+                        .write(context.gc_context)
+                        .script_limits(reader, context.avm1),
+                    TagCode::SoundStreamHead => {
+                        self.0.write(context.gc_context).preload_sound_stream_head(
+                            context,
+                            reader,
+                            &mut progress.preload_stream_handle,
+                            1,
+                        )
+                    }
+                    TagCode::VideoFrame => self
+                        .0
+                        .write(context.gc_context)
+                        .preload_video_frame(context, reader),
+                    TagCode::SoundStreamHead2 => {
+                        self.0.write(context.gc_context).preload_sound_stream_head(
+                            context,
+                            reader,
+                            &mut progress.preload_stream_handle,
+                            2,
+                        )
+                    }
+                    TagCode::SoundStreamBlock => {
+                        self.0.write(context.gc_context).preload_sound_stream_block(
+                            context,
+                            reader,
+                            progress.preload_stream_handle,
+                            progress.cur_preload_frame,
+                            tag_len,
+                        )
+                    }
+                    TagCode::DefineBinaryData => self
+                        .0
+                        .write(context.gc_context)
+                        .define_binary_data(context, reader),
+                    TagCode::End => {
+                        end_tag_found = true;
+                        Ok(())
+                    }
+                    _ => Ok(()),
+                };
+            let result = tag_utils::decode_tags(&mut reader, tag_callback, TagCode::ShowFrame);
+            if result.is_err() || end_tag_found {
+                // Anything after the end of the tag stream is ignored.
+                is_complete = true;
+                break;
             }
         }
+        progress.next_preload_chunk = reader.get_ref().as_ptr() as u64 - tag_stream_start;
 
-        self.0.write(context.gc_context).static_data =
-            Gc::allocate(context.gc_context, static_data);
+        let mut static_data = static_data.write(context.gc_context);
+        if is_complete {
+            // Finalize audio stream.
+            if let Some(stream) = progress.preload_stream_handle {
+                if let Some(sound) = context.audio.preload_sound_stream_end(stream) {
+                    static_data.audio_stream_handle = Some(sound);
+                }
+            }
+            static_data.frames_loaded = static_data.total_frames;
+        } else {
+            static_data.frames_loaded =
+                (progress.cur_preload_frame - 1).min(static_data.total_frames);
+            static_data.preload_progress = Some(progress);
+        }
+        is_complete
     }
 
     #[inline]
@@ -505,6 +543,7 @@ impl<'gc> MovieClip<'gc> {
             .0
             .read()
             .static_data
+            .read()
             .swf
             .resize_to_reader(reader, tag_len)
             .ok_or_else(|| {
@@ -546,6 +585,7 @@ impl<'gc> MovieClip<'gc> {
             .0
             .read()
             .static_data
+            .read()
             .swf
             .resize_to_reader(reader, tag_len)
             .ok_or_else(|| {
@@ -640,10 +680,12 @@ impl<'gc> MovieClip<'gc> {
     #[inline]
     fn scene_and_frame_labels(
         self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
         reader: &mut SwfStream<'_>,
-        static_data: &mut MovieClipStatic,
     ) -> DecodeResult {
         let mut sfl_data = reader.read_define_scene_and_frame_label_data()?;
+        let static_data = self.0.read().static_data;
+        let mut static_data = static_data.write(context.gc_context);
         sfl_data
             .scenes
             .sort_unstable_by(|s1, s2| s1.frame_num.cmp(&s2.frame_num));
@@ -698,6 +740,26 @@ impl<'gc> MovieClip<'gc> {
         self.0.write(gc_context).drop_target = drop_target;
     }
 
+    pub fn button_mode(self) -> bool {
+        self.0.read().button_mode
+    }
+
+    pub fn set_button_mode(self, gc_context: MutationContext<'gc, '_>, button_mode: bool) {
+        self.0.write(gc_context).button_mode = button_mode;
+    }
+
+    pub fn hit_area(self) -> Option<DisplayObject<'gc>> {
+        self.0.read().hit_area
+    }
+
+    pub fn set_hit_area(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        hit_area: Option<DisplayObject<'gc>>,
+    ) {
+        self.0.write(gc_context).hit_area = hit_area;
+    }
+
     pub fn set_programmatically_played(self, mc: MutationContext<'gc, '_>) {
         self.0.write(mc).set_programmatically_played()
     }
@@ -844,6 +906,7 @@ impl<'gc> MovieClip<'gc> {
             .0
             .read()
             .static_data
+            .read()
             .scene_labels
             .values()
             .cloned()
@@ -861,7 +924,7 @@ impl<'gc> MovieClip<'gc> {
         let read = self.0.read();
         let mut best: Option<&Scene> = None;
 
-        for (_, scene) in read.static_data.scene_labels.iter() {
+        for (_, scene) in read.static_data.read().scene_labels.iter() {
             if cond(best, scene) {
                 best = Some(scene);
             }
@@ -876,7 +939,7 @@ impl<'gc> MovieClip<'gc> {
         let current_frame = read.current_frame();
         let mut best: Option<(&str, FrameNumber)> = None;
 
-        for (label, frame) in read.static_data.frame_labels.iter() {
+        for (label, frame) in read.static_data.read().frame_labels.iter() {
             if *frame > current_frame {
                 continue;
             }
@@ -897,6 +960,7 @@ impl<'gc> MovieClip<'gc> {
 
         let mut values: Vec<(String, FrameNumber)> = read
             .static_data
+            .read()
             .frame_labels
             .iter()
             .filter(|(_label, frame)| **frame >= from && **frame < to)
@@ -909,12 +973,11 @@ impl<'gc> MovieClip<'gc> {
     }
 
     pub fn total_frames(self) -> FrameNumber {
-        self.0.read().static_data.total_frames
+        self.0.read().static_data.read().total_frames
     }
 
     pub fn frames_loaded(self) -> FrameNumber {
-        // TODO(Herschel): root needs to progressively stream in frames.
-        self.0.read().static_data.total_frames
+        self.0.read().static_data.read().frames_loaded
     }
 
     pub fn set_avm2_class(
@@ -929,7 +992,13 @@ impl<'gc> MovieClip<'gc> {
     pub fn frame_label_to_number(self, frame_label: &str) -> Option<FrameNumber> {
         // Frame labels are case insensitive.
         let label = frame_label.to_ascii_lowercase();
-        self.0.read().static_data.frame_labels.get(&label).copied()
+        self.0
+            .read()
+            .static_data
+            .read()
+            .frame_labels
+            .get(&label)
+            .copied()
     }
 
     pub fn scene_label_to_number(self, scene_label: &str) -> Option<FrameNumber> {
@@ -937,6 +1006,7 @@ impl<'gc> MovieClip<'gc> {
         self.0
             .read()
             .static_data
+            .read()
             .scene_labels
             .get(scene_label)
             .map(|Scene { start, .. }| start)
@@ -962,7 +1032,7 @@ impl<'gc> MovieClip<'gc> {
                     start: new_scene_start,
                     ..
                 },
-            ) in self.0.read().static_data.scene_labels.iter()
+            ) in self.0.read().static_data.read().scene_labels.iter()
             {
                 if *new_scene_start < end && *new_scene_start > scene {
                     end = *new_scene_start;
@@ -1006,8 +1076,9 @@ impl<'gc> MovieClip<'gc> {
         // Iterate through this clip's tags, counting frames until we reach the target frame.
         if frame > 0 && frame <= self.total_frames() {
             let mut cur_frame = 1;
-            let clip = self.0.read();
-            let mut reader = clip.static_data.swf.read_from(0);
+            let static_data = self.0.read().static_data;
+            let static_data = static_data.read();
+            let mut reader = static_data.swf.read_from(0);
             while cur_frame <= frame && !reader.get_ref().is_empty() {
                 let tag_callback = |reader: &mut Reader<'_>, tag_code, tag_len| match tag_code {
                     TagCode::ShowFrame => {
@@ -1016,7 +1087,7 @@ impl<'gc> MovieClip<'gc> {
                     }
                     TagCode::DoAction if cur_frame == frame => {
                         // On the target frame, add any DoAction tags to the array.
-                        if let Some(code) = clip.static_data.swf.resize_to_reader(reader, tag_len) {
+                        if let Some(code) = static_data.swf.resize_to_reader(reader, tag_len) {
                             actions.push(code);
                         }
                         Ok(())
@@ -1044,7 +1115,11 @@ impl<'gc> MovieClip<'gc> {
     /// Determine what the clip's next frame should be.
     fn determine_next_frame(self) -> NextFrame {
         if self.current_frame() < self.total_frames() {
-            NextFrame::Next
+            if self.current_frame() < self.frames_loaded() {
+                NextFrame::Next
+            } else {
+                NextFrame::Wait
+            }
         } else if self.total_frames() > 1 {
             NextFrame::First
         } else {
@@ -1061,11 +1136,12 @@ impl<'gc> MovieClip<'gc> {
             NextFrame::Next => self.0.write(context.gc_context).current_frame += 1,
             NextFrame::First => return self.run_goto(context, 1, true),
             NextFrame::Same => self.stop(context),
+            NextFrame::Wait => return,
         }
 
         let mc = self.0.read();
-        let tag_stream_start = mc.static_data.swf.as_ref().as_ptr() as u64;
-        let data = mc.static_data.swf.clone();
+        let tag_stream_start = mc.static_data.read().swf.as_ref().as_ptr() as u64;
+        let data = mc.static_data.read().swf.clone();
         let mut reader = data.read_from(mc.tag_stream_pos);
         let mut has_stream_block = false;
         drop(mc);
@@ -1090,6 +1166,7 @@ impl<'gc> MovieClip<'gc> {
             TagCode::RemoveObject if run_display_actions => self.remove_object(context, reader, 1),
             TagCode::RemoveObject2 if run_display_actions => self.remove_object(context, reader, 2),
             TagCode::SetBackgroundColor => self.set_background_color(context, reader),
+            TagCode::SetTabIndex => self.set_tab_index_tag(context, reader),
             TagCode::StartSound => self.start_sound_1(context, reader),
             TagCode::SoundStreamBlock => {
                 has_stream_block = true;
@@ -1252,14 +1329,13 @@ impl<'gc> MovieClip<'gc> {
 
         // Step through the intermediate frames, and aggregate the deltas of each frame.
         let mc = self.0.read();
-        let tag_stream_start = mc.static_data.swf.as_ref().as_ptr() as u64;
+        let tag_stream_start = mc.static_data.read().swf.as_ref().as_ptr() as u64;
         let mut frame_pos = mc.tag_stream_pos;
-        let data = mc.static_data.swf.clone();
+        let data = mc.static_data.read().swf.clone();
         let mut index = 0;
 
         // Sanity; let's make sure we don't seek way too far.
-        // TODO: This should be self.frames_loaded() when we implement that.
-        let clamped_frame = frame.min(mc.total_frames());
+        let clamped_frame = frame.min(mc.static_data.read().frames_loaded);
         drop(mc);
 
         let mut reader = data.read_from(frame_pos);
@@ -1661,6 +1737,11 @@ impl<'gc> MovieClip<'gc> {
     }
 
     pub fn is_button_mode(&self, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        if self.is_avm2_clip() {
+            // AVM2 clips only act as buttons when `buttonMode` is set.
+            return self.button_mode();
+        }
+
         if self
             .0
             .read()
@@ -1680,6 +1761,89 @@ impl<'gc> MovieClip<'gc> {
                 .any(|handler| object.has_property(&mut activation, handler))
         }
     }
+
+    /// Whether this clip is controlled by an AVM2 object, in which case it
+    /// follows the `InteractiveObject` rules for mouse input.
+    fn is_avm2_clip(self) -> bool {
+        matches!(self.object2(), Avm2Value::Object(_))
+    }
+
+    /// Mouse picking for AVM2 clips.
+    ///
+    /// Unlike AVM1, any clip with `mouseEnabled` set is a mouse target, and
+    /// hits on non-interactive children such as shapes target the clip
+    /// itself. The `hitArea` object replaces the clip's own shape, if set.
+    ///
+    /// If `require_button_mode` is false, `mouseEnabled` and `mouseChildren`
+    /// are ignored and the deepest display object under the point is
+    /// returned instead, as needed for `dropTarget`.
+    fn mouse_pick_avm2(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        point: (Twips, Twips),
+        require_button_mode: bool,
+    ) -> Option<DisplayObject<'gc>> {
+        let this: DisplayObject<'gc> = self.into();
+        let mouse_enabled = !require_button_mode || self.mouse_enabled();
+        let mouse_children = !require_button_mode || self.mouse_children();
+        let hit_area = self.hit_area();
+
+        if let Some(masker) = self.masker() {
+            if !masker.hit_test_shape(context, point, HitTestOptions::SKIP_INVISIBLE) {
+                return None;
+            }
+        }
+
+        let mut hit_depth = 0;
+        let mut result = None;
+
+        for child in self.iter_render_list().rev() {
+            if child.clip_depth() > 0 {
+                if result.is_some() && child.clip_depth() >= hit_depth {
+                    if child.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK) {
+                        return result;
+                    } else {
+                        result = None;
+                    }
+                }
+            } else if result.is_none() {
+                if mouse_children {
+                    result = child.mouse_pick(context, point, require_button_mode);
+                }
+
+                if result.is_none()
+                    && mouse_enabled
+                    && hit_area.is_none()
+                    && child.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK)
+                {
+                    result = Some(if require_button_mode { this } else { child });
+                }
+
+                if result.is_some() {
+                    hit_depth = child.depth();
+                }
+            }
+        }
+
+        if result.is_some() || !mouse_enabled {
+            return result;
+        }
+
+        if let Some(hit_area) = hit_area {
+            // The hit area may be invisible, but still acts as the shape of this clip.
+            if hit_area.hit_test_shape(context, point, HitTestOptions::SKIP_MASK) {
+                return Some(this);
+            }
+        } else {
+            let local_matrix = self.global_to_local_matrix();
+            let point = local_matrix * point;
+            if self.0.read().drawing.hit_test(point, &local_matrix) {
+                return Some(this);
+            }
+        }
+
+        None
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
@@ -1706,8 +1870,9 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         }
 
         // AVM1 code expects to execute in line with timeline instructions, so
-        // it's exempted from frame construction.
-        if self.avm_type() == AvmType::Avm2 {
+        // it's exempted from frame construction. A streaming clip isn't
+        // constructed until its first frame (and its class) has arrived.
+        if self.avm_type() == AvmType::Avm2 && self.frames_loaded() > 0 {
             let needs_construction = if matches!(self.object2(), Avm2Value::Undefined) {
                 self.allocate_as_avm2_object(context, (*self).into());
 
@@ -1716,9 +1881,12 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                 false
             };
 
-            if self.determine_next_frame() != NextFrame::First {
+            if !matches!(
+                self.determine_next_frame(),
+                NextFrame::First | NextFrame::Wait
+            ) {
                 let mc = self.0.read();
-                let data = mc.static_data.swf.clone();
+                let data = mc.static_data.read().swf.clone();
                 let mut reader = data.read_from(mc.tag_stream_pos);
                 drop(mc);
 
@@ -1878,6 +2046,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         point: (Twips, Twips),
         require_button_mode: bool,
     ) -> Option<DisplayObject<'gc>> {
+        if self.visible() && self.is_avm2_clip() {
+            return self.mouse_pick_avm2(context, point, require_button_mode);
+        }
+
         if self.visible() {
             let this: DisplayObject<'gc> = (*self).into();
 
@@ -1941,6 +2113,23 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     }
 
     fn mouse_cursor(&self) -> MouseCursor {
+        if self.is_avm2_clip() {
+            // AVM2 clips show the hand cursor over themselves and their
+            // children only when in button mode.
+            let mut node = Some(*self);
+            while let Some(clip) = node {
+                if clip.button_mode() {
+                    return if clip.use_hand_cursor() {
+                        MouseCursor::Hand
+                    } else {
+                        MouseCursor::Arrow
+                    };
+                }
+                node = clip.parent().and_then(|parent| parent.as_movie_clip());
+            }
+            return MouseCursor::Arrow;
+        }
+
         if self.use_hand_cursor() {
             MouseCursor::Hand
         } else {
@@ -2089,6 +2278,15 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         self.0.read().is_focusable
     }
 
+    fn is_tab_enabled_by_default(&self) -> bool {
+        let mc = self.0.read();
+        mc.is_focusable
+            || mc.button_mode
+            || mc
+                .clip_event_flags
+                .intersects(ClipEvent::BUTTON_EVENT_FLAGS)
+    }
+
     fn on_focus_changed(&self, gc_context: MutationContext<'gc, '_>, focused: bool) {
         self.0.write(gc_context).has_focus = focused;
     }
@@ -2117,7 +2315,7 @@ impl<'gc> MovieClipData<'gc> {
         let total_frames = movie.num_frames();
 
         self.base.reset_for_movie_load();
-        self.static_data = Gc::allocate(
+        self.static_data = GcCell::allocate(
             gc_context,
             MovieClipStatic::with_data(0, movie.into(), total_frames),
         );
@@ -2130,7 +2328,7 @@ impl<'gc> MovieClipData<'gc> {
     }
 
     fn id(&self) -> CharacterId {
-        self.static_data.id
+        self.static_data.read().id
     }
 
     fn current_frame(&self) -> FrameNumber {
@@ -2138,7 +2336,7 @@ impl<'gc> MovieClipData<'gc> {
     }
 
     fn total_frames(&self) -> FrameNumber {
-        self.static_data.total_frames
+        self.static_data.read().total_frames
     }
 
     fn playing(&self) -> bool {
@@ -2170,7 +2368,7 @@ impl<'gc> MovieClipData<'gc> {
     }
 
     fn tag_stream_len(&self) -> usize {
-        self.static_data.swf.end - self.static_data.swf.start
+        self.static_data.read().swf.end - self.static_data.read().swf.start
     }
 
     /// Handles a PlaceObject tag when running a goto action.
@@ -2324,8 +2522,8 @@ impl<'gc> MovieClipData<'gc> {
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
     ) -> Option<Avm1Object<'gc>> {
-        let symbol_name = self.static_data.exported_name.borrow();
-        let symbol_name = symbol_name.as_ref()?;
+        let static_data = self.static_data.read();
+        let symbol_name = static_data.exported_name.as_ref()?;
         let library = context.library.library_for_movie_mut(self.movie());
         let registry = library.avm1_constructor_registry()?;
         let ctor = registry.get(symbol_name)?;
@@ -2333,7 +2531,7 @@ impl<'gc> MovieClipData<'gc> {
     }
 
     pub fn movie(&self) -> Arc<SwfMovie> {
-        self.static_data.swf.movie.clone()
+        self.static_data.read().swf.movie.clone()
     }
 }
 
@@ -2369,13 +2567,18 @@ impl<'gc, 'a> MovieClipData<'gc> {
         &mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
         reader: &mut SwfStream<'a>,
-        morph_shapes: &mut fnv::FnvHashMap<CharacterId, MorphShapeStatic>,
         version: u8,
     ) -> DecodeResult {
-        // Certain backends may have to preload morph shape frames, so defer registering until the end.
+        // Certain backends may have to preload morph shape frames, so the ratios used by
+        // later PlaceObject tags are registered on the shape as they are preloaded.
         let swf_shape = reader.read_define_morph_shape(version)?;
-        let morph_shape = MorphShapeStatic::from_swf_tag(context, &swf_shape, self.movie());
-        morph_shapes.insert(swf_shape.id, morph_shape);
+        let id = swf_shape.id;
+        let static_data = MorphShapeStatic::from_swf_tag(context, &swf_shape, self.movie());
+        let morph_shape = MorphShape::new(context.gc_context, static_data);
+        context
+            .library
+            .library_for_movie_mut(self.movie())
+            .register_character(id, Character::MorphShape(morph_shape));
         Ok(())
     }
 
@@ -2404,7 +2607,6 @@ impl<'gc, 'a> MovieClipData<'gc> {
         reader: &mut SwfStream<'a>,
         tag_len: usize,
         ids: &mut fnv::FnvHashMap<Depth, CharacterId>,
-        morph_shapes: &mut fnv::FnvHashMap<CharacterId, MorphShapeStatic>,
         version: u8,
     ) -> DecodeResult {
         use swf::PlaceObjectAction;
@@ -2413,9 +2615,10 @@ impl<'gc, 'a> MovieClipData<'gc> {
         } else {
             reader.read_place_object_2_or_3(version)
         }?;
+        let library = context.library.library_for_movie_mut(self.movie());
         match place_object.action {
             PlaceObjectAction::Place(id) => {
-                if let Some(morph_shape) = morph_shapes.get_mut(&id) {
+                if let Some(morph_shape) = library.get_morph_shape(id) {
                     ids.insert(place_object.depth.into(), id);
                     if let Some(ratio) = place_object.ratio {
                         morph_shape.register_ratio(context, ratio);
@@ -2424,7 +2627,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
             }
             PlaceObjectAction::Modify => {
                 if let Some(&id) = ids.get(&place_object.depth.into()) {
-                    if let Some(morph_shape) = morph_shapes.get_mut(&id) {
+                    if let Some(morph_shape) = library.get_morph_shape(id) {
                         ids.insert(place_object.depth.into(), id);
                         if let Some(ratio) = place_object.ratio {
                             morph_shape.register_ratio(context, ratio);
@@ -2433,7 +2636,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
                 }
             }
             PlaceObjectAction::Replace(id) => {
-                if let Some(morph_shape) = morph_shapes.get_mut(&id) {
+                if let Some(morph_shape) = library.get_morph_shape(id) {
                     ids.insert(place_object.depth.into(), id);
                     if let Some(ratio) = place_object.ratio {
                         morph_shape.register_ratio(context, ratio);
@@ -2472,12 +2675,11 @@ impl<'gc, 'a> MovieClipData<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         reader: &mut SwfStream<'a>,
         stream: &mut Option<PreloadStreamHandle>,
-        static_data: &mut MovieClipStatic,
         _version: u8,
     ) -> DecodeResult {
         let audio_stream_info = reader.read_sound_stream_head()?;
         *stream = context.audio.preload_sound_stream_head(&audio_stream_info);
-        static_data.audio_stream_info = Some(audio_stream_info);
+        self.static_data.write(context.gc_context).audio_stream_info = Some(audio_stream_info);
         Ok(())
     }
 
@@ -2711,7 +2913,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
         if library.avm_type() == AvmType::Avm1 {
             let button = Avm1Button::from_swf_tag(
                 &swf_button,
-                &self.static_data.swf,
+                &self.static_data.read().swf,
                 context.library,
                 context.gc_context,
             );
@@ -2720,7 +2922,8 @@ impl<'gc, 'a> MovieClipData<'gc> {
                 .library_for_movie_mut(movie)
                 .register_character(swf_button.id, Character::Avm1Button(button));
         } else {
-            let button = Avm2Button::from_swf_tag(&swf_button, &self.static_data.swf, context);
+            let button =
+                Avm2Button::from_swf_tag(&swf_button, &self.static_data.read().swf, context);
             context
                 .library
                 .library_for_movie_mut(movie)
@@ -2954,7 +3157,6 @@ impl<'gc, 'a> MovieClipData<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         reader: &mut SwfStream<'a>,
         tag_len: usize,
-        morph_shapes: &mut fnv::FnvHashMap<CharacterId, MorphShapeStatic>,
     ) -> DecodeResult {
         let id = reader.read_character_id()?;
         let num_frames = reader.read_u16()?;
@@ -2962,6 +3164,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
             context.gc_context,
             id,
             self.static_data
+                .read()
                 .swf
                 .resize_to_reader(reader, tag_len - 4)
                 .ok_or_else(|| {
@@ -2973,7 +3176,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
             num_frames,
         );
 
-        movie_clip.preload(context, morph_shapes);
+        movie_clip.preload(context);
 
         context
             .library
@@ -3040,8 +3243,12 @@ impl<'gc, 'a> MovieClipData<'gc> {
 
             // TODO: do other types of Character need to know their exported name?
             if let Some(Character::MovieClip(movie_clip)) = character {
-                *movie_clip.0.read().static_data.exported_name.borrow_mut() =
-                    Some(name.to_string());
+                movie_clip
+                    .0
+                    .read()
+                    .static_data
+                    .write(context.gc_context)
+                    .exported_name = Some(name.to_string());
             }
         }
         Ok(())
@@ -3050,11 +3257,10 @@ impl<'gc, 'a> MovieClipData<'gc> {
     #[inline]
     fn frame_label(
         &mut self,
-        _context: &mut UpdateContext<'_, 'gc, '_>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
         reader: &mut SwfStream<'a>,
         tag_len: usize,
         cur_frame: FrameNumber,
-        static_data: &mut MovieClipStatic,
     ) -> DecodeResult {
         let frame_label = reader.read_frame_label(tag_len)?;
         // Frame labels are case insensitive (ASCII).
@@ -3062,11 +3268,13 @@ impl<'gc, 'a> MovieClipData<'gc> {
             .label
             .to_str_lossy(reader.encoding())
             .to_ascii_lowercase();
+        let id = self.id();
+        let mut static_data = self.static_data.write(context.gc_context);
         if let std::collections::hash_map::Entry::Vacant(v) = static_data.frame_labels.entry(label)
         {
             v.insert(cur_frame);
         } else {
-            log::warn!("Movie clip {}: Duplicated frame label", self.id());
+            log::warn!("Movie clip {}: Duplicated frame label", id);
         }
         Ok(())
     }
@@ -3137,6 +3345,7 @@ impl<'gc, 'a> MovieClip<'gc> {
             .0
             .read()
             .static_data
+            .read()
             .swf
             .resize_to_reader(reader, tag_len)
             .ok_or_else(|| {
@@ -3236,6 +3445,20 @@ impl<'gc, 'a> MovieClip<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn set_tab_index_tag(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<'a>,
+    ) -> DecodeResult {
+        let depth: Depth = reader.read_u16()?.into();
+        let tab_index = reader.read_u16()?;
+        if let Some(child) = self.child_by_depth(depth) {
+            child.set_tab_index(context.gc_context, Some(tab_index.into()));
+        }
+        Ok(())
+    }
+
     #[inline]
     fn sound_stream_block(
         self,
@@ -3244,10 +3467,10 @@ impl<'gc, 'a> MovieClip<'gc> {
     ) -> DecodeResult {
         let mc = self.0.read();
         if mc.playing() {
-            if let (Some(stream_info), None) = (&mc.static_data.audio_stream_info, mc.audio_stream)
-            {
-                let slice = mc
-                    .static_data
+            let static_data = mc.static_data;
+            let static_data = static_data.read();
+            if let (Some(stream_info), None) = (&static_data.audio_stream_info, mc.audio_stream) {
+                let slice = static_data
                     .swf
                     .to_start_and_end(mc.tag_stream_pos as usize, mc.tag_stream_len())
                     .ok_or_else(|| {
@@ -3257,7 +3480,7 @@ impl<'gc, 'a> MovieClip<'gc> {
                         )
                     })?;
                 let audio_stream = context.start_stream(
-                    mc.static_data.audio_stream_handle,
+                    static_data.audio_stream_handle,
                     self,
                     mc.current_frame() + 1,
                     slice,
@@ -3347,15 +3570,27 @@ struct MovieClipStatic {
     total_frames: FrameNumber,
     /// The last known symbol name under which this movie clip was exported.
     /// Used for looking up constructors registered with `Object.registerClass`.
-    exported_name: RefCell<Option<String>>,
+    exported_name: Option<String>,
+    /// The number of frames that have been preloaded and can be played.
+    frames_loaded: FrameNumber,
+    /// How far preloading of this clip's tags has progressed, or `None` once
+    /// all of them have been preloaded.
+    preload_progress: Option<PreloadProgress>,
 }
 
 impl MovieClipStatic {
     fn empty(swf: SwfSlice) -> Self {
-        Self::with_data(0, swf, 1)
+        // An empty clip has nothing to preload.
+        Self {
+            frames_loaded: 1,
+            preload_progress: None,
+            ..Self::with_data(0, swf, 1)
+        }
     }
 
     fn with_data(id: CharacterId, swf: SwfSlice, total_frames: FrameNumber) -> Self {
+        // A clip without any tags has nothing to preload.
+        let is_empty = swf.as_ref().is_empty() && swf.movie.is_loaded();
         Self {
             id,
             swf,
@@ -3364,7 +3599,81 @@ impl MovieClipStatic {
             scene_labels: HashMap::new(),
             audio_stream_info: None,
             audio_stream_handle: None,
-            exported_name: RefCell::new(None),
+            exported_name: None,
+            frames_loaded: if is_empty { total_frames } else { 0 },
+            preload_progress: if is_empty {
+                None
+            } else {
+                Some(Default::default())
+            },
+        }
+    }
+
+    /// Extend this clip's tag stream over the whole frames that have been
+    /// loaded past `start` since it was last preloaded.
+    ///
+    /// Only a clip that spans an entire movie grows, as that movie downloads.
+    /// Returns `true` if the rest of the tag stream has been loaded too.
+    fn grow_to_whole_frames(&mut self, start: usize) -> bool {
+        if self.swf.start != 0 {
+            return true;
+        }
+
+        let movie = self.swf.movie.clone();
+        let is_loaded = movie.is_loaded();
+        let data = movie.data();
+        self.swf.end = if is_loaded {
+            data.len()
+        } else {
+            start + whole_frames_len(&data[start..], movie.version())
+        };
+        is_loaded
+    }
+}
+
+/// The length of the part of `data` that holds whole frames, ending with a
+/// complete `ShowFrame` or `End` tag.
+fn whole_frames_len(data: &[u8], version: u8) -> usize {
+    let mut reader = swf::read::Reader::new(data, version);
+    let mut len = 0;
+    while let Ok((tag_code, tag_len)) = reader.read_tag_code_and_length() {
+        if tag_len > reader.get_ref().len() {
+            break;
+        }
+        *reader.get_mut() = &reader.get_ref()[tag_len..];
+        if tag_code == swf::TagCode::ShowFrame as u16 {
+            len = data.len() - reader.get_ref().len();
+        } else if tag_code == swf::TagCode::End as u16 {
+            len = data.len() - reader.get_ref().len();
+            break;
+        }
+    }
+    len
+}
+
+/// The state of a clip's preload, which may be spread across many player frames.
+#[derive(Clone)]
+struct PreloadProgress {
+    /// The position in the tag stream that the next chunk will be preloaded from.
+    next_preload_chunk: u64,
+
+    /// The frame whose tags are currently being preloaded.
+    cur_preload_frame: FrameNumber,
+
+    /// The character placed at each depth, used to register morph shape ratios.
+    ids: fnv::FnvHashMap<Depth, CharacterId>,
+
+    /// The streaming sound that is being preloaded, if any.
+    preload_stream_handle: Option<PreloadStreamHandle>,
+}
+
+impl Default for PreloadProgress {
+    fn default() -> Self {
+        Self {
+            next_preload_chunk: 0,
+            cur_preload_frame: 1,
+            ids: fnv::FnvHashMap::default(),
+            preload_stream_handle: None,
         }
     }
 }
@@ -88,8 +88,18 @@ pub struct StageData<'gc> {
     /// Whether to show default context menu items
     show_menu: bool,
 
+    /// Whether a yellow rectangle is drawn around objects focused with the keyboard.
+    /// Individual display objects may override this with `_focusrect`.
+    stage_focus_rect: bool,
+
     /// The AVM2 view of this stage object.
     avm2_object: Avm2Object<'gc>,
+
+    /// The AVM2 `LoaderInfo` of the root movie.
+    ///
+    /// It's created the first time it's accessed, and kept so that event
+    /// listeners added to it stay registered.
+    root_loader_info: Option<Avm2Object<'gc>>,
 }
 
 impl<'gc> Stage<'gc> {
@@ -111,7 +121,9 @@ impl<'gc> Stage<'gc> {
                 viewport_scale_factor: 1.0,
                 view_bounds: Default::default(),
                 show_menu: true,
+                stage_focus_rect: true,
                 avm2_object: Avm2ScriptObject::bare_object(gc_context),
+                root_loader_info: None,
             },
         ));
         stage.set_is_root(gc_context, true);
@@ -269,6 +281,17 @@ impl<'gc> Stage<'gc> {
         write.show_menu = show_menu;
     }
 
+    /// Whether a focus rectangle is drawn around objects focused with the keyboard.
+    /// Used by the AVM1 `_root._focusrect` and AVM2 `Stage.stageFocusRect` properties.
+    pub fn stage_focus_rect(self) -> bool {
+        self.0.read().stage_focus_rect
+    }
+
+    /// Sets whether a focus rectangle is drawn around objects focused with the keyboard.
+    pub fn set_stage_focus_rect(self, gc_context: MutationContext<'gc, '_>, value: bool) {
+        self.0.write(gc_context).stage_focus_rect = value;
+    }
+
     /// Determine if we should letterbox the stage content.
     fn should_letterbox(self, ui: &mut dyn UiBackend) -> bool {
         // Only enable letterbox is the default `ShowAll` scale mode.
@@ -481,6 +504,19 @@ impl<'gc> Stage<'gc> {
             .expect("Stage must always have a root movie")
     }
 
+    /// The AVM2 `LoaderInfo` of the root movie, if it has been accessed yet.
+    pub fn root_loader_info(self) -> Option<Avm2Object<'gc>> {
+        self.0.read().root_loader_info
+    }
+
+    pub fn set_root_loader_info(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        loader_info: Option<Avm2Object<'gc>>,
+    ) {
+        self.0.write(gc_context).root_loader_info = loader_info;
+    }
+
     /// Fires `Stage.onResize` in AVM1 or `Event.RESIZE` in AVM2.
     fn fire_resize_event(self, context: &mut UpdateContext<'_, 'gc, '_>) {
         // This event fires immediately when scaleMode is changed;
@@ -566,6 +602,9 @@ impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
 
         render_base((*self).into(), context);
 
+        let focus_tracker = context.focus_tracker;
+        focus_tracker.render_highlight(context);
+
         if self.should_letterbox(context.ui) {
             self.draw_letterbox(context);
         }
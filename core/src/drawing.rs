@@ -1,6 +1,7 @@
 use crate::backend::render::{BitmapInfo, BitmapSource, ShapeHandle};
 use crate::bounding_box::BoundingBox;
 use crate::context::RenderContext;
+use crate::profiler::ProfileCategory;
 use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath};
 use gc_arena::Collect;
 use std::cell::Cell;
@@ -239,12 +240,16 @@ impl Drawing {
                 edge_bounds: self.edge_bounds.clone(),
                 id: 0,
             };
+            let span = context.profiler.start();
             if let Some(handle) = self.render_handle.get() {
                 context.renderer.replace_shape(shape, self, handle);
             } else {
                 self.render_handle
                     .set(Some(context.renderer.register_shape(shape, self)));
             }
+            context
+                .profiler
+                .finish(span, ProfileCategory::Tessellation, || "Drawing");
         }
 
         if let Some(handle) = self.render_handle.get() {
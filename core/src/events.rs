@@ -1,19 +1,54 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use swf::ClipEventFlag;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PlayerEvent {
-    KeyDown { key_code: KeyCode },
-    KeyUp { key_code: KeyCode },
-    MouseMove { x: f64, y: f64 },
-    MouseUp { x: f64, y: f64 },
-    MouseDown { x: f64, y: f64 },
+    KeyDown {
+        key_code: KeyCode,
+    },
+    KeyUp {
+        key_code: KeyCode,
+    },
+    MouseMove {
+        x: f64,
+        y: f64,
+    },
+    MouseUp {
+        x: f64,
+        y: f64,
+    },
+    MouseDown {
+        x: f64,
+        y: f64,
+    },
     MouseLeft,
-    MouseWheel { delta: MouseWheelDelta },
-    TextInput { codepoint: char },
+    MouseWheel {
+        delta: MouseWheelDelta,
+    },
+    TextInput {
+        codepoint: char,
+    },
+
+    /// The user started composing text with an input method editor (IME).
+    ImeCompositionStart,
+
+    /// The text being composed with an IME changed.
+    ImeCompositionUpdate {
+        text: String,
+    },
+
+    /// The user finished composing text with an IME, entering `text`.
+    /// An empty `text` means the composition was cancelled.
+    ImeCompositionCommit {
+        text: String,
+    },
 }
 
 /// The distance scrolled by the mouse wheel.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MouseWheelDelta {
     Lines(f64),
     Pixels(f64),
@@ -164,6 +199,7 @@ impl ClipEvent {
 
 /// Flash virtual keycode.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum KeyCode {
     Unknown = 0,
     Backspace = 8,
@@ -7,13 +7,21 @@ use crate::avm1::{
     ArrayObject as Avm1ArrayObject, AvmString as Avm1String, Error as Avm1Error,
     Object as Avm1Object, ScriptObject as Avm1ScriptObject,
 };
+use crate::avm2::TObject as Avm2TObject;
+use crate::avm2::Value as Avm2Value;
+use crate::avm2::{
+    Activation as Avm2Activation, ArrayObject as Avm2ArrayObject, ArrayStorage as Avm2ArrayStorage,
+    AvmString as Avm2String, Error as Avm2Error, Namespace as Avm2Namespace, Object as Avm2Object,
+    QName as Avm2QName, ScriptObject as Avm2ScriptObject,
+};
 use crate::context::UpdateContext;
 use gc_arena::Collect;
 use std::collections::BTreeMap;
 
 /// An intermediate format of representing shared data between ActionScript and elsewhere.
 /// Regardless of the capabilities of both sides, all data will be translated to this potentially
-/// lossy format. Any recursion or additional metadata in ActionScript will not be translated.
+/// lossy format. Any recursion or additional metadata in ActionScript will not be translated;
+/// an object that contains itself is translated as `Null` where it recurs.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Null,
@@ -118,6 +126,16 @@ impl Value {
     pub fn from_avm1<'gc>(
         activation: &mut Avm1Activation<'_, 'gc, '_>,
         value: Avm1Value<'gc>,
+    ) -> Result<Value, Avm1Error<'gc>> {
+        Self::from_avm1_visiting(activation, value, &mut Vec::new())
+    }
+
+    /// Convert an AVM1 value, replacing any object that is already being
+    /// converted further up the tree with `Null`.
+    fn from_avm1_visiting<'gc>(
+        activation: &mut Avm1Activation<'_, 'gc, '_>,
+        value: Avm1Value<'gc>,
+        visiting: &mut Vec<Avm1Object<'gc>>,
     ) -> Result<Value, Avm1Error<'gc>> {
         Ok(match value {
             Avm1Value::Undefined | Avm1Value::Null => Value::Null,
@@ -125,28 +143,49 @@ impl Value {
             Avm1Value::Number(value) => value.into(),
             Avm1Value::String(value) => Value::String(value.to_string()),
             Avm1Value::Object(object) => {
-                if object.as_array_object().is_some() {
-                    let length = object.length(activation)?;
-                    let values: Result<Vec<_>, Avm1Error<'gc>> = (0..length)
-                        .map(|i| {
-                            let element = object.get_element(activation, i);
-                            Value::from_avm1(activation, element)
-                        })
-                        .collect();
-                    Value::List(values?)
-                } else {
-                    let keys = object.get_keys(activation);
-                    let mut values = BTreeMap::new();
-                    for key in keys {
-                        let value = object.get(&key, activation)?;
-                        values.insert(key, Value::from_avm1(activation, value)?);
-                    }
-                    Value::Object(values)
+                if visiting.iter().any(|o| Avm1Object::ptr_eq(*o, object)) {
+                    return Ok(Value::Null);
                 }
+                visiting.push(object);
+                let result = if object.as_array_object().is_some() {
+                    Self::from_avm1_array(activation, object, visiting)
+                } else {
+                    Self::from_avm1_object(activation, object, visiting)
+                };
+                visiting.pop();
+                result?
             }
         })
     }
 
+    fn from_avm1_array<'gc>(
+        activation: &mut Avm1Activation<'_, 'gc, '_>,
+        object: Avm1Object<'gc>,
+        visiting: &mut Vec<Avm1Object<'gc>>,
+    ) -> Result<Value, Avm1Error<'gc>> {
+        let length = object.length(activation)?;
+        let mut values = Vec::with_capacity(length.max(0) as usize);
+        for i in 0..length {
+            let element = object.get_element(activation, i);
+            values.push(Self::from_avm1_visiting(activation, element, visiting)?);
+        }
+        Ok(Value::List(values))
+    }
+
+    fn from_avm1_object<'gc>(
+        activation: &mut Avm1Activation<'_, 'gc, '_>,
+        object: Avm1Object<'gc>,
+        visiting: &mut Vec<Avm1Object<'gc>>,
+    ) -> Result<Value, Avm1Error<'gc>> {
+        let keys = object.get_keys(activation);
+        let mut values = BTreeMap::new();
+        for key in keys {
+            let value = object.get(&key, activation)?;
+            values.insert(key, Self::from_avm1_visiting(activation, value, visiting)?);
+        }
+        Ok(Value::Object(values))
+    }
+
     pub fn into_avm1<'gc>(self, activation: &mut Avm1Activation<'_, 'gc, '_>) -> Avm1Value<'gc> {
         match self {
             Value::Null => Avm1Value::Null,
@@ -175,6 +214,104 @@ impl Value {
             .into(),
         }
     }
+
+    pub fn from_avm2<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        value: Avm2Value<'gc>,
+    ) -> Result<Value, Avm2Error> {
+        Self::from_avm2_visiting(activation, value, &mut Vec::new())
+    }
+
+    /// Convert an AVM2 value, replacing any object that is already being
+    /// converted further up the tree with `Null`.
+    fn from_avm2_visiting<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        value: Avm2Value<'gc>,
+        visiting: &mut Vec<Avm2Object<'gc>>,
+    ) -> Result<Value, Avm2Error> {
+        Ok(match value {
+            Avm2Value::Undefined | Avm2Value::Null => Value::Null,
+            Avm2Value::Bool(value) => value.into(),
+            Avm2Value::Number(value) => value.into(),
+            Avm2Value::Unsigned(value) => value.into(),
+            Avm2Value::Integer(value) => value.into(),
+            Avm2Value::String(value) => Value::String(value.to_string()),
+            Avm2Value::Object(object) => {
+                if visiting.iter().any(|o| Avm2Object::ptr_eq(*o, object)) {
+                    return Ok(Value::Null);
+                }
+                visiting.push(object);
+                let result = Self::from_avm2_object(activation, object, visiting);
+                visiting.pop();
+                result?
+            }
+        })
+    }
+
+    fn from_avm2_object<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        object: Avm2Object<'gc>,
+        visiting: &mut Vec<Avm2Object<'gc>>,
+    ) -> Result<Value, Avm2Error> {
+        let elements: Option<Vec<_>> = object
+            .as_array_storage()
+            .map(|array| array.iter().collect());
+        if let Some(elements) = elements {
+            let mut values = Vec::with_capacity(elements.len());
+            for element in elements {
+                let element = element.unwrap_or(Avm2Value::Undefined);
+                values.push(Self::from_avm2_visiting(activation, element, visiting)?);
+            }
+            Ok(Value::List(values))
+        } else {
+            let mut values = BTreeMap::new();
+            let mut index = 1;
+            while let Some(name) = object.get_enumerant_name(index) {
+                let value = object.get_property(object, &name, activation)?;
+                values.insert(
+                    name.local_name().to_string(),
+                    Self::from_avm2_visiting(activation, value, visiting)?,
+                );
+                index += 1;
+            }
+            Ok(Value::Object(values))
+        }
+    }
+
+    pub fn into_avm2<'gc>(self, activation: &mut Avm2Activation<'_, 'gc, '_>) -> Avm2Value<'gc> {
+        match self {
+            Value::Null => Avm2Value::Null,
+            Value::Bool(value) => Avm2Value::Bool(value),
+            Value::Number(value) => Avm2Value::Number(value),
+            Value::String(value) => {
+                Avm2Value::String(Avm2String::new(activation.context.gc_context, value))
+            }
+            Value::Object(values) => {
+                let mut object = Avm2ScriptObject::object(
+                    activation.context.gc_context,
+                    activation.avm2().prototypes().object,
+                );
+                for (key, value) in values {
+                    let name = Avm2QName::new(
+                        Avm2Namespace::public(),
+                        Avm2String::new(activation.context.gc_context, key),
+                    );
+                    let value = value.into_avm2(activation);
+                    let _ = object.set_property(object, &name, value, activation);
+                }
+                object.into()
+            }
+            Value::List(values) => {
+                let storage: Avm2ArrayStorage<'gc> = values
+                    .into_iter()
+                    .map(|value| value.into_avm2(activation))
+                    .collect();
+                Avm2ArrayObject::from_storage(activation, storage)
+                    .map(Avm2Value::Object)
+                    .unwrap_or(Avm2Value::Null)
+            }
+        }
+    }
 }
 
 #[derive(Collect, Clone)]
@@ -184,6 +321,9 @@ pub enum Callback<'gc> {
         this: Avm1Value<'gc>,
         method: Avm1Object<'gc>,
     },
+    Avm2 {
+        method: Avm2Object<'gc>,
+    },
 }
 
 impl<'gc> Callback<'gc> {
@@ -219,6 +359,21 @@ impl<'gc> Callback<'gc> {
                     Value::Null
                 }
             }
+            Callback::Avm2 { method } => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let args: Vec<Avm2Value> = args
+                    .into_iter()
+                    .map(|v| v.into_avm2(&mut activation))
+                    .collect();
+                if let Ok(result) = method
+                    .call(None, &args, &mut activation, None)
+                    .and_then(|value| Value::from_avm2(&mut activation, value))
+                {
+                    result
+                } else {
+                    Value::Null
+                }
+            }
         }
     }
 }
@@ -229,6 +384,11 @@ pub trait ExternalInterfaceProvider {
     fn on_callback_available(&self, name: &str);
 
     fn on_fs_command(&self, command: &str, args: &str) -> bool;
+
+    /// The ID of the element the movie is embedded in, if the host has one.
+    fn object_id(&self) -> Option<String> {
+        None
+    }
 }
 
 pub trait ExternalInterfaceMethod {
@@ -285,6 +445,12 @@ impl<'gc> ExternalInterface<'gc> {
         !self.providers.is_empty()
     }
 
+    pub fn object_id(&self) -> Option<String> {
+        self.providers
+            .iter()
+            .find_map(|provider| provider.object_id())
+    }
+
     pub fn invoke_fs_command(&self, command: &str, args: &str) -> bool {
         for provider in &self.providers {
             if provider.on_fs_command(command, args) {
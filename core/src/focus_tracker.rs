@@ -1,19 +1,47 @@
 use crate::avm1::{Avm1, Value};
-use crate::context::UpdateContext;
+use crate::avm2::{Avm2, Object as Avm2Object, Value as Avm2Value};
+use crate::context::{RenderContext, UpdateContext};
 pub use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::matrix::Matrix;
 use gc_arena::{Collect, GcCell, MutationContext};
+use swf::{Color, Twips};
+
+/// The thickness of the focus rectangle, in pixels.
+const FOCUS_RECT_THICKNESS: f32 = 2.0;
 
 #[derive(Clone, Copy, Collect, Debug)]
 #[collect(no_drop)]
-pub struct FocusTracker<'gc>(GcCell<'gc, Option<DisplayObject<'gc>>>);
+pub struct FocusTracker<'gc>(GcCell<'gc, FocusTrackerData<'gc>>);
+
+#[derive(Clone, Debug, Collect)]
+#[collect(no_drop)]
+struct FocusTrackerData<'gc> {
+    /// The currently focused display object.
+    focus: Option<DisplayObject<'gc>>,
+
+    /// Whether the focus was moved with the keyboard, in which case
+    /// the focus rectangle is drawn around the focused object.
+    highlight: bool,
+}
 
 impl<'gc> FocusTracker<'gc> {
     pub fn new(gc_context: MutationContext<'gc, '_>) -> Self {
-        Self(GcCell::allocate(gc_context, None))
+        Self(GcCell::allocate(
+            gc_context,
+            FocusTrackerData {
+                focus: None,
+                highlight: false,
+            },
+        ))
     }
 
     pub fn get(&self) -> Option<DisplayObject<'gc>> {
-        *self.0.read()
+        self.0.read().focus
+    }
+
+    /// Whether the focused object was reached with the keyboard.
+    pub fn is_highlighted(&self) -> bool {
+        self.0.read().highlight
     }
 
     pub fn set(
@@ -21,15 +49,13 @@ impl<'gc> FocusTracker<'gc> {
         focused_element: Option<DisplayObject<'gc>>,
         context: &mut UpdateContext<'_, 'gc, '_>,
     ) {
-        let old = std::mem::replace(&mut *self.0.write(context.gc_context), focused_element);
+        let old = {
+            let mut write = self.0.write(context.gc_context);
+            write.highlight = false;
+            std::mem::replace(&mut write.focus, focused_element)
+        };
 
-        if old.is_none() && focused_element.is_none() {
-            // We didn't have anything, we still don't, no change.
-            return;
-        }
-        if old.is_some() == focused_element.is_some()
-            && old.unwrap().as_ptr() == focused_element.unwrap().as_ptr()
-        {
+        if DisplayObject::option_ptr_eq(old, focused_element) {
             // We're setting it to the same object as before, no change.
             return;
         }
@@ -55,5 +81,193 @@ impl<'gc> FocusTracker<'gc> {
                 focused_element.map(|v| v.object()).unwrap_or(Value::Null),
             ],
         );
+
+        let old_object = old.and_then(avm2_object);
+        let new_object = focused_element.and_then(avm2_object);
+        if let Some(old_object) = old_object {
+            if let Err(e) =
+                Avm2::dispatch_focus_event(context, "focusOut", false, old_object, new_object)
+            {
+                log::error!("Encountered AVM2 error when dispatching focusOut: {}", e);
+            }
+        }
+        if let Some(new_object) = new_object {
+            if let Err(e) =
+                Avm2::dispatch_focus_event(context, "focusIn", false, new_object, old_object)
+            {
+                log::error!("Encountered AVM2 error when dispatching focusIn: {}", e);
+            }
+        }
+    }
+
+    /// Moves the focus to the next (or previous, if `reverse` is set) object
+    /// in tab order, as done by pressing Tab or Shift+Tab.
+    pub fn cycle(&self, context: &mut UpdateContext<'_, 'gc, '_>, reverse: bool) {
+        let order = tab_order(context);
+        if order.is_empty() {
+            return;
+        }
+
+        let current = self.get();
+        let current_index =
+            current.and_then(|c| order.iter().position(|o| DisplayObject::ptr_eq(*o, c)));
+        let next_index = match current_index {
+            Some(i) if reverse => (i + order.len() - 1) % order.len(),
+            Some(i) => (i + 1) % order.len(),
+            None if reverse => order.len() - 1,
+            None => 0,
+        };
+        let next = order[next_index];
+
+        if let Some(current_object) = current.and_then(avm2_object) {
+            match Avm2::dispatch_focus_event(
+                context,
+                "keyFocusChange",
+                true,
+                current_object,
+                avm2_object(next),
+            ) {
+                Ok(false) => return,
+                Ok(true) => {}
+                Err(e) => {
+                    log::error!(
+                        "Encountered AVM2 error when dispatching keyFocusChange: {}",
+                        e
+                    )
+                }
+            }
+        }
+
+        self.set(Some(next), context);
+
+        // Text fields show their own cursor instead of the focus rectangle.
+        self.0.write(context.gc_context).highlight = next.as_edit_text().is_none();
+    }
+
+    /// Draws the yellow focus rectangle around the focused object, if it was
+    /// focused with the keyboard.
+    pub fn render_highlight(&self, context: &mut RenderContext<'_, 'gc>) {
+        let focus = match *self.0.read() {
+            FocusTrackerData {
+                focus: Some(focus),
+                highlight: true,
+            } => focus,
+            _ => return,
+        };
+
+        if focus.removed()
+            || !focus
+                .focus_rect()
+                .unwrap_or_else(|| context.stage.stage_focus_rect())
+        {
+            return;
+        }
+
+        let bounds = focus.world_bounds().transform(&context.stage.matrix());
+        if !bounds.valid {
+            return;
+        }
+
+        let color = Color::from_rgb(0xFFFF00, 255);
+        let width = (bounds.x_max - bounds.x_min).to_pixels() as f32;
+        let height = (bounds.y_max - bounds.y_min).to_pixels() as f32;
+        let thickness = Twips::from_pixels(FOCUS_RECT_THICKNESS.into());
+        let sides = [
+            (width, FOCUS_RECT_THICKNESS, bounds.x_min, bounds.y_min),
+            (
+                width,
+                FOCUS_RECT_THICKNESS,
+                bounds.x_min,
+                bounds.y_max - thickness,
+            ),
+            (FOCUS_RECT_THICKNESS, height, bounds.x_min, bounds.y_min),
+            (
+                FOCUS_RECT_THICKNESS,
+                height,
+                bounds.x_max - thickness,
+                bounds.y_min,
+            ),
+        ];
+        for &(width, height, x, y) in &sides {
+            context
+                .renderer
+                .draw_rect(color.clone(), &Matrix::create_box(width, height, 0.0, x, y));
+        }
+    }
+}
+
+/// The AVM2 object of a display object, if it has one.
+fn avm2_object(object: DisplayObject<'_>) -> Option<Avm2Object<'_>> {
+    match object.object2() {
+        Avm2Value::Object(object) => Some(object),
+        _ => None,
+    }
+}
+
+/// Whether a display object can be reached by pressing Tab.
+fn is_tab_enabled<'gc>(
+    object: DisplayObject<'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> bool {
+    if object.is_tab_enabled() {
+        return true;
+    }
+
+    // AVM1 clips with button handlers assigned by script also act as buttons.
+    match (object.tab_enabled_override(), object.as_movie_clip()) {
+        (None, Some(clip)) if matches!(clip.object(), Value::Object(_)) => {
+            clip.is_button_mode(context)
+        }
+        _ => false,
+    }
+}
+
+/// Collects all visible tab-enabled objects on the stage, in the order that
+/// Tab moves through them.
+///
+/// If any of these objects has an explicit `tabIndex`, only objects with
+/// a `tabIndex` take part and are ordered by it. Otherwise, the objects are
+/// ordered geometrically, from top to bottom and then from left to right.
+fn tab_order<'gc>(context: &mut UpdateContext<'_, 'gc, '_>) -> Vec<DisplayObject<'gc>> {
+    let mut objects = Vec::new();
+    collect_tab_enabled(context.stage.into(), context, &mut objects);
+
+    if objects.iter().any(|o| o.tab_index().is_some()) {
+        objects.retain(|o| o.tab_index().is_some());
+        objects.sort_by_key(|o| o.tab_index());
+    } else {
+        let mut positioned: Vec<_> = objects
+            .into_iter()
+            .map(|o| {
+                let bounds = o.world_bounds();
+                ((bounds.y_min, bounds.x_min), o)
+            })
+            .collect();
+        positioned.sort_by_key(|(position, _)| *position);
+        objects = positioned.into_iter().map(|(_, o)| o).collect();
+    }
+
+    objects
+}
+
+fn collect_tab_enabled<'gc>(
+    parent: DisplayObject<'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    objects: &mut Vec<DisplayObject<'gc>>,
+) {
+    if let Some(container) = parent.as_container() {
+        for child in container.iter_render_list() {
+            if !child.visible() {
+                continue;
+            }
+
+            if is_tab_enabled(child, context) {
+                objects.push(child);
+            }
+
+            if child.tab_children() {
+                collect_tab_enabled(child, context, objects);
+            }
+        }
     }
 }
@@ -215,6 +215,14 @@ impl<'gc> Font<'gc> {
         Twips::new((self.0.ascent as f32 * scale) as i32)
     }
 
+    /// Get the distance from the baseline to the bottom of the glyph at a
+    /// given height.
+    pub fn get_descent_for_height(&self, height: Twips) -> Twips {
+        let scale = height.get() as f32 / self.scale();
+
+        Twips::new((self.0.descent as f32 * scale) as i32)
+    }
+
     /// Returns whether this font contains kerning information.
     pub fn has_kerning_info(&self) -> bool {
         !self.0.kerning_pairs.is_empty()
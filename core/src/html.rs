@@ -3,13 +3,15 @@
 mod dimensions;
 mod iterators;
 mod layout;
+mod stylesheet;
 mod text_format;
 
 pub use dimensions::BoxBounds;
 pub use dimensions::Position;
 pub use dimensions::Size;
 pub use layout::{LayoutBox, LayoutContent};
-pub use text_format::{FormatSpans, TextFormat, TextSpan};
+pub use stylesheet::{apply_css_style, css_property_name, CssStyle, StyleSheet};
+pub use text_format::{FormatSpans, ImageAlign, TextFormat, TextImage, TextSpan};
 
 #[cfg(test)]
 mod test;
@@ -4,7 +4,7 @@ use crate::context::UpdateContext;
 use crate::drawing::Drawing;
 use crate::font::{EvalParameters, Font};
 use crate::html::dimensions::{BoxBounds, Position, Size};
-use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::text_format::{FormatSpans, ImageAlign, TextFormat, TextImage, TextSpan};
 use crate::shape_utils::DrawCommand;
 use crate::string_utils;
 use crate::tag_utils::SwfMovie;
@@ -33,6 +33,23 @@ fn draw_underline(drawing: &mut Drawing, starting_pos: Position<Twips>, width: T
     });
 }
 
+/// An inline image waiting to be floated to the side of the text.
+#[derive(Clone, Copy, Debug)]
+struct FloatingImage {
+    /// The index of the image in the `FormatSpans` being laid out.
+    index: usize,
+    align: ImageAlign,
+
+    /// The size the image is displayed at.
+    size: Size<Twips>,
+
+    /// The space kept clear of text to either side of the image.
+    hspace: Twips,
+
+    /// The space kept clear of text above and below the image.
+    vspace: Twips,
+}
+
 /// Contains information relating to the current layout operation.
 pub struct LayoutContext<'a, 'gc> {
     /// The movie this layout context is pulling fonts from.
@@ -87,6 +104,20 @@ pub struct LayoutContext<'a, 'gc> {
 
     /// The total width of the text field being laid out.
     max_bounds: Twips,
+
+    /// The area taken up by each image floated so far, including the space
+    /// around it, and the side it was floated to.
+    floats: Vec<(BoxBounds<Twips>, ImageAlign)>,
+
+    /// Images anchored partway through the current line, which will be
+    /// floated at the start of the next line.
+    pending_images: Vec<FloatingImage>,
+
+    /// The layout boxes of all floated images.
+    ///
+    /// These are kept apart from the boxes of the current line, so that they
+    /// aren't moved by line alignment.
+    image_boxes: Vec<LayoutBox<'gc>>,
 }
 
 impl<'a, 'gc> LayoutContext<'a, 'gc> {
@@ -104,6 +135,9 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             current_line: 0,
             current_line_span: Default::default(),
             max_bounds,
+            floats: Vec::new(),
+            pending_images: Vec::new(),
+            image_boxes: Vec::new(),
         }
     }
 
@@ -250,9 +284,11 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
         let mut line_bounds = line_bounds.unwrap_or_default();
 
+        let (float_left, float_right) = self.float_insets();
         let left_adjustment =
-            Self::left_alignment_offset(&self.current_line_span, self.is_first_line);
-        let right_adjustment = Twips::from_pixels(self.current_line_span.right_margin);
+            Self::left_alignment_offset(&self.current_line_span, self.is_first_line) + float_left;
+        let right_adjustment =
+            Twips::from_pixels(self.current_line_span.right_margin) + float_right;
 
         let misalignment =
             self.max_bounds - left_adjustment - right_adjustment - line_bounds.width();
@@ -334,6 +370,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
         self.is_first_line = true;
         self.has_line_break = true;
+        self.float_pending_images();
     }
 
     /// Adjust the text layout cursor down to the next line.
@@ -352,6 +389,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
         self.is_first_line = false;
         self.has_line_break = true;
+        self.float_pending_images();
     }
 
     /// Adjust the text layout cursor in response to a tab.
@@ -526,12 +564,171 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     ///
     /// Offsets returned by this function should not be considered final;
     fn wrap_dimensions(&self, current_span: &TextSpan) -> (Twips, Twips) {
-        let width = self.max_bounds - Twips::from_pixels(self.current_line_span.right_margin);
-        let offset = Self::left_alignment_offset(current_span, self.is_first_line);
+        let (float_left, float_right) = self.float_insets();
+        let width =
+            self.max_bounds - Twips::from_pixels(self.current_line_span.right_margin) - float_right;
+        let offset = Self::left_alignment_offset(current_span, self.is_first_line) + float_left;
 
         (width, offset + self.cursor.x())
     }
 
+    /// Calculate how far the current line has to be inset from the left and
+    /// right edges of the field to make room for floated images.
+    fn float_insets(&self) -> (Twips, Twips) {
+        let y = self.cursor.y();
+        let mut left = Twips::ZERO;
+        let mut right = Twips::ZERO;
+
+        for (bounds, align) in self.floats.iter() {
+            if bounds.offset_y() <= y && y < bounds.extent_y() {
+                match align {
+                    ImageAlign::Left => left = max(left, bounds.extent_x()),
+                    ImageAlign::Right => right = max(right, self.max_bounds - bounds.offset_x()),
+                }
+            }
+        }
+
+        (left, right)
+    }
+
+    /// Add an inline image to the layout.
+    ///
+    /// Images are floated to the side of the field at the top of the line
+    /// they are anchored in. If that line already has some text on it, the
+    /// image is floated at the start of the next line instead.
+    fn append_image(&mut self, index: usize, image: &TextImage, size: Size<Twips>) {
+        let image = FloatingImage {
+            index,
+            align: image.align,
+            size,
+            hspace: Twips::from_pixels(image.hspace),
+            vspace: Twips::from_pixels(image.vspace),
+        };
+
+        if self.is_start_of_line() {
+            self.float_image(image);
+        } else {
+            self.pending_images.push(image);
+        }
+    }
+
+    /// Float an image to the side of the current line, next to any images
+    /// already floated there.
+    fn float_image(&mut self, image: FloatingImage) {
+        let outer_size = Size::from((
+            image.size.width() + image.hspace * 2,
+            image.size.height() + image.vspace * 2,
+        ));
+        let (float_left, float_right) = self.float_insets();
+        let x = match image.align {
+            ImageAlign::Left => float_left,
+            ImageAlign::Right => self.max_bounds - float_right - outer_size.width(),
+        };
+        let position = Position::from((x, self.cursor.y()));
+
+        self.floats.push((
+            BoxBounds::from_position_and_size(position, outer_size),
+            image.align,
+        ));
+
+        let mut image_box = LayoutBox::from_image(image.index);
+        image_box.bounds = BoxBounds::from_position_and_size(
+            position + Position::from((image.hspace, image.vspace)),
+            image.size,
+        );
+        self.image_boxes.push(image_box);
+    }
+
+    /// Float all images that were waiting for a new line.
+    fn float_pending_images(&mut self) {
+        for image in std::mem::take(&mut self.pending_images) {
+            self.float_image(image);
+        }
+    }
+
+    /// Append text to the current line, wrapping it onto new lines as
+    /// necessary.
+    fn append_wrapped_text(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        font: Font<'gc>,
+        span: &TextSpan,
+        text: &'a str,
+        start: usize,
+        is_word_wrap: bool,
+    ) {
+        let params = EvalParameters::from_span(span);
+        let mut last_breakpoint = 0;
+
+        if is_word_wrap {
+            let (mut width, mut offset) = self.wrap_dimensions(span);
+
+            while let Some(breakpoint) = font.wrap_line(
+                &text[last_breakpoint..],
+                params,
+                width,
+                offset,
+                self.is_start_of_line(),
+            ) {
+                // If text doesn't fit at the start of a line, it
+                // won't fit on the next either, abort and put the
+                // whole text on the line (will be cut-off). This
+                // can happen for small text fields with single
+                // characters.
+                if breakpoint == 0 && self.is_start_of_line() {
+                    break;
+                } else if breakpoint == 0 {
+                    self.newline(context);
+
+                    let next_dim = self.wrap_dimensions(span);
+
+                    width = next_dim.0;
+                    offset = next_dim.1;
+
+                    if last_breakpoint >= text.len() {
+                        break;
+                    } else {
+                        continue;
+                    }
+                }
+
+                // This ensures that the space causing the line break
+                // is included in the line it broke.
+                let next_breakpoint =
+                    string_utils::next_char_boundary(text, last_breakpoint + breakpoint);
+
+                self.append_text(
+                    &text[last_breakpoint..next_breakpoint],
+                    start + last_breakpoint,
+                    start + next_breakpoint,
+                    span,
+                );
+
+                last_breakpoint = next_breakpoint;
+                if last_breakpoint >= text.len() {
+                    break;
+                }
+
+                self.newline(context);
+                let next_dim = self.wrap_dimensions(span);
+
+                width = next_dim.0;
+                offset = next_dim.1;
+            }
+        }
+
+        let span_end = text.len();
+
+        if last_breakpoint < span_end {
+            self.append_text(
+                &text[last_breakpoint..span_end],
+                start + last_breakpoint,
+                start + span_end,
+                span,
+            );
+        }
+    }
+
     /// Destroy the layout context, returning the newly constructed layout list.
     fn end_layout(
         mut self,
@@ -539,6 +736,27 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>) {
         self.fixup_line(context, !self.has_line_break, true);
 
+        // Images anchored in the last line still go below it.
+        if !self.pending_images.is_empty() {
+            self.cursor.set_x(Twips::ZERO);
+            self.cursor += (
+                Twips::ZERO,
+                self.max_font_size + self.line_leading_adjustment(),
+            )
+                .into();
+            self.float_pending_images();
+        }
+
+        for (bounds, _align) in self.floats.iter() {
+            if let Some(eb) = &mut self.exterior_bounds {
+                *eb += *bounds;
+            } else {
+                self.exterior_bounds = Some(*bounds);
+            }
+        }
+
+        self.boxes.append(&mut self.image_boxes);
+
         (self.boxes, self.exterior_bounds.unwrap_or_default())
     }
 
@@ -619,6 +837,13 @@ pub enum LayoutContent<'gc> {
     /// layout box's bounds. The size of those bounds do not affect the
     /// rendering of the drawing.
     Drawing(Drawing),
+
+    /// A layout box containing an inline image.
+    ///
+    /// The image is the one at `index` in the `FormatSpans` that generated
+    /// this layout box. The box's bounds are the size the image is displayed
+    /// at.
+    Image { index: usize },
 }
 
 impl<'gc> LayoutBox<'gc> {
@@ -662,8 +887,18 @@ impl<'gc> LayoutBox<'gc> {
         }
     }
 
+    /// Construct an inline image.
+    pub fn from_image(index: usize) -> Self {
+        Self {
+            bounds: Default::default(),
+            content: LayoutContent::Image { index },
+        }
+    }
+
     /// Construct a new layout hierarchy from text spans.
     ///
+    /// `image_sizes` holds the size to display each of the spans' images at.
+    ///
     /// The returned bounds will include both the text bounds itself, as well
     /// as left and right margins on any of the lines.
     pub fn lower_from_text_spans(
@@ -673,15 +908,16 @@ impl<'gc> LayoutBox<'gc> {
         bounds: Twips,
         is_word_wrap: bool,
         is_device_font: bool,
+        image_sizes: &[Size<Twips>],
     ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>) {
         let mut layout_context = LayoutContext::new(movie, bounds, fs.displayed_text());
+        let mut images = fs.images().iter().enumerate().peekable();
+        let image_size = |index: usize| image_sizes.get(index).copied().unwrap_or_default();
 
         for (span_start, _end, span_text, span) in fs.iter_spans() {
             if let Some(font) = layout_context.resolve_font(context, span, is_device_font) {
                 layout_context.newspan(span);
 
-                let params = EvalParameters::from_span(span);
-
                 for text in span_text.split(&['\n', '\r', '\t'][..]) {
                     let slice_start = text.as_ptr() as usize - span_text.as_ptr() as usize;
                     let delimiter = if slice_start > 0 {
@@ -699,83 +935,52 @@ impl<'gc> LayoutBox<'gc> {
                         _ => {}
                     }
 
-                    let start = span_start + slice_start;
-
-                    let mut last_breakpoint = 0;
-
-                    if is_word_wrap {
-                        let (mut width, mut offset) = layout_context.wrap_dimensions(span);
-
-                        while let Some(breakpoint) = font.wrap_line(
-                            &text[last_breakpoint..],
-                            params,
-                            width,
-                            offset,
-                            layout_context.is_start_of_line(),
-                        ) {
-                            // If text doesn't fit at the start of a line, it
-                            // won't fit on the next either, abort and put the
-                            // whole text on the line (will be cut-off). This
-                            // can happen for small text fields with single
-                            // characters.
-                            if breakpoint == 0 && layout_context.is_start_of_line() {
-                                break;
-                            } else if breakpoint == 0 {
-                                layout_context.newline(context);
-
-                                let next_dim = layout_context.wrap_dimensions(span);
-
-                                width = next_dim.0;
-                                offset = next_dim.1;
-
-                                if last_breakpoint >= text.len() {
-                                    break;
-                                } else {
-                                    continue;
-                                }
-                            }
-
-                            // This ensures that the space causing the line break
-                            // is included in the line it broke.
-                            let next_breakpoint = string_utils::next_char_boundary(
-                                text,
-                                last_breakpoint + breakpoint,
-                            );
-
-                            layout_context.append_text(
-                                &text[last_breakpoint..next_breakpoint],
-                                start + last_breakpoint,
-                                start + next_breakpoint,
-                                span,
-                            );
-
-                            last_breakpoint = next_breakpoint;
-                            if last_breakpoint >= text.len() {
-                                break;
-                            }
-
-                            layout_context.newline(context);
-                            let next_dim = layout_context.wrap_dimensions(span);
+                    let mut start = span_start + slice_start;
+                    let mut text = text;
 
-                            width = next_dim.0;
-                            offset = next_dim.1;
+                    // Split the text around any images anchored within it.
+                    while let Some((index, image)) = images.peek() {
+                        if image.position >= start + text.len() {
+                            break;
                         }
-                    }
 
-                    let span_end = text.len();
-
-                    if last_breakpoint < span_end {
-                        layout_context.append_text(
-                            &text[last_breakpoint..span_end],
-                            start + last_breakpoint,
-                            start + span_end,
+                        let split = image.position.saturating_sub(start);
+                        let split = if text.is_char_boundary(split) {
+                            split
+                        } else {
+                            0
+                        };
+                        layout_context.append_wrapped_text(
+                            context,
+                            font,
                             span,
+                            &text[..split],
+                            start,
+                            is_word_wrap,
                         );
+                        layout_context.append_image(*index, image, image_size(*index));
+
+                        text = &text[split..];
+                        start += split;
+                        images.next();
                     }
+
+                    layout_context.append_wrapped_text(
+                        context,
+                        font,
+                        span,
+                        text,
+                        start,
+                        is_word_wrap,
+                    );
                 }
             }
         }
 
+        for (index, image) in images {
+            layout_context.append_image(index, image, image_size(index));
+        }
+
         layout_context.end_layout(context)
     }
 
@@ -815,6 +1020,7 @@ impl<'gc> LayoutBox<'gc> {
                 color,
             } => Some(("\u{2022}", text_format, *font, *params, color.clone())),
             LayoutContent::Drawing(..) => None,
+            LayoutContent::Image { .. } => None,
         }
     }
 
@@ -824,6 +1030,15 @@ impl<'gc> LayoutBox<'gc> {
             LayoutContent::Text { .. } => None,
             LayoutContent::Bullet { .. } => None,
             LayoutContent::Drawing(drawing) => Some(drawing),
+            LayoutContent::Image { .. } => None,
+        }
+    }
+
+    /// Returns the index of the image this box contains, if it has one.
+    pub fn as_image(&self) -> Option<usize> {
+        match &self.content {
+            LayoutContent::Image { index } => Some(*index),
+            _ => None,
         }
     }
 
@@ -0,0 +1,380 @@
+//! CSS style sheets for HTML text fields.
+
+use crate::html::TextFormat;
+use crate::xml::{XmlName, XmlNode};
+use gc_arena::Collect;
+use std::collections::BTreeMap;
+
+/// The declarations of a single CSS rule, such as `font-size: 12px`.
+///
+/// Property names are stored in the camel case form that ActionScript sees
+/// them in, so `font-size` becomes `fontSize`. Values are kept as written.
+pub type CssStyle = BTreeMap<String, String>;
+
+/// A set of named CSS styles, as used by `TextField.StyleSheet`.
+///
+/// Only a subset of CSS is understood: simple tag selectors such as `p`,
+/// class selectors such as `.heading`, and the handful of properties that
+/// can be expressed as a `TextFormat`.
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(require_static)]
+pub struct StyleSheet {
+    /// The styles, keyed by their lowercased selector.
+    styles: BTreeMap<String, CssStyle>,
+}
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The selectors of all styles in this sheet.
+    pub fn style_names(&self) -> impl Iterator<Item = &str> {
+        self.styles.keys().map(|name| name.as_str())
+    }
+
+    pub fn style(&self, name: &str) -> Option<&CssStyle> {
+        self.styles.get(&name.to_ascii_lowercase())
+    }
+
+    /// Adds or replaces the style for a selector, or removes it if `style` is `None`.
+    pub fn set_style(&mut self, name: &str, style: Option<CssStyle>) {
+        let name = name.to_ascii_lowercase();
+        match style {
+            Some(style) => {
+                self.styles.insert(name, style);
+            }
+            None => {
+                self.styles.remove(&name);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.styles.clear();
+    }
+
+    /// Parses CSS text and adds its rules to this sheet.
+    ///
+    /// Declarations for a selector that already has a style are merged into
+    /// it. If the text can't be parsed, the sheet is left unchanged and
+    /// `false` is returned.
+    pub fn parse_css(&mut self, css: &str) -> bool {
+        let rules = match parse_rules(css) {
+            Some(rules) => rules,
+            None => return false,
+        };
+
+        for (selector, declarations) in rules {
+            self.styles
+                .entry(selector)
+                .or_insert_with(CssStyle::new)
+                .extend(declarations);
+        }
+        true
+    }
+
+    /// Applies the styles that select an HTML element to a text format.
+    ///
+    /// The style for the element's tag is applied first, followed by the
+    /// styles for each of the classes in its `class` attribute.
+    pub fn apply_to_node(&self, node: XmlNode<'_>, mut tf: TextFormat) -> TextFormat {
+        if let Some(tag) = node.tag_name() {
+            let tag = tag.node_name().to_ascii_lowercase();
+            if let Some(style) = self.styles.get(&tag) {
+                tf = apply_css_style(style, tf);
+            }
+            if tag == "a" {
+                if let Some(style) = self.styles.get("a:link") {
+                    tf = apply_css_style(style, tf);
+                }
+            }
+        }
+
+        if let Some(classes) = node.attribute_value_ignore_ascii_case(&XmlName::from_str("class")) {
+            for class in classes.split_whitespace() {
+                if let Some(style) = self.style(&format!(".{}", class)) {
+                    tf = apply_css_style(style, tf);
+                }
+            }
+        }
+
+        tf
+    }
+}
+
+/// Applies the properties of a CSS style to a text format.
+///
+/// Properties that can't be represented in a text format, or have values
+/// that can't be understood, are ignored.
+pub fn apply_css_style(style: &CssStyle, mut tf: TextFormat) -> TextFormat {
+    for (name, value) in style {
+        let value = value.trim();
+        match name.as_str() {
+            "color" => {
+                if let Some(color) = parse_color(value) {
+                    tf.color = Some(color);
+                }
+            }
+            "fontFamily" => {
+                if let Some(font) = parse_font_family(value) {
+                    tf.font = Some(font);
+                }
+            }
+            "fontSize" => {
+                if let Some(size) = parse_number(value) {
+                    tf.size = Some(size);
+                }
+            }
+            "fontWeight" => match value {
+                "bold" => tf.bold = Some(true),
+                "normal" => tf.bold = Some(false),
+                _ => {}
+            },
+            "fontStyle" => match value {
+                "italic" => tf.italic = Some(true),
+                "normal" => tf.italic = Some(false),
+                _ => {}
+            },
+            "textDecoration" => match value {
+                "underline" => tf.underline = Some(true),
+                "none" => tf.underline = Some(false),
+                _ => {}
+            },
+            "textAlign" => match value {
+                "left" => tf.align = Some(swf::TextAlign::Left),
+                "center" => tf.align = Some(swf::TextAlign::Center),
+                "right" => tf.align = Some(swf::TextAlign::Right),
+                "justify" => tf.align = Some(swf::TextAlign::Justify),
+                _ => {}
+            },
+            "marginLeft" => {
+                if let Some(margin) = parse_number(value) {
+                    tf.left_margin = Some(margin);
+                }
+            }
+            "marginRight" => {
+                if let Some(margin) = parse_number(value) {
+                    tf.right_margin = Some(margin);
+                }
+            }
+            "textIndent" => {
+                if let Some(indent) = parse_number(value) {
+                    tf.indent = Some(indent);
+                }
+            }
+            "leading" => {
+                if let Some(leading) = parse_number(value) {
+                    tf.leading = Some(leading);
+                }
+            }
+            "letterSpacing" => {
+                if let Some(letter_spacing) = parse_number(value) {
+                    tf.letter_spacing = Some(letter_spacing);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    tf
+}
+
+/// Converts a CSS property name to camel case, such as `font-size` to `fontSize`.
+pub fn css_property_name(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut uppercase_next = false;
+    for c in name.chars() {
+        if c == '-' {
+            uppercase_next = !result.is_empty();
+        } else if uppercase_next {
+            result.push(c.to_ascii_uppercase());
+            uppercase_next = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Parses CSS text into a list of selectors and their declarations.
+///
+/// Returns `None` if the braces in the text don't match up.
+fn parse_rules(css: &str) -> Option<Vec<(String, CssStyle)>> {
+    let css = strip_comments(css);
+    let mut rules = Vec::new();
+    let mut rest = css.as_str();
+
+    while let Some(open) = rest.find('{') {
+        let selectors = &rest[..open];
+        let body_and_rest = &rest[open + 1..];
+        let close = body_and_rest.find('}')?;
+        let body = &body_and_rest[..close];
+        if selectors.contains('}') || body.contains('{') {
+            return None;
+        }
+
+        let mut declarations = CssStyle::new();
+        for declaration in body.split(';') {
+            let mut parts = declaration.splitn(2, ':');
+            if let (Some(name), Some(value)) = (parts.next(), parts.next()) {
+                let name = name.trim();
+                if !name.is_empty() {
+                    declarations.insert(css_property_name(name), value.trim().to_string());
+                }
+            } else if !declaration.trim().is_empty() {
+                return None;
+            }
+        }
+
+        for selector in selectors.split(',') {
+            let selector = selector.trim();
+            if selector.is_empty() {
+                return None;
+            }
+            rules.push((selector.to_ascii_lowercase(), declarations.clone()));
+        }
+
+        rest = &body_and_rest[close + 1..];
+    }
+
+    if rest.trim().is_empty() {
+        Some(rules)
+    } else {
+        None
+    }
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut result = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        result.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Parses a `#rrggbb` or `#rgb` color.
+fn parse_color(value: &str) -> Option<swf::Color> {
+    let hex = value.strip_prefix('#')?;
+    let component = |i: usize, len: usize| {
+        let digits = hex.get(i * len..(i + 1) * len)?;
+        let value = u8::from_str_radix(digits, 16).ok()?;
+        Some(if len == 1 { value * 0x11 } else { value })
+    };
+    let len = match hex.len() {
+        6 => 2,
+        3 => 1,
+        _ => return None,
+    };
+
+    Some(swf::Color {
+        r: component(0, len)?,
+        g: component(1, len)?,
+        b: component(2, len)?,
+        a: 255,
+    })
+}
+
+/// Parses the first font in a `font-family` list, mapping the generic CSS
+/// families to Flash's device fonts.
+fn parse_font_family(value: &str) -> Option<String> {
+    let family = value
+        .split(',')
+        .next()?
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'');
+    let font = match family {
+        "" => return None,
+        "sans-serif" => "_sans",
+        "serif" => "_serif",
+        "monospace" => "_typewriter",
+        family => family,
+    };
+    Some(font.to_string())
+}
+
+/// Parses a number, ignoring any unit after it, such as in `12px` or `12pt`.
+fn parse_number(value: &str) -> Option<f64> {
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == '+'))
+        .unwrap_or_else(|| value.len());
+    value[..end].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_css_rules() {
+        let mut sheet = StyleSheet::new();
+        assert!(sheet.parse_css(
+            "/* Help text */
+            P, .Body { font-size: 12px; color: #FF0000 }
+            .heading { font-weight: bold; font-family: 'Arial', sans-serif; }"
+        ));
+
+        assert_eq!(
+            sheet.style_names().collect::<Vec<_>>(),
+            vec![".body", ".heading", "p"]
+        );
+        let style = sheet.style("p").unwrap();
+        assert_eq!(style.get("fontSize").map(String::as_str), Some("12px"));
+        assert_eq!(style.get("color").map(String::as_str), Some("#FF0000"));
+
+        let tf = apply_css_style(sheet.style(".heading").unwrap(), TextFormat::default());
+        assert_eq!(tf.bold, Some(true));
+        assert_eq!(tf.font.as_deref(), Some("Arial"));
+    }
+
+    #[test]
+    fn parse_css_errors_leave_sheet_unchanged() {
+        let mut sheet = StyleSheet::new();
+        assert!(sheet.parse_css("p { color: #000000; }"));
+        assert!(!sheet.parse_css("a { color: #0000ff; } b { font-weight: bold;"));
+        assert!(!sheet.parse_css("a { color }"));
+        assert_eq!(sheet.style_names().collect::<Vec<_>>(), vec!["p"]);
+    }
+
+    #[test]
+    fn transform_properties() {
+        let style: CssStyle = vec![
+            ("color", "#0f0"),
+            ("fontSize", "14pt"),
+            ("fontStyle", "italic"),
+            ("textDecoration", "underline"),
+            ("textAlign", "center"),
+            ("marginLeft", "5"),
+            ("marginRight", "6px"),
+            ("leading", "-2"),
+            ("letterSpacing", "1.5"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+
+        let tf = apply_css_style(&style, TextFormat::default());
+        assert_eq!(tf.color, Some(swf::Color::from_rgb(0x00ff00, 255)));
+        assert_eq!(tf.size, Some(14.0));
+        assert_eq!(tf.italic, Some(true));
+        assert_eq!(tf.underline, Some(true));
+        assert_eq!(tf.align, Some(swf::TextAlign::Center));
+        assert_eq!(tf.left_margin, Some(5.0));
+        assert_eq!(tf.right_margin, Some(6.0));
+        assert_eq!(tf.leading, Some(-2.0));
+        assert_eq!(tf.letter_spacing, Some(1.5));
+    }
+
+    #[test]
+    fn property_names() {
+        assert_eq!(css_property_name("font-size"), "fontSize");
+        assert_eq!(css_property_name("color"), "color");
+        assert_eq!(css_property_name("-moz-thing"), "mozThing");
+    }
+}
@@ -1,7 +1,9 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
-use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::text_format::{FormatSpans, ImageAlign, TextFormat, TextSpan};
+use crate::xml::XmlDocument;
+use gc_arena::rootless_arena;
 use swf::{Rectangle, Twips};
 
 #[test]
@@ -863,3 +865,80 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn formatspans_lower_images() {
+    rootless_arena(|mc| {
+        let document = XmlDocument::new(mc);
+        document
+            .as_node()
+            .replace_with_str(
+                mc,
+                "<p>ab<img src='logo' width='20' align='right' hspace='0' id='pic'/>cd</p><img src='end.jpg'/>",
+                false,
+                false,
+            )
+            .expect("Parsed document");
+
+        let mut fs = FormatSpans::new();
+        fs.lower_from_html(document);
+
+        assert_eq!("abcd\n", fs.text());
+        let images = fs.images();
+        assert_eq!(2, images.len());
+        assert_eq!(2, images[0].position);
+        assert_eq!("logo", images[0].src);
+        assert_eq!(Some("pic"), images[0].id.as_deref());
+        assert_eq!(Some(20.0), images[0].width);
+        assert_eq!(None, images[0].height);
+        assert_eq!(ImageAlign::Right, images[0].align);
+        assert_eq!(0.0, images[0].hspace);
+        assert_eq!(8.0, images[0].vspace);
+        assert_eq!(5, images[1].position);
+        assert_eq!(ImageAlign::Left, images[1].align);
+
+        let html = fs
+            .raise_to_html(mc)
+            .as_node()
+            .into_string(&mut |_node| true)
+            .expect("Serialized document");
+        assert!(html.contains("SRC=\"logo\""));
+        assert!(html.contains("SRC=\"end.jpg\""));
+    })
+}
+
+#[test]
+fn formatspans_replace_text_moves_images() {
+    rootless_arena(|mc| {
+        let document = XmlDocument::new(mc);
+        document
+            .as_node()
+            .replace_with_str(mc, "abc<img src='a'/>def<img src='b'/>ghi", false, false)
+            .expect("Parsed document");
+
+        let mut fs = FormatSpans::new();
+        fs.lower_from_html(document);
+        assert_eq!(
+            vec![3, 6],
+            fs.images().iter().map(|i| i.position).collect::<Vec<_>>()
+        );
+
+        // Inserting at an image's position keeps the image before the new text.
+        fs.replace_text(3, 3, "12", None);
+        assert_eq!(
+            vec![3, 8],
+            fs.images().iter().map(|i| i.position).collect::<Vec<_>>()
+        );
+
+        // Images within removed text are kept, after the replacement.
+        fs.replace_text(2, 8, "x", None);
+        assert_eq!("abxghi", fs.text());
+        assert_eq!(
+            vec![3, 3],
+            fs.images().iter().map(|i| i.position).collect::<Vec<_>>()
+        );
+
+        fs.clear_images();
+        assert!(fs.images().is_empty());
+    })
+}
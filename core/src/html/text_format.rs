@@ -12,6 +12,7 @@ use crate::avm2::{
 };
 use crate::context::UpdateContext;
 use crate::html::iterators::TextSpanIter;
+use crate::html::StyleSheet;
 use crate::tag_utils::SwfMovie;
 use crate::xml::{Step, XmlDocument, XmlName, XmlNode};
 use gc_arena::{Collect, MutationContext};
@@ -1198,6 +1199,119 @@ impl TextSpan {
     }
 }
 
+/// Which side of the text field an inline image floats to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageAlign {
+    Left,
+    Right,
+}
+
+/// An image embedded in HTML text with an `<img>` tag.
+///
+/// Images don't take up any characters of the text. Instead, each image is
+/// anchored before a position in the text, and floats to the left or right
+/// edge of the field at that point, with the text wrapping around it.
+#[derive(Clone, Debug, PartialEq, Collect)]
+#[collect(require_static)]
+pub struct TextImage {
+    /// The position in the text that this image is anchored before.
+    pub position: usize,
+
+    /// The export name of a library symbol, or the URL of an image or SWF.
+    pub src: String,
+
+    /// The instance name of the clip that holds the image.
+    pub id: Option<String>,
+
+    /// The width to display the image at, in pixels.
+    ///
+    /// If not given, the image's own width is used.
+    pub width: Option<f64>,
+
+    /// The height to display the image at, in pixels.
+    ///
+    /// If not given, the image's own height is used.
+    pub height: Option<f64>,
+
+    pub align: ImageAlign,
+
+    /// The space to keep clear of text to the left and right of the image, in
+    /// pixels.
+    pub hspace: f64,
+
+    /// The space to keep clear of text above and below the image, in pixels.
+    pub vspace: f64,
+}
+
+impl TextImage {
+    /// Read an image from the attributes of an `<img>` tag.
+    ///
+    /// Returns `None` if the tag has no `src` attribute.
+    pub fn from_html_node(node: XmlNode<'_>, position: usize) -> Option<Self> {
+        let attribute =
+            |name: &str| node.attribute_value_ignore_ascii_case(&XmlName::from_str(name));
+        let number = |name: &str| {
+            attribute(name)
+                .and_then(|value| value.trim().parse::<f64>().ok())
+                .filter(|value| value.is_finite() && *value >= 0.0)
+        };
+
+        Some(Self {
+            position,
+            src: attribute("src")?,
+            id: attribute("id").filter(|id| !id.is_empty()),
+            width: number("width"),
+            height: number("height"),
+            align: match attribute("align") {
+                Some(align) if align.eq_ignore_ascii_case("right") => ImageAlign::Right,
+                _ => ImageAlign::Left,
+            },
+            hspace: number("hspace").unwrap_or(8.0),
+            vspace: number("vspace").unwrap_or(8.0),
+        })
+    }
+
+    /// Create an `<img>` tag describing this image.
+    fn to_html_node<'gc>(
+        &self,
+        mc: MutationContext<'gc, '_>,
+        document: XmlDocument<'gc>,
+    ) -> XmlNode<'gc> {
+        let node = XmlNode::new_element(mc, "IMG", document);
+
+        node.set_attribute_value(mc, &XmlName::from_str("SRC"), &self.src);
+        if let Some(width) = self.width {
+            node.set_attribute_value(mc, &XmlName::from_str("WIDTH"), &format!("{}", width));
+        }
+        if let Some(height) = self.height {
+            node.set_attribute_value(mc, &XmlName::from_str("HEIGHT"), &format!("{}", height));
+        }
+        node.set_attribute_value(
+            mc,
+            &XmlName::from_str("ALIGN"),
+            match self.align {
+                ImageAlign::Left => "left",
+                ImageAlign::Right => "right",
+            },
+        );
+        node.set_attribute_value(
+            mc,
+            &XmlName::from_str("HSPACE"),
+            &format!("{}", self.hspace),
+        );
+        node.set_attribute_value(
+            mc,
+            &XmlName::from_str("VSPACE"),
+            &format!("{}", self.vspace),
+        );
+        if let Some(id) = &self.id {
+            node.set_attribute_value(mc, &XmlName::from_str("ID"), id);
+        }
+
+        node
+    }
+}
+
 /// Struct which contains text formatted by `TextSpan`s.
 #[derive(Clone, Debug, Collect)]
 #[collect(require_static)]
@@ -1206,6 +1320,9 @@ pub struct FormatSpans {
     displayed_text: String,
     spans: Vec<TextSpan>,
     default_format: TextFormat,
+
+    /// The images embedded in the text, in the order of their positions.
+    images: Vec<TextImage>,
 }
 
 impl Default for FormatSpans {
@@ -1221,6 +1338,7 @@ impl FormatSpans {
             displayed_text: "".to_string(),
             spans: vec![TextSpan::default()],
             default_format: TextFormat::default(),
+            images: vec![],
         }
     }
 
@@ -1232,6 +1350,7 @@ impl FormatSpans {
             displayed_text: "".to_string(),
             spans: spans.to_vec(),
             default_format: Default::default(),
+            images: vec![],
         }
     }
 
@@ -1268,6 +1387,16 @@ impl FormatSpans {
         }
     }
 
+    /// The images embedded in the text, in the order of their positions.
+    pub fn images(&self) -> &[TextImage] {
+        &self.images
+    }
+
+    /// Remove all images embedded in the text.
+    pub fn clear_images(&mut self) {
+        self.images.clear();
+    }
+
     /// Retrieve the text span at a particular index.
     ///
     /// Text span indices are ephemeral and can change arbitrarily any time the
@@ -1551,6 +1680,14 @@ impl FormatSpans {
 
         self.text = new_string;
 
+        // Images stay anchored to the text around them. Images anchored
+        // within the replaced text end up after the new text.
+        for image in &mut self.images {
+            if image.position > from {
+                image.position = image.position.max(to) - to + from + with.len();
+            }
+        }
+
         self.normalize();
     }
 
@@ -1575,11 +1712,25 @@ impl FormatSpans {
     /// styling. There's also a `lower_from_css` that respects both
     /// presentational markup and CSS stylesheets.
     pub fn lower_from_html(&mut self, tree: XmlDocument<'_>) {
+        self.lower(tree, None)
+    }
+
+    /// Lower an HTML tree into text-span representation, styled by a CSS
+    /// stylesheet.
+    ///
+    /// Each element is first styled by its presentational markup, and then by
+    /// any styles in the stylesheet that select its tag or classes.
+    pub fn lower_from_css(&mut self, tree: XmlDocument<'_>, style_sheet: &StyleSheet) {
+        self.lower(tree, Some(style_sheet))
+    }
+
+    fn lower(&mut self, tree: XmlDocument<'_>, style_sheet: Option<&StyleSheet>) {
         let mut format_stack = vec![self.default_format.clone()];
         let mut last_successful_format = None;
 
         self.text = "".to_string();
         self.spans = vec![];
+        self.images = vec![];
 
         for step in tree.as_node().walk() {
             match step {
@@ -1613,10 +1764,28 @@ impl FormatSpans {
                             .unwrap()
                             .node_name()
                             .eq_ignore_ascii_case("br") => {}
-                Step::In(node) => format_stack.push(TextFormat::from_presentational_markup(
-                    node,
-                    format_stack.last().cloned().unwrap_or_default(),
-                )),
+                Step::In(node)
+                    if node
+                        .tag_name()
+                        .unwrap()
+                        .node_name()
+                        .eq_ignore_ascii_case("img") =>
+                {
+                    if let Some(image) = TextImage::from_html_node(node, self.text.len()) {
+                        self.images.push(image);
+                    }
+                    format_stack.push(format_stack.last().cloned().unwrap_or_default());
+                }
+                Step::In(node) => {
+                    let mut tf = TextFormat::from_presentational_markup(
+                        node,
+                        format_stack.last().cloned().unwrap_or_default(),
+                    );
+                    if let Some(style_sheet) = style_sheet {
+                        tf = style_sheet.apply_to_node(node, tf);
+                    }
+                    format_stack.push(tf);
+                }
                 Step::Around(node) if node.is_text() => {
                     self.replace_text(
                         self.text.len(),
@@ -1673,6 +1842,8 @@ impl FormatSpans {
         let mut last_i = None;
         let mut last_u = None;
 
+        let mut images = self.images.iter().peekable();
+
         for (start, _end, text, span) in self.iter_spans() {
             let ls = &last_span.unwrap();
 
@@ -1963,7 +2134,7 @@ impl FormatSpans {
                     XmlNode::new_text(mc, line_with_newline, document)
                 };
 
-                last_u
+                let mut parent = last_u
                     .or(last_i)
                     .or(last_b)
                     .or(last_a)
@@ -1971,15 +2142,35 @@ impl FormatSpans {
                     .or(last_paragraph)
                     .or(last_bullet)
                     .or(last_text_format_element)
-                    .unwrap_or(root)
-                    .append_child(mc, span_text)
-                    .unwrap();
+                    .unwrap_or(root);
+
+                // Images are written at the start of the line they're anchored in.
+                let line_end =
+                    start + (line.as_ptr() as usize - text.as_ptr() as usize) + line.len();
+                while let Some(image) = images.peek() {
+                    if image.position > line_end {
+                        break;
+                    }
+                    parent
+                        .append_child(mc, image.to_html_node(mc, document))
+                        .unwrap();
+                    images.next();
+                }
+
+                parent.append_child(mc, span_text).unwrap();
 
                 last_span = Some(span);
                 can_span_create_bullets = true;
             }
         }
 
+        for image in images {
+            let mut parent = last_text_format_element.unwrap_or(root);
+            parent
+                .append_child(mc, image.to_html_node(mc, document))
+                .unwrap();
+        }
+
         document
     }
 }
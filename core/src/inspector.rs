@@ -0,0 +1,200 @@
+//! Introspection of the display list, for diagnosing layout and rendering bugs.
+//!
+//! `Player::inspect_display_list` takes a snapshot of the whole display object
+//! tree as plain data, which can then be printed or serialized to JSON.
+
+use crate::avm1::Value as Avm1Value;
+use crate::avm2::{TObject, Value as Avm2Value};
+use crate::prelude::*;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// A snapshot of a single display object and all of its children.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DisplayObjectInfo {
+    pub depth: Depth,
+    pub name: String,
+
+    /// The ID of the character this object was instantiated from.
+    /// Dynamically created objects have an ID of 0.
+    pub character_id: CharacterId,
+
+    /// The kind of display object, such as `MovieClip` or `EditText`.
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub kind: &'static str,
+
+    pub matrix: MatrixInfo,
+    pub color_transform: ColorTransformInfo,
+
+    /// The bounds of this object in stage pixels, if it has any.
+    pub bounds: Option<BoundsInfo>,
+
+    pub visible: bool,
+
+    /// The depth up to which this object masks its siblings, or 0 if it isn't a mask.
+    pub clip_depth: Depth,
+
+    /// The current frame of this object, if it has a timeline.
+    pub current_frame: Option<u16>,
+
+    /// The ActionScript object attached to this display object, if any.
+    pub object: Option<ObjectLink>,
+
+    pub children: Vec<DisplayObjectInfo>,
+}
+
+/// The transformation matrix of a display object, relative to its parent.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MatrixInfo {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+
+    /// The horizontal translation, in pixels.
+    pub tx: f64,
+
+    /// The vertical translation, in pixels.
+    pub ty: f64,
+}
+
+impl From<&Matrix> for MatrixInfo {
+    fn from(matrix: &Matrix) -> Self {
+        Self {
+            a: matrix.a,
+            b: matrix.b,
+            c: matrix.c,
+            d: matrix.d,
+            tx: matrix.tx.to_pixels(),
+            ty: matrix.ty.to_pixels(),
+        }
+    }
+}
+
+/// The color transform of a display object, relative to its parent.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ColorTransformInfo {
+    pub r_mult: f32,
+    pub g_mult: f32,
+    pub b_mult: f32,
+    pub a_mult: f32,
+    pub r_add: i16,
+    pub g_add: i16,
+    pub b_add: i16,
+    pub a_add: i16,
+}
+
+impl From<&ColorTransform> for ColorTransformInfo {
+    fn from(color_transform: &ColorTransform) -> Self {
+        Self {
+            r_mult: color_transform.r_mult.to_f32(),
+            g_mult: color_transform.g_mult.to_f32(),
+            b_mult: color_transform.b_mult.to_f32(),
+            a_mult: color_transform.a_mult.to_f32(),
+            r_add: color_transform.r_add,
+            g_add: color_transform.g_add,
+            b_add: color_transform.b_add,
+            a_add: color_transform.a_add,
+        }
+    }
+}
+
+/// A bounding box, in stage pixels.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct BoundsInfo {
+    pub x_min: f64,
+    pub y_min: f64,
+    pub x_max: f64,
+    pub y_max: f64,
+}
+
+impl BoundsInfo {
+    fn from_bounding_box(bounds: &BoundingBox) -> Option<Self> {
+        if bounds.valid {
+            Some(Self {
+                x_min: bounds.x_min.to_pixels(),
+                y_min: bounds.y_min.to_pixels(),
+                x_max: bounds.x_max.to_pixels(),
+                y_max: bounds.y_max.to_pixels(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// The ActionScript object linked to a display object.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum ObjectLink {
+    /// An AVM1 object, addressed by its target path.
+    Avm1 { path: String },
+
+    /// An AVM2 object, described by the name of its class.
+    Avm2 { class_name: String },
+}
+
+impl DisplayObjectInfo {
+    /// Takes a snapshot of the given display object and all of its children.
+    pub(crate) fn from_display_object(object: DisplayObject<'_>) -> Self {
+        let children = object
+            .as_container()
+            .map(|container| {
+                container
+                    .iter_render_list()
+                    .map(Self::from_display_object)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            depth: object.depth(),
+            name: object.name().to_string(),
+            character_id: object.id(),
+            kind: display_object_kind(object),
+            matrix: MatrixInfo::from(&*object.matrix()),
+            color_transform: ColorTransformInfo::from(&*object.color_transform()),
+            bounds: BoundsInfo::from_bounding_box(&object.world_bounds()),
+            visible: object.visible(),
+            clip_depth: object.clip_depth(),
+            current_frame: object.as_movie_clip().map(|clip| clip.current_frame()),
+            object: object_link(object),
+            children,
+        }
+    }
+}
+
+fn display_object_kind(object: DisplayObject<'_>) -> &'static str {
+    match object {
+        DisplayObject::Stage(_) => "Stage",
+        DisplayObject::Bitmap(_) => "Bitmap",
+        DisplayObject::Avm1Button(_) => "Avm1Button",
+        DisplayObject::Avm2Button(_) => "Avm2Button",
+        DisplayObject::EditText(_) => "EditText",
+        DisplayObject::Graphic(_) => "Graphic",
+        DisplayObject::MorphShape(_) => "MorphShape",
+        DisplayObject::MovieClip(_) => "MovieClip",
+        DisplayObject::Text(_) => "Text",
+        DisplayObject::Video(_) => "Video",
+    }
+}
+
+fn object_link(object: DisplayObject<'_>) -> Option<ObjectLink> {
+    if let Avm2Value::Object(avm2_object) = object.object2() {
+        let class_name = avm2_object
+            .as_class()
+            .map(|class| class.read().name().to_qualified_name())
+            .unwrap_or_else(|| "Object".to_string());
+        Some(ObjectLink::Avm2 { class_name })
+    } else if let Avm1Value::Object(_) = object.object() {
+        Some(ObjectLink::Avm1 {
+            path: object.path(),
+        })
+    } else {
+        None
+    }
+}
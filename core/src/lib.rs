@@ -31,17 +31,21 @@ mod character;
 pub mod color_transform;
 pub mod context;
 pub mod context_menu;
+pub mod debugger;
 mod drawing;
 mod ecma_conversions;
 pub mod events;
 pub mod focus_tracker;
 mod font;
 mod html;
+pub mod inspector;
 mod library;
 pub mod loader;
 pub mod matrix;
+pub mod profiler;
 mod player;
 mod prelude;
+pub mod recording;
 pub mod shape_utils;
 pub mod string_utils;
 pub mod tag_utils;
@@ -1,3 +1,4 @@
+use crate::avm1::bytecode::DecodedActions;
 use crate::avm1::function::FunctionObject;
 use crate::avm1::property_map::PropertyMap as Avm1PropertyMap;
 use crate::avm2::{Domain as Avm2Domain, Object as Avm2Object};
@@ -6,7 +7,7 @@ use crate::character::Character;
 use crate::display_object::{Bitmap, Graphic, MorphShape, TDisplayObject, Text};
 use crate::font::{Font, FontDescriptor};
 use crate::prelude::*;
-use crate::tag_utils::SwfMovie;
+use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::vminterface::AvmType;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use std::collections::HashMap;
@@ -144,6 +145,10 @@ pub struct MovieLibrary<'gc> {
     /// Shared reference to the constructor registry used for this movie.
     /// Should be `None` if this is an AVM2 movie.
     avm1_constructor_registry: Option<Gc<'gc, Avm1ConstructorRegistry<'gc>>>,
+
+    /// AVM1 action lists of this movie that have already been decoded, keyed
+    /// by their start and end offsets and the SWF version they run with.
+    avm1_actions: HashMap<(usize, usize, u8), Gc<'gc, DecodedActions<'gc>>>,
 }
 
 impl<'gc> MovieLibrary<'gc> {
@@ -156,6 +161,7 @@ impl<'gc> MovieLibrary<'gc> {
             avm_type,
             avm2_domain: None,
             avm1_constructor_registry: None,
+            avm1_actions: HashMap::new(),
         }
     }
 
@@ -209,6 +215,25 @@ impl<'gc> MovieLibrary<'gc> {
         self.avm1_constructor_registry
     }
 
+    /// Retrieve the decoded form of an AVM1 action list of this movie,
+    /// decoding it if it has not been run before.
+    pub fn avm1_actions(
+        &mut self,
+        code: &SwfSlice,
+        swf_version: u8,
+        gc_context: MutationContext<'gc, '_>,
+    ) -> Gc<'gc, DecodedActions<'gc>> {
+        *self
+            .avm1_actions
+            .entry((code.start, code.end, swf_version))
+            .or_insert_with(|| {
+                Gc::allocate(
+                    gc_context,
+                    DecodedActions::decode(code, swf_version, gc_context),
+                )
+            })
+    }
+
     /// Instantiates the library item with the given character ID into a display object.
     /// The object must then be post-instantiated before being used.
     pub fn instantiate_by_id(
@@ -3,9 +3,10 @@
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::{Avm1, AvmString, Object, TObject, Value};
 use crate::avm2::{Activation as Avm2Activation, Domain as Avm2Domain};
-use crate::backend::navigator::OwnedFuture;
-use crate::context::{ActionQueue, ActionType};
-use crate::display_object::{DisplayObject, MorphShape, TDisplayObject};
+use crate::backend::navigator::{OwnedFuture, ResponseBody};
+use crate::backend::render::{determine_jpeg_tag_format, JpegTagFormat};
+use crate::context::{ActionQueue, ActionType, UpdateContext};
+use crate::display_object::{Bitmap, DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::player::{Player, NEWEST_PLAYER_VERSION};
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
@@ -15,6 +16,7 @@ use gc_arena::{Collect, CollectionContext};
 use generational_arena::{Arena, Index};
 use std::string::FromUtf8Error;
 use std::sync::{Arc, Mutex, Weak};
+use swf::SwfDecompressor;
 use thiserror::Error;
 use url::form_urlencoded;
 
@@ -46,6 +48,9 @@ pub enum Error {
     #[error("Invalid SWF")]
     InvalidSwf(#[from] crate::tag_utils::Error),
 
+    #[error("Invalid image: {0}")]
+    InvalidImage(String),
+
     #[error("Invalid XML encoding")]
     InvalidXmlEncoding(#[from] FromUtf8Error),
 
@@ -124,7 +129,7 @@ impl<'gc> LoadManager<'gc> {
     pub fn load_root_movie(
         &mut self,
         player: Weak<Mutex<Player>>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Box<dyn ResponseBody>, Error>,
         url: String,
         parameters: Vec<(String, String)>,
         on_metadata: Box<dyn FnOnce(&swf::HeaderExt)>,
@@ -145,7 +150,7 @@ impl<'gc> LoadManager<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         target_clip: DisplayObject<'gc>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Box<dyn ResponseBody>, Error>,
         url: String,
         loader_url: Option<String>,
         target_broadcaster: Option<Object<'gc>>,
@@ -264,6 +269,8 @@ impl<'gc> Default for LoadManager<'gc> {
 pub enum LoaderStatus {
     /// The movie hasn't been loaded yet.
     Pending,
+    /// The movie is still downloading, and its frames are preloaded as they arrive.
+    Parsing,
     /// The movie loaded successfully.
     Succeeded,
     /// An error occurred while loading the movie.
@@ -363,7 +370,7 @@ impl<'gc> Loader<'gc> {
     pub fn root_movie_loader(
         &mut self,
         player: Weak<Mutex<Player>>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Box<dyn ResponseBody>, Error>,
         mut url: String,
         parameters: Vec<(String, String)>,
         on_metadata: Box<dyn FnOnce(&swf::HeaderExt)>,
@@ -389,17 +396,30 @@ impl<'gc> Loader<'gc> {
                     Ok(())
                 })?;
 
-            let data = (fetch.await).and_then(|data| {
-                Ok((
-                    data.len(),
-                    SwfMovie::from_data(&data, Some(url.clone()), None)?,
-                ))
-            });
-
-            if let Ok((_length, mut movie)) = data {
-                on_metadata(movie.header());
-                movie.append_parameters(parameters);
-                player.lock().unwrap().set_root_movie(Arc::new(movie));
+            let result = async {
+                let body = fetch.await?;
+                stream_movie(
+                    body,
+                    Vec::new(),
+                    Some(url.clone()),
+                    None,
+                    |mut movie| {
+                        on_metadata(movie.header());
+                        movie.append_parameters(parameters);
+                        let movie = Arc::new(movie);
+                        player.lock().unwrap().set_root_movie(movie.clone());
+                        Ok(movie)
+                    },
+                    |_movie| {
+                        player.lock().unwrap().preload_root_movie();
+                        Ok(())
+                    },
+                )
+                .await
+            }
+            .await;
+
+            if result.is_ok() {
                 Ok(())
             } else {
                 player
@@ -422,7 +442,7 @@ impl<'gc> Loader<'gc> {
     pub fn movie_loader(
         &mut self,
         player: Weak<Mutex<Player>>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Box<dyn ResponseBody>, Error>,
         mut url: String,
         loader_url: Option<String>,
     ) -> OwnedFuture<(), Error> {
@@ -451,7 +471,7 @@ impl<'gc> Loader<'gc> {
                             ..
                         }) => (*target_clip, *target_broadcaster),
                         None => return Err(Error::Cancelled),
-                        _ => unreachable!(),
+                        _ => return Err(Error::NotMovieLoader),
                     };
 
                     replacing_root_movie = DisplayObject::ptr_eq(clip, uc.stage.root_clip());
@@ -476,141 +496,298 @@ impl<'gc> Loader<'gc> {
                     Ok(())
                 })?;
 
-            let data = (fetch.await).and_then(|data| {
-                Ok((
-                    data.len(),
-                    SwfMovie::from_data(&data, Some(url.clone()), loader_url.clone())?,
-                ))
-            });
-            if let Ok((length, movie)) = data {
-                let movie = Arc::new(movie);
+            // The number of bytes last reported by `onLoadProgress`.
+            let mut bytes_reported = None;
+
+            let result = async {
+                let mut body = fetch.await?;
+
+                // Read enough of the body to tell images apart from movies.
+                let mut data = Vec::new();
+                while data.len() < 8 {
+                    match body.next_chunk().await? {
+                        Some(chunk) => data.extend(chunk),
+                        None => break,
+                    }
+                }
+
+                // JPEG, PNG and GIF images are loaded as a bitmap inside the clip.
+                if determine_jpeg_tag_format(&data) != JpegTagFormat::Unknown {
+                    while let Some(chunk) = body.next_chunk().await? {
+                        data.extend(chunk);
+                    }
+                    return Ok(Some(data));
+                }
+
                 if replacing_root_movie {
-                    player.lock().unwrap().set_root_movie(movie);
-                    return Ok(());
+                    stream_movie(
+                        body,
+                        data,
+                        Some(url.clone()),
+                        loader_url.clone(),
+                        |movie| {
+                            let movie = Arc::new(movie);
+                            player.lock().unwrap().set_root_movie(movie.clone());
+                            Ok(movie)
+                        },
+                        |_movie| {
+                            player.lock().unwrap().preload_root_movie();
+                            Ok(())
+                        },
+                    )
+                    .await?;
+                    return Ok(None);
                 }
 
-                player
+                stream_movie(
+                    body,
+                    data,
+                    Some(url.clone()),
+                    loader_url.clone(),
+                    |movie| {
+                        let movie = Arc::new(movie);
+                        player
+                            .lock()
+                            .expect("Could not lock player!!")
+                            .update(|uc| Loader::movie_header_loaded(uc, handle, movie.clone()))?;
+                        Ok(movie)
+                    },
+                    |movie| {
+                        player
+                            .lock()
+                            .expect("Could not lock player!!")
+                            .update(|uc| {
+                                Loader::movie_data_loaded(uc, handle, movie, &mut bytes_reported)
+                            })
+                    },
+                )
+                .await?;
+                Ok::<_, Error>(None)
+            }
+            .await;
+
+            match result {
+                Ok(Some(image)) => player
                     .lock()
                     .expect("Could not lock player!!")
-                    .update(|uc| {
-                        let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
-                            Some(Loader::Movie {
-                                target_clip,
-                                target_broadcaster,
-                                ..
-                            }) => (*target_clip, *target_broadcaster),
-                            None => return Err(Error::Cancelled),
-                            _ => unreachable!(),
-                        };
-
-                        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
-                        let parent_domain = activation.avm2().global_domain();
-                        let domain = Avm2Domain::movie_domain(&mut activation, parent_domain);
-                        uc.library
-                            .library_for_movie_mut(movie.clone())
-                            .set_avm2_domain(domain);
-
-                        if let Some(broadcaster) = broadcaster {
-                            Avm1::run_stack_frame_for_method(
-                                clip,
-                                broadcaster,
-                                NEWEST_PLAYER_VERSION,
-                                uc,
-                                "broadcastMessage",
-                                &[
-                                    "onLoadProgress".into(),
-                                    Value::Object(broadcaster),
-                                    length.into(),
-                                    length.into(),
-                                ],
-                            );
-                        }
-
-                        let mut mc = clip
-                            .as_movie_clip()
-                            .expect("Attempted to load movie into not movie clip");
-
-                        mc.replace_with_movie(uc.gc_context, Some(movie.clone()));
-                        mc.post_instantiation(uc, clip, None, Instantiator::Movie, false);
-
-                        let mut morph_shapes = fnv::FnvHashMap::default();
-                        mc.preload(uc, &mut morph_shapes);
-
-                        // Finalize morph shapes.
-                        for (id, static_data) in morph_shapes {
-                            let morph_shape = MorphShape::new(uc.gc_context, static_data);
-                            uc.library
-                                .library_for_movie_mut(movie.clone())
-                                .register_character(
-                                    id,
-                                    crate::character::Character::MorphShape(morph_shape),
-                                );
-                        }
-
-                        if let Some(broadcaster) = broadcaster {
-                            Avm1::run_stack_frame_for_method(
-                                clip,
-                                broadcaster,
-                                NEWEST_PLAYER_VERSION,
-                                uc,
-                                "broadcastMessage",
-                                &["onLoadComplete".into(), Value::Object(broadcaster)],
-                            );
-                        }
-
-                        if let Some(Loader::Movie { loader_status, .. }) =
-                            uc.load_manager.get_loader_mut(handle)
-                        {
-                            *loader_status = LoaderStatus::Succeeded;
-                        };
-
-                        Ok(())
-                    })
-            } else {
+                    .update(|uc| Loader::image_loaded(uc, handle, &image)),
+                Ok(None) => Ok(()),
                 //TODO: Inspect the fetch error.
                 //This requires cooperation from the backend to send abstract
                 //error types we can actually inspect.
                 //This also can get errors from decoding an invalid SWF file,
                 //too. We should distinguish those to player code.
-                player
+                Err(_) => player
                     .lock()
                     .expect("Could not lock player!!")
-                    .update(|uc| -> Result<(), Error> {
-                        let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
-                            Some(Loader::Movie {
-                                target_clip,
-                                target_broadcaster,
-                                ..
-                            }) => (*target_clip, *target_broadcaster),
-                            None => return Err(Error::Cancelled),
-                            _ => unreachable!(),
-                        };
+                    .update(|uc| Loader::movie_load_failed(uc, handle)),
+            }
+        })
+    }
 
-                        if let Some(broadcaster) = broadcaster {
-                            Avm1::run_stack_frame_for_method(
-                                clip,
-                                broadcaster,
-                                NEWEST_PLAYER_VERSION,
-                                uc,
-                                "broadcastMessage",
-                                &[
-                                    "onLoadError".into(),
-                                    Value::Object(broadcaster),
-                                    "LoadNeverCompleted".into(),
-                                ],
-                            );
-                        }
-
-                        if let Some(Loader::Movie { loader_status, .. }) =
-                            uc.load_manager.get_loader_mut(handle)
-                        {
-                            *loader_status = LoaderStatus::Failed;
-                        };
+    /// Place a movie whose header has been downloaded into the clip of a
+    /// movie loader.
+    ///
+    /// The movie starts playing as soon as its first frame has arrived.
+    fn movie_header_loaded(
+        uc: &mut UpdateContext<'_, 'gc, '_>,
+        handle: Handle,
+        movie: Arc<SwfMovie>,
+    ) -> Result<(), Error> {
+        let clip = match uc.load_manager.get_loader(handle) {
+            Some(Loader::Movie { target_clip, .. }) => *target_clip,
+            None => return Err(Error::Cancelled),
+            _ => return Err(Error::NotMovieLoader),
+        };
 
-                        Ok(())
-                    })
+        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+        let parent_domain = activation.avm2().global_domain();
+        let domain = Avm2Domain::movie_domain(&mut activation, parent_domain);
+        uc.library
+            .library_for_movie_mut(movie.clone())
+            .set_avm2_domain(domain);
+
+        let mut mc = clip
+            .as_movie_clip()
+            .expect("Attempted to load movie into not movie clip");
+
+        mc.replace_with_movie(uc.gc_context, Some(movie));
+        mc.post_instantiation(uc, clip, None, Instantiator::Movie, false);
+
+        if let Some(Loader::Movie { loader_status, .. }) = uc.load_manager.get_loader_mut(handle) {
+            *loader_status = LoaderStatus::Parsing;
+        };
+
+        Ok(())
+    }
+
+    /// Preload the part of a movie that has been downloaded since the movie
+    /// loader last saw it.
+    ///
+    /// Fires `onLoadProgress` whenever more of the movie has arrived, and
+    /// `onLoadComplete` once the whole movie has been loaded.
+    fn movie_data_loaded(
+        uc: &mut UpdateContext<'_, 'gc, '_>,
+        handle: Handle,
+        movie: &SwfMovie,
+        bytes_reported: &mut Option<u32>,
+    ) -> Result<(), Error> {
+        let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
+            Some(Loader::Movie {
+                target_clip,
+                target_broadcaster,
+                ..
+            }) => (*target_clip, *target_broadcaster),
+            None => return Err(Error::Cancelled),
+            _ => return Err(Error::NotMovieLoader),
+        };
+
+        let mc = clip
+            .as_movie_clip()
+            .expect("Attempted to load movie into not movie clip");
+        mc.preload(uc);
+
+        // Match `getBytesLoaded` and `getBytesTotal`, which count the whole uncompressed SWF.
+        let bytes_loaded = movie.uncompressed_loaded();
+        if let Some(broadcaster) = broadcaster {
+            if *bytes_reported != Some(bytes_loaded) {
+                Avm1::run_stack_frame_for_method(
+                    clip,
+                    broadcaster,
+                    NEWEST_PLAYER_VERSION,
+                    uc,
+                    "broadcastMessage",
+                    &[
+                        "onLoadProgress".into(),
+                        Value::Object(broadcaster),
+                        bytes_loaded.into(),
+                        movie.uncompressed_len().into(),
+                    ],
+                );
             }
-        })
+        }
+        *bytes_reported = Some(bytes_loaded);
+
+        if !movie.is_loaded() {
+            return Ok(());
+        }
+
+        if let Some(broadcaster) = broadcaster {
+            Avm1::run_stack_frame_for_method(
+                clip,
+                broadcaster,
+                NEWEST_PLAYER_VERSION,
+                uc,
+                "broadcastMessage",
+                &["onLoadComplete".into(), Value::Object(broadcaster)],
+            );
+        }
+
+        if let Some(Loader::Movie { loader_status, .. }) = uc.load_manager.get_loader_mut(handle) {
+            *loader_status = LoaderStatus::Succeeded;
+        };
+
+        // If the clip already ran its first frame while the rest of the movie was
+        // downloading, it won't fire another load event; finish the load now.
+        if mc.current_frame() > 0 {
+            let clip_object = match clip.object() {
+                Value::Object(object) => Some(object),
+                _ => None,
+            };
+            uc.load_manager
+                .movie_clip_on_load(clip, clip_object, uc.action_queue);
+        }
+
+        Ok(())
+    }
+
+    /// Report the failure of a movie loader to its broadcaster.
+    fn movie_load_failed(uc: &mut UpdateContext<'_, 'gc, '_>, handle: Handle) -> Result<(), Error> {
+        let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
+            Some(Loader::Movie {
+                target_clip,
+                target_broadcaster,
+                ..
+            }) => (*target_clip, *target_broadcaster),
+            None => return Err(Error::Cancelled),
+            _ => return Err(Error::NotMovieLoader),
+        };
+
+        if let Some(broadcaster) = broadcaster {
+            Avm1::run_stack_frame_for_method(
+                clip,
+                broadcaster,
+                NEWEST_PLAYER_VERSION,
+                uc,
+                "broadcastMessage",
+                &[
+                    "onLoadError".into(),
+                    Value::Object(broadcaster),
+                    "LoadNeverCompleted".into(),
+                ],
+            );
+        }
+
+        if let Some(Loader::Movie { loader_status, .. }) = uc.load_manager.get_loader_mut(handle) {
+            *loader_status = LoaderStatus::Failed;
+        };
+
+        Ok(())
+    }
+
+    /// Place an image that has been fetched by a movie loader into its clip,
+    /// completing the load.
+    fn image_loaded(
+        uc: &mut UpdateContext<'_, 'gc, '_>,
+        handle: Handle,
+        data: &[u8],
+    ) -> Result<(), Error> {
+        let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
+            Some(Loader::Movie {
+                target_clip,
+                target_broadcaster,
+                ..
+            }) => (*target_clip, *target_broadcaster),
+            None => return Err(Error::Cancelled),
+            _ => return Err(Error::NotMovieLoader),
+        };
+
+        let bitmap = uc
+            .renderer
+            .register_bitmap_jpeg_2(data)
+            .map_err(|e| Error::InvalidImage(e.to_string()))?;
+        let bitmap = Bitmap::new(uc, 0, bitmap.handle, bitmap.width, bitmap.height);
+
+        let mc = clip
+            .as_movie_clip()
+            .expect("Attempted to load image into not movie clip");
+        mc.replace_at_depth(uc, bitmap.into(), 1);
+        bitmap.post_instantiation(uc, bitmap.into(), None, Instantiator::Movie, false);
+
+        if let Some(broadcaster) = broadcaster {
+            Avm1::run_stack_frame_for_method(
+                clip,
+                broadcaster,
+                NEWEST_PLAYER_VERSION,
+                uc,
+                "broadcastMessage",
+                &["onLoadComplete".into(), Value::Object(broadcaster)],
+            );
+        }
+
+        if let Some(Loader::Movie { loader_status, .. }) = uc.load_manager.get_loader_mut(handle) {
+            *loader_status = LoaderStatus::Succeeded;
+        };
+
+        // An image has no frames to run, so it is initialized straight away.
+        let clip_object = match clip.object() {
+            Value::Object(object) => Some(object),
+            _ => None,
+        };
+        uc.load_manager
+            .movie_clip_on_load(clip, clip_object, uc.action_queue);
+
+        Ok(())
     }
 
     pub fn form_loader(
@@ -740,7 +917,7 @@ impl<'gc> Loader<'gc> {
         }
 
         match loader_status {
-            LoaderStatus::Pending => false,
+            LoaderStatus::Pending | LoaderStatus::Parsing => false,
             LoaderStatus::Failed => true,
             LoaderStatus::Succeeded => {
                 if let Some(broadcaster) = broadcaster {
@@ -859,3 +1036,72 @@ impl<'gc> Loader<'gc> {
         })
     }
 }
+
+/// Download a movie, decompressing it as it arrives.
+///
+/// `data` holds the start of the movie, if it has already been downloaded.
+/// Once the SWF header has been decompressed, the movie is constructed and
+/// handed to `on_header`, which shares it. From then on, `on_data` is called
+/// every time more of the movie has been added to it, for the last time once
+/// the whole movie has been loaded.
+async fn stream_movie(
+    mut body: Box<dyn ResponseBody>,
+    data: Vec<u8>,
+    url: Option<String>,
+    loader_url: Option<String>,
+    on_header: impl FnOnce(SwfMovie) -> Result<Arc<SwfMovie>, Error>,
+    mut on_data: impl FnMut(&SwfMovie) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let compressed_len = body.total_len();
+    let mut compressed_loaded = 0;
+    let mut decompressor = SwfDecompressor::new();
+    let mut on_header = Some(on_header);
+    let mut movie = None;
+    let mut chunk = Some(data);
+
+    loop {
+        match &chunk {
+            Some(chunk) => {
+                compressed_loaded += chunk.len();
+                decompressor
+                    .write(chunk)
+                    .map_err(|e| Error::InvalidSwf(e.into()))?;
+            }
+            None => decompressor
+                .finish()
+                .map_err(|e| Error::InvalidSwf(e.into()))?,
+        }
+
+        if let (None, Some(header), Some(tag_stream_len)) =
+            (&movie, decompressor.header(), decompressor.tag_stream_len())
+        {
+            let movie_header = SwfMovie::from_header(
+                header.clone(),
+                tag_stream_len,
+                compressed_len,
+                url.clone(),
+                loader_url.clone(),
+            );
+            if let Some(on_header) = on_header.take() {
+                movie = Some(on_header(movie_header)?);
+            }
+        }
+
+        match &movie {
+            Some(movie) => {
+                movie.append_data(&decompressor.take_data(), compressed_loaded);
+                if chunk.is_none() {
+                    movie.finish_loading();
+                }
+                on_data(movie)?;
+            }
+            None if chunk.is_none() => return Err(Error::InvalidSwf("Invalid SWF".into())),
+            None => (),
+        }
+
+        if chunk.is_none() {
+            return Ok(());
+        }
+        chunk = body.next_chunk().await?;
+    }
+}
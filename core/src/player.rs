@@ -4,12 +4,16 @@ use crate::avm1::globals::system::SystemProperties;
 use crate::avm1::object::Object;
 use crate::avm1::property::Attribute;
 use crate::avm1::{Avm1, AvmString, ScriptObject, TObject, Timers, Value};
-use crate::avm2::{Activation as Avm2Activation, Avm2, Domain as Avm2Domain};
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, Domain as Avm2Domain, Event as Avm2Event,
+    Value as Avm2Value,
+};
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
+    debugger::DebuggerBackend,
     locale::LocaleBackend,
     log::LogBackend,
-    navigator::{NavigatorBackend, RequestOptions},
+    navigator::{NavigatorBackend, NullNavigatorBackend, RequestOptions},
     render::RenderBackend,
     storage::StorageBackend,
     ui::{MouseCursor, UiBackend},
@@ -18,14 +22,20 @@ use crate::backend::{
 use crate::config::Letterbox;
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{ContextMenuCallback, ContextMenuItem, ContextMenuState};
-use crate::display_object::{EditText, MorphShape, MovieClip, Stage};
+use crate::debugger::Debugger;
+use crate::display_object::{EditText, MovieClip, Stage};
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, PlayerEvent};
 use crate::external::Value as ExternalValue;
 use crate::external::{ExternalInterface, ExternalInterfaceProvider};
 use crate::focus_tracker::FocusTracker;
+use crate::inspector::DisplayObjectInfo;
 use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::prelude::*;
+use crate::profiler::{ProfileCategory, Profiler};
+use crate::recording::{
+    RecordedInput, Recording, ReplayClock, ReplayLocaleBackend, ReplayNavigatorBackend,
+};
 use crate::tag_utils::SwfMovie;
 use crate::transform::TransformStack;
 use crate::vminterface::{AvmType, Instantiator};
@@ -44,6 +54,10 @@ pub static DEVICE_FONT_TAG: &[u8] = include_bytes!("../assets/noto-sans-definefo
 /// `player_version`.
 pub const NEWEST_PLAYER_VERSION: u8 = 32;
 
+/// The longest time between two clicks on the same object that still counts
+/// as a double click.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
 #[derive(Collect)]
 #[collect(no_drop)]
 struct GcRoot<'gc>(GcCell<'gc, GcRootData<'gc>>);
@@ -65,6 +79,9 @@ struct GcRootData<'gc> {
     /// If the mouse is down, the display object that the mouse is currently pressing.
     mouse_pressed_object: Option<DisplayObject<'gc>>,
 
+    /// The last click of the mouse, used to detect double clicks.
+    last_click: Option<MouseClick<'gc>>,
+
     /// The object being dragged via a `startDrag` action.
     drag_object: Option<DragObject<'gc>>,
 
@@ -171,6 +188,10 @@ pub struct Player {
     is_playing: bool,
     needs_render: bool,
 
+    /// How many bytes of the root movie its `LoaderInfo` has reported with
+    /// `progress` events, or `None` once it has fired `complete`.
+    root_bytes_reported: Option<usize>,
+
     renderer: Renderer,
     audio: Audio,
     navigator: Navigator,
@@ -195,6 +216,19 @@ pub struct Player {
     frame_accumulator: f64,
     recent_run_frame_timings: VecDeque<f64>,
 
+    /// Whether frame pacing ignores how long frames actually took to run.
+    /// Recorded sessions rely on this to replay identically.
+    is_deterministic: bool,
+
+    /// The session currently being recorded, if any.
+    recording: Option<RecordingSession>,
+
+    /// The step debugger, which is only active while a client is attached.
+    debugger: Debugger,
+
+    /// Times scripts, rendering and tessellation while enabled.
+    profiler: Profiler,
+
     /// Faked time passage for fooling hand-written busy-loop FPS limiters.
     time_offset: u32,
 
@@ -228,6 +262,17 @@ pub struct Player {
     current_frame: Option<u16>,
 }
 
+/// A session being recorded, along with the locale backend it replaced.
+struct RecordingSession {
+    recording: Recording,
+
+    /// Drives the navigator and locale clocks while recording.
+    clock: ReplayClock,
+
+    /// The locale backend in use before recording started.
+    locale: Locale,
+}
+
 #[allow(clippy::too_many_arguments)]
 impl Player {
     #[allow(clippy::too_many_arguments)]
@@ -270,6 +315,7 @@ impl Player {
                         stage: Stage::empty(gc_context, movie_width, movie_height),
                         mouse_hovered_object: None,
                         mouse_pressed_object: None,
+                        last_click: None,
                         drag_object: None,
                         avm1: Avm1::new(gc_context, NEWEST_PLAYER_VERSION),
                         avm2: Avm2::new(gc_context),
@@ -289,6 +335,10 @@ impl Player {
             frame_rate,
             frame_accumulator: 0.0,
             recent_run_frame_timings: VecDeque::with_capacity(10),
+            is_deterministic: false,
+            recording: None,
+            debugger: Debugger::new(),
+            profiler: Profiler::new(),
             time_offset: 0,
 
             mouse_pos: (Twips::ZERO, Twips::ZERO),
@@ -305,6 +355,7 @@ impl Player {
             self_reference: None,
             system: SystemProperties::default(),
             instance_counter: 0,
+            root_bytes_reported: None,
             time_til_next_timer: None,
             storage,
             max_execution_duration: Duration::from_secs(max_execution_duration),
@@ -353,7 +404,9 @@ impl Player {
         on_metadata: Box<dyn FnOnce(&swf::HeaderExt)>,
     ) {
         self.mutate_with_update_context(|context| {
-            let fetch = context.navigator.fetch(movie_url, RequestOptions::get());
+            let fetch = context
+                .navigator
+                .fetch_body(movie_url, RequestOptions::get());
             let process = context.load_manager.load_root_movie(
                 context.player.clone().unwrap(),
                 fetch,
@@ -382,6 +435,7 @@ impl Player {
         self.frame_rate = movie.frame_rate().into();
         self.swf = movie;
         self.instance_counter = 0;
+        self.root_bytes_reported = Some(0);
 
         self.mutate_with_update_context(|context| {
             context.stage.set_movie_size(
@@ -424,6 +478,7 @@ impl Player {
             root.post_instantiation(context, root, flashvars, Instantiator::Movie, false);
             root.set_default_root_name(context);
             context.stage.replace_at_depth(context, root, 0);
+            context.stage.set_root_loader_info(context.gc_context, None);
 
             // Load and parse the device font.
             if context.library.device_font().is_none() {
@@ -456,7 +511,10 @@ impl Player {
             stage.build_matrices(&mut activation.context);
         });
 
-        self.preload();
+        self.preload_root_movie();
+        if self.swf.avm_type() == AvmType::Avm2 && self.warn_on_unsupported_content {
+            self.ui.display_unsupported_message();
+        }
         self.audio.set_frame_rate(self.frame_rate);
     }
 
@@ -476,8 +534,8 @@ impl Player {
     fn max_frames_per_tick(&self) -> u32 {
         const MAX_FRAMES_PER_TICK: u32 = 5;
 
-        if self.recent_run_frame_timings.is_empty() {
-            5
+        if self.is_deterministic || self.recent_run_frame_timings.is_empty() {
+            MAX_FRAMES_PER_TICK
         } else {
             let frame_time = 1000.0 / self.frame_rate;
             let average_run_frame_time = self.recent_run_frame_timings.iter().sum::<f64>()
@@ -496,6 +554,13 @@ impl Player {
     }
 
     pub fn tick(&mut self, dt: f64) {
+        if let Some(session) = &mut self.recording {
+            session.clock.advance(dt);
+            session.recording.push(RecordedInput::Tick { dt });
+        }
+
+        self.debugger.poll();
+
         // Don't run until preloading is complete.
         // TODO: Eventually we want to stream content similar to the Flash player.
         if !self.audio.is_loading_complete() {
@@ -547,6 +612,103 @@ impl Player {
         }
     }
 
+    /// Starts recording all input given to this player, so that the session can
+    /// later be reproduced with a `Replayer`.
+    ///
+    /// This reseeds the random number generator and makes frame pacing
+    /// deterministic, so it should be called before the movie starts playing.
+    ///
+    /// While recording, the navigator and locale clocks only advance with the
+    /// deltas passed to `tick`, exactly as they will when the session is
+    /// replayed. `navigator_mut` returns a `ReplayNavigatorBackend` wrapping
+    /// the original navigator until recording stops.
+    pub fn start_recording(&mut self) {
+        if self.recording.is_some() {
+            return;
+        }
+
+        let start_time = self.locale.get_current_date_time();
+        let timezone = self.locale.get_timezone();
+        let seed = start_time.timestamp_millis() as u64;
+        self.set_random_seed(seed);
+        self.set_deterministic(true);
+
+        let clock = ReplayClock::new();
+        let navigator =
+            std::mem::replace(&mut self.navigator, Box::new(NullNavigatorBackend::new()));
+        self.navigator = Box::new(ReplayNavigatorBackend::new(navigator, clock.clone()));
+        let locale = std::mem::replace(
+            &mut self.locale,
+            Box::new(ReplayLocaleBackend::new(
+                start_time,
+                timezone,
+                clock.clone(),
+            )),
+        );
+
+        self.recording = Some(RecordingSession {
+            recording: Recording::new(seed, start_time, timezone),
+            clock,
+            locale,
+        });
+    }
+
+    /// Stops recording and returns everything recorded since `start_recording`.
+    ///
+    /// The navigator and locale backends are restored to the ones the player
+    /// had before recording started.
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        let session = self.recording.take()?;
+
+        let navigator =
+            std::mem::replace(&mut self.navigator, Box::new(NullNavigatorBackend::new()));
+        self.navigator = match navigator.downcast::<ReplayNavigatorBackend>() {
+            Ok(navigator) => navigator.into_inner(),
+            Err(navigator) => navigator,
+        };
+        self.locale = session.locale;
+
+        Some(session.recording)
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Connects a debugger client to this player.
+    ///
+    /// Breakpoints can be set once the client is attached. Whenever execution
+    /// stops, the player blocks until the client resumes it.
+    pub fn attach_debugger(&mut self, backend: Box<dyn DebuggerBackend>) {
+        self.debugger.attach(backend);
+    }
+
+    /// Disconnects the current debugger client, if any.
+    pub fn detach_debugger(&mut self) -> Option<Box<dyn DebuggerBackend>> {
+        self.debugger.detach()
+    }
+
+    /// The profiler, which must be enabled before it records anything.
+    pub fn profiler(&self) -> &Profiler {
+        &self.profiler
+    }
+
+    pub fn profiler_mut(&mut self) -> &mut Profiler {
+        &mut self.profiler
+    }
+
+    /// Reseeds the random number generator used by `Math.random`.
+    pub fn set_random_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    /// Sets whether the number of frames run per tick should only depend on
+    /// the tick delta, rather than also on how long previous frames took to run.
+    pub fn set_deterministic(&mut self, is_deterministic: bool) {
+        self.is_deterministic = is_deterministic;
+        self.recent_run_frame_timings.clear();
+    }
+
     /// Returns the approximate duration of time until the next frame is due to run.
     /// This is only an approximation to be used for sleep durations.
     pub fn time_til_next_frame(&self) -> std::time::Duration {
@@ -773,6 +935,10 @@ impl Player {
     }
 
     pub fn handle_event(&mut self, event: PlayerEvent) {
+        if let Some(session) = &mut self.recording {
+            session.recording.push(RecordedInput::Event(event.clone()));
+        }
+
         if cfg!(feature = "avm_debug") {
             if let PlayerEvent::KeyDown {
                 key_code: KeyCode::V,
@@ -881,6 +1047,38 @@ impl Player {
             });
         }
 
+        // Text fields handle editing shortcuts, such as copy and paste.
+        // Ctrl+Alt is left alone, as some keyboard layouts use it to type characters.
+        if let PlayerEvent::KeyDown { key_code } = event {
+            if self.ui.is_key_down(KeyCode::Control) && !self.ui.is_key_down(KeyCode::Alt) {
+                self.mutate_with_update_context(|context| {
+                    if let Some(text) = context.focus_tracker.get().and_then(|o| o.as_edit_text()) {
+                        text.handle_shortcut(context, key_code);
+                    }
+                });
+            }
+        }
+
+        // Tab moves the focus between tab-enabled objects,
+        // and Enter or Space activates a button focused this way.
+        if !key_press_handled {
+            if let PlayerEvent::KeyDown { key_code } = event {
+                let reverse = self.ui.is_key_down(KeyCode::Shift);
+                self.mutate_with_update_context(|context| {
+                    let tracker = context.focus_tracker;
+                    match key_code {
+                        KeyCode::Tab => tracker.cycle(context, reverse),
+                        KeyCode::Return | KeyCode::Space if tracker.is_highlighted() => {
+                            if let Some(focus) = tracker.get() {
+                                Self::activate(context, focus);
+                            }
+                        }
+                        _ => {}
+                    }
+                });
+            }
+        }
+
         // keyPress events take precedence over text input.
         if !key_press_handled {
             if let PlayerEvent::TextInput { codepoint } = event {
@@ -892,6 +1090,29 @@ impl Player {
             }
         }
 
+        // Text being composed with an IME is shown in the focused text field until it is committed.
+        if matches!(
+            event,
+            PlayerEvent::ImeCompositionStart
+                | PlayerEvent::ImeCompositionUpdate { .. }
+                | PlayerEvent::ImeCompositionCommit { .. }
+        ) {
+            self.mutate_with_update_context(|context| {
+                if let Some(text) = context.focus_tracker.get().and_then(|o| o.as_edit_text()) {
+                    match &event {
+                        PlayerEvent::ImeCompositionStart => text.start_ime_composition(context),
+                        PlayerEvent::ImeCompositionUpdate { text: composition } => {
+                            text.set_ime_composition(composition, context);
+                        }
+                        PlayerEvent::ImeCompositionCommit { text: composition } => {
+                            text.commit_ime_composition(composition, context)
+                        }
+                        _ => {}
+                    }
+                }
+            });
+        }
+
         // Propagate clip events.
         self.mutate_with_update_context(|context| {
             let (clip_event, listener) = match event {
@@ -1003,6 +1224,7 @@ impl Player {
         // Update mouse state based on event type.
         let mut is_mouse_down = self.is_mouse_down;
         let mut new_mouse_pos = None;
+        let mut wheel_delta = None;
         match event {
             Some(&PlayerEvent::MouseMove { x, y }) => {
                 new_mouse_pos = Some((x, y));
@@ -1015,6 +1237,9 @@ impl Player {
                 new_mouse_pos = Some((x, y));
                 is_mouse_down = false;
             }
+            Some(&PlayerEvent::MouseWheel { delta }) => {
+                wheel_delta = Some(delta);
+            }
             // Explicity requested an update.
             None => (),
             // Don't care about non-mouse events.
@@ -1041,6 +1266,10 @@ impl Player {
 
             let mut events: smallvec::SmallVec<[(DisplayObject<'_>, ClipEvent); 2]> =
                 Default::default();
+            // AVM2 mouse events, along with the related object of hover events.
+            // Presses and releases that don't hit any object are sent to the stage.
+            let mut avm2_events = Vec::new();
+            let stage_object: DisplayObject<'_> = context.stage.into();
 
             // Cancel hover if an object is removed from the stage.
             if let Some(hovered) = context.mouse_over_object {
@@ -1057,6 +1286,9 @@ impl Player {
             let cur_over_object = context.mouse_over_object;
             // Check if a new object has been hovered over.
             if !DisplayObject::option_ptr_eq(cur_over_object, new_over_object) {
+                // AVM2 objects see hover changes even while the mouse button is down.
+                Self::avm2_hover_events(cur_over_object, new_over_object, &mut avm2_events);
+
                 // If the mouse button is down, the object the user clicked on grabs the focus
                 // and fires "drag" events. Other objects are ignroed.
                 if is_mouse_down {
@@ -1099,6 +1331,8 @@ impl Player {
                         events.push((over_object, ClipEvent::Press));
                         context.mouse_down_object = context.mouse_over_object;
                     }
+                    let target = context.mouse_over_object.unwrap_or(stage_object);
+                    avm2_events.push((target, "mouseDown", None));
                 } else {
                     let released_inside = DisplayObject::option_ptr_eq(
                         context.mouse_down_object,
@@ -1109,11 +1343,16 @@ impl Player {
                         if let Some(down_object) = context.mouse_down_object {
                             events.push((down_object, ClipEvent::Release));
                         }
+                        let target = context.mouse_down_object.unwrap_or(stage_object);
+                        avm2_events.push((target, "mouseUp", None));
+                        avm2_events.push((target, Self::click_event_type(context, target), None));
                     } else {
                         // Released outside the clicked object.
                         if let Some(down_object) = context.mouse_down_object {
                             events.push((down_object, ClipEvent::ReleaseOutside));
                         }
+                        let target = context.mouse_over_object.unwrap_or(stage_object);
+                        avm2_events.push((target, "mouseUp", None));
                         // The new object is rolled over immediately.
                         if let Some(over_object) = context.mouse_over_object {
                             new_cursor = over_object.mouse_cursor();
@@ -1126,15 +1365,53 @@ impl Player {
                 }
             }
 
+            // Mouse moves and wheel scrolls are sent to the hovered object.
+            let hover_target = context.mouse_over_object.unwrap_or(stage_object);
+            if new_mouse_pos.is_some() && !is_mouse_button_changed {
+                avm2_events.push((hover_target, "mouseMove", None));
+            }
+            if wheel_delta.is_some() {
+                avm2_events.push((hover_target, "mouseWheel", None));
+            }
+
+            // Dragging with the mouse down selects text in the clicked text field.
+            let mut is_selecting_text = false;
+            if is_mouse_down && !is_mouse_button_changed {
+                if let Some(text) = context.mouse_down_object.and_then(|o| o.as_edit_text()) {
+                    text.drag_selection(context);
+                    is_selecting_text = true;
+                }
+            }
+
+            // Only display objects with an AVM2 side receive AVM2 events.
+            avm2_events.retain(|(object, _, _)| matches!(object.object2(), Avm2Value::Object(_)));
+
             // Fire any pending mouse events.
-            let needs_render = if events.is_empty() {
-                false
+            let needs_render = if events.is_empty() && avm2_events.is_empty() {
+                is_selecting_text
             } else {
                 for (object, event) in events {
                     if !object.removed() {
                         object.handle_clip_event(context, event);
                     }
                 }
+                let delta = wheel_delta.map_or(0, |delta| delta.lines() as i32);
+                for (object, event_type, related_object) in avm2_events {
+                    if let Err(e) = Avm2::dispatch_mouse_event(
+                        context,
+                        event_type,
+                        object,
+                        related_object,
+                        is_mouse_down,
+                        delta,
+                    ) {
+                        log::error!(
+                            "Encountered AVM2 error when dispatching {}: {}",
+                            event_type,
+                            e
+                        );
+                    }
+                }
                 true
             };
             Self::run_actions(context);
@@ -1150,34 +1427,157 @@ impl Player {
         needs_render
     }
 
-    /// Preload the first movie in the player.
+    /// Collects the AVM2 events fired when the mouse moves from hovering over
+    /// `from` to hovering over `to`.
+    ///
+    /// `mouseOut` and `mouseOver` are sent to the objects themselves and bubble.
+    /// Every object that the mouse leaves or enters, including ancestors not
+    /// shared by both objects, receives its own `rollOut` or `rollOver`.
+    #[allow(clippy::type_complexity)]
+    fn avm2_hover_events<'gc>(
+        from: Option<DisplayObject<'gc>>,
+        to: Option<DisplayObject<'gc>>,
+        events: &mut Vec<(DisplayObject<'gc>, &'static str, Option<DisplayObject<'gc>>)>,
+    ) {
+        // The stage is always under the mouse, so it is left out.
+        let ancestors = |object: Option<DisplayObject<'gc>>| {
+            let mut ancestors = Vec::new();
+            let mut node = object.filter(|o| o.as_stage().is_none());
+            while let Some(object) = node {
+                ancestors.push(object);
+                node = object.parent().filter(|o| o.as_stage().is_none());
+            }
+            ancestors
+        };
+        let from_ancestors = ancestors(from);
+        let to_ancestors = ancestors(to);
+        let contains = |ancestors: &[DisplayObject<'gc>], object: DisplayObject<'gc>| {
+            ancestors.iter().any(|o| DisplayObject::ptr_eq(*o, object))
+        };
+
+        if let Some(from) = from {
+            events.push((from, "mouseOut", to));
+        }
+        for &object in &from_ancestors {
+            if !contains(&to_ancestors, object) {
+                events.push((object, "rollOut", to));
+            }
+        }
+        for &object in to_ancestors.iter().rev() {
+            if !contains(&from_ancestors, object) {
+                events.push((object, "rollOver", from));
+            }
+        }
+        if let Some(to) = to {
+            events.push((to, "mouseOver", from));
+        }
+    }
+
+    /// Activates an object focused with the keyboard, as if it had been
+    /// clicked where the mouse is.
+    fn activate<'gc>(context: &mut UpdateContext<'_, 'gc, '_>, object: DisplayObject<'gc>) {
+        object.handle_clip_event(context, ClipEvent::Press);
+        object.handle_clip_event(context, ClipEvent::Release);
+
+        for &(event_type, button_down) in
+            &[("mouseDown", true), ("mouseUp", false), ("click", false)]
+        {
+            if let Err(e) =
+                Avm2::dispatch_mouse_event(context, event_type, object, None, button_down, 0)
+            {
+                log::error!(
+                    "Encountered AVM2 error when dispatching {}: {}",
+                    event_type,
+                    e
+                );
+            }
+        }
+    }
+
+    /// Determines whether releasing the mouse on `object` is a single click or
+    /// the second click of a double click, and records the click.
+    ///
+    /// Double clicks are only reported to objects with `doubleClickEnabled` set.
+    fn click_event_type<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: DisplayObject<'gc>,
+    ) -> &'static str {
+        let time = context.navigator.time_since_launch();
+        let is_double_click = object.double_click_enabled()
+            && context.last_click.map_or(false, |click| {
+                DisplayObject::ptr_eq(click.display_object, object)
+                    && time
+                        .checked_sub(click.time)
+                        .map_or(false, |elapsed| elapsed <= DOUBLE_CLICK_INTERVAL)
+            });
+
+        if is_double_click {
+            // A third click starts a new double click.
+            context.last_click = None;
+            "doubleClick"
+        } else {
+            context.last_click = Some(MouseClick {
+                display_object: object,
+                time,
+            });
+            "click"
+        }
+    }
+
+    /// Preload the part of the root movie that has been loaded so far.
     ///
-    /// This should only be called once. Further movie loads should preload the
-    /// specific `MovieClip` referenced.
-    fn preload(&mut self) {
+    /// The root movie loader calls this again every time more of the movie
+    /// has downloaded. Frames become playable as soon as they're preloaded.
+    pub fn preload_root_movie(&mut self) {
         self.mutate_with_update_context(|context| {
-            let mut morph_shapes = fnv::FnvHashMap::default();
             let root = context.stage.root_clip();
-            root.as_movie_clip()
-                .unwrap()
-                .preload(context, &mut morph_shapes);
+            root.as_movie_clip().unwrap().preload(context);
+        });
+    }
 
-            let lib = context
-                .library
-                .library_for_movie_mut(root.as_movie_clip().unwrap().movie().unwrap());
+    /// Fires `progress` on the root movie's `LoaderInfo` if more of the movie
+    /// has downloaded since it was last reported, and `complete` once the
+    /// whole movie has been loaded.
+    fn dispatch_root_load_events<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        bytes_reported: &mut Option<usize>,
+    ) {
+        let last_bytes_reported = match *bytes_reported {
+            Some(bytes) => bytes,
+            None => return,
+        };
+        let bytes_loaded = context.swf.compressed_loaded();
+        let is_loaded = context.swf.is_loaded();
+        *bytes_reported = if is_loaded { None } else { Some(bytes_loaded) };
+
+        // Nothing can be listening if the `LoaderInfo` was never accessed.
+        let loader_info = match context.stage.root_loader_info() {
+            Some(loader_info) => loader_info,
+            None => return,
+        };
 
-            // Finalize morph shapes.
-            for (id, static_data) in morph_shapes {
-                let morph_shape = MorphShape::new(context.gc_context, static_data);
-                lib.register_character(id, crate::character::Character::MorphShape(morph_shape));
+        if bytes_loaded > last_bytes_reported {
+            let bytes_total = context.swf.compressed_len();
+            if let Err(e) = Avm2::dispatch_progress_event(
+                context,
+                "progress",
+                loader_info,
+                bytes_loaded,
+                bytes_total,
+            ) {
+                log::error!("Unhandled AVM2 exception in event handler: {}", e);
+            }
+        }
+        if is_loaded {
+            if let Err(e) = Avm2::dispatch_event(context, Avm2Event::new("complete"), loader_info) {
+                log::error!("Unhandled AVM2 exception in event handler: {}", e);
             }
-        });
-        if self.swf.avm_type() == AvmType::Avm2 && self.warn_on_unsupported_content {
-            self.ui.display_unsupported_message();
         }
     }
 
     pub fn run_frame(&mut self) {
+        let span = self.profiler.start();
+        let mut root_bytes_reported = self.root_bytes_reported;
         self.update(|context| {
             let stage = context.stage;
             match context.swf.avm_type() {
@@ -1201,16 +1601,26 @@ impl Player {
                     stage.frame_constructed(context);
                     stage.run_frame_avm2(context);
                     stage.run_frame_scripts(context);
+
+                    Self::dispatch_root_load_events(context, &mut root_bytes_reported);
                 }
             }
             context.update_sounds();
         });
+        self.root_bytes_reported = root_bytes_reported;
         self.needs_render = true;
+        self.profiler
+            .finish(span, ProfileCategory::Frame, || "run_frame");
     }
 
     pub fn render(&mut self) {
-        let (renderer, ui, transform_stack) =
-            (&mut self.renderer, &mut self.ui, &mut self.transform_stack);
+        let span = self.profiler.start();
+        let (renderer, ui, transform_stack, profiler) = (
+            &mut self.renderer,
+            &mut self.ui,
+            &mut self.transform_stack,
+            &mut self.profiler,
+        );
 
         self.gc_arena.mutate(|_gc_context, gc_root| {
             let root_data = gc_root.0.read();
@@ -1220,14 +1630,18 @@ impl Player {
                 library: &root_data.library,
                 transform_stack,
                 stage: root_data.stage,
+                focus_tracker: root_data.focus_tracker,
                 clip_depth_stack: vec![],
                 allow_mask: true,
+                profiler,
             };
 
             root_data.stage.render(&mut render_context);
         });
 
         self.needs_render = false;
+        self.profiler
+            .finish(span, ProfileCategory::Render, || "render");
     }
 
     /// The current frame of the main timeline, if available.
@@ -1236,6 +1650,13 @@ impl Player {
         self.current_frame
     }
 
+    /// Takes a snapshot of the entire display list, starting at the stage.
+    pub fn inspect_display_list(&mut self) -> DisplayObjectInfo {
+        self.mutate_with_update_context(|context| {
+            DisplayObjectInfo::from_display_object(context.stage.into())
+        })
+    }
+
     pub fn audio(&self) -> &Audio {
         &self.audio
     }
@@ -1257,6 +1678,14 @@ impl Player {
         &mut self.renderer
     }
 
+    pub fn navigator(&self) -> &Navigator {
+        &self.navigator
+    }
+
+    pub fn navigator_mut(&mut self) -> &mut Navigator {
+        &mut self.navigator
+    }
+
     pub fn storage(&self) -> &Storage {
         &self.storage
     }
@@ -1282,6 +1711,8 @@ impl Player {
     }
 
     pub fn run_actions<'gc>(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let span = context.profiler.start();
+
         // Note that actions can queue further actions, so a while loop is necessary here.
         while let Some(actions) = context.action_queue.pop_action() {
             // We don't run frame actions if the clip was removed after it queued the action.
@@ -1400,6 +1831,10 @@ impl Player {
                 }
             }
         }
+
+        context
+            .profiler
+            .finish(span, ProfileCategory::Actions, || "run_actions");
     }
 
     /// Runs the closure `f` with an `UpdateContext`.
@@ -1431,6 +1866,8 @@ impl Player {
             current_frame,
             time_offset,
             frame_rate,
+            debugger,
+            profiler,
         ) = (
             self.player_version,
             &self.swf,
@@ -1452,12 +1889,15 @@ impl Player {
             &mut self.current_frame,
             &mut self.time_offset,
             &mut self.frame_rate,
+            &mut self.debugger,
+            &mut self.profiler,
         );
 
         self.gc_arena.mutate(|gc_context, gc_root| {
             let mut root_data = gc_root.0.write(gc_context);
             let mouse_hovered_object = root_data.mouse_hovered_object;
             let mouse_pressed_object = root_data.mouse_pressed_object;
+            let last_click = root_data.last_click;
             let focus_tracker = root_data.focus_tracker;
             let (
                 stage,
@@ -1489,6 +1929,7 @@ impl Player {
                 stage,
                 mouse_over_object: mouse_hovered_object,
                 mouse_down_object: mouse_pressed_object,
+                last_click,
                 mouse_position,
                 drag_object,
                 player,
@@ -1514,6 +1955,8 @@ impl Player {
                 time_offset,
                 audio_manager,
                 frame_rate,
+                debugger,
+                profiler,
             };
 
             let old_frame_rate = *update_context.frame_rate;
@@ -1539,6 +1982,7 @@ impl Player {
             let mouse_pressed_object = update_context.mouse_down_object;
             root_data.mouse_hovered_object = mouse_hovered_object;
             root_data.mouse_pressed_object = mouse_pressed_object;
+            root_data.last_click = update_context.last_click;
 
             ret
         })
@@ -1588,7 +2032,10 @@ impl Player {
         self.update_mouse_state(None);
 
         // GC
+        let span = self.profiler.start();
         self.gc_arena.collect_debt();
+        self.profiler
+            .finish(span, ProfileCategory::GarbageCollection, || "collect_debt");
 
         rval
     }
@@ -1664,3 +2111,14 @@ pub struct DragObject<'gc> {
     #[collect(require_static)]
     pub constraint: BoundingBox,
 }
+
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct MouseClick<'gc> {
+    /// The display object that was clicked.
+    pub display_object: DisplayObject<'gc>,
+
+    /// When the click happened, relative to the launch of the player.
+    #[collect(require_static)]
+    pub time: Duration,
+}
@@ -0,0 +1,409 @@
+//! Per-frame profiling of scripts, rendering and tessellation.
+//!
+//! The profiler is off by default. Once enabled, it records a timed span for
+//! every frame, action run, ActionScript function call, shape tessellation,
+//! render and garbage collection. Spans are always aggregated into totals per
+//! category and name. They can optionally also be kept as a timeline, which can
+//! be exported in the Chrome trace event format (viewable in `about:tracing`
+//! or Perfetto).
+
+use instant::Instant;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+use std::rc::Rc;
+use std::time::Duration;
+
+/// The most spans kept on the timeline. Later spans still count towards the
+/// totals, but are left off the timeline.
+pub const MAX_TIMELINE_EVENTS: usize = 1_000_000;
+
+/// The kind of work measured by a profiling span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ProfileCategory {
+    /// A call to `Player::run_frame`.
+    Frame,
+
+    /// A call to `Player::run_actions`, which runs all queued scripts.
+    Actions,
+
+    /// A call to an AVM1 function, named after the function.
+    Avm1Function,
+
+    /// A call to an AVM2 method, named after its class and method.
+    Avm2Function,
+
+    /// Tessellation of a shape by `RenderBackend::register_shape`.
+    Tessellation,
+
+    /// A call to `Player::render`.
+    Render,
+
+    /// A garbage collection pass.
+    GarbageCollection,
+}
+
+impl ProfileCategory {
+    pub fn name(self) -> &'static str {
+        match self {
+            ProfileCategory::Frame => "frame",
+            ProfileCategory::Actions => "actions",
+            ProfileCategory::Avm1Function => "avm1",
+            ProfileCategory::Avm2Function => "avm2",
+            ProfileCategory::Tessellation => "tessellation",
+            ProfileCategory::Render => "render",
+            ProfileCategory::GarbageCollection => "gc",
+        }
+    }
+}
+
+/// The start of a span being measured, returned by `Profiler::start`.
+///
+/// This is empty when the profiler is disabled, so unprofiled code doesn't
+/// pay for reading the clock.
+#[derive(Debug, Clone, Copy)]
+#[must_use]
+pub struct Span(Option<Instant>);
+
+/// A single measured span on the timeline.
+#[derive(Debug, Clone)]
+pub struct ProfileEvent {
+    pub category: ProfileCategory,
+    pub name: Rc<str>,
+
+    /// The start of the span, relative to when profiling was enabled.
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+/// The totals of all spans with the same category and name.
+#[derive(Debug, Clone)]
+pub struct ProfileEntry {
+    pub category: ProfileCategory,
+    pub name: Rc<str>,
+    pub calls: u32,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Totals {
+    calls: u32,
+    total: Duration,
+    max: Duration,
+}
+
+#[derive(Debug)]
+pub struct Profiler {
+    /// The time at which profiling was enabled, or `None` if it is disabled.
+    epoch: Option<Instant>,
+
+    /// Whether spans are kept on the timeline, rather than only counted
+    /// towards the totals.
+    timeline: bool,
+
+    /// The most spans kept on the timeline.
+    max_events: usize,
+
+    /// Every distinct span name, so that each name is only allocated once.
+    names: HashSet<Rc<str>>,
+
+    events: Vec<ProfileEvent>,
+
+    /// The number of spans left off the timeline because it was full.
+    dropped_events: usize,
+
+    totals: HashMap<(ProfileCategory, Rc<str>), Totals>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self {
+            epoch: None,
+            timeline: false,
+            max_events: MAX_TIMELINE_EVENTS,
+            names: HashSet::new(),
+            events: Vec::new(),
+            dropped_events: 0,
+            totals: HashMap::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.epoch.is_some()
+    }
+
+    /// Starts recording spans, discarding any previous results.
+    ///
+    /// If `timeline` is set, individual spans are kept for
+    /// `write_chrome_trace`, up to `MAX_TIMELINE_EVENTS` of them. Otherwise
+    /// only the totals are kept.
+    pub fn enable(&mut self, timeline: bool) {
+        self.clear();
+        self.timeline = timeline;
+        self.epoch = Some(Instant::now());
+    }
+
+    /// Stops recording spans. Results recorded so far are kept.
+    pub fn disable(&mut self) {
+        self.epoch = None;
+    }
+
+    pub fn clear(&mut self) {
+        self.names.clear();
+        self.events.clear();
+        self.dropped_events = 0;
+        self.totals.clear();
+    }
+
+    /// Starts measuring a span, to be ended by `Profiler::finish`.
+    pub fn start(&self) -> Span {
+        Span(self.epoch.map(|_| Instant::now()))
+    }
+
+    /// Ends a span started by `Profiler::start` and records it.
+    ///
+    /// The name is only built if the span is actually recorded.
+    pub fn finish<N: AsRef<str>>(
+        &mut self,
+        span: Span,
+        category: ProfileCategory,
+        name: impl FnOnce() -> N,
+    ) {
+        let (epoch, start) = match (self.epoch, span.0) {
+            (Some(epoch), Some(start)) => (epoch, start),
+            _ => return,
+        };
+        let duration = start.elapsed();
+        let name = self.intern(name().as_ref());
+        // A span started before profiling was last re-enabled begins at the epoch.
+        let start = if start > epoch {
+            start.duration_since(epoch)
+        } else {
+            Duration::default()
+        };
+
+        let totals = self
+            .totals
+            .entry((category, name.clone()))
+            .or_insert_with(Totals::default);
+        totals.calls += 1;
+        totals.total += duration;
+        totals.max = totals.max.max(duration);
+
+        if !self.timeline {
+            return;
+        }
+
+        if self.events.len() < self.max_events {
+            self.events.push(ProfileEvent {
+                category,
+                name,
+                start,
+                duration,
+            });
+        } else {
+            self.dropped_events += 1;
+        }
+    }
+
+    /// Returns the shared copy of a span name.
+    fn intern(&mut self, name: &str) -> Rc<str> {
+        if let Some(name) = self.names.get(name) {
+            return name.clone();
+        }
+
+        let name: Rc<str> = Rc::from(name);
+        self.names.insert(name.clone());
+        name
+    }
+
+    /// All spans recorded on the timeline, in the order they ended.
+    pub fn events(&self) -> &[ProfileEvent] {
+        &self.events
+    }
+
+    /// The number of spans left off the timeline because it was full.
+    pub fn dropped_events(&self) -> usize {
+        self.dropped_events
+    }
+
+    /// The totals for every category and name, most expensive first.
+    pub fn summary(&self) -> Vec<ProfileEntry> {
+        let mut entries: Vec<_> = self
+            .totals
+            .iter()
+            .map(|((category, name), totals)| ProfileEntry {
+                category: *category,
+                name: name.clone(),
+                calls: totals.calls,
+                total: totals.total,
+                max: totals.max,
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            b.total
+                .cmp(&a.total)
+                .then_with(|| a.category.cmp(&b.category))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        entries
+    }
+
+    /// Writes all spans on the timeline as a Chrome trace event JSON file.
+    pub fn write_chrome_trace<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{{\"traceEvents\":[")?;
+        for (i, event) in self.events.iter().enumerate() {
+            if i > 0 {
+                writeln!(writer, ",")?;
+            }
+            write!(writer, "{{\"name\":")?;
+            write_json_string(&mut writer, &event.name)?;
+            write!(
+                writer,
+                ",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":1,\"tid\":1}}",
+                event.category.name(),
+                event.start.as_micros(),
+                event.duration.as_micros()
+            )?;
+        }
+        writeln!(writer, "\n],\"displayTimeUnit\":\"ms\"}}")
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Profiler::new()
+    }
+}
+
+fn write_json_string<W: Write>(writer: &mut W, value: &str) -> io::Result<()> {
+    write!(writer, "\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => write!(writer, "\\\"")?,
+            '\\' => write!(writer, "\\\\")?,
+            '\n' => write!(writer, "\\n")?,
+            '\r' => write!(writer, "\\r")?,
+            '\t' => write!(writer, "\\t")?,
+            c if (c as u32) < 0x20 => write!(writer, "\\u{:04x}", c as u32)?,
+            c => write!(writer, "{}", c)?,
+        }
+    }
+    write!(writer, "\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finish_all(profiler: &mut Profiler, spans: &[(ProfileCategory, &str)]) {
+        for (category, name) in spans {
+            let span = profiler.start();
+            profiler.finish(span, *category, || *name);
+        }
+    }
+
+    #[test]
+    fn aggregates_spans_by_category_and_name() {
+        let mut profiler = Profiler::new();
+        profiler.enable(true);
+        finish_all(
+            &mut profiler,
+            &[
+                (ProfileCategory::Avm1Function, "onEnterFrame"),
+                (ProfileCategory::Avm2Function, "onEnterFrame"),
+                (ProfileCategory::Avm1Function, "onEnterFrame"),
+                (ProfileCategory::Render, "render"),
+            ],
+        );
+
+        let mut summary: Vec<_> = profiler
+            .summary()
+            .into_iter()
+            .map(|entry| (entry.category, entry.name.to_string(), entry.calls))
+            .collect();
+        summary.sort();
+        assert_eq!(
+            summary,
+            [
+                (ProfileCategory::Avm1Function, "onEnterFrame".to_string(), 2),
+                (ProfileCategory::Avm2Function, "onEnterFrame".to_string(), 1),
+                (ProfileCategory::Render, "render".to_string(), 1),
+            ]
+        );
+
+        // Spans with the same name share a single copy of it.
+        let events = profiler.events();
+        assert_eq!(events.len(), 4);
+        assert!(Rc::ptr_eq(&events[0].name, &events[1].name));
+        assert!(Rc::ptr_eq(&events[0].name, &events[2].name));
+    }
+
+    #[test]
+    fn nothing_is_recorded_while_disabled() {
+        let mut profiler = Profiler::new();
+        let span = profiler.start();
+        profiler.enable(true);
+        profiler.finish(span, ProfileCategory::Frame, || "run_frame");
+        profiler.disable();
+        finish_all(&mut profiler, &[(ProfileCategory::Frame, "run_frame")]);
+
+        assert!(profiler.events().is_empty());
+        assert!(profiler.summary().is_empty());
+    }
+
+    #[test]
+    fn timeline_is_optional_and_capped() {
+        let spans = [(ProfileCategory::Frame, "run_frame"); 3];
+
+        let mut profiler = Profiler::new();
+        profiler.enable(false);
+        finish_all(&mut profiler, &spans);
+        assert!(profiler.events().is_empty());
+        assert_eq!(profiler.summary()[0].calls, 3);
+
+        profiler.enable(true);
+        profiler.max_events = 2;
+        finish_all(&mut profiler, &spans);
+        assert_eq!(profiler.events().len(), 2);
+        assert_eq!(profiler.dropped_events(), 1);
+        assert_eq!(profiler.summary()[0].calls, 3);
+
+        // Re-enabling starts over.
+        profiler.enable(true);
+        assert!(profiler.events().is_empty());
+        assert_eq!(profiler.dropped_events(), 0);
+        assert!(profiler.summary().is_empty());
+    }
+
+    #[test]
+    fn chrome_trace_escapes_names() {
+        let mut profiler = Profiler::new();
+        profiler.events.push(ProfileEvent {
+            category: ProfileCategory::Avm2Function,
+            name: Rc::from("say \"hi\"\\\n\t\u{1}é"),
+            start: Duration::from_micros(1500),
+            duration: Duration::from_micros(250),
+        });
+        profiler.events.push(ProfileEvent {
+            category: ProfileCategory::Render,
+            name: Rc::from("render"),
+            start: Duration::from_millis(2),
+            duration: Duration::from_millis(1),
+        });
+
+        let mut trace = Vec::new();
+        profiler.write_chrome_trace(&mut trace).unwrap();
+        assert_eq!(
+            String::from_utf8(trace).unwrap(),
+            concat!(
+                "{\"traceEvents\":[\n",
+                "{\"name\":\"say \\\"hi\\\"\\\\\\n\\t\\u0001é\",\"cat\":\"avm2\",\"ph\":\"X\",",
+                "\"ts\":1500,\"dur\":250,\"pid\":1,\"tid\":1},\n",
+                "{\"name\":\"render\",\"cat\":\"render\",\"ph\":\"X\",",
+                "\"ts\":2000,\"dur\":1000,\"pid\":1,\"tid\":1}\n",
+                "],\"displayTimeUnit\":\"ms\"}\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,438 @@
+//! Recording and replaying of play sessions.
+//!
+//! A `Recording` captures everything that feeds into a running movie from the
+//! outside world: every `PlayerEvent` passed to `Player::handle_event`, the
+//! time deltas passed to `Player::tick`, the seed of the random number
+//! generator, and the wall clock at the start of the session.
+//!
+//! A `Replayer` feeds a recording back into a fresh player. During replay, time
+//! is only advanced by the recorded tick deltas, so `getTimer` and `Date` see
+//! the same clock on every run and the session reproduces frame-for-frame.
+
+use crate::backend::locale::LocaleBackend;
+use crate::backend::navigator::{
+    NavigationMethod, NavigatorBackend, OwnedFuture, RequestOptions, ResponseBody,
+};
+use crate::events::PlayerEvent;
+use crate::loader::Error;
+use crate::player::Player;
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use indexmap::IndexMap;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+use url::Url;
+
+/// A single input fed into the player during a recorded session.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RecordedInput {
+    /// A call to `Player::tick` with the given delta, in milliseconds.
+    Tick { dt: f64 },
+
+    /// A call to `Player::handle_event`.
+    Event(PlayerEvent),
+}
+
+/// A log of all input that was fed into a player during a session.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Recording {
+    /// The seed of the random number generator used by `Math.random`.
+    pub seed: u64,
+
+    /// The wall clock time at the start of the session, in milliseconds since
+    /// the Unix epoch.
+    pub start_time: i64,
+
+    /// The timezone offset of the session, in seconds east of UTC.
+    pub timezone_offset: i32,
+
+    /// Every input in the order it was given to the player.
+    pub inputs: Vec<RecordedInput>,
+}
+
+impl Recording {
+    pub fn new(seed: u64, start_time: DateTime<Utc>, timezone: FixedOffset) -> Self {
+        Self {
+            seed,
+            start_time: start_time.timestamp_millis(),
+            timezone_offset: timezone.local_minus_utc(),
+            inputs: Vec::new(),
+        }
+    }
+
+    /// Appends an input to the end of this recording.
+    pub fn push(&mut self, input: RecordedInput) {
+        self.inputs.push(input);
+    }
+
+    /// The wall clock time at the start of the session.
+    pub fn start_time(&self) -> DateTime<Utc> {
+        Utc.timestamp_millis(self.start_time)
+    }
+
+    /// The timezone of the session.
+    pub fn timezone(&self) -> FixedOffset {
+        FixedOffset::east(self.timezone_offset)
+    }
+}
+
+/// The clock of a session being replayed.
+///
+/// It is shared between the `Replayer` and the backends it hands out, and only
+/// moves forward when the replayer ticks the player.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayClock(Rc<Cell<Duration>>);
+
+impl ReplayClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The amount of time that has passed since the start of the session.
+    pub fn elapsed(&self) -> Duration {
+        self.0.get()
+    }
+
+    /// Moves the clock forward by the given delta, in milliseconds.
+    pub fn advance(&self, dt: f64) {
+        self.0
+            .set(self.0.get() + Duration::from_secs_f64(dt.max(0.0) / 1000.0));
+    }
+}
+
+/// Locale backend whose clock only advances with a `ReplayClock`.
+pub struct ReplayLocaleBackend {
+    start_time: DateTime<Utc>,
+    timezone: FixedOffset,
+    clock: ReplayClock,
+}
+
+impl ReplayLocaleBackend {
+    pub fn new(start_time: DateTime<Utc>, timezone: FixedOffset, clock: ReplayClock) -> Self {
+        Self {
+            start_time,
+            timezone,
+            clock,
+        }
+    }
+}
+
+impl LocaleBackend for ReplayLocaleBackend {
+    fn get_current_date_time(&self) -> DateTime<Utc> {
+        let elapsed = chrono::Duration::from_std(self.clock.elapsed())
+            .unwrap_or_else(|_| chrono::Duration::zero());
+        self.start_time + elapsed
+    }
+
+    fn get_timezone(&self) -> FixedOffset {
+        self.timezone
+    }
+}
+
+/// Navigator backend that reports time from a `ReplayClock`.
+///
+/// All other requests are forwarded to the wrapped backend.
+pub struct ReplayNavigatorBackend {
+    inner: Box<dyn NavigatorBackend>,
+    clock: ReplayClock,
+}
+
+impl ReplayNavigatorBackend {
+    pub fn new(inner: Box<dyn NavigatorBackend>, clock: ReplayClock) -> Self {
+        Self { inner, clock }
+    }
+
+    pub fn inner(&self) -> &dyn NavigatorBackend {
+        self.inner.as_ref()
+    }
+
+    pub fn inner_mut(&mut self) -> &mut dyn NavigatorBackend {
+        self.inner.as_mut()
+    }
+
+    /// Unwraps the navigator backend this one forwards to.
+    pub fn into_inner(self) -> Box<dyn NavigatorBackend> {
+        self.inner
+    }
+}
+
+impl NavigatorBackend for ReplayNavigatorBackend {
+    fn navigate_to_url(
+        &self,
+        url: String,
+        window: Option<String>,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        self.inner.navigate_to_url(url, window, vars_method)
+    }
+
+    fn fetch(&self, url: &str, request_options: RequestOptions) -> OwnedFuture<Vec<u8>, Error> {
+        self.inner.fetch(url, request_options)
+    }
+
+    fn fetch_body(
+        &self,
+        url: &str,
+        request_options: RequestOptions,
+    ) -> OwnedFuture<Box<dyn ResponseBody>, Error> {
+        self.inner.fetch_body(url, request_options)
+    }
+
+    fn time_since_launch(&mut self) -> Duration {
+        self.clock.elapsed()
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+        self.inner.spawn_future(future)
+    }
+
+    fn resolve_relative_url<'a>(&mut self, url: &'a str) -> Cow<'a, str> {
+        self.inner.resolve_relative_url(url)
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        self.inner.pre_process_url(url)
+    }
+}
+
+/// Feeds a `Recording` back into a player.
+///
+/// The player must be constructed with the backends returned by
+/// `Replayer::navigator` and `Replayer::locale`, and `Replayer::start` must be
+/// called before the movie starts playing.
+pub struct Replayer {
+    recording: Recording,
+    position: usize,
+    clock: ReplayClock,
+}
+
+impl Replayer {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            position: 0,
+            clock: ReplayClock::new(),
+        }
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    pub fn clock(&self) -> &ReplayClock {
+        &self.clock
+    }
+
+    /// Wraps a navigator backend so that it reports the replayed time.
+    pub fn navigator(&self, inner: Box<dyn NavigatorBackend>) -> ReplayNavigatorBackend {
+        ReplayNavigatorBackend::new(inner, self.clock.clone())
+    }
+
+    /// Creates a locale backend that reports the replayed wall clock.
+    pub fn locale(&self) -> ReplayLocaleBackend {
+        ReplayLocaleBackend::new(
+            self.recording.start_time(),
+            self.recording.timezone(),
+            self.clock.clone(),
+        )
+    }
+
+    /// Puts the player in the same state it was in when recording started.
+    pub fn start(&self, player: &mut Player) {
+        player.set_random_seed(self.recording.seed);
+        player.set_deterministic(true);
+    }
+
+    /// Whether every recorded input has been fed into the player.
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.recording.inputs.len()
+    }
+
+    /// Feeds the next recorded input into the player.
+    ///
+    /// Returns `false` once the recording has been exhausted.
+    pub fn step(&mut self, player: &mut Player) -> bool {
+        let input = match self.recording.inputs.get(self.position) {
+            Some(input) => input,
+            None => return false,
+        };
+        self.position += 1;
+
+        match input {
+            RecordedInput::Tick { dt } => {
+                self.clock.advance(*dt);
+                player.tick(*dt);
+            }
+            RecordedInput::Event(event) => player.handle_event(event.clone()),
+        }
+
+        true
+    }
+
+    /// Feeds all remaining recorded input into the player.
+    pub fn run(&mut self, player: &mut Player) {
+        while self.step(player) {}
+    }
+
+    /// Feeds recorded input into the player until the next tick would move
+    /// the session past `elapsed`, so that it plays back at its recorded pace.
+    pub fn run_until(&mut self, player: &mut Player, elapsed: Duration) {
+        loop {
+            match self.recording.inputs.get(self.position) {
+                Some(RecordedInput::Tick { dt })
+                    if self.clock.elapsed() + Duration::from_secs_f64(dt.max(0.0) / 1000.0)
+                        > elapsed =>
+                {
+                    break
+                }
+                Some(_) => {
+                    self.step(player);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::audio::NullAudioBackend;
+    use crate::backend::log::NullLogBackend;
+    use crate::backend::navigator::NullNavigatorBackend;
+    use crate::backend::render::NullRenderer;
+    use crate::backend::storage::MemoryStorageBackend;
+    use crate::backend::ui::NullUiBackend;
+    use crate::backend::video::NullVideoBackend;
+    use std::sync::{Arc, Mutex};
+
+    /// Locale backend that follows the real wall clock.
+    struct WallClockLocaleBackend;
+
+    impl LocaleBackend for WallClockLocaleBackend {
+        fn get_current_date_time(&self) -> DateTime<Utc> {
+            Utc::now()
+        }
+
+        fn get_timezone(&self) -> FixedOffset {
+            FixedOffset::east(3600)
+        }
+    }
+
+    fn new_player(
+        navigator: Box<dyn NavigatorBackend>,
+        locale: Box<dyn LocaleBackend>,
+    ) -> Arc<Mutex<Player>> {
+        Player::new(
+            Box::new(NullRenderer::new()),
+            Box::new(NullAudioBackend::new()),
+            navigator,
+            Box::new(MemoryStorageBackend::default()),
+            locale,
+            Box::new(NullVideoBackend::new()),
+            Box::new(NullLogBackend::new()),
+            Box::new(NullUiBackend::new()),
+        )
+        .unwrap()
+    }
+
+    /// The time the player's backends currently report.
+    fn observe(player: &mut Player) -> (Duration, DateTime<Utc>) {
+        (
+            player.navigator_mut().time_since_launch(),
+            player.locale().get_current_date_time(),
+        )
+    }
+
+    #[test]
+    fn record_and_replay_see_the_same_clock() {
+        let player = new_player(
+            Box::new(NullNavigatorBackend::new()),
+            Box::new(WallClockLocaleBackend),
+        );
+        let mut player = player.lock().unwrap();
+        player.start_recording();
+
+        let mut recorded_times = vec![];
+        for &dt in &[16.0, 33.5, 1000.0] {
+            player.tick(dt);
+            player.handle_event(PlayerEvent::MouseMove { x: dt, y: 10.0 });
+            // Let the wall clock run ahead of the recorded ticks.
+            std::thread::sleep(Duration::from_millis(5));
+            recorded_times.push(observe(&mut player));
+        }
+
+        let recording = player.stop_recording().unwrap();
+        assert_eq!(recording.inputs.len(), 6);
+        let ms = |dt: f64| Duration::from_secs_f64(dt / 1000.0);
+        assert_eq!(
+            recorded_times.iter().map(|(t, _)| *t).collect::<Vec<_>>(),
+            [
+                ms(16.0),
+                ms(16.0) + ms(33.5),
+                ms(16.0) + ms(33.5) + ms(1000.0)
+            ]
+        );
+
+        // Stopping the recording hands back the original backends.
+        assert!(player
+            .navigator_mut()
+            .downcast_mut::<NullNavigatorBackend>()
+            .is_some());
+        // The wall clock has only moved on by a few milliseconds, not the recorded second.
+        assert!(player.locale().get_current_date_time() < recorded_times[2].1);
+
+        let mut replayer = Replayer::new(recording.clone());
+        let replay_player = new_player(
+            Box::new(replayer.navigator(Box::new(NullNavigatorBackend::new()))),
+            Box::new(replayer.locale()),
+        );
+        let mut replay_player = replay_player.lock().unwrap();
+        replayer.start(&mut replay_player);
+        // Recording the replay must reproduce the original recording exactly.
+        replay_player.start_recording();
+
+        let mut replayed_times = vec![];
+        for input in recording.inputs.iter() {
+            assert!(replayer.step(&mut replay_player));
+            if let RecordedInput::Event(_) = input {
+                replayed_times.push(observe(&mut replay_player));
+            }
+        }
+        assert!(replayer.is_finished());
+        assert!(!replayer.step(&mut replay_player));
+
+        assert_eq!(replayed_times, recorded_times);
+        assert_eq!(replay_player.stop_recording(), Some(recording));
+    }
+
+    #[test]
+    fn replay_runs_at_the_recorded_pace() {
+        let mut recording = Recording::new(0, Utc.timestamp_millis(0), FixedOffset::east(0));
+        for &dt in &[16.0, 16.0, 16.0] {
+            recording.push(RecordedInput::Tick { dt });
+            recording.push(RecordedInput::Event(PlayerEvent::MouseLeft));
+        }
+
+        let mut replayer = Replayer::new(recording);
+        let player = new_player(
+            Box::new(replayer.navigator(Box::new(NullNavigatorBackend::new()))),
+            Box::new(replayer.locale()),
+        );
+        let mut player = player.lock().unwrap();
+        replayer.start(&mut player);
+
+        replayer.run_until(&mut player, Duration::from_millis(40));
+        assert_eq!(replayer.clock().elapsed(), Duration::from_millis(32));
+        assert!(!replayer.is_finished());
+
+        replayer.run_until(&mut player, Duration::from_secs(1));
+        assert_eq!(replayer.clock().elapsed(), Duration::from_millis(48));
+        assert!(replayer.is_finished());
+    }
+}
@@ -24,6 +24,120 @@ pub fn next_char_boundary(slice: &str, pos: usize) -> usize {
     }
 }
 
+/// The kinds of characters that separate words when moving by word.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Word,
+    Whitespace,
+    Punctuation,
+}
+
+fn char_class(c: char) -> CharClass {
+    if c.is_alphanumeric() || c == '_' {
+        CharClass::Word
+    } else if c.is_whitespace() {
+        CharClass::Whitespace
+    } else {
+        CharClass::Punctuation
+    }
+}
+
+/// Gets the byte position of the start of the word before `pos`,
+/// skipping any whitespace in between.
+/// `pos` must already lie on a char boundary
+pub fn prev_word_boundary(slice: &str, pos: usize) -> usize {
+    let mut start = pos;
+    let mut class = CharClass::Whitespace;
+    for (i, c) in slice[..pos].char_indices().rev() {
+        let c_class = char_class(c);
+        if c_class != class {
+            if class != CharClass::Whitespace {
+                break;
+            }
+            class = c_class;
+        }
+        start = i;
+    }
+    start
+}
+
+/// Gets the byte position of the start of the word after `pos`,
+/// skipping the rest of the current word and any whitespace after it.
+/// `pos` must already lie on a char boundary
+pub fn next_word_boundary(slice: &str, pos: usize) -> usize {
+    let mut chars = slice[pos..].char_indices();
+    let class = match chars.next() {
+        Some((_, c)) => char_class(c),
+        None => return slice.len(),
+    };
+    let mut in_word = class != CharClass::Whitespace;
+    for (i, c) in chars {
+        let c_class = char_class(c);
+        if in_word && c_class != class {
+            in_word = false;
+        }
+        if !in_word && c_class != CharClass::Whitespace {
+            return pos + i;
+        }
+    }
+    slice.len()
+}
+
+/// Gets the byte range of the word containing the char at `pos`,
+/// or of the run of whitespace or punctuation if it isn't part of a word.
+/// `pos` must already lie on a char boundary
+pub fn word_at(slice: &str, pos: usize) -> (usize, usize) {
+    let class = match slice[pos..]
+        .chars()
+        .next()
+        .or_else(|| slice[..pos].chars().next_back())
+    {
+        Some(c) => char_class(c),
+        None => return (pos, pos),
+    };
+    let start = slice[..pos]
+        .char_indices()
+        .rev()
+        .take_while(|&(_, c)| char_class(c) == class)
+        .last()
+        .map_or(pos, |(i, _)| i);
+    let end = slice[pos..]
+        .char_indices()
+        .find(|&(_, c)| char_class(c) != class)
+        .map_or(slice.len(), |(i, _)| pos + i);
+    (start, end)
+}
+
+/// Gets the UTF-16 index of the char at byte position `pos`, which is how
+/// ActionScript counts the chars in a string.
+pub fn byte_to_utf16_index(slice: &str, pos: usize) -> usize {
+    slice
+        .char_indices()
+        .take_while(|&(i, _)| i < pos)
+        .map(|(_, c)| c.len_utf16())
+        .sum()
+}
+
+/// Gets the byte position of the char at the UTF-16 index `index`.
+/// Returns `None` if `index` lies past the end of the string or in the middle
+/// of a surrogate pair.
+pub fn utf16_index_to_byte(slice: &str, index: usize) -> Option<usize> {
+    let mut utf16_index = 0;
+    for (i, c) in slice.char_indices() {
+        if utf16_index == index {
+            return Some(i);
+        } else if utf16_index > index {
+            return None;
+        }
+        utf16_index += c.len_utf16();
+    }
+    if utf16_index == index {
+        Some(slice.len())
+    } else {
+        None
+    }
+}
+
 /// Creates a `String` from an iterator of UTF-16 code units.
 /// TODO: Unpaired surrogates will get replaced with the Unicode replacement character.
 pub fn utf16_iter_to_string<I: Iterator<Item = u16>>(it: I) -> String {
@@ -1556,3 +1670,72 @@ static LOWERCASE_TABLE: &[(u16, u16)] = &[
     (65337, 65369),
     (65338, 65370),
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        byte_to_utf16_index, next_word_boundary, prev_word_boundary, utf16_index_to_byte, word_at,
+    };
+
+    #[test]
+    fn prev_word_boundaries() {
+        let text = "foo bar_1  baz.qux";
+        assert_eq!(prev_word_boundary(text, 18), 15);
+        assert_eq!(prev_word_boundary(text, 15), 14);
+        assert_eq!(prev_word_boundary(text, 14), 11);
+        assert_eq!(prev_word_boundary(text, 11), 4);
+        assert_eq!(prev_word_boundary(text, 6), 4);
+        assert_eq!(prev_word_boundary(text, 4), 0);
+        assert_eq!(prev_word_boundary(text, 0), 0);
+        assert_eq!(prev_word_boundary("  ", 2), 0);
+    }
+
+    #[test]
+    fn next_word_boundaries() {
+        let text = "foo bar_1  baz.qux";
+        assert_eq!(next_word_boundary(text, 0), 4);
+        assert_eq!(next_word_boundary(text, 1), 4);
+        assert_eq!(next_word_boundary(text, 3), 4);
+        assert_eq!(next_word_boundary(text, 4), 11);
+        assert_eq!(next_word_boundary(text, 11), 14);
+        assert_eq!(next_word_boundary(text, 14), 15);
+        assert_eq!(next_word_boundary(text, 15), 18);
+        assert_eq!(next_word_boundary(text, 18), 18);
+    }
+
+    #[test]
+    fn word_boundaries_are_char_boundaries() {
+        let text = "déjà vu";
+        assert_eq!(next_word_boundary(text, 0), 7);
+        assert_eq!(prev_word_boundary(text, 7), 0);
+        assert_eq!(prev_word_boundary(text, text.len()), 7);
+        assert_eq!(word_at(text, 3), (0, 6));
+    }
+
+    #[test]
+    fn words_at_positions() {
+        let text = "foo bar_1  baz.qux";
+        assert_eq!(word_at(text, 0), (0, 3));
+        assert_eq!(word_at(text, 2), (0, 3));
+        assert_eq!(word_at(text, 5), (4, 9));
+        assert_eq!(word_at(text, 9), (9, 11));
+        assert_eq!(word_at(text, 14), (14, 15));
+        // The end of the text selects the last word.
+        assert_eq!(word_at(text, 18), (15, 18));
+        assert_eq!(word_at("", 0), (0, 0));
+    }
+
+    #[test]
+    fn utf16_indices() {
+        // 'é' is two bytes and one UTF-16 unit; '😀' is four bytes and two units.
+        let text = "aé😀b";
+        let pairs = [(0, 0), (1, 1), (3, 2), (7, 4), (8, 5)];
+        for &(byte, utf16) in &pairs {
+            assert_eq!(byte_to_utf16_index(text, byte), utf16);
+            assert_eq!(utf16_index_to_byte(text, utf16), Some(byte));
+        }
+        assert_eq!(utf16_index_to_byte(text, 3), None);
+        assert_eq!(utf16_index_to_byte(text, 6), None);
+        assert_eq!(byte_to_utf16_index(text, 100), 5);
+    }
+}
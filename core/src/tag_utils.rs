@@ -2,23 +2,26 @@ use crate::backend::navigator::url_from_relative_path;
 use crate::vminterface::AvmType;
 use gc_arena::Collect;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use swf::{Fixed8, HeaderExt, Rectangle, TagCode, Twips};
 
 pub type Error = Box<dyn std::error::Error>;
 pub type DecodeResult = Result<(), Error>;
 pub type SwfStream<'a> = swf::read::Reader<'a>;
 
-/// An open, fully parsed SWF movie ready to play back, either in a Player or a
-/// MovieClip.
-#[derive(Debug, Clone, Collect)]
+/// An open SWF movie ready to play back, either in a Player or a MovieClip.
+///
+/// A movie may still be downloading: its tag stream then grows as the rest of
+/// the file arrives, until `finish_loading` is called.
+#[derive(Debug, Collect)]
 #[collect(require_static)]
 pub struct SwfMovie {
     /// The SWF header parsed from the data stream.
     header: HeaderExt,
 
     /// Uncompressed SWF data.
-    data: Vec<u8>,
+    data: MovieData,
 
     /// The URL the SWF was downloaded from.
     url: Option<String>,
@@ -34,7 +37,13 @@ pub struct SwfMovie {
     encoding: &'static swf::Encoding,
 
     /// The compressed length of the entire datastream
-    compressed_len: usize,
+    compressed_len: AtomicUsize,
+
+    /// The number of bytes of the compressed datastream that have been downloaded.
+    compressed_loaded: AtomicUsize,
+
+    /// Whether the entire datastream has been downloaded.
+    is_loaded: AtomicBool,
 }
 
 impl SwfMovie {
@@ -42,12 +51,14 @@ impl SwfMovie {
     pub fn empty(swf_version: u8) -> Self {
         Self {
             header: HeaderExt::default_with_swf_version(swf_version),
-            data: vec![],
+            data: MovieData::from(vec![]),
             url: None,
             loader_url: None,
             parameters: Vec::new(),
             encoding: swf::UTF_8,
-            compressed_len: 0,
+            compressed_len: AtomicUsize::new(0),
+            compressed_loaded: AtomicUsize::new(0),
+            is_loaded: AtomicBool::new(true),
         }
     }
 
@@ -74,15 +85,76 @@ impl SwfMovie {
         let encoding = swf::SwfStr::encoding_for_version(swf_buf.header.version());
         Ok(Self {
             header: swf_buf.header,
-            data: swf_buf.data,
+            data: MovieData::from(swf_buf.data),
             url,
             loader_url,
             parameters: Vec::new(),
             encoding,
-            compressed_len,
+            compressed_len: AtomicUsize::new(compressed_len),
+            compressed_loaded: AtomicUsize::new(compressed_len),
+            is_loaded: AtomicBool::new(true),
         })
     }
 
+    /// Construct a movie that is still downloading, from its header.
+    ///
+    /// The tag stream is initially empty; the rest of it is added with
+    /// `append_data` as it's decompressed. `tag_stream_len` is the length of
+    /// the tag stream announced by the header, and `compressed_len` the
+    /// length of the whole SWF file, if it is known. Otherwise, the file is
+    /// assumed to be as long as the uncompressed SWF until it has been
+    /// downloaded.
+    pub fn from_header(
+        header: HeaderExt,
+        tag_stream_len: usize,
+        compressed_len: Option<usize>,
+        url: Option<String>,
+        loader_url: Option<String>,
+    ) -> Self {
+        let encoding = swf::SwfStr::encoding_for_version(header.version());
+        let compressed_len = compressed_len.unwrap_or(header.uncompressed_len() as usize);
+        Self {
+            header,
+            data: MovieData::with_capacity(tag_stream_len),
+            url,
+            loader_url,
+            parameters: Vec::new(),
+            encoding,
+            compressed_len: AtomicUsize::new(compressed_len),
+            compressed_loaded: AtomicUsize::new(0),
+            is_loaded: AtomicBool::new(false),
+        }
+    }
+
+    /// Add the next part of the tag stream to a movie that is still downloading.
+    ///
+    /// `compressed_loaded` is the number of bytes of the SWF file that have
+    /// been downloaded so far.
+    ///
+    /// Data that has already been added never changes, so existing slices of
+    /// this movie stay valid. Any data beyond the length announced by the SWF
+    /// header is dropped.
+    pub fn append_data(&self, data: &[u8], compressed_loaded: usize) {
+        if self.data.append(data) < data.len() {
+            log::warn!("SWF length doesn't match header, may be corrupt");
+        }
+        self.compressed_loaded
+            .store(compressed_loaded, Ordering::Release);
+    }
+
+    /// Mark a movie that was downloading as complete.
+    pub fn finish_loading(&self) {
+        let compressed_loaded = self.compressed_loaded.load(Ordering::Acquire);
+        self.compressed_len
+            .store(compressed_loaded, Ordering::Release);
+        self.is_loaded.store(true, Ordering::Release);
+    }
+
+    /// Whether the entire movie has been downloaded.
+    pub fn is_loaded(&self) -> bool {
+        self.is_loaded.load(Ordering::Acquire)
+    }
+
     pub fn header(&self) -> &HeaderExt {
         &self.header
     }
@@ -92,8 +164,9 @@ impl SwfMovie {
         self.header.version()
     }
 
+    /// The part of the tag stream that has been loaded so far.
     pub fn data(&self) -> &[u8] {
-        &self.data
+        self.data.as_slice()
     }
 
     /// Returns the suggested string encoding for the given SWF version.
@@ -133,13 +206,28 @@ impl SwfMovie {
     }
 
     pub fn compressed_len(&self) -> usize {
-        self.compressed_len
+        self.compressed_len.load(Ordering::Acquire)
+    }
+
+    /// The number of bytes of the compressed datastream that have been downloaded.
+    pub fn compressed_loaded(&self) -> usize {
+        self.compressed_loaded.load(Ordering::Acquire)
     }
 
     pub fn uncompressed_len(&self) -> u32 {
         self.header.uncompressed_len()
     }
 
+    /// The number of bytes of the uncompressed SWF that have been loaded,
+    /// counting the header.
+    pub fn uncompressed_loaded(&self) -> u32 {
+        if self.is_loaded() {
+            return self.uncompressed_len();
+        }
+        let pending = self.data.capacity() - self.data().len();
+        self.uncompressed_len().saturating_sub(pending as u32)
+    }
+
     pub fn avm_type(&self) -> AvmType {
         if self.header.is_action_script_3() {
             AvmType::Avm2
@@ -161,6 +249,122 @@ impl SwfMovie {
     }
 }
 
+impl Clone for SwfMovie {
+    fn clone(&self) -> Self {
+        Self {
+            header: self.header.clone(),
+            data: self.data.clone(),
+            url: self.url.clone(),
+            loader_url: self.loader_url.clone(),
+            parameters: self.parameters.clone(),
+            encoding: self.encoding,
+            compressed_len: AtomicUsize::new(self.compressed_len()),
+            compressed_loaded: AtomicUsize::new(self.compressed_loaded()),
+            is_loaded: AtomicBool::new(self.is_loaded()),
+        }
+    }
+}
+
+/// The uncompressed tag stream of a movie, which may still be arriving.
+///
+/// The buffer is allocated once, at the length announced by the SWF header,
+/// and new data is only ever written past the part that has been loaded.
+/// Loaded data therefore never moves or changes, which lets slices of a movie
+/// be handed out while the loader keeps adding to it.
+struct MovieData {
+    /// The start of the buffer, which is owned by this struct.
+    ptr: *mut u8,
+
+    /// The length of the buffer.
+    capacity: usize,
+
+    /// The number of bytes at the start of the buffer that have been loaded.
+    len: AtomicUsize,
+
+    /// Held while data is being appended, so that there's only one writer.
+    append_lock: Mutex<()>,
+}
+
+// SAFETY: Loaded data is never written to again, and new data is only written
+// by one thread at a time, to the part of the buffer that isn't visible yet.
+unsafe impl Send for MovieData {}
+unsafe impl Sync for MovieData {}
+
+impl MovieData {
+    fn with_capacity(capacity: usize) -> Self {
+        let mut data = Self::from(vec![0; capacity]);
+        data.len = AtomicUsize::new(0);
+        data
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        let len = self.len.load(Ordering::Acquire);
+        // SAFETY: The first `len` bytes of the buffer have been written, and
+        // are never written to again.
+        unsafe { std::slice::from_raw_parts(self.ptr, len) }
+    }
+
+    /// Append as much of `data` as fits in the buffer.
+    ///
+    /// Returns the number of bytes appended.
+    fn append(&self, data: &[u8]) -> usize {
+        let _guard = self.append_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let len = self.len.load(Ordering::Acquire);
+        let count = data.len().min(self.capacity - len);
+        // SAFETY: The bytes past `len` lie within the buffer, aren't visible
+        // through `as_slice` yet, and the lock keeps out any other writer.
+        unsafe { std::ptr::copy_nonoverlapping(data.as_ptr(), self.ptr.add(len), count) };
+        self.len.store(len + count, Ordering::Release);
+        count
+    }
+}
+
+impl From<Vec<u8>> for MovieData {
+    fn from(data: Vec<u8>) -> Self {
+        let data = data.into_boxed_slice();
+        let capacity = data.len();
+        Self {
+            ptr: Box::into_raw(data) as *mut u8,
+            capacity,
+            len: AtomicUsize::new(capacity),
+            append_lock: Mutex::new(()),
+        }
+    }
+}
+
+impl Clone for MovieData {
+    fn clone(&self) -> Self {
+        let data = Self::with_capacity(self.capacity);
+        data.append(self.as_slice());
+        data
+    }
+}
+
+impl Drop for MovieData {
+    fn drop(&mut self) {
+        // SAFETY: The buffer was created from a boxed slice of this length.
+        unsafe {
+            drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                self.ptr,
+                self.capacity,
+            )))
+        };
+    }
+}
+
+impl std::fmt::Debug for MovieData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MovieData")
+            .field("len", &self.as_slice().len())
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
 /// A shared-ownership reference to some portion of an SWF datastream.
 #[derive(Debug, Clone, Collect)]
 #[collect(no_drop)]
@@ -0,0 +1,37 @@
+//! Command line client for the Ruffle debugger.
+//!
+//! Start Ruffle with `--debugger-port <PORT>`, then run this client with the
+//! same address to set breakpoints and step through ActionScript.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::thread;
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:7979";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let address = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_ADDRESS.to_string());
+    let stream = TcpStream::connect(&address)?;
+    eprintln!("Connected to {}", address);
+
+    let reader = BufReader::new(stream.try_clone()?);
+    thread::spawn(move || {
+        for line in reader.lines() {
+            match line {
+                Ok(line) => println!("{}", line),
+                Err(_) => break,
+            }
+        }
+        eprintln!("Disconnected");
+        std::process::exit(0);
+    });
+
+    let mut writer = stream;
+    for line in io::stdin().lock().lines() {
+        writeln!(writer, "{}", line?)?;
+    }
+
+    Ok(())
+}
@@ -1,7 +1,12 @@
 //! Custom event type for desktop ruffle
 
+use ruffle_core::PlayerEvent;
+
 /// User-defined events.
 pub enum RuffleEvent {
     /// Indicates that one or more tasks are ready to poll on our executor.
     TaskPoll,
+
+    /// Text composed with an input method editor (IME), which winit doesn't report.
+    Ime(PlayerEvent),
 }
@@ -0,0 +1,127 @@
+use ruffle_core::backend::debugger::DebuggerBackend;
+use std::io::{self, BufRead, BufReader, ErrorKind, Write};
+use std::net::{TcpListener, TcpStream};
+
+const GREETING: &str = "Connected to Ruffle. Type `help` for a list of commands.";
+
+/// A debugger client connected over TCP.
+struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+
+    /// The part of the next command received so far.
+    line: Vec<u8>,
+}
+
+/// Debugger backend that accepts a single client at a time on a local TCP port.
+pub struct TcpDebuggerBackend {
+    listener: TcpListener,
+    client: Option<Client>,
+}
+
+impl TcpDebuggerBackend {
+    pub fn bind(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        listener.set_nonblocking(true)?;
+        log::info!("Debugger listening on {}", listener.local_addr()?);
+
+        Ok(Self {
+            listener,
+            client: None,
+        })
+    }
+
+    fn accept(&mut self) {
+        if self.client.is_some() {
+            return;
+        }
+
+        let stream = match self.listener.accept() {
+            Ok((stream, address)) => {
+                log::info!("Debugger client connected from {}", address);
+                stream
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => return,
+            Err(e) => {
+                log::warn!("Unable to accept debugger client: {}", e);
+                return;
+            }
+        };
+
+        let client = stream
+            .set_nonblocking(true)
+            .and_then(|_| stream.try_clone())
+            .map(|writer| Client {
+                reader: BufReader::new(stream),
+                writer,
+                line: Vec::new(),
+            });
+        match client {
+            Ok(client) => {
+                self.client = Some(client);
+                self.send(GREETING);
+            }
+            Err(e) => log::warn!("Unable to set up debugger client: {}", e),
+        }
+    }
+
+    /// Reads the next command from the client, if a whole one is available.
+    fn read_command(&mut self, blocking: bool) -> Option<String> {
+        let client = self.client.as_mut()?;
+
+        if blocking {
+            let _ = client.reader.get_ref().set_nonblocking(false);
+        }
+        // A failed non-blocking read still keeps whatever was read so far in
+        // `line`, so partial commands are completed by the next poll.
+        let result = client.reader.read_until(b'\n', &mut client.line);
+        if blocking {
+            let _ = client.reader.get_ref().set_nonblocking(true);
+        }
+
+        match result {
+            Ok(0) => {
+                log::info!("Debugger client disconnected");
+                self.client = None;
+                None
+            }
+            Ok(_) if client.line.ends_with(b"\n") => {
+                let command = String::from_utf8_lossy(&client.line).trim().to_string();
+                client.line.clear();
+                Some(command)
+            }
+            Ok(_) => None,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => None,
+            Err(e) => {
+                log::warn!("Debugger client disconnected: {}", e);
+                self.client = None;
+                None
+            }
+        }
+    }
+}
+
+impl DebuggerBackend for TcpDebuggerBackend {
+    fn poll_command(&mut self) -> Option<String> {
+        self.accept();
+        self.read_command(false)
+    }
+
+    fn wait_command(&mut self) -> Option<String> {
+        while self.client.is_some() {
+            if let Some(command) = self.read_command(true) {
+                return Some(command);
+            }
+        }
+        None
+    }
+
+    fn send(&mut self, message: &str) {
+        if let Some(client) = &mut self.client {
+            if let Err(e) = writeln!(client.writer, "{}", message) {
+                log::warn!("Debugger client disconnected: {}", e);
+                self.client = None;
+            }
+        }
+    }
+}
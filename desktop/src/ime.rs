@@ -0,0 +1,153 @@
+//! Text composition with an input method editor (IME).
+//!
+//! winit doesn't report the text being composed, so it's read from the
+//! platform where we can. Elsewhere the IME shows the composition itself,
+//! and the result arrives as ordinary `ReceivedCharacter` events.
+
+use crate::custom_event::RuffleEvent;
+use winit::event_loop::EventLoopProxy;
+use winit::window::Window;
+
+/// Sends text composed with an IME in `window` to the event loop as `RuffleEvent::Ime`.
+#[cfg(windows)]
+pub fn install(window: &Window, event_loop: EventLoopProxy<RuffleEvent>) {
+    windows::install(window, event_loop);
+}
+
+/// Sends text composed with an IME in `window` to the event loop as `RuffleEvent::Ime`.
+#[cfg(not(windows))]
+pub fn install(_window: &Window, _event_loop: EventLoopProxy<RuffleEvent>) {}
+
+#[cfg(windows)]
+mod windows {
+    use crate::custom_event::RuffleEvent;
+    use ruffle_core::PlayerEvent;
+    use std::ptr;
+    use winapi::shared::basetsd::{DWORD_PTR, UINT_PTR};
+    use winapi::shared::minwindef::{DWORD, LPARAM, LPVOID, LRESULT, UINT, WPARAM};
+    use winapi::shared::ntdef::LONG;
+    use winapi::shared::windef::HWND;
+    use winapi::um::commctrl::{DefSubclassProc, RemoveWindowSubclass, SetWindowSubclass};
+    use winapi::um::imm::{ImmGetContext, ImmReleaseContext, HIMC};
+    use winapi::um::winuser::{
+        WM_IME_COMPOSITION, WM_IME_ENDCOMPOSITION, WM_IME_SETCONTEXT, WM_IME_STARTCOMPOSITION,
+        WM_NCDESTROY,
+    };
+    use winit::event_loop::EventLoopProxy;
+    use winit::platform::windows::WindowExtWindows;
+    use winit::window::Window;
+
+    // These aren't defined by `winapi`.
+    const GCS_COMPSTR: DWORD = 0x0008;
+    const GCS_RESULTSTR: DWORD = 0x0800;
+    const ISC_SHOWUICOMPOSITIONWINDOW: DWORD = 0x8000_0000;
+
+    #[link(name = "imm32")]
+    extern "system" {
+        fn ImmGetCompositionStringW(himc: HIMC, index: DWORD, buf: LPVOID, len: DWORD) -> LONG;
+    }
+
+    const SUBCLASS_ID: UINT_PTR = 1;
+
+    struct Ime {
+        event_loop: EventLoopProxy<RuffleEvent>,
+
+        /// Whether text is being composed, so that a composition ending
+        /// without a result can be reported as cancelled.
+        composing: bool,
+    }
+
+    impl Ime {
+        fn send(&self, event: PlayerEvent) {
+            // The event loop outlives the window, so this can't fail.
+            let _ = self.event_loop.send_event(RuffleEvent::Ime(event));
+        }
+    }
+
+    pub fn install(window: &Window, event_loop: EventLoopProxy<RuffleEvent>) {
+        let ime = Box::new(Ime {
+            event_loop,
+            composing: false,
+        });
+        unsafe {
+            SetWindowSubclass(
+                window.hwnd() as HWND,
+                Some(subclass_proc),
+                SUBCLASS_ID,
+                Box::into_raw(ime) as DWORD_PTR,
+            );
+        }
+    }
+
+    unsafe extern "system" fn subclass_proc(
+        hwnd: HWND,
+        msg: UINT,
+        wparam: WPARAM,
+        lparam: LPARAM,
+        _id: UINT_PTR,
+        data: DWORD_PTR,
+    ) -> LRESULT {
+        if msg == WM_NCDESTROY {
+            RemoveWindowSubclass(hwnd, Some(subclass_proc), SUBCLASS_ID);
+            drop(Box::from_raw(data as *mut Ime));
+            return DefSubclassProc(hwnd, msg, wparam, lparam);
+        }
+
+        let ime = &mut *(data as *mut Ime);
+        match msg {
+            WM_IME_SETCONTEXT => {
+                // The composition is shown in the text field instead of the IME's own window.
+                let lparam = lparam & !(ISC_SHOWUICOMPOSITIONWINDOW as LPARAM);
+                DefSubclassProc(hwnd, msg, wparam, lparam)
+            }
+            WM_IME_STARTCOMPOSITION => {
+                ime.composing = true;
+                ime.send(PlayerEvent::ImeCompositionStart);
+                DefSubclassProc(hwnd, msg, wparam, lparam)
+            }
+            WM_IME_COMPOSITION => {
+                let himc = ImmGetContext(hwnd);
+                let changes = lparam as DWORD;
+                if changes & GCS_RESULTSTR != 0 {
+                    ime.composing = false;
+                    ime.send(PlayerEvent::ImeCompositionCommit {
+                        text: composition_string(himc, GCS_RESULTSTR),
+                    });
+                }
+                if changes & GCS_COMPSTR != 0 {
+                    ime.composing = true;
+                    ime.send(PlayerEvent::ImeCompositionUpdate {
+                        text: composition_string(himc, GCS_COMPSTR),
+                    });
+                }
+                ImmReleaseContext(hwnd, himc);
+
+                // The default handling would send the result again as `WM_CHAR`s.
+                0
+            }
+            WM_IME_ENDCOMPOSITION => {
+                if ime.composing {
+                    ime.composing = false;
+                    ime.send(PlayerEvent::ImeCompositionCommit {
+                        text: String::new(),
+                    });
+                }
+                DefSubclassProc(hwnd, msg, wparam, lparam)
+            }
+            _ => DefSubclassProc(hwnd, msg, wparam, lparam),
+        }
+    }
+
+    /// Reads the composition string of the given kind, such as `GCS_COMPSTR`.
+    unsafe fn composition_string(himc: HIMC, kind: DWORD) -> String {
+        let len = ImmGetCompositionStringW(himc, kind, ptr::null_mut(), 0);
+        if len <= 0 {
+            return String::new();
+        }
+
+        // The length is in bytes.
+        let mut buf = vec![0u16; len as usize / 2];
+        ImmGetCompositionStringW(himc, kind, buf.as_mut_ptr() as LPVOID, len as DWORD);
+        String::from_utf16_lossy(&buf)
+    }
+}
@@ -7,7 +7,9 @@
 
 mod audio;
 mod custom_event;
+mod debugger;
 mod executor;
+mod ime;
 mod locale;
 mod navigator;
 mod storage;
@@ -21,13 +23,16 @@ use isahc::{config::RedirectPolicy, prelude::*, HttpClient};
 use ruffle_core::{
     backend::{
         audio::{AudioBackend, NullAudioBackend},
+        locale::LocaleBackend,
         log as log_backend,
-        navigator::NullNavigatorBackend,
+        navigator::{NavigatorBackend, NullNavigatorBackend},
         storage::MemoryStorageBackend,
         ui::NullUiBackend,
         video,
     },
     config::Letterbox,
+    profiler::{Profiler, MAX_TIMELINE_EVENTS},
+    recording::{Recording, Replayer},
     Player,
 };
 use ruffle_render_wgpu::WgpuRenderBackend;
@@ -101,11 +106,33 @@ struct Opt {
     #[clap(long, takes_value = false)]
     upgrade_to_https: bool,
 
+    /// Run the movie as fast as possible without a window, then print a summary of where the
+    /// time was spent
     #[clap(long, takes_value = false)]
     timedemo: bool,
 
+    /// (Optional) With --timedemo, write a Chrome trace event file of the run to this path.
+    /// It can be opened in about:tracing or Perfetto.
+    #[clap(long, parse(from_os_str))]
+    profile_path: Option<PathBuf>,
+
     #[clap(long, takes_value = false)]
     dont_warn_on_unsupported_content: bool,
+
+    /// (Optional) Listen for a debugger client on the given local TCP port.
+    /// Connect to it with `ruffle_debug_client`.
+    #[clap(long)]
+    debugger_port: Option<u16>,
+
+    /// (Optional) Record all input given to the movie, and write it to this file on exit.
+    /// The session can be played back with --replay.
+    #[clap(long, parse(from_os_str))]
+    record: Option<PathBuf>,
+
+    /// (Optional) Play back a session recorded with --record.
+    /// Input from the window is ignored until the recording has finished.
+    #[clap(long, parse(from_os_str))]
+    replay: Option<PathBuf>,
 }
 
 #[cfg(feature = "render_trace")]
@@ -180,6 +207,18 @@ fn load_from_file_dialog(opt: &Opt) -> Result<Option<(SwfMovie, Url)>, Box<dyn s
     Ok(Some(load_movie_from_path(&absolute_path, opt)?))
 }
 
+fn load_recording(path: &Path) -> Result<Recording, Box<dyn std::error::Error>> {
+    let recording = std::fs::read_to_string(path)?;
+    serde_json::from_str(&recording)
+        .map_err(|e| format!("Couldn't parse recording {}: {}", path.to_string_lossy(), e).into())
+}
+
+fn save_recording(path: &Path, recording: &Recording) -> Result<(), Box<dyn std::error::Error>> {
+    let file = std::fs::File::create(path)?;
+    serde_json::to_writer(std::io::BufWriter::new(file), recording)?;
+    Ok(())
+}
+
 struct App {
     #[allow(dead_code)]
     opt: Opt,
@@ -188,6 +227,7 @@ struct App {
     executor: Arc<Mutex<GlutinAsyncExecutor>>,
     player: Arc<Mutex<Player>>,
     movie: Option<Arc<SwfMovie>>,
+    replayer: Option<Replayer>,
 }
 
 impl App {
@@ -253,6 +293,7 @@ impl App {
 
         let viewport_size = window.inner_size();
         let viewport_scale_factor = window.scale_factor();
+        ime::install(&window, event_loop.create_proxy());
 
         let window = Rc::new(window);
         let renderer = Box::new(WgpuRenderBackend::for_window(
@@ -269,6 +310,11 @@ impl App {
                 Box::new(NullAudioBackend::new())
             }
         };
+        let replayer = match &opt.replay {
+            Some(path) => Some(Replayer::new(load_recording(path)?)),
+            None => None,
+        };
+
         let (executor, channel) = GlutinAsyncExecutor::new(event_loop.create_proxy());
         let navigator = Box::new(navigator::ExternalNavigatorBackend::new(
             movie.as_ref().unwrap().1.clone(), // TODO: Get rid of this parameter.
@@ -278,7 +324,14 @@ impl App {
             opt.upgrade_to_https,
         ));
         let storage = Box::new(storage::DiskStorageBackend::new());
-        let locale = Box::new(locale::DesktopLocaleBackend::new());
+        let (navigator, locale): (Box<dyn NavigatorBackend>, Box<dyn LocaleBackend>) =
+            match &replayer {
+                Some(replayer) => (
+                    Box::new(replayer.navigator(navigator)),
+                    Box::new(replayer.locale()),
+                ),
+                None => (navigator, Box::new(locale::DesktopLocaleBackend::new())),
+            };
         let video = Box::new(video::SoftwareVideoBackend::new());
         let log = Box::new(log_backend::NullLogBackend::new());
         let ui = Box::new(ui::DesktopUiBackend::new(window.clone()));
@@ -289,6 +342,15 @@ impl App {
         {
             let mut player_lock = player.lock().unwrap();
             player_lock.set_warn_on_unsupported_content(!opt.dont_warn_on_unsupported_content);
+            if let Some(port) = opt.debugger_port {
+                player_lock.attach_debugger(Box::new(debugger::TcpDebuggerBackend::bind(port)?));
+            }
+            if let Some(replayer) = &replayer {
+                replayer.start(&mut player_lock);
+            }
+            if opt.record.is_some() {
+                player_lock.start_recording();
+            }
             if let Some(movie) = &movie {
                 player_lock.set_root_movie(movie.to_owned());
                 player_lock.set_is_playing(true); // Desktop player will auto-play.
@@ -308,6 +370,7 @@ impl App {
             executor,
             player,
             movie,
+            replayer,
         })
     }
 
@@ -317,9 +380,12 @@ impl App {
         let player = self.player;
         let executor = self.executor;
         let movie = self.movie;
+        let record_path = self.opt.record;
+        let mut replayer = self.replayer;
 
         let mut mouse_pos = PhysicalPosition::new(0.0, 0.0);
         let mut time = Instant::now();
+        let start_time = time;
         let mut next_frame_time = Instant::now();
         let mut minimized = false;
         let mut fullscreen_down = false;
@@ -335,7 +401,15 @@ impl App {
                     #[allow(deprecated)]
                     match &event {
                         winit::event::Event::LoopDestroyed => {
-                            player.lock().unwrap().flush_shared_objects();
+                            let mut player_lock = player.lock().unwrap();
+                            player_lock.flush_shared_objects();
+                            if let Some(path) = &record_path {
+                                if let Some(recording) = player_lock.stop_recording() {
+                                    if let Err(e) = save_recording(path, &recording) {
+                                        log::error!("Unable to save recording: {}", e);
+                                    }
+                                }
+                            }
                             shutdown(&Ok(()));
                             return;
                         }
@@ -383,6 +457,24 @@ impl App {
                                 window.set_fullscreen(None);
                                 return;
                             }
+                            WindowEvent::KeyboardInput {
+                                input:
+                                    KeyboardInput {
+                                        state: ElementState::Pressed,
+                                        virtual_keycode: Some(VirtualKeyCode::D),
+                                        modifiers,
+                                        ..
+                                    },
+                                ..
+                            } if modifiers.ctrl() && modifiers.shift() => {
+                                // Dump the display list for debugging.
+                                let display_list = player.lock().unwrap().inspect_display_list();
+                                match serde_json::to_string_pretty(&display_list) {
+                                    Ok(json) => log::info!("Display list:\n{}", json),
+                                    Err(e) => log::error!("Unable to dump display list: {}", e),
+                                }
+                                return;
+                            }
                             _ => (),
                         },
                         _ => (),
@@ -392,6 +484,11 @@ impl App {
                         return;
                     }
 
+                    let replaying = replayer.as_ref().map_or(false, |r| !r.is_finished());
+                    if replaying && is_input_event(&event) {
+                        return;
+                    }
+
                     match event {
                         // Core loop
                         winit::event::Event::MainEventsCleared => {
@@ -400,7 +497,23 @@ impl App {
                             if dt > 0 {
                                 time = new_time;
                                 let mut player_lock = player.lock().unwrap();
-                                player_lock.tick(dt as f64 / 1000.0);
+                                match &mut replayer {
+                                    Some(replayer) if replaying => {
+                                        replayer.run_until(
+                                            &mut player_lock,
+                                            new_time.duration_since(start_time),
+                                        );
+                                        if replayer.is_finished() {
+                                            log::info!("Replay finished");
+                                        }
+                                    }
+                                    Some(replayer) => {
+                                        // The player still reads the time from the replay clock.
+                                        replayer.clock().advance(dt as f64 / 1000.0);
+                                        player_lock.tick(dt as f64 / 1000.0);
+                                    }
+                                    None => player_lock.tick(dt as f64 / 1000.0),
+                                }
                                 next_frame_time = new_time + player_lock.time_til_next_frame();
                                 if player_lock.needs_render() {
                                     window.request_redraw();
@@ -512,6 +625,13 @@ impl App {
                             .lock()
                             .expect("active executor reference")
                             .poll_all(),
+                        winit::event::Event::UserEvent(RuffleEvent::Ime(event)) => {
+                            let mut player_lock = player.lock().unwrap();
+                            player_lock.handle_event(event);
+                            if player_lock.needs_render() {
+                                window.request_redraw();
+                            }
+                        }
                         _ => (),
                     }
 
@@ -524,6 +644,23 @@ impl App {
     }
 }
 
+/// Whether an event is input for the movie, which is ignored while a session is replayed.
+fn is_input_event(event: &winit::event::Event<RuffleEvent>) -> bool {
+    match event {
+        winit::event::Event::WindowEvent { event, .. } => matches!(
+            event,
+            WindowEvent::CursorMoved { .. }
+                | WindowEvent::CursorLeft { .. }
+                | WindowEvent::MouseInput { .. }
+                | WindowEvent::MouseWheel { .. }
+                | WindowEvent::KeyboardInput { .. }
+                | WindowEvent::ReceivedCharacter(_)
+        ),
+        winit::event::Event::UserEvent(RuffleEvent::Ime(_)) => true,
+        _ => false,
+    }
+}
+
 fn run_timedemo(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
     let path = opt
         .input_path
@@ -558,6 +695,10 @@ fn run_timedemo(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
 
     println!("Running {}...", path.to_string_lossy());
 
+    // Individual spans are only needed for the trace file.
+    player_lock
+        .profiler_mut()
+        .enable(opt.profile_path.is_some());
     let start = Instant::now();
     let mut num_frames = 0;
     const MAX_FRAMES: u32 = 5000;
@@ -571,9 +712,48 @@ fn run_timedemo(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
 
     println!("Ran {} frames in {}s.", num_frames, duration.as_secs_f32());
 
+    let profiler = player_lock.profiler_mut();
+    profiler.disable();
+    print_profile_summary(profiler, num_frames);
+    if let Some(profile_path) = &opt.profile_path {
+        let file = std::fs::File::create(profile_path)?;
+        profiler.write_chrome_trace(std::io::BufWriter::new(file))?;
+        println!("Wrote trace to {}", profile_path.to_string_lossy());
+        if profiler.dropped_events() > 0 {
+            println!(
+                "The trace is missing the last {} spans, as it reached its limit of {} spans.",
+                profiler.dropped_events(),
+                MAX_TIMELINE_EVENTS
+            );
+        }
+    }
+
     Ok(())
 }
 
+/// Prints the most expensive entries recorded by the profiler.
+fn print_profile_summary(profiler: &Profiler, num_frames: u32) {
+    const MAX_ENTRIES: usize = 30;
+
+    println!();
+    println!(
+        "{:<14} {:<48} {:>8} {:>11} {:>11} {:>11}",
+        "Category", "Name", "Calls", "Total (ms)", "Frame (ms)", "Max (ms)"
+    );
+    for entry in profiler.summary().iter().take(MAX_ENTRIES) {
+        let total = entry.total.as_secs_f64() * 1000.0;
+        println!(
+            "{:<14} {:<48} {:>8} {:>11.3} {:>11.3} {:>11.3}",
+            entry.category.name(),
+            entry.name,
+            entry.calls,
+            total,
+            total / f64::from(num_frames.max(1)),
+            entry.max.as_secs_f64() * 1000.0
+        );
+    }
+}
+
 fn init() {
     // When linked with the windows subsystem windows won't automatically attach
     // to the console of the parent process, so we do it explicitly. This fails
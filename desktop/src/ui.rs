@@ -1,9 +1,10 @@
 use clipboard::{ClipboardContext, ClipboardProvider};
-use ruffle_core::backend::ui::{MouseCursor, UiBackend};
+use ruffle_core::backend::ui::{ImeCursorArea, MouseCursor, UiBackend};
 use ruffle_core::events::{KeyCode, PlayerEvent};
 use std::collections::HashSet;
 use std::rc::Rc;
 use tinyfiledialogs::{message_box_ok, MessageBoxIcon};
+use winit::dpi::PhysicalPosition;
 use winit::event::{ElementState, ModifiersState, VirtualKeyCode, WindowEvent};
 use winit::window::Window;
 
@@ -222,10 +223,20 @@ impl UiBackend for DesktopUiBackend {
         self.clipboard.set_contents(content).unwrap();
     }
 
+    fn get_clipboard_content(&mut self) -> String {
+        self.clipboard.get_contents().unwrap_or_default()
+    }
+
     fn is_fullscreen(&self) -> bool {
         self.window.fullscreen().is_some()
     }
 
+    fn set_ime_cursor_area(&mut self, area: ImeCursorArea) {
+        // Place the candidate window just below the text being composed.
+        self.window
+            .set_ime_position(PhysicalPosition::new(area.x, area.y + area.height));
+    }
+
     fn display_unsupported_message(&self) {
         message_box_ok(
             "Ruffle - Unsupported content",
@@ -0,0 +1,18 @@
+use std::io::{self, Write};
+use swf::avm2::disassemble::disassemble;
+use swf::avm2::read::Reader;
+use swf::{Swf, Tag, UTF_8};
+
+/// Writes a listing of every ABC block in the movie.
+pub fn dump_abc<W: Write>(swf: &Swf, output: &mut W) -> io::Result<()> {
+    for tag in &swf.tags {
+        if let Tag::DoAbc(do_abc) = tag {
+            writeln!(output, "// DoAbc: {}", do_abc.name.to_str_lossy(UTF_8))?;
+            match Reader::new(do_abc.data).read() {
+                Ok(abc_file) => writeln!(output, "{}", disassemble(&abc_file))?,
+                Err(e) => writeln!(output, "// Unable to parse ABC: {}\n", e)?,
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use std::io::{self, Write};
+use swf::avm1::disassemble::disassemble;
+use swf::{Swf, Tag};
+
+/// Writes a listing of every AVM1 script in the movie, including those in sprites and buttons.
+pub fn dump_scripts<W: Write>(swf: &Swf, output: &mut W) -> io::Result<()> {
+    let version = swf.header.version();
+    dump_timeline(&swf.tags, "root", version, output)
+}
+
+fn dump_timeline<W: Write>(
+    tags: &[Tag],
+    timeline: &str,
+    version: u8,
+    output: &mut W,
+) -> io::Result<()> {
+    let mut frame = 1;
+    for tag in tags {
+        match tag {
+            Tag::ShowFrame => frame += 1,
+            Tag::DoAction(action_data) => {
+                let title = format!("DoAction: {} frame {}", timeline, frame);
+                dump_script(&title, action_data, version, output)?;
+            }
+            Tag::DoInitAction { id, action_data } => {
+                let title = format!("DoInitAction: sprite {}", id);
+                dump_script(&title, action_data, version, output)?;
+            }
+            Tag::PlaceObject(place_object) => {
+                for clip_action in place_object.clip_actions.iter().flatten() {
+                    let title = format!(
+                        "ClipAction: {} frame {} depth {} {:?}",
+                        timeline, frame, place_object.depth, clip_action.events
+                    );
+                    dump_script(&title, clip_action.action_data, version, output)?;
+                }
+            }
+            Tag::DefineButton(button) | Tag::DefineButton2(button) => {
+                for button_action in &button.actions {
+                    let title = format!(
+                        "ButtonAction: button {} {:?}",
+                        button.id, button_action.conditions
+                    );
+                    dump_script(&title, button_action.action_data, version, output)?;
+                }
+            }
+            Tag::DefineSprite(sprite) => {
+                let timeline = format!("sprite {}", sprite.id);
+                dump_timeline(&sprite.tags, &timeline, version, output)?;
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+fn dump_script<W: Write>(
+    title: &str,
+    action_data: &[u8],
+    version: u8,
+    output: &mut W,
+) -> io::Result<()> {
+    writeln!(output, "// {}", title)?;
+    writeln!(output, "{}", disassemble(action_data, version))
+}
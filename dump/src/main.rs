@@ -0,0 +1,53 @@
+mod abc;
+mod avm1;
+
+use clap::Clap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+#[derive(Clap, Debug)]
+#[clap(name = "Ruffle Dump", author, version)]
+struct Opt {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Clap, Debug)]
+enum Command {
+    /// Disassemble every ABC (AVM2 bytecode) block in a SWF
+    Abc {
+        /// The SWF file to read
+        #[clap(name = "swf", parse(from_os_str))]
+        swf: PathBuf,
+    },
+    /// Disassemble every AVM1 script in a SWF
+    Avm1 {
+        /// The SWF file to read
+        #[clap(name = "swf", parse(from_os_str))]
+        swf: PathBuf,
+    },
+}
+
+fn read_swf(path: &Path) -> Result<swf::SwfBuf, Box<dyn Error>> {
+    let data = std::fs::read(path)?;
+    Ok(swf::decompress_swf(&data[..])?)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let opt = Opt::parse();
+
+    match opt.command {
+        Command::Abc { swf } => {
+            let swf_buf = read_swf(&swf)?;
+            let swf = swf::parse_swf(&swf_buf)?;
+            abc::dump_abc(&swf, &mut std::io::stdout().lock())?;
+        }
+        Command::Avm1 { swf } => {
+            let swf_buf = read_swf(&swf)?;
+            let swf = swf::parse_swf(&swf_buf)?;
+            avm1::dump_scripts(&swf, &mut std::io::stdout().lock())?;
+        }
+    }
+
+    Ok(())
+}
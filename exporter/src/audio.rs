@@ -0,0 +1,158 @@
+use ruffle_core::backend::audio::mixer::AudioMixer;
+use ruffle_core::backend::audio::{
+    swf, AudioBackend, SoundHandle, SoundInstanceHandle, SoundTransform,
+};
+use ruffle_core::tag_utils::SwfSlice;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// The sample rate of the recorded audio.
+pub const SAMPLE_RATE: u32 = 44100;
+
+type Error = Box<dyn std::error::Error>;
+
+/// An audio backend that mixes all playing sounds into an in-memory buffer.
+///
+/// Instead of playing in real time, audio is only mixed when `record_frame` is
+/// called, so the recorded audio always lines up with the recorded frames.
+pub struct RecordingAudioBackend {
+    mixer: AudioMixer,
+    frame_rate: f64,
+
+    /// Sample frames that could not be mixed yet, because one video frame
+    /// does not last a whole number of sample frames.
+    pending_sample_frames: f64,
+
+    /// Whether the mixed audio should be kept.
+    is_recording: bool,
+
+    /// The mixed audio recorded so far, as interleaved stereo samples.
+    samples: Vec<i16>,
+}
+
+impl RecordingAudioBackend {
+    pub fn new() -> Self {
+        Self {
+            mixer: AudioMixer::new(SAMPLE_RATE),
+            frame_rate: 1.0,
+            pending_sample_frames: 0.0,
+            is_recording: true,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Sets whether mixed audio is kept.
+    /// Sounds keep advancing while not recording, so that skipped frames stay silent.
+    pub fn set_recording(&mut self, is_recording: bool) {
+        self.is_recording = is_recording;
+    }
+
+    /// Mixes the audio lasting for one frame of the movie.
+    pub fn record_frame(&mut self) {
+        self.pending_sample_frames += f64::from(SAMPLE_RATE) / self.frame_rate;
+        let num_sample_frames = self.pending_sample_frames.floor();
+        self.pending_sample_frames -= num_sample_frames;
+
+        let mut buffer = vec![0; num_sample_frames as usize * 2];
+        self.mixer.mix(&mut buffer, 2, |sample| sample);
+        if self.is_recording {
+            self.samples.extend_from_slice(&buffer);
+        }
+    }
+
+    /// Writes the recorded audio to a 16-bit stereo WAV file.
+    pub fn write_wav(&self, path: &Path) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        let data_len = self.samples.len() as u32 * 2;
+        writer.write_all(b"RIFF")?;
+        writer.write_all(&(36 + data_len).to_le_bytes())?;
+        writer.write_all(b"WAVE")?;
+
+        writer.write_all(b"fmt ")?;
+        writer.write_all(&16u32.to_le_bytes())?; // Chunk size
+        writer.write_all(&1u16.to_le_bytes())?; // PCM
+        writer.write_all(&2u16.to_le_bytes())?; // Channels
+        writer.write_all(&SAMPLE_RATE.to_le_bytes())?;
+        writer.write_all(&(SAMPLE_RATE * 4).to_le_bytes())?; // Byte rate
+        writer.write_all(&4u16.to_le_bytes())?; // Block align
+        writer.write_all(&16u16.to_le_bytes())?; // Bits per sample
+
+        writer.write_all(b"data")?;
+        writer.write_all(&data_len.to_le_bytes())?;
+        for sample in &self.samples {
+            writer.write_all(&sample.to_le_bytes())?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl Default for RecordingAudioBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioBackend for RecordingAudioBackend {
+    fn play(&mut self) {}
+    fn pause(&mut self) {}
+
+    fn register_sound(&mut self, swf_sound: &swf::Sound) -> Result<SoundHandle, Error> {
+        self.mixer.register_sound(swf_sound)
+    }
+
+    fn start_sound(
+        &mut self,
+        sound_handle: SoundHandle,
+        settings: &swf::SoundInfo,
+    ) -> Result<SoundInstanceHandle, Error> {
+        self.mixer.start_sound(sound_handle, settings)
+    }
+
+    fn start_stream(
+        &mut self,
+        _stream_handle: Option<SoundHandle>,
+        _clip_frame: u16,
+        clip_data: SwfSlice,
+        stream_info: &swf::SoundStreamHead,
+    ) -> Result<SoundInstanceHandle, Error> {
+        self.mixer.start_stream(clip_data, stream_info)
+    }
+
+    fn stop_sound(&mut self, sound: SoundInstanceHandle) {
+        self.mixer.stop_sound(sound)
+    }
+
+    fn stop_all_sounds(&mut self) {
+        self.mixer.stop_all_sounds()
+    }
+
+    fn get_sound_position(&self, instance: SoundInstanceHandle) -> Option<u32> {
+        self.mixer.get_sound_position(instance)
+    }
+
+    fn get_sound_duration(&self, sound: SoundHandle) -> Option<f64> {
+        self.mixer.get_sound_duration(sound)
+    }
+
+    fn get_sound_size(&self, sound: SoundHandle) -> Option<u32> {
+        self.mixer.get_sound_size(sound)
+    }
+
+    fn get_sound_format(&self, sound: SoundHandle) -> Option<&swf::SoundFormat> {
+        self.mixer.get_sound_format(sound)
+    }
+
+    fn set_sound_transform(&mut self, instance: SoundInstanceHandle, transform: SoundTransform) {
+        self.mixer.set_sound_transform(instance, transform)
+    }
+
+    fn set_frame_rate(&mut self, frame_rate: f64) {
+        if frame_rate > 0.0 {
+            self.frame_rate = frame_rate;
+        }
+    }
+}
@@ -1,3 +1,6 @@
+mod audio;
+
+use crate::audio::RecordingAudioBackend;
 use clap::Clap;
 use image::RgbaImage;
 use indicatif::{ProgressBar, ProgressStyle};
@@ -8,6 +11,7 @@ use ruffle_core::backend::navigator::NullNavigatorBackend;
 use ruffle_core::backend::storage::MemoryStorageBackend;
 use ruffle_core::backend::ui::NullUiBackend;
 use ruffle_core::backend::video::SoftwareVideoBackend;
+use ruffle_core::inspector::DisplayObjectInfo;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::Player;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
@@ -16,7 +20,9 @@ use ruffle_render_wgpu::{wgpu, Descriptors, WgpuRenderBackend};
 use std::error::Error;
 use std::fs::create_dir_all;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::sync::Arc;
+use std::time::Duration;
 use walkdir::{DirEntry, WalkDir};
 
 #[derive(Clap, Debug, Copy, Clone)]
@@ -34,6 +40,30 @@ struct SizeOpt {
     height: Option<u32>,
 }
 
+#[derive(Clap, Debug, Clone)]
+struct RecordOpt {
+    /// Record every frame of the movie at its frame rate into a directory of numbered PNGs,
+    /// instead of taking screenshots
+    #[clap(long = "record")]
+    enabled: bool,
+
+    /// The first frame to record, counting from 1
+    #[clap(long = "start-frame", default_value = "1")]
+    start_frame: u32,
+
+    /// The last frame to record. Defaults to the last frame of the main timeline
+    #[clap(long = "end-frame")]
+    end_frame: Option<u32>,
+
+    /// Also record the audio of the movie, and store it as "audio.wav" next to the frames
+    #[clap(long = "audio")]
+    audio: bool,
+
+    /// Encode the recorded frames (and audio) into this video file using ffmpeg
+    #[clap(long = "video", parse(from_os_str))]
+    video: Option<PathBuf>,
+}
+
 #[derive(Clap, Debug)]
 #[clap(name = "Ruffle Exporter", author, version)]
 struct Opt {
@@ -61,9 +91,16 @@ struct Opt {
     #[clap(short, long)]
     silent: bool,
 
+    /// Also dump the display list of each captured frame as JSON, next to its screenshot
+    #[clap(long = "display-list")]
+    display_list: bool,
+
     #[clap(flatten)]
     size: SizeOpt,
 
+    #[clap(flatten)]
+    record: RecordOpt,
+
     /// Type of graphics backend to use. Not all options may be supported by your current system.
     /// Default will attempt to pick the most supported graphics backend.
     #[clap(
@@ -86,16 +123,7 @@ struct Opt {
     trace_path: Option<PathBuf>,
 }
 
-fn take_screenshot(
-    descriptors: Descriptors,
-    swf_path: &Path,
-    frames: u32,
-    skipframes: u32,
-    progress: &Option<ProgressBar>,
-    size: SizeOpt,
-) -> Result<(Descriptors, Vec<RgbaImage>), Box<dyn std::error::Error>> {
-    let movie = SwfMovie::from_path(&swf_path, None)?;
-
+fn output_size(movie: &SwfMovie, size: SizeOpt) -> (u32, u32) {
     let width = size
         .width
         .map(f64::from)
@@ -108,6 +136,22 @@ fn take_screenshot(
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
 
+    (width, height)
+}
+
+fn take_screenshot(
+    descriptors: Descriptors,
+    swf_path: &Path,
+    frames: u32,
+    skipframes: u32,
+    progress: &Option<ProgressBar>,
+    size: SizeOpt,
+    display_list: bool,
+) -> Result<(Descriptors, Vec<(RgbaImage, Option<DisplayObjectInfo>)>), Box<dyn std::error::Error>>
+{
+    let movie = SwfMovie::from_path(&swf_path, None)?;
+    let (width, height) = output_size(&movie, size);
+
     let target = TextureTarget::new(&descriptors.device, (width, height));
     let player = Player::new(
         Box::new(WgpuRenderBackend::new(descriptors, target)?),
@@ -147,7 +191,12 @@ fn take_screenshot(
                 .unwrap();
             let target = renderer.target();
             if let Some(image) = target.capture(renderer.device()) {
-                result.push(image);
+                let display_list = if display_list {
+                    Some(player.inspect_display_list())
+                } else {
+                    None
+                };
+                result.push((image, display_list));
             } else {
                 return Err(format!("Unable to capture frame {} of {:?}", i, swf_path).into());
             }
@@ -170,6 +219,20 @@ fn take_screenshot(
     Ok((descriptors, result))
 }
 
+/// Saves a captured frame, along with its display list as JSON if it was dumped.
+fn save_frame(
+    image: &RgbaImage,
+    display_list: &Option<DisplayObjectInfo>,
+    path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    image.save(path)?;
+    if let Some(display_list) = display_list {
+        let json = serde_json::to_string_pretty(display_list)?;
+        std::fs::write(path.with_extension("json"), json)?;
+    }
+    Ok(())
+}
+
 fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     let progress = if with_progress {
         Some(ProgressBar::new_spinner())
@@ -235,6 +298,7 @@ fn capture_single_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn
         opt.skipframes,
         &progress,
         opt.size,
+        opt.display_list,
     )?;
 
     if let Some(progress) = &progress {
@@ -242,12 +306,13 @@ fn capture_single_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn
     }
 
     if frames.len() == 1 {
-        frames.get(0).unwrap().save(&output)?;
+        let (image, display_list) = frames.get(0).unwrap();
+        save_frame(image, display_list, &output)?;
     } else {
-        for (frame, image) in frames.iter().enumerate() {
+        for (frame, (image, display_list)) in frames.iter().enumerate() {
             let mut path: PathBuf = (&output).into();
             path.push(format!("{}.png", frame));
-            image.save(&path)?;
+            save_frame(image, display_list, &path)?;
         }
     }
 
@@ -275,6 +340,155 @@ fn capture_single_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn
     Ok(())
 }
 
+fn record_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn Error>> {
+    let record = &opt.record;
+    let movie = SwfMovie::from_path(&opt.swf, None)?;
+
+    let start_frame = record.start_frame.max(1);
+    let end_frame = record
+        .end_frame
+        .unwrap_or_else(|| movie.num_frames().into())
+        .max(start_frame);
+
+    let output = opt.output_path.clone().unwrap_or_else(|| {
+        let mut result = PathBuf::new();
+        result.set_file_name(opt.swf.file_stem().unwrap());
+        result
+    });
+    create_dir_all(&output)?;
+
+    let (width, height) = output_size(&movie, opt.size);
+    let target = TextureTarget::new(&descriptors.device, (width, height));
+    let player = Player::new(
+        Box::new(WgpuRenderBackend::new(descriptors, target)?),
+        Box::new(RecordingAudioBackend::new()),
+        Box::new(NullNavigatorBackend::new()),
+        Box::new(MemoryStorageBackend::default()),
+        Box::new(NullLocaleBackend::new()),
+        Box::new(SoftwareVideoBackend::new()),
+        Box::new(NullLogBackend::new()),
+        Box::new(NullUiBackend::new()),
+    )?;
+    let mut player = player.lock().unwrap();
+    player.set_viewport_dimensions(width, height, opt.size.scale);
+    player.set_root_movie(Arc::new(movie));
+
+    let progress = if !opt.silent {
+        let progress = ProgressBar::new(u64::from(end_frame));
+        progress.set_style(
+            ProgressStyle::default_bar()
+                .template(
+                    "[{elapsed_precise}] {bar:40.cyan/blue} [{eta_precise}] {pos:>7}/{len:7} {msg}",
+                )
+                .progress_chars("##-"),
+        );
+        Some(progress)
+    } else {
+        None
+    };
+
+    // Time only advances by exactly one frame per frame, so that recordings are
+    // the same regardless of how long it takes to render them.
+    let frame_rate = player.frame_rate();
+    let mut time = Duration::default();
+    for frame in 1..=end_frame {
+        if let Some(progress) = &progress {
+            progress.set_message(format!(
+                "{} frame {}",
+                opt.swf.file_stem().unwrap().to_string_lossy(),
+                frame
+            ));
+        }
+
+        let is_recording = frame >= start_frame;
+        player
+            .navigator_mut()
+            .downcast_mut::<NullNavigatorBackend>()
+            .unwrap()
+            .set_time_since_launch(time);
+        player.run_frame();
+
+        let audio = player
+            .audio_mut()
+            .downcast_mut::<RecordingAudioBackend>()
+            .unwrap();
+        audio.set_recording(is_recording && record.audio);
+        audio.record_frame();
+
+        let frame_time = Duration::from_secs_f64(1.0 / player.frame_rate());
+        player.update_timers(frame_time.as_secs_f64() * 1000.0);
+        time += frame_time;
+
+        if is_recording {
+            player.render();
+            let renderer = player
+                .renderer_mut()
+                .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+                .unwrap();
+            let image = renderer
+                .target()
+                .capture(renderer.device())
+                .ok_or_else(|| format!("Unable to capture frame {} of {:?}", frame, opt.swf))?;
+            image.save(output.join(format!("{:05}.png", frame - start_frame + 1)))?;
+        }
+
+        if let Some(progress) = &progress {
+            progress.inc(1);
+        }
+    }
+
+    let audio_path = output.join("audio.wav");
+    if record.audio {
+        player
+            .audio()
+            .downcast_ref::<RecordingAudioBackend>()
+            .unwrap()
+            .write_wav(&audio_path)?;
+    }
+
+    if let Some(video) = &record.video {
+        if let Some(progress) = &progress {
+            progress.set_message(format!("Encoding {}", video.to_string_lossy()));
+        }
+
+        let mut ffmpeg = Command::new("ffmpeg");
+        ffmpeg
+            .arg("-y")
+            .arg("-framerate")
+            .arg(frame_rate.to_string())
+            .arg("-i")
+            .arg(output.join("%05d.png"));
+        if record.audio {
+            ffmpeg.arg("-i").arg(&audio_path).args(&["-c:a", "aac"]);
+        }
+        let status = ffmpeg
+            .args(&["-c:v", "libx264", "-pix_fmt", "yuv420p"])
+            .arg(video)
+            .output()
+            .map_err(|e| format!("Unable to run ffmpeg: {}", e))?
+            .status;
+        if !status.success() {
+            return Err(format!("ffmpeg failed to encode {:?} ({})", video, status).into());
+        }
+    }
+
+    let message = format!(
+        "Recorded frames {} to {} of {} to {}",
+        start_frame,
+        end_frame,
+        opt.swf.to_string_lossy(),
+        record.video.as_ref().unwrap_or(&output).to_string_lossy()
+    );
+
+    if let Some(progress) = progress {
+        progress.finish_with_message(message);
+    } else {
+        println!("{}", message);
+    }
+
+    Ok(())
+}
+
 #[allow(unknown_lints, clippy::branches_sharing_code)]
 fn capture_multiple_swfs(mut descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn Error>> {
     let output = opt.output_path.clone().unwrap();
@@ -302,6 +516,7 @@ fn capture_multiple_swfs(mut descriptors: Descriptors, opt: &Opt) -> Result<(),
             opt.skipframes,
             &progress,
             opt.size,
+            opt.display_list,
         )?;
         descriptors = new_descriptors;
 
@@ -328,16 +543,17 @@ fn capture_multiple_swfs(mut descriptors: Descriptors, opt: &Opt) -> Result<(),
             if let Some(parent) = destination.parent() {
                 let _ = create_dir_all(parent);
             }
-            frames.get(0).unwrap().save(&destination)?;
+            let (image, display_list) = frames.get(0).unwrap();
+            save_frame(image, display_list, &destination)?;
         } else {
             let mut parent: PathBuf = (&output).into();
             relative_path.set_extension("");
             parent.push(&relative_path);
             let _ = create_dir_all(&parent);
-            for (frame, image) in frames.iter().enumerate() {
+            for (frame, (image, display_list)) in frames.iter().enumerate() {
                 let mut destination = parent.clone();
                 destination.push(format!("{}.png", frame));
-                image.save(&destination)?;
+                save_frame(image, display_list, &destination)?;
             }
         }
     }
@@ -392,7 +608,12 @@ fn main() -> Result<(), Box<dyn Error>> {
         trace_path(&opt),
     )?;
 
-    if opt.swf.is_file() {
+    if opt.record.enabled {
+        if !opt.swf.is_file() {
+            return Err("Recording is only supported for a single file.".into());
+        }
+        record_swf(descriptors, &opt)?;
+    } else if opt.swf.is_file() {
         capture_single_swf(descriptors, &opt)?;
     } else if opt.output_path.is_some() {
         capture_multiple_swfs(descriptors, &opt)?;
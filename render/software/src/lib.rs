@@ -0,0 +1,496 @@
+use ruffle_core::backend::render::{
+    Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, BitmapSource, Color, RenderBackend,
+    ShapeHandle, Transform,
+};
+use ruffle_core::matrix::Matrix;
+use ruffle_core::shape_utils::DistilledShape;
+use ruffle_core::swf;
+use ruffle_render_common_tess::{DrawType as TessDrawType, ShapeTessellator, Vertex as TessVertex};
+use std::collections::HashMap;
+
+use crate::paint::{BitmapFill, ColorAdjustments, Gradient, Paint, Texture};
+use crate::raster::{Framebuffer, MaskState, StencilState};
+
+pub use image::RgbaImage;
+
+mod paint;
+mod raster;
+
+type Error = Box<dyn std::error::Error>;
+
+/// A render backend that rasterizes everything on the CPU.
+///
+/// This needs neither a GPU nor a browser, so it can be used to render
+/// frames on headless machines. The rendered frame can be read back with
+/// `capture` after `end_frame` has been called.
+pub struct SoftwareRenderBackend {
+    framebuffer: Framebuffer,
+    /// The resolved image of the most recently finished frame.
+    pixels: Vec<u8>,
+    meshes: Vec<Mesh>,
+    shape_tessellator: ShapeTessellator,
+    textures: Vec<Texture>,
+    mask_state: MaskState,
+    num_masks: u8,
+    bitmap_registry: HashMap<BitmapHandle, Bitmap>,
+}
+
+struct Mesh {
+    draws: Vec<Draw>,
+}
+
+struct Draw {
+    paint: Paint,
+    vertices: Vec<Vertex>,
+    indices: Vec<u32>,
+}
+
+#[derive(Clone, Debug)]
+struct Vertex {
+    x: f32,
+    y: f32,
+    color: [f32; 4],
+}
+
+impl From<TessVertex> for Vertex {
+    fn from(vertex: TessVertex) -> Self {
+        Self {
+            x: vertex.x,
+            y: vertex.y,
+            color: [
+                f32::from(vertex.color.r) / 255.0,
+                f32::from(vertex.color.g) / 255.0,
+                f32::from(vertex.color.b) / 255.0,
+                f32::from(vertex.color.a) / 255.0,
+            ],
+        }
+    }
+}
+
+/// A unit quad used to draw bitmaps and rectangles.
+const QUAD_VERTICES: [(f32, f32); 4] = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+const QUAD_INDICES: [u32; 6] = [0, 1, 2, 0, 2, 3];
+
+impl SoftwareRenderBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        // Avoid 0-sized framebuffers.
+        let width = std::cmp::max(width, 1);
+        let height = std::cmp::max(height, 1);
+
+        Self {
+            framebuffer: Framebuffer::new(width, height),
+            pixels: vec![0; width as usize * height as usize * 4],
+            meshes: Vec::new(),
+            shape_tessellator: ShapeTessellator::new(),
+            textures: Vec::new(),
+            mask_state: MaskState::NoMask,
+            num_masks: 0,
+            bitmap_registry: HashMap::new(),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.framebuffer.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.framebuffer.height()
+    }
+
+    /// Returns the last rendered frame as a straight-alpha RGBA image.
+    pub fn capture(&self) -> RgbaImage {
+        RgbaImage::from_raw(self.width(), self.height(), self.pixels.clone())
+            .expect("Framebuffer size should match its dimensions")
+    }
+
+    fn register_shape_internal(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Mesh {
+        let lyon_mesh = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
+
+        let draws = lyon_mesh
+            .into_iter()
+            .map(|draw| Draw {
+                paint: match draw.draw_type {
+                    TessDrawType::Color => Paint::Color,
+                    TessDrawType::Gradient(gradient) => Paint::Gradient(Gradient::from(gradient)),
+                    TessDrawType::Bitmap(bitmap) => Paint::Bitmap(BitmapFill {
+                        matrix: bitmap.matrix,
+                        bitmap: bitmap.bitmap,
+                        is_smoothed: bitmap.is_smoothed,
+                        is_repeating: bitmap.is_repeating,
+                    }),
+                },
+                vertices: draw.vertices.into_iter().map(Vertex::from).collect(),
+                indices: draw.indices,
+            })
+            .collect();
+
+        Mesh { draws }
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> BitmapInfo {
+        let data = match &bitmap.data {
+            BitmapFormat::Rgba(data) => data.clone(),
+            BitmapFormat::Rgb(data) => {
+                // Expand to RGBA.
+                let mut as_rgba =
+                    Vec::with_capacity(bitmap.width as usize * bitmap.height as usize * 4);
+                for rgb in data.chunks_exact(3) {
+                    as_rgba.extend_from_slice(rgb);
+                    as_rgba.push(255);
+                }
+                as_rgba
+            }
+        };
+
+        let handle = BitmapHandle(self.textures.len());
+        let width = bitmap.width;
+        let height = bitmap.height;
+        self.bitmap_registry.insert(handle, bitmap);
+        self.textures.push(Texture {
+            width,
+            height,
+            data,
+        });
+
+        BitmapInfo {
+            handle,
+            width: width as u16,
+            height: height as u16,
+        }
+    }
+
+    /// Rasterizes a list of triangles transformed by `matrix`.
+    fn draw_triangles(
+        &mut self,
+        vertices: &[Vertex],
+        indices: &[u32],
+        matrix: &Matrix,
+        paint: &Paint,
+        color_adjustments: ColorAdjustments,
+    ) {
+        let stencil = StencilState::new(self.mask_state, self.num_masks);
+        let textures = &self.textures;
+        let framebuffer = &mut self.framebuffer;
+
+        let tx = matrix.tx.to_pixels() as f32;
+        let ty = matrix.ty.to_pixels() as f32;
+        let to_screen = |vertex: &Vertex| {
+            (
+                matrix.a * vertex.x + matrix.c * vertex.y + tx,
+                matrix.b * vertex.x + matrix.d * vertex.y + ty,
+            )
+        };
+
+        for triangle in indices.chunks_exact(3) {
+            let (v0, v1, v2) = match (
+                vertices.get(triangle[0] as usize),
+                vertices.get(triangle[1] as usize),
+                vertices.get(triangle[2] as usize),
+            ) {
+                (Some(v0), Some(v1), Some(v2)) => (v0, v1, v2),
+                _ => continue,
+            };
+
+            let points = [to_screen(v0), to_screen(v1), to_screen(v2)];
+            framebuffer.fill_triangle(points, stencil, |[w0, w1, w2]| {
+                // Interpolate the shape-space position and vertex color of the fragment.
+                let x = v0.x * w0 + v1.x * w1 + v2.x * w2;
+                let y = v0.y * w0 + v1.y * w1 + v2.y * w2;
+                match paint {
+                    Paint::Color => {
+                        let mut color = [0.0; 4];
+                        for (i, channel) in color.iter_mut().enumerate() {
+                            *channel = v0.color[i] * w0 + v1.color[i] * w1 + v2.color[i] * w2;
+                        }
+                        color_adjustments.apply(color)
+                    }
+                    Paint::Gradient(gradient) => color_adjustments.apply(gradient.color_at(x, y)),
+                    Paint::Bitmap(fill) => {
+                        if let Some(texture) = textures.get(fill.bitmap.0) {
+                            let (u, v) = fill.uv(x, y);
+                            let color = texture.sample(u, v, fill.is_smoothed, fill.is_repeating);
+                            color_adjustments.apply_premultiplied(color)
+                        } else {
+                            [0.0; 4]
+                        }
+                    }
+                }
+            });
+        }
+    }
+
+    fn draw_quad(&mut self, matrix: &Matrix, paint: &Paint, color_adjustments: ColorAdjustments) {
+        let vertices: Vec<Vertex> = QUAD_VERTICES
+            .iter()
+            .map(|&(x, y)| Vertex {
+                x,
+                y,
+                color: [1.0, 1.0, 1.0, 1.0],
+            })
+            .collect();
+        self.draw_triangles(&vertices, &QUAD_INDICES, matrix, paint, color_adjustments);
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
+        // Avoid 0-sized framebuffers.
+        let width = std::cmp::max(width, 1);
+        let height = std::cmp::max(height, 1);
+
+        if width != self.width() || height != self.height() {
+            self.framebuffer = Framebuffer::new(width, height);
+            self.pixels = vec![0; width as usize * height as usize * 4];
+        }
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        let handle = ShapeHandle(self.meshes.len());
+        let mesh = self.register_shape_internal(shape, bitmap_source);
+        self.meshes.push(mesh);
+        handle
+    }
+
+    fn replace_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+        handle: ShapeHandle,
+    ) {
+        let mesh = self.register_shape_internal(shape, bitmap_source);
+        self.meshes[handle.0] = mesh;
+    }
+
+    fn register_glyph_shape(&mut self, glyph: &swf::Glyph) -> ShapeHandle {
+        let shape = ruffle_core::shape_utils::swf_glyph_to_shape(glyph);
+        let handle = ShapeHandle(self.meshes.len());
+        let mesh = self.register_shape_internal(
+            (&shape).into(),
+            &ruffle_core::backend::render::NullBitmapSource,
+        );
+        self.meshes.push(mesh);
+        handle
+    }
+
+    fn register_bitmap_jpeg(
+        &mut self,
+        data: &[u8],
+        jpeg_tables: Option<&[u8]>,
+    ) -> Result<BitmapInfo, Error> {
+        let data = ruffle_core::backend::render::glue_tables_to_jpeg(data, jpeg_tables);
+        self.register_bitmap_jpeg_2(&data[..])
+    }
+
+    fn register_bitmap_jpeg_2(&mut self, data: &[u8]) -> Result<BitmapInfo, Error> {
+        let bitmap = ruffle_core::backend::render::decode_define_bits_jpeg(data, None)?;
+        Ok(self.register_bitmap(bitmap))
+    }
+
+    fn register_bitmap_jpeg_3(
+        &mut self,
+        jpeg_data: &[u8],
+        alpha_data: &[u8],
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap =
+            ruffle_core::backend::render::decode_define_bits_jpeg(jpeg_data, Some(alpha_data))?;
+        Ok(self.register_bitmap(bitmap))
+    }
+
+    fn register_bitmap_png(
+        &mut self,
+        swf_tag: &swf::DefineBitsLossless,
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap = ruffle_core::backend::render::decode_define_bits_lossless(swf_tag)?;
+        Ok(self.register_bitmap(bitmap))
+    }
+
+    fn begin_frame(&mut self, clear: Color) {
+        self.mask_state = MaskState::NoMask;
+        self.num_masks = 0;
+
+        let a = u16::from(clear.a);
+        let premultiply = |c: u8| ((u16::from(c) * a + 127) / 255) as u8;
+        self.framebuffer.clear([
+            premultiply(clear.r),
+            premultiply(clear.g),
+            premultiply(clear.b),
+            clear.a,
+        ]);
+    }
+
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool) {
+        if let Some(texture) = self.textures.get(bitmap.0) {
+            let matrix = transform.matrix
+                * Matrix {
+                    a: texture.width as f32,
+                    d: texture.height as f32,
+                    ..Default::default()
+                };
+
+            // The unit quad doubles as the texture coordinates of the bitmap.
+            let paint = Paint::Bitmap(BitmapFill {
+                matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+                bitmap,
+                is_smoothed: smoothing,
+                is_repeating: false,
+            });
+            self.draw_quad(&matrix, &paint, transform.color_transform.into());
+        }
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
+        // Temporarily take the mesh so that it can be drawn while borrowing `self` mutably.
+        let mesh = match self.meshes.get_mut(shape.0) {
+            Some(mesh) => std::mem::replace(mesh, Mesh { draws: Vec::new() }),
+            None => return,
+        };
+
+        for draw in &mesh.draws {
+            self.draw_triangles(
+                &draw.vertices,
+                &draw.indices,
+                &transform.matrix,
+                &draw.paint,
+                transform.color_transform.into(),
+            );
+        }
+
+        self.meshes[shape.0] = mesh;
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: &Matrix) {
+        let color_adjustments = ColorAdjustments::tint([
+            f32::from(color.r) / 255.0,
+            f32::from(color.g) / 255.0,
+            f32::from(color.b) / 255.0,
+            f32::from(color.a) / 255.0,
+        ]);
+        self.draw_quad(matrix, &Paint::Color, color_adjustments);
+    }
+
+    fn end_frame(&mut self) {
+        self.pixels = self.framebuffer.resolve();
+    }
+
+    fn push_mask(&mut self) {
+        debug_assert!(
+            self.mask_state == MaskState::NoMask || self.mask_state == MaskState::DrawMaskedContent
+        );
+        self.num_masks += 1;
+        self.mask_state = MaskState::DrawMaskStencil;
+    }
+
+    fn activate_mask(&mut self) {
+        debug_assert!(self.num_masks > 0 && self.mask_state == MaskState::DrawMaskStencil);
+        self.mask_state = MaskState::DrawMaskedContent;
+    }
+
+    fn deactivate_mask(&mut self) {
+        debug_assert!(self.num_masks > 0 && self.mask_state == MaskState::DrawMaskedContent);
+        self.mask_state = MaskState::ClearMaskStencil;
+    }
+
+    fn pop_mask(&mut self) {
+        debug_assert!(self.num_masks > 0 && self.mask_state == MaskState::ClearMaskStencil);
+        self.num_masks -= 1;
+        self.mask_state = if self.num_masks == 0 {
+            MaskState::NoMask
+        } else {
+            MaskState::DrawMaskedContent
+        };
+    }
+
+    fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
+        self.bitmap_registry.get(&bitmap).cloned()
+    }
+
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(self
+            .register_bitmap(Bitmap {
+                height,
+                width,
+                data: BitmapFormat::Rgba(rgba),
+            })
+            .handle)
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let texture = if let Some(texture) = self.textures.get_mut(handle.0) {
+            texture
+        } else {
+            return Err("update_texture: Bitmap not registered".into());
+        };
+
+        if rgba.len() != width as usize * height as usize * 4 {
+            return Err("update_texture: Pixel data does not match dimensions".into());
+        }
+
+        texture.width = width;
+        texture.height = height;
+        texture.data = rgba.clone();
+        self.bitmap_registry.insert(
+            handle,
+            Bitmap {
+                width,
+                height,
+                data: BitmapFormat::Rgba(rgba),
+            },
+        );
+
+        Ok(handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn updated_texture_pixels_are_read_back() {
+        let mut renderer = SoftwareRenderBackend::new(1, 1);
+        let handle = renderer
+            .register_bitmap_raw(1, 1, vec![255, 0, 0, 255])
+            .unwrap();
+
+        renderer
+            .update_texture(handle, 2, 1, vec![0, 255, 0, 255, 0, 0, 255, 128])
+            .unwrap();
+
+        let bitmap = renderer.get_bitmap_pixels(handle).unwrap();
+        assert_eq!((bitmap.width, bitmap.height), (2, 1));
+        match bitmap.data {
+            BitmapFormat::Rgba(data) => assert_eq!(data, vec![0, 255, 0, 255, 0, 0, 255, 128]),
+            BitmapFormat::Rgb(_) => panic!("Updated bitmaps should be RGBA"),
+        }
+    }
+
+    #[test]
+    fn updating_an_unknown_texture_fails() {
+        let mut renderer = SoftwareRenderBackend::new(1, 1);
+        assert!(renderer
+            .update_texture(BitmapHandle(0), 1, 1, vec![0; 4])
+            .is_err());
+        assert!(renderer.get_bitmap_pixels(BitmapHandle(0)).is_none());
+    }
+}
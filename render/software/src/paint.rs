@@ -0,0 +1,253 @@
+use ruffle_core::backend::render::{swf, BitmapHandle};
+use ruffle_core::color_transform::ColorTransform;
+use ruffle_render_common_tess::{Gradient as TessGradient, GradientType};
+
+/// The number of precomputed colors in a gradient ramp.
+const GRADIENT_RAMP_SIZE: usize = 256;
+
+/// How the pixels of a draw are colored.
+pub enum Paint {
+    Color,
+    Gradient(Gradient),
+    Bitmap(BitmapFill),
+}
+
+pub struct Gradient {
+    matrix: [[f32; 3]; 3],
+    gradient_type: GradientType,
+    repeat_mode: swf::GradientSpread,
+    focal_point: f32,
+    /// Straight-alpha sRGB colors sampled evenly across the gradient.
+    ramp: Vec<[f32; 4]>,
+}
+
+impl From<TessGradient> for Gradient {
+    fn from(gradient: TessGradient) -> Self {
+        let ratios = &gradient.ratios[..gradient.num_colors];
+        let colors = &gradient.colors[..gradient.num_colors];
+        let is_linear_rgb = gradient.interpolation == swf::GradientInterpolation::LinearRgb;
+
+        let ramp = (0..GRADIENT_RAMP_SIZE)
+            .map(|i| {
+                let t = i as f32 / (GRADIENT_RAMP_SIZE - 1) as f32;
+                let color = interpolate_gradient(ratios, colors, t);
+                if is_linear_rgb {
+                    [
+                        linear_to_srgb(color[0]),
+                        linear_to_srgb(color[1]),
+                        linear_to_srgb(color[2]),
+                        color[3],
+                    ]
+                } else {
+                    color
+                }
+            })
+            .collect();
+
+        Self {
+            matrix: gradient.matrix,
+            gradient_type: gradient.gradient_type,
+            repeat_mode: gradient.repeat_mode,
+            focal_point: gradient.focal_point.to_f32(),
+            ramp,
+        }
+    }
+}
+
+impl Gradient {
+    /// Returns the straight-alpha color of the gradient at the given point in shape space.
+    pub fn color_at(&self, x: f32, y: f32) -> [f32; 4] {
+        let (u, v) = transform_point(&self.matrix, x, y);
+        let t = match self.gradient_type {
+            GradientType::Linear => u,
+            GradientType::Radial => {
+                let (u, v) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                (u * u + v * v).sqrt()
+            }
+            GradientType::Focal => {
+                let focal_point = self.focal_point;
+                let (u, v) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                let (dx, dy) = (focal_point - u, -v);
+                let length = (dx * dx + dy * dy).sqrt();
+                let (dx, dy) = (dx / length, dy / length);
+                length / ((1.0 - focal_point * focal_point * dy * dy).sqrt() + focal_point * dx)
+            }
+        };
+
+        let t = match self.repeat_mode {
+            swf::GradientSpread::Pad => t.clamp(0.0, 1.0),
+            swf::GradientSpread::Repeat => t - t.floor(),
+            swf::GradientSpread::Reflect => {
+                let t = t.abs();
+                if (t as i32) & 1 == 0 {
+                    t.fract()
+                } else {
+                    1.0 - t.fract()
+                }
+            }
+        };
+
+        let index = (t * (GRADIENT_RAMP_SIZE - 1) as f32).round() as usize;
+        self.ramp[index.min(GRADIENT_RAMP_SIZE - 1)]
+    }
+}
+
+pub struct BitmapFill {
+    pub matrix: [[f32; 3]; 3],
+    pub bitmap: BitmapHandle,
+    pub is_smoothed: bool,
+    pub is_repeating: bool,
+}
+
+impl BitmapFill {
+    /// Returns the texture coordinates for the given point in shape space.
+    pub fn uv(&self, x: f32, y: f32) -> (f32, f32) {
+        transform_point(&self.matrix, x, y)
+    }
+}
+
+/// A bitmap stored as premultiplied RGBA.
+pub struct Texture {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl Texture {
+    /// Samples the texture at normalized texture coordinates, returning a premultiplied color.
+    pub fn sample(&self, u: f32, v: f32, is_smoothed: bool, is_repeating: bool) -> [f32; 4] {
+        if self.width == 0 || self.height == 0 || !u.is_finite() || !v.is_finite() {
+            return [0.0; 4];
+        }
+
+        let x = u * self.width as f32;
+        let y = v * self.height as f32;
+        if !is_smoothed {
+            return self.texel(x.floor() as i64, y.floor() as i64, is_repeating);
+        }
+
+        // Bilinear filtering between the four nearest texel centers.
+        let (x, y) = (x - 0.5, y - 0.5);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        let top_left = self.texel(x0, y0, is_repeating);
+        let top_right = self.texel(x0 + 1, y0, is_repeating);
+        let bottom_left = self.texel(x0, y0 + 1, is_repeating);
+        let bottom_right = self.texel(x0 + 1, y0 + 1, is_repeating);
+
+        let mut color = [0.0; 4];
+        for (i, channel) in color.iter_mut().enumerate() {
+            let top = top_left[i] + (top_right[i] - top_left[i]) * fx;
+            let bottom = bottom_left[i] + (bottom_right[i] - bottom_left[i]) * fx;
+            *channel = top + (bottom - top) * fy;
+        }
+        color
+    }
+
+    fn texel(&self, x: i64, y: i64, is_repeating: bool) -> [f32; 4] {
+        let (width, height) = (i64::from(self.width), i64::from(self.height));
+        let (x, y) = if is_repeating {
+            (x.rem_euclid(width), y.rem_euclid(height))
+        } else {
+            (x.clamp(0, width - 1), y.clamp(0, height - 1))
+        };
+        let i = (y * width + x) as usize * 4;
+        match self.data.get(i..i + 4) {
+            Some(texel) => [
+                f32::from(texel[0]) / 255.0,
+                f32::from(texel[1]) / 255.0,
+                f32::from(texel[2]) / 255.0,
+                f32::from(texel[3]) / 255.0,
+            ],
+            None => [0.0; 4],
+        }
+    }
+}
+
+/// The color transform of a draw, normalized for use while shading.
+#[derive(Debug, Clone, Copy)]
+pub struct ColorAdjustments {
+    mult_color: [f32; 4],
+    add_color: [f32; 4],
+}
+
+impl From<ColorTransform> for ColorAdjustments {
+    fn from(transform: ColorTransform) -> Self {
+        Self {
+            mult_color: transform.mult_rgba_normalized(),
+            add_color: transform.add_rgba_normalized(),
+        }
+    }
+}
+
+impl ColorAdjustments {
+    pub fn tint(color: [f32; 4]) -> Self {
+        Self {
+            mult_color: color,
+            add_color: [0.0; 4],
+        }
+    }
+
+    /// Applies the adjustments to a straight-alpha color, returning a premultiplied color.
+    pub fn apply(&self, color: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        for (i, channel) in out.iter_mut().enumerate() {
+            *channel = (self.mult_color[i] * color[i] + self.add_color[i]).clamp(0.0, 1.0);
+        }
+        [out[0] * out[3], out[1] * out[3], out[2] * out[3], out[3]]
+    }
+
+    /// Applies the adjustments to a premultiplied color, returning a premultiplied color.
+    pub fn apply_premultiplied(&self, color: [f32; 4]) -> [f32; 4] {
+        let alpha = color[3];
+        if alpha <= 0.0 {
+            return [0.0; 4];
+        }
+        self.apply([color[0] / alpha, color[1] / alpha, color[2] / alpha, alpha])
+    }
+}
+
+/// Applies a column-major 3x3 texture matrix to a point.
+fn transform_point(matrix: &[[f32; 3]; 3], x: f32, y: f32) -> (f32, f32) {
+    (
+        matrix[0][0] * x + matrix[1][0] * y + matrix[2][0],
+        matrix[0][1] * x + matrix[1][1] * y + matrix[2][1],
+    )
+}
+
+fn interpolate_gradient(ratios: &[f32], colors: &[[f32; 4]], t: f32) -> [f32; 4] {
+    let last = match ratios.len() {
+        0 => return [0.0; 4],
+        1 => return colors[0],
+        len => len - 1,
+    };
+
+    let t = t.clamp(ratios[0], ratios[last]);
+    let j = ratios
+        .iter()
+        .position(|ratio| t <= *ratio)
+        .unwrap_or(last)
+        .max(1);
+    let i = j - 1;
+
+    let range = ratios[j] - ratios[i];
+    let a = if range > 0.0 {
+        (t - ratios[i]) / range
+    } else {
+        0.0
+    };
+    let mut color = [0.0; 4];
+    for (k, channel) in color.iter_mut().enumerate() {
+        *channel = colors[i][k] + (colors[j][k] - colors[i][k]) * a;
+    }
+    color
+}
+
+fn linear_to_srgb(n: f32) -> f32 {
+    if n <= 0.0031308 {
+        n * 12.92
+    } else {
+        1.055 * n.powf(1.0 / 2.4) - 0.055
+    }
+}
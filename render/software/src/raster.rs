@@ -0,0 +1,354 @@
+/// The number of coverage samples taken for every pixel.
+pub const SAMPLE_COUNT: usize = 4;
+
+/// The position of each sample inside of a pixel.
+/// This is the standard 4x rotated grid pattern used by most GPUs.
+const SAMPLE_OFFSETS: [(f32, f32); SAMPLE_COUNT] = [
+    (0.375, 0.125),
+    (0.875, 0.375),
+    (0.125, 0.625),
+    (0.625, 0.875),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaskState {
+    NoMask,
+    DrawMaskStencil,
+    DrawMaskedContent,
+    ClearMaskStencil,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StencilOp {
+    Keep,
+    IncrementClamp,
+    DecrementClamp,
+}
+
+/// Describes how a draw interacts with the stencil buffer, mirroring the
+/// stencil states used by the GPU backends for masking.
+#[derive(Debug, Clone, Copy)]
+pub struct StencilState {
+    /// The value the stencil must be equal to for a sample to pass, or `None` to always pass.
+    reference: Option<u8>,
+    op: StencilOp,
+    write_color: bool,
+}
+
+impl StencilState {
+    pub fn new(mask_state: MaskState, num_masks: u8) -> Self {
+        match mask_state {
+            MaskState::NoMask => Self {
+                reference: None,
+                op: StencilOp::Keep,
+                write_color: true,
+            },
+            MaskState::DrawMaskStencil => {
+                debug_assert!(num_masks > 0);
+                Self {
+                    reference: Some(num_masks.saturating_sub(1)),
+                    op: StencilOp::IncrementClamp,
+                    write_color: false,
+                }
+            }
+            MaskState::DrawMaskedContent => {
+                debug_assert!(num_masks > 0);
+                Self {
+                    reference: Some(num_masks),
+                    op: StencilOp::Keep,
+                    write_color: true,
+                }
+            }
+            MaskState::ClearMaskStencil => {
+                debug_assert!(num_masks > 0);
+                Self {
+                    reference: Some(num_masks),
+                    op: StencilOp::DecrementClamp,
+                    write_color: false,
+                }
+            }
+        }
+    }
+
+    fn test(&self, value: u8) -> bool {
+        self.reference.map_or(true, |reference| value == reference)
+    }
+
+    fn apply(&self, value: &mut u8) {
+        match self.op {
+            StencilOp::Keep => (),
+            StencilOp::IncrementClamp => *value = value.saturating_add(1),
+            StencilOp::DecrementClamp => *value = value.saturating_sub(1),
+        }
+    }
+}
+
+/// A multisampled color and stencil buffer.
+///
+/// Colors are stored per sample with premultiplied alpha, and are averaged
+/// together when the buffer is resolved into the final image.
+pub struct Framebuffer {
+    width: u32,
+    height: u32,
+    color: Vec<[u8; 4]>,
+    stencil: Vec<u8>,
+}
+
+impl Framebuffer {
+    pub fn new(width: u32, height: u32) -> Self {
+        let num_samples = width as usize * height as usize * SAMPLE_COUNT;
+        Self {
+            width,
+            height,
+            color: vec![[0; 4]; num_samples],
+            stencil: vec![0; num_samples],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Fills every sample with the given premultiplied color, and resets the stencil.
+    pub fn clear(&mut self, color: [u8; 4]) {
+        self.color.iter_mut().for_each(|sample| *sample = color);
+        self.stencil.iter_mut().for_each(|sample| *sample = 0);
+    }
+
+    /// Averages the samples of each pixel, returning straight-alpha RGBA data.
+    pub fn resolve(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.width as usize * self.height as usize * 4);
+        for samples in self.color.chunks_exact(SAMPLE_COUNT) {
+            let mut sum = [0u32; 4];
+            for sample in samples {
+                for (total, channel) in sum.iter_mut().zip(sample) {
+                    *total += u32::from(*channel);
+                }
+            }
+            let half = SAMPLE_COUNT as u32 / 2;
+            let [r, g, b, a] = sum.map(|total| (total + half) / SAMPLE_COUNT as u32);
+            if a > 0 {
+                out.push(((r * 255 + a / 2) / a).min(255) as u8);
+                out.push(((g * 255 + a / 2) / a).min(255) as u8);
+                out.push(((b * 255 + a / 2) / a).min(255) as u8);
+            } else {
+                out.extend_from_slice(&[0, 0, 0]);
+            }
+            out.push(a as u8);
+        }
+        out
+    }
+
+    /// Rasterizes a triangle given in pixel coordinates.
+    ///
+    /// `shade` is called once per covered pixel with the barycentric weights of
+    /// the pixel center, and returns the premultiplied color of the fragment.
+    pub fn fill_triangle(
+        &mut self,
+        points: [(f32, f32); 3],
+        stencil: StencilState,
+        mut shade: impl FnMut([f32; 3]) -> [f32; 4],
+    ) {
+        if points.iter().any(|(x, y)| !x.is_finite() || !y.is_finite()) {
+            return;
+        }
+
+        // Normalize the winding order so that the edge functions are positive inside.
+        let [p0, mut p1, mut p2] = points;
+        let mut area = edge(p0, p1, p2);
+        let swapped = area < 0.0;
+        if swapped {
+            std::mem::swap(&mut p1, &mut p2);
+            area = -area;
+        }
+        if area <= f32::EPSILON {
+            return;
+        }
+
+        let min_x = p0.0.min(p1.0).min(p2.0).floor().max(0.0) as u32;
+        let min_y = p0.1.min(p1.1).min(p2.1).floor().max(0.0) as u32;
+        let max_x = (p0.0.max(p1.0).max(p2.0).ceil().max(0.0) as u32).min(self.width);
+        let max_y = (p0.1.max(p1.1).max(p2.1).ceil().max(0.0) as u32).min(self.height);
+
+        let top_left = [
+            is_top_left(p1, p2),
+            is_top_left(p2, p0),
+            is_top_left(p0, p1),
+        ];
+        let inside = |p: (f32, f32)| {
+            let weights = [edge(p1, p2, p), edge(p2, p0, p), edge(p0, p1, p)];
+            weights
+                .iter()
+                .zip(&top_left)
+                .all(|(w, top_left)| *w > 0.0 || (*top_left && *w >= 0.0))
+        };
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let base = (y as usize * self.width as usize + x as usize) * SAMPLE_COUNT;
+
+                let mut coverage = [false; SAMPLE_COUNT];
+                let mut any_covered = false;
+                for (i, (offset_x, offset_y)) in SAMPLE_OFFSETS.iter().enumerate() {
+                    if inside((x as f32 + offset_x, y as f32 + offset_y))
+                        && stencil.test(self.stencil[base + i])
+                    {
+                        coverage[i] = true;
+                        any_covered = true;
+                    }
+                }
+                if !any_covered {
+                    continue;
+                }
+
+                let color = if stencil.write_color {
+                    // Shade once per pixel at its center, like a GPU does with MSAA.
+                    let center = (x as f32 + 0.5, y as f32 + 0.5);
+                    let w0 = edge(p1, p2, center) / area;
+                    let w1 = edge(p2, p0, center) / area;
+                    let w2 = 1.0 - w0 - w1;
+                    Some(shade(if swapped { [w0, w2, w1] } else { [w0, w1, w2] }))
+                } else {
+                    None
+                };
+
+                for (i, _) in coverage.iter().enumerate().filter(|(_, covered)| **covered) {
+                    stencil.apply(&mut self.stencil[base + i]);
+                    if let Some(color) = color {
+                        blend(&mut self.color[base + i], color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The edge function of `c` relative to the line from `a` to `b`.
+/// This is twice the signed area of the triangle `abc`.
+fn edge(a: (f32, f32), b: (f32, f32), c: (f32, f32)) -> f32 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+/// Tie-breaking rule for samples lying exactly on an edge.
+/// Edges shared between two triangles run in opposite directions, so exactly
+/// one of the triangles will claim the sample.
+fn is_top_left(a: (f32, f32), b: (f32, f32)) -> bool {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    dy > 0.0 || (dy >= 0.0 && dx > 0.0)
+}
+
+/// Blends a premultiplied source color over a premultiplied destination sample.
+fn blend(dst: &mut [u8; 4], src: [f32; 4]) {
+    let inv_alpha = 1.0 - src[3].clamp(0.0, 1.0);
+    for (dst, src) in dst.iter_mut().zip(&src) {
+        let value = src + f32::from(*dst) / 255.0 * inv_alpha;
+        *dst = (value.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPAQUE_RED: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+
+    fn fill_rect(framebuffer: &mut Framebuffer, x0: f32, y0: f32, x1: f32, y1: f32) {
+        let stencil = StencilState::new(MaskState::NoMask, 0);
+        framebuffer.fill_triangle([(x0, y0), (x1, y0), (x1, y1)], stencil, |_| OPAQUE_RED);
+        framebuffer.fill_triangle([(x0, y0), (x1, y1), (x0, y1)], stencil, |_| OPAQUE_RED);
+    }
+
+    fn pixel(pixels: &[u8], width: u32, x: u32, y: u32) -> [u8; 4] {
+        let i = (y * width + x) as usize * 4;
+        [pixels[i], pixels[i + 1], pixels[i + 2], pixels[i + 3]]
+    }
+
+    #[test]
+    fn pixel_aligned_rect() {
+        let mut framebuffer = Framebuffer::new(4, 4);
+        framebuffer.clear([0, 0, 0, 0]);
+        fill_rect(&mut framebuffer, 1.0, 1.0, 3.0, 3.0);
+        let pixels = framebuffer.resolve();
+
+        for y in 0..4 {
+            for x in 0..4 {
+                let expected = if (1..3).contains(&x) && (1..3).contains(&y) {
+                    [255, 0, 0, 255]
+                } else {
+                    [0, 0, 0, 0]
+                };
+                assert_eq!(pixel(&pixels, 4, x, y), expected, "pixel ({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn shared_edges_are_drawn_once() {
+        // Both triangles are half transparent; a sample on the shared diagonal
+        // would come out darker if it were blended twice.
+        let mut framebuffer = Framebuffer::new(2, 2);
+        framebuffer.clear([0, 0, 0, 0]);
+        let stencil = StencilState::new(MaskState::NoMask, 0);
+        let half = [0.5, 0.0, 0.0, 0.5];
+        framebuffer.fill_triangle([(0.0, 0.0), (2.0, 0.0), (2.0, 2.0)], stencil, |_| half);
+        framebuffer.fill_triangle([(0.0, 0.0), (2.0, 2.0), (0.0, 2.0)], stencil, |_| half);
+
+        for sample in &framebuffer.color {
+            assert_eq!(*sample, [128, 0, 0, 128]);
+        }
+    }
+
+    #[test]
+    fn partial_coverage_is_antialiased() {
+        let mut framebuffer = Framebuffer::new(1, 1);
+        framebuffer.clear([0, 0, 0, 0]);
+        // Covers the left half of the pixel, which holds two of the four samples.
+        fill_rect(&mut framebuffer, 0.0, 0.0, 0.5, 1.0);
+        assert_eq!(framebuffer.resolve(), vec![255, 0, 0, 128]);
+    }
+
+    #[test]
+    fn winding_order_does_not_matter() {
+        let stencil = StencilState::new(MaskState::NoMask, 0);
+        let mut clockwise = Framebuffer::new(3, 3);
+        clockwise.fill_triangle([(0.0, 0.0), (3.0, 0.0), (0.0, 3.0)], stencil, |_| {
+            OPAQUE_RED
+        });
+        let mut counter_clockwise = Framebuffer::new(3, 3);
+        counter_clockwise.fill_triangle([(0.0, 0.0), (0.0, 3.0), (3.0, 0.0)], stencil, |_| {
+            OPAQUE_RED
+        });
+        assert_eq!(clockwise.resolve(), counter_clockwise.resolve());
+    }
+
+    #[test]
+    fn masked_content_is_clipped_to_the_mask() {
+        let mut framebuffer = Framebuffer::new(4, 1);
+        framebuffer.clear([0, 0, 0, 0]);
+        let white = [1.0, 1.0, 1.0, 1.0];
+
+        // Mask the left half of the buffer.
+        let stencil = StencilState::new(MaskState::DrawMaskStencil, 1);
+        framebuffer.fill_triangle([(0.0, 0.0), (2.0, 0.0), (2.0, 1.0)], stencil, |_| white);
+        framebuffer.fill_triangle([(0.0, 0.0), (2.0, 1.0), (0.0, 1.0)], stencil, |_| white);
+
+        // Draw over the whole buffer; only the masked area should be colored.
+        let stencil = StencilState::new(MaskState::DrawMaskedContent, 1);
+        framebuffer.fill_triangle([(0.0, 0.0), (4.0, 0.0), (4.0, 1.0)], stencil, |_| {
+            OPAQUE_RED
+        });
+        framebuffer.fill_triangle([(0.0, 0.0), (4.0, 1.0), (0.0, 1.0)], stencil, |_| {
+            OPAQUE_RED
+        });
+
+        let pixels = framebuffer.resolve();
+        assert_eq!(pixel(&pixels, 4, 0, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(&pixels, 4, 1, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(&pixels, 4, 2, 0), [0, 0, 0, 0]);
+        assert_eq!(pixel(&pixels, 4, 3, 0), [0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,76 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::sync::{Arc, Mutex};
+
+/// A log message emitted while scanning a file.
+#[derive(Debug, Clone)]
+pub struct CapturedLog {
+    pub level: Level,
+    /// The module the message was logged from.
+    pub target: String,
+    pub message: String,
+}
+
+/// A handle to the messages recorded by the scan logger.
+#[derive(Clone)]
+pub struct LogCapture(Arc<Mutex<Vec<CapturedLog>>>);
+
+impl LogCapture {
+    /// Returns and clears every message captured since the last call.
+    pub fn take(&self) -> Vec<CapturedLog> {
+        self.0
+            .lock()
+            .map(|mut captured| std::mem::take(&mut *captured))
+            .unwrap_or_default()
+    }
+}
+
+/// A logger which records every warning and error emitted by the player, so
+/// they can be attributed to the file being scanned.
+///
+/// Everything is also forwarded to `env_logger`, so `RUST_LOG` keeps working.
+struct ScanLogger {
+    inner: env_logger::Logger,
+    capture: LogCapture,
+}
+
+impl Log for ScanLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Warn || self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if record.level() <= Level::Warn {
+            if let Ok(mut captured) = self.capture.0.lock() {
+                captured.push(CapturedLog {
+                    level: record.level(),
+                    target: record.target().to_string(),
+                    message: record.args().to_string(),
+                });
+            }
+        }
+
+        if self.inner.matches(record) {
+            self.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Installs the capturing logger, replacing `env_logger::init`.
+pub fn init() -> LogCapture {
+    let inner = env_logger::Builder::from_default_env().build();
+    let max_level = inner.filter().max(LevelFilter::Warn);
+    let capture = LogCapture(Arc::new(Mutex::new(Vec::new())));
+
+    log::set_boxed_logger(Box::new(ScanLogger {
+        inner,
+        capture: capture.clone(),
+    }))
+    .expect("Logger should only be initialized once");
+    log::set_max_level(max_level);
+
+    capture
+}
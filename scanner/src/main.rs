@@ -1,19 +1,26 @@
+mod logging;
+mod output;
+mod pool;
+mod scan;
+
+use crate::logging::LogCapture;
+use crate::output::{OutputFormat, ResultWriter};
+use crate::pool::{ScanJob, WorkerOptions};
+use crate::scan::{scan_file, RunOptions};
 use clap::Clap;
 use indicatif::{ProgressBar, ProgressStyle};
 use path_slash::PathExt;
-use ruffle_core::swf::{decompress_swf, parse_swf};
 
-use serde::Serialize;
+use std::error::Error;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::time::Duration;
 
-use std::panic::catch_unwind;
 use walkdir::{DirEntry, WalkDir};
 
-#[derive(Serialize, Debug)]
-struct FileResults {
-    name: String,
-    error: Option<String>,
-}
+/// Extra time given to a worker before it's killed, so that movies which
+/// time out can still report how far they got.
+const WORKER_GRACE_PERIOD: Duration = Duration::from_secs(2);
 
 #[derive(Clap, Debug)]
 #[clap(version, about, author)]
@@ -22,13 +29,46 @@ struct Opt {
     #[clap(name = "directory", parse(from_os_str))]
     input_path: PathBuf,
 
-    /// The file to store results in CSV format
+    /// The file to store results in
     #[clap(name = "results", parse(from_os_str))]
     output_path: PathBuf,
 
     /// Filenames to ignore
     #[clap(short = 'i', long = "ignore")]
     ignore: Vec<String>,
+
+    /// The format to store results in
+    #[clap(long, case_insensitive = true, default_value = "csv", arg_enum)]
+    format: OutputFormat,
+
+    /// Run each file in a headless player, and record any errors or warnings it produces
+    #[clap(long = "run")]
+    run: bool,
+
+    /// Number of frames to run each file for
+    #[clap(long = "frames", default_value = "60")]
+    frames: u32,
+
+    /// Number of seconds a file may run for before it's considered to have timed out
+    #[clap(long = "timeout", default_value = "10")]
+    timeout: u64,
+
+    /// Number of files to scan in parallel. Defaults to the number of CPU cores
+    #[clap(short = 'j', long = "jobs")]
+    jobs: Option<usize>,
+
+    /// Maximum amount of memory in megabytes that scanning a single file may use
+    #[clap(long = "memory-limit", default_value = "2048")]
+    memory_limit: u64,
+
+    /// Continue a previous scan, skipping all files which are already in the results file
+    #[clap(long = "resume")]
+    resume: bool,
+
+    /// Scan only the given file and print the results to stdout.
+    /// Used internally for each child process
+    #[clap(long = "worker", hidden = true)]
+    worker: bool,
 }
 
 fn find_files(root: &Path, ignore: &[String]) -> Vec<DirEntry> {
@@ -52,51 +92,58 @@ fn find_files(root: &Path, ignore: &[String]) -> Vec<DirEntry> {
     results
 }
 
-fn scan_file(file: DirEntry, name: String) -> FileResults {
-    let data = match std::fs::read(file.path()) {
-        Ok(data) => data,
-        Err(e) => {
-            return {
-                FileResults {
-                    name,
-                    error: Some(format!("File error: {}", e.to_string())),
-                }
-            }
-        }
-    };
-
-    let swf_buf = decompress_swf(&data[..]).unwrap();
-    match catch_unwind(|| parse_swf(&swf_buf)) {
-        Ok(swf) => match swf {
-            Ok(_swf) => FileResults { name, error: None },
-            Err(e) => FileResults {
-                name,
-                error: Some(format!("Parse error: {}", e.to_string())),
-            },
-        },
-        Err(e) => match e.downcast::<String>() {
-            Ok(e) => FileResults {
-                name,
-                error: Some(format!("PANIC: {}", e.to_string())),
-            },
-            Err(_) => FileResults {
-                name,
-                error: Some("PANIC".to_string()),
-            },
-        },
-    }
+/// The entry point of each child process, which scans a single file.
+fn run_worker(
+    opt: &Opt,
+    run: Option<RunOptions>,
+    log_capture: &LogCapture,
+) -> Result<(), Box<dyn Error>> {
+    pool::set_memory_limit(opt.memory_limit);
+
+    let name = opt.input_path.to_slash_lossy();
+    let results = scan_file(&opt.input_path, name, run, log_capture);
+    serde_json::to_writer(std::io::stdout(), &results)?;
+    Ok(())
 }
 
-fn main() -> Result<(), std::io::Error> {
-    env_logger::init();
+fn main() -> Result<(), Box<dyn Error>> {
+    let log_capture = logging::init();
 
     let opt = Opt::parse();
-    let to_scan = find_files(&opt.input_path, &opt.ignore);
+    let run = if opt.run {
+        Some(RunOptions {
+            frames: opt.frames,
+            timeout: Duration::from_secs(opt.timeout),
+        })
+    } else {
+        None
+    };
+
+    if opt.worker {
+        return run_worker(&opt, run, &log_capture);
+    }
+
+    let (mut writer, scanned) =
+        ResultWriter::open(&opt.output_path, opt.format, opt.resume, opt.run)?;
+    let to_scan: Vec<ScanJob> = find_files(&opt.input_path, &opt.ignore)
+        .into_iter()
+        .map(|file| {
+            let name = file
+                .path()
+                .strip_prefix(&opt.input_path)
+                .unwrap_or_else(|_| file.path())
+                .to_slash_lossy();
+            ScanJob {
+                path: file.into_path(),
+                name,
+            }
+        })
+        .filter(|job| !scanned.contains(&job.name))
+        .collect();
     let total = to_scan.len() as u64;
     let mut good = 0;
     let mut bad = 0;
     let progress = ProgressBar::new(total);
-    let mut writer = csv::Writer::from_path(opt.output_path)?;
 
     progress.set_style(
         ProgressStyle::default_bar()
@@ -105,27 +152,42 @@ fn main() -> Result<(), std::io::Error> {
             )
             .progress_chars("##-"),
     );
+    if !scanned.is_empty() {
+        progress.println(format!(
+            "Skipping {} files which were already scanned",
+            scanned.len()
+        ));
+    }
 
-    writer.write_record(&["Filename", "Error"])?;
+    let (sender, receiver) = channel();
+    let workers = pool::scan_all(
+        to_scan,
+        opt.jobs.unwrap_or_else(num_cpus::get),
+        WorkerOptions {
+            run,
+            timeout: Duration::from_secs(opt.timeout) + WORKER_GRACE_PERIOD,
+            memory_limit: opt.memory_limit,
+        },
+        sender,
+    );
 
-    for file in to_scan {
-        let name = file
-            .path()
-            .strip_prefix(&opt.input_path)
-            .unwrap_or_else(|_| file.path())
-            .to_slash_lossy();
+    for result in receiver {
         progress.inc(1);
-        progress.set_message(name.clone());
-        let result = scan_file(file, name);
+        progress.set_message(result.name.clone());
 
-        if result.error.is_none() {
-            good += 1;
-        } else {
+        if result.is_error() {
             bad += 1;
+        } else {
+            good += 1;
         }
 
-        writer.serialize(result)?;
+        writer.write(result)?;
+    }
+
+    for worker in workers {
+        let _ = worker.join();
     }
+    writer.finish()?;
 
     progress.finish_with_message(format!(
         "Scanned {} swf files. {} successfully parsed, {} encountered errors",
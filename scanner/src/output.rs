@@ -0,0 +1,355 @@
+use crate::scan::FileResults;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Copy, Clone, PartialEq, Debug, clap::ArgEnum)]
+pub enum OutputFormat {
+    Csv,
+    /// A single JSON array, written once the scan is done.
+    Json,
+    /// One JSON object per line, written as soon as each file is scanned.
+    JsonLines,
+}
+
+/// A single line of the CSV output of a scan that only parsed the files.
+///
+/// These are the columns the scanner has always written, so existing consumers keep working.
+#[derive(Serialize, Debug)]
+struct BasicCsvRow<'a> {
+    name: &'a str,
+    error: Option<&'a str>,
+}
+
+/// The header of the CSV output of a scan that only parsed the files.
+const BASIC_CSV_HEADER: &[&str] = &["name", "error"];
+
+impl<'a> From<&'a FileResults> for BasicCsvRow<'a> {
+    fn from(results: &'a FileResults) -> Self {
+        // Panics and timeouts have their own columns with `--run`, but were always
+        // reported as errors here.
+        let error = results
+            .error
+            .as_deref()
+            .or_else(|| results.panic.as_deref())
+            .or_else(|| results.timed_out.then(|| "Timed out"));
+        Self {
+            name: &results.name,
+            error,
+        }
+    }
+}
+
+/// A single line of the CSV output of a scan with `--run`, with all lists joined into one column.
+///
+/// The first columns match `BasicCsvRow`; everything else is appended after them.
+#[derive(Serialize, Debug)]
+struct CsvRow<'a> {
+    name: &'a str,
+    error: Option<&'a str>,
+    swf_version: Option<u8>,
+    avm_version: Option<u8>,
+    tags: String,
+    frames_run: u32,
+    unimplemented: String,
+    warnings: String,
+    avm_errors: String,
+    panic: Option<&'a str>,
+    timed_out: bool,
+}
+
+/// The header of the CSV output of a scan with `--run`.
+const RUN_CSV_HEADER: &[&str] = &[
+    "name",
+    "error",
+    "swf_version",
+    "avm_version",
+    "tags",
+    "frames_run",
+    "unimplemented",
+    "warnings",
+    "avm_errors",
+    "panic",
+    "timed_out",
+];
+
+impl<'a> From<&'a FileResults> for CsvRow<'a> {
+    fn from(results: &'a FileResults) -> Self {
+        Self {
+            name: &results.name,
+            error: results.error.as_deref(),
+            swf_version: results.swf_version,
+            avm_version: results.avm_version,
+            tags: results
+                .tags
+                .iter()
+                .map(|(tag, count)| format!("{}={}", tag, count))
+                .collect::<Vec<_>>()
+                .join(" "),
+            frames_run: results.frames_run,
+            unimplemented: results.unimplemented.join("\n"),
+            warnings: results.warnings.join("\n"),
+            avm_errors: results.avm_errors.join("\n"),
+            panic: results.panic.as_deref(),
+            timed_out: results.timed_out,
+        }
+    }
+}
+
+/// The only column needed to resume a scan.
+#[derive(Deserialize)]
+struct ScannedFile {
+    name: String,
+}
+
+/// Writes results as soon as they come in, so that an interrupted scan can be resumed.
+/// The only exception is a plain JSON array, which is written once the scan is done.
+pub enum ResultWriter {
+    Csv {
+        writer: csv::Writer<File>,
+        /// Whether to write the extra columns of a scan with `--run`.
+        run: bool,
+    },
+    Json {
+        path: PathBuf,
+        results: Vec<FileResults>,
+    },
+    JsonLines(BufWriter<File>),
+}
+
+impl ResultWriter {
+    /// Creates the results file, or appends to it when resuming.
+    /// Returns the names of all files that were already scanned.
+    pub fn open(
+        path: &Path,
+        format: OutputFormat,
+        resume: bool,
+        run: bool,
+    ) -> Result<(Self, HashSet<String>), Box<dyn Error>> {
+        let previous = if resume && path.exists() {
+            Some(std::fs::read(path)?)
+        } else {
+            None
+        };
+
+        if format == OutputFormat::Json {
+            let results: Vec<FileResults> = match &previous {
+                Some(data) => serde_json::from_slice(data)
+                    .map_err(|e| format!("Unable to resume from {:?}: {}", path, e))?,
+                None => Vec::new(),
+            };
+            let scanned = results.iter().map(|result| result.name.clone()).collect();
+            let writer = ResultWriter::Json {
+                path: path.to_owned(),
+                results,
+            };
+            return Ok((writer, scanned));
+        }
+
+        let (file, scanned) = if let Some(data) = previous {
+            let (scanned, valid_len) = if format == OutputFormat::Csv {
+                let header = if run {
+                    RUN_CSV_HEADER
+                } else {
+                    BASIC_CSV_HEADER
+                };
+                read_scanned_csv(&data, header)
+                    .map_err(|e| format!("Unable to resume from {:?}: {}", path, e))?
+            } else {
+                read_scanned_json_lines(&data)
+            };
+
+            // Drop any partially written row, so that new rows start on a line of their own.
+            let file = OpenOptions::new().append(true).open(path)?;
+            file.set_len(valid_len)?;
+            (file, scanned)
+        } else {
+            (File::create(path)?, HashSet::new())
+        };
+        let is_empty = file.metadata()?.len() == 0;
+
+        let writer = match format {
+            OutputFormat::Csv => ResultWriter::Csv {
+                writer: csv::WriterBuilder::new()
+                    .has_headers(is_empty)
+                    .from_writer(file),
+                run,
+            },
+            _ => ResultWriter::JsonLines(BufWriter::new(file)),
+        };
+        Ok((writer, scanned))
+    }
+
+    pub fn write(&mut self, results: FileResults) -> Result<(), Box<dyn Error>> {
+        match self {
+            ResultWriter::Csv { writer, run } => {
+                if *run {
+                    writer.serialize(CsvRow::from(&results))?;
+                } else {
+                    writer.serialize(BasicCsvRow::from(&results))?;
+                }
+                writer.flush()?;
+            }
+            ResultWriter::Json { results: all, .. } => all.push(results),
+            ResultWriter::JsonLines(writer) => {
+                serde_json::to_writer(&mut *writer, &results)?;
+                writer.write_all(b"\n")?;
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes out any results that were held back until the end of the scan.
+    pub fn finish(self) -> Result<(), Box<dyn Error>> {
+        if let ResultWriter::Json { path, results } = self {
+            let mut writer = BufWriter::new(File::create(path)?);
+            serde_json::to_writer_pretty(&mut writer, &results)?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether the data before `position` ends with a complete line.
+fn ends_line(data: &[u8], position: &csv::Position) -> bool {
+    let end = position.byte() as usize;
+    end > 0 && data.get(end - 1) == Some(&b'\n')
+}
+
+/// Reads the names of the files in the results of a previous CSV scan.
+/// Also returns the length of the data up to the end of its last complete row.
+///
+/// Fails if the previous scan wrote different columns than `header`, since appending to it
+/// would mix two layouts in one file.
+fn read_scanned_csv(data: &[u8], header: &[&str]) -> Result<(HashSet<String>, u64), String> {
+    let mut scanned = HashSet::new();
+    let mut reader = csv::Reader::from_reader(data);
+    let previous_header = match reader.headers() {
+        Ok(previous_header) if ends_line(data, reader.position()) => previous_header.clone(),
+        _ => return Ok((scanned, 0)),
+    };
+    if previous_header.iter().ne(header.iter().copied()) {
+        return Err(format!(
+            "it has the columns {:?}, but this scan writes {:?} (is `--run` set the same way?)",
+            previous_header.iter().collect::<Vec<_>>(),
+            header
+        ));
+    }
+
+    // The last row may be incomplete if the previous scan was interrupted.
+    let mut valid_len = reader.position().byte();
+    let mut record = csv::StringRecord::new();
+    while let Ok(true) = reader.read_record(&mut record) {
+        if !ends_line(data, reader.position()) {
+            break;
+        }
+        if let Some(name) = record.get(0) {
+            scanned.insert(name.to_string());
+        }
+        valid_len = reader.position().byte();
+    }
+    Ok((scanned, valid_len))
+}
+
+/// Reads the names of the files in the results of a previous JSON Lines scan.
+/// Also returns the length of the data up to the end of its last complete line.
+fn read_scanned_json_lines(data: &[u8]) -> (HashSet<String>, u64) {
+    let mut scanned = HashSet::new();
+    let mut valid_len = 0;
+    for line in data.split_inclusive(|byte| *byte == b'\n') {
+        if !line.ends_with(b"\n") {
+            break;
+        }
+        if let Ok(record) = serde_json::from_slice::<ScannedFile>(line) {
+            scanned.insert(record.name);
+        }
+        valid_len += line.len() as u64;
+    }
+    (scanned, valid_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serializes rows the way `ResultWriter` does, returning the header and the first row.
+    fn to_csv<T: Serialize>(row: T) -> (Vec<String>, Vec<String>) {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer.serialize(row).unwrap();
+        let data = writer.into_inner().unwrap();
+        let mut reader = csv::Reader::from_reader(&data[..]);
+        let header = reader.headers().unwrap().iter().map(String::from).collect();
+        let row = reader.records().next().unwrap().unwrap();
+        (header, row.iter().map(String::from).collect())
+    }
+
+    #[test]
+    fn csv_headers_match_rows() {
+        let results = FileResults::new("a.swf".to_string());
+        assert_eq!(to_csv(BasicCsvRow::from(&results)).0, BASIC_CSV_HEADER);
+        assert_eq!(to_csv(CsvRow::from(&results)).0, RUN_CSV_HEADER);
+    }
+
+    #[test]
+    fn basic_csv_reports_panics_and_timeouts_as_errors() {
+        let mut panicked = FileResults::new("a.swf".to_string());
+        panicked.panic = Some("PANIC: oops".to_string());
+        assert_eq!(
+            to_csv(BasicCsvRow::from(&panicked)).1,
+            ["a.swf", "PANIC: oops"]
+        );
+
+        let mut timed_out = FileResults::new("b.swf".to_string());
+        timed_out.timed_out = true;
+        assert_eq!(
+            to_csv(BasicCsvRow::from(&timed_out)).1,
+            ["b.swf", "Timed out"]
+        );
+
+        let mut failed = FileResults::new("c.swf".to_string());
+        failed.error = Some("Parse error: bad".to_string());
+        failed.panic = Some("PANIC: oops".to_string());
+        assert_eq!(
+            to_csv(BasicCsvRow::from(&failed)).1,
+            ["c.swf", "Parse error: bad"]
+        );
+    }
+
+    #[test]
+    fn csv_resume_rejects_other_header() {
+        let data = b"name,error\na.swf,\n";
+        assert!(read_scanned_csv(data, RUN_CSV_HEADER).is_err());
+        assert!(read_scanned_csv(data, BASIC_CSV_HEADER).is_ok());
+    }
+
+    #[test]
+    fn csv_resume_drops_partial_row() {
+        let data = b"name,error\na.swf,\nb.swf,\"Parse error:\nbad\"\nc.swf,\"Parse";
+        let (scanned, valid_len) = read_scanned_csv(data, BASIC_CSV_HEADER).unwrap();
+        assert_eq!(valid_len as usize, data.len() - "c.swf,\"Parse".len());
+        assert!(scanned.contains("a.swf"));
+        assert!(scanned.contains("b.swf"));
+        assert!(!scanned.contains("c.swf"));
+    }
+
+    #[test]
+    fn csv_resume_with_partial_header() {
+        assert_eq!(
+            read_scanned_csv(b"name,er", BASIC_CSV_HEADER),
+            Ok((HashSet::new(), 0))
+        );
+    }
+
+    #[test]
+    fn json_lines_resume_drops_partial_line() {
+        let data = b"{\"name\":\"a.swf\"}\n{\"name\":\"b.s";
+        let (scanned, valid_len) = read_scanned_json_lines(data);
+        assert_eq!(valid_len, 17);
+        assert_eq!(scanned.len(), 1);
+        assert!(scanned.contains("a.swf"));
+    }
+}
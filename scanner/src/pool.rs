@@ -0,0 +1,218 @@
+use crate::scan::{FileResults, RunOptions};
+use std::collections::VecDeque;
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How often a worker checks whether its child process has finished.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A file waiting to be scanned.
+pub struct ScanJob {
+    pub path: PathBuf,
+    pub name: String,
+}
+
+/// Settings shared by every child process.
+#[derive(Debug, Clone, Copy)]
+pub struct WorkerOptions {
+    pub run: Option<RunOptions>,
+
+    /// How long a child process may take before it's killed.
+    pub timeout: Duration,
+
+    /// The address space limit of each child process, in megabytes.
+    pub memory_limit: u64,
+}
+
+/// Scans all files in separate child processes, `num_workers` at a time.
+///
+/// Every child runs this same executable in worker mode, so that a panic
+/// (which aborts), an infinite loop or a runaway allocation only takes down
+/// the scan of a single file. Results are sent as soon as each file is done.
+pub fn scan_all(
+    jobs: Vec<ScanJob>,
+    num_workers: usize,
+    options: WorkerOptions,
+    results: Sender<FileResults>,
+) -> Vec<JoinHandle<()>> {
+    let queue = Arc::new(Mutex::new(VecDeque::from(jobs)));
+
+    (0..num_workers.max(1))
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            let results = results.clone();
+            thread::spawn(move || loop {
+                let job = match queue.lock().unwrap().pop_front() {
+                    Some(job) => job,
+                    None => break,
+                };
+                if results.send(scan_in_child(job, options)).is_err() {
+                    break;
+                }
+            })
+        })
+        .collect()
+}
+
+fn scan_in_child(job: ScanJob, options: WorkerOptions) -> FileResults {
+    let mut results = FileResults::new(job.name.clone());
+
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => {
+            results.error = Some(format!("Unable to find scanner executable: {}", e));
+            return results;
+        }
+    };
+
+    let mut command = Command::new(exe);
+    command
+        .arg("--worker")
+        .arg(&job.path)
+        .arg("-")
+        .arg("--memory-limit")
+        .arg(options.memory_limit.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(run) = options.run {
+        command
+            .arg("--run")
+            .arg("--frames")
+            .arg(run.frames.to_string())
+            .arg("--timeout")
+            .arg(run.timeout.as_secs().to_string());
+    }
+
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            results.error = Some(format!("Unable to start worker: {}", e));
+            return results;
+        }
+    };
+
+    // Drain both pipes while waiting, so a chatty child can't block on a full pipe.
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+    let status = wait_with_timeout(&mut child, options.timeout);
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    match status {
+        Some(status) if status.success() => match serde_json::from_str(&stdout) {
+            Ok(child_results) => {
+                results = child_results;
+                results.name = job.name;
+            }
+            Err(e) => results.error = Some(format!("Invalid worker output: {}", e)),
+        },
+        Some(status) => {
+            if stderr.contains("memory allocation of") {
+                results.error = Some("Out of memory".to_string());
+            } else {
+                results.panic = Some(match panic_message(&stderr) {
+                    Some(message) => format!("PANIC: {}", message),
+                    None => format!("PANIC: worker exited with {}", status),
+                });
+            }
+        }
+        None => results.timed_out = true,
+    }
+
+    results
+}
+
+/// Extracts the panic message from a worker's stderr.
+/// Since Rust 1.57, the message is printed on the lines after "panicked at", so those are kept too.
+fn panic_message(stderr: &str) -> Option<String> {
+    let mut lines = stderr
+        .lines()
+        .skip_while(|line| !line.contains("panicked at"));
+    let mut message = lines.next()?.trim().to_string();
+    for line in lines.take_while(|line| !line.trim().is_empty() && !line.starts_with("note:")) {
+        message.push(' ');
+        message.push_str(line.trim());
+    }
+    Some(message)
+}
+
+/// Waits for the child to exit, killing it if it takes longer than `timeout`.
+/// Returns `None` if the child was killed.
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Option<ExitStatus> {
+    let start = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Some(status),
+            Ok(None) if start.elapsed() < timeout => thread::sleep(POLL_INTERVAL),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+}
+
+fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut output);
+        }
+        output
+    })
+}
+
+/// Limits the address space of this process, so that a runaway movie aborts
+/// instead of exhausting the memory of the whole machine.
+#[cfg(unix)]
+pub fn set_memory_limit(megabytes: u64) {
+    let bytes = megabytes.saturating_mul(1024 * 1024) as libc::rlim_t;
+    let limit = libc::rlimit {
+        rlim_cur: bytes,
+        rlim_max: bytes,
+    };
+    // SAFETY: `setrlimit` only reads the given struct.
+    if unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } != 0 {
+        log::warn!("Unable to limit worker memory to {}MB", megabytes);
+    }
+}
+
+#[cfg(not(unix))]
+pub fn set_memory_limit(_megabytes: u64) {
+    log::warn!("Limiting worker memory is not supported on this platform");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panic_message_on_one_line() {
+        let stderr = "thread 'main' panicked at 'oops', core/src/lib.rs:1:1\nnote: run with `RUST_BACKTRACE=1`\n";
+        assert_eq!(
+            panic_message(stderr).as_deref(),
+            Some("thread 'main' panicked at 'oops', core/src/lib.rs:1:1")
+        );
+    }
+
+    #[test]
+    fn panic_message_on_next_line() {
+        let stderr = "warning\nthread 'main' panicked at core/src/lib.rs:1:1:\noops\nnote: run with `RUST_BACKTRACE=1`\n";
+        assert_eq!(
+            panic_message(stderr).as_deref(),
+            Some("thread 'main' panicked at core/src/lib.rs:1:1: oops")
+        );
+    }
+
+    #[test]
+    fn no_panic_message() {
+        assert_eq!(panic_message("Segmentation fault\n"), None);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::logging::{CapturedLog, LogCapture};
+use ruffle_core::backend::audio::NullAudioBackend;
+use ruffle_core::backend::locale::NullLocaleBackend;
+use ruffle_core::backend::log::{is_avm_error, NullLogBackend};
+use ruffle_core::backend::navigator::NullNavigatorBackend;
+use ruffle_core::backend::render::NullRenderer;
+use ruffle_core::backend::storage::MemoryStorageBackend;
+use ruffle_core::backend::ui::NullUiBackend;
+use ruffle_core::backend::video::NullVideoBackend;
+use ruffle_core::swf::read::Reader;
+use ruffle_core::swf::{decompress_swf, parse_swf, SwfBuf, TagCode};
+use ruffle_core::tag_utils::SwfMovie;
+use ruffle_core::Player;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Settings for running files in the player.
+#[derive(Debug, Clone, Copy)]
+pub struct RunOptions {
+    /// The number of frames to run each file for.
+    pub frames: u32,
+
+    /// How long a single file may run for before it is abandoned.
+    pub timeout: Duration,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct FileResults {
+    pub name: String,
+    pub error: Option<String>,
+    pub swf_version: Option<u8>,
+    pub avm_version: Option<u8>,
+
+    /// The number of times each tag appears in the main timeline.
+    pub tags: BTreeMap<String, u32>,
+
+    /// The number of frames that were run in the player.
+    pub frames_run: u32,
+
+    /// Warnings about features that Ruffle does not implement yet.
+    pub unimplemented: Vec<String>,
+
+    /// Any other warnings or errors emitted while running the file.
+    pub warnings: Vec<String>,
+
+    /// Errors emitted while running the file, such as uncaught AVM exceptions.
+    pub avm_errors: Vec<String>,
+
+    pub panic: Option<String>,
+    pub timed_out: bool,
+}
+
+impl FileResults {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    /// Whether anything went wrong while scanning this file.
+    pub fn is_error(&self) -> bool {
+        self.error.is_some() || self.panic.is_some() || self.timed_out
+    }
+
+    fn add_logs(&mut self, logs: Vec<CapturedLog>) {
+        for log in logs {
+            let list = if is_avm_error(log.level, &log.target, &log.message) {
+                &mut self.avm_errors
+            } else if is_unimplemented(&log.message) {
+                &mut self.unimplemented
+            } else {
+                &mut self.warnings
+            };
+
+            if !list.contains(&log.message) {
+                list.push(log.message);
+            }
+        }
+    }
+}
+
+/// Whether a log message is about missing functionality, rather than a misbehaving movie.
+fn is_unimplemented(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    message.contains("not yet implemented")
+        || message.contains("not implemented")
+        || message.contains("unimplemented")
+        || message.contains("unsupported")
+}
+
+pub fn scan_file(
+    path: &Path,
+    name: String,
+    run: Option<RunOptions>,
+    log_capture: &LogCapture,
+) -> FileResults {
+    let mut results = FileResults::new(name);
+
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            results.error = Some(format!("File error: {}", e.to_string()));
+            return results;
+        }
+    };
+
+    let swf_buf = match decompress_swf(&data[..]) {
+        Ok(swf_buf) => swf_buf,
+        Err(e) => {
+            results.error = Some(format!("Decompress error: {}", e.to_string()));
+            return results;
+        }
+    };
+    results.swf_version = Some(swf_buf.header.version());
+    results.avm_version = Some(if swf_buf.header.is_action_script_3() {
+        2
+    } else {
+        1
+    });
+
+    match parse_swf(&swf_buf) {
+        Ok(_swf) => results.tags = tag_histogram(&swf_buf),
+        Err(e) => {
+            results.error = Some(format!("Parse error: {}", e.to_string()));
+            return results;
+        }
+    }
+
+    if let Some(run) = run {
+        // Discard anything logged by a previous file.
+        log_capture.take();
+
+        let start = Instant::now();
+        match run_movie(&data, path, run, start, &mut results.frames_run) {
+            Ok(timed_out) => results.timed_out = timed_out,
+            Err(e) => results.error = Some(format!("Player error: {}", e.to_string())),
+        }
+
+        results.add_logs(log_capture.take());
+    }
+
+    results
+}
+
+/// Runs the movie in a headless player, returning whether the timeout was reached.
+fn run_movie(
+    data: &[u8],
+    path: &Path,
+    run: RunOptions,
+    start: Instant,
+    frames_run: &mut u32,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let url = path.to_string_lossy().into_owned();
+    let movie = SwfMovie::from_data(data, Some(url), None)?;
+
+    let player = Player::new(
+        Box::new(NullRenderer::new()),
+        Box::new(NullAudioBackend::new()),
+        Box::new(NullNavigatorBackend::new()),
+        Box::new(MemoryStorageBackend::default()),
+        Box::new(NullLocaleBackend::new()),
+        Box::new(NullVideoBackend::new()),
+        Box::new(NullLogBackend::new()),
+        Box::new(NullUiBackend::new()),
+    )?;
+    let mut player = player.lock().unwrap();
+    player.set_max_execution_duration(run.timeout);
+    player.set_root_movie(Arc::new(movie));
+
+    for _ in 0..run.frames {
+        if start.elapsed() >= run.timeout {
+            return Ok(true);
+        }
+
+        player.run_frame();
+        let frame_time = 1000.0 / player.frame_rate();
+        player.update_timers(frame_time);
+        player.render();
+        *frames_run += 1;
+    }
+
+    Ok(start.elapsed() >= run.timeout)
+}
+
+/// Counts the tags in the main timeline, without parsing their contents.
+fn tag_histogram(swf_buf: &SwfBuf) -> BTreeMap<String, u32> {
+    let mut tags = BTreeMap::new();
+    let mut reader = Reader::new(&swf_buf.data[..], swf_buf.header.version());
+    while let Ok((tag_code, length)) = reader.read_tag_code_and_length() {
+        *tags.entry(TagCode::format(tag_code)).or_default() += 1;
+
+        let input = reader.get_mut();
+        if tag_code == TagCode::End as u16 || length > input.len() {
+            break;
+        }
+        *input = &input[length..];
+    }
+    tags
+}
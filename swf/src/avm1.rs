@@ -1,3 +1,4 @@
+pub mod disassemble;
 pub(crate) mod opcode;
 pub mod read;
 pub mod types;
@@ -0,0 +1,344 @@
+//! Human-readable listings of AVM1 bytecode.
+//!
+//! # Example
+//! ```
+//! // push "Hello"; trace
+//! let actions = [0x96, 0x07, 0x00, 0x00, b'H', b'e', b'l', b'l', b'o', 0x00, 0x26, 0x00];
+//! println!("{}", swf::avm1::disassemble::disassemble(&actions, 6));
+//! ```
+
+use crate::avm1::read::Reader;
+use crate::avm1::types::*;
+use crate::string::{Encoding, SwfStr};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{self, Write};
+
+/// The number of spaces each nested block is indented by.
+const INDENT: usize = 4;
+
+/// Returns a listing of the given block of actions, such as the contents of a `DoAction` tag.
+pub fn disassemble(actions: &[u8], swf_version: u8) -> String {
+    let mut output = String::new();
+    // Writing to a `String` can't fail.
+    let _ = Disassembler::new(actions, swf_version).write(&mut output);
+    output
+}
+
+/// Writes a listing of a block of AVM1 bytecode.
+///
+/// Each action is prefixed with its offset from the start of the block. Branch targets are
+/// written as labels, constant pool lookups are resolved to their strings, and the bodies of
+/// functions, `With` and `Try` blocks are written nested below the action that defines them.
+///
+/// Constant pool lookups are resolved using the most recent `ConstantPool` action in the
+/// listing, which is what the player sees for all but the most contrived control flow.
+pub struct Disassembler<'a> {
+    actions: &'a [u8],
+    swf_version: u8,
+    encoding: &'static Encoding,
+    constant_pool: Vec<&'a SwfStr>,
+}
+
+impl<'a> Disassembler<'a> {
+    pub fn new(actions: &'a [u8], swf_version: u8) -> Self {
+        Self {
+            actions,
+            swf_version,
+            encoding: SwfStr::encoding_for_version(swf_version),
+            constant_pool: Vec::new(),
+        }
+    }
+
+    pub fn write<W: Write>(&mut self, output: &mut W) -> fmt::Result {
+        let mut targets = BTreeSet::new();
+        self.collect_targets(self.actions, &mut targets);
+        self.constant_pool.clear();
+        self.write_block(output, self.actions, &HashMap::new(), &targets, 0)
+    }
+
+    /// The offset of a sub-slice from the start of the listing.
+    fn offset_of(&self, data: &[u8]) -> usize {
+        data.as_ptr() as usize - self.actions.as_ptr() as usize
+    }
+
+    /// Finds the offset of every branch target, including those in nested blocks.
+    fn collect_targets(&self, data: &'a [u8], targets: &mut BTreeSet<usize>) {
+        let mut reader = Reader::new(data, self.swf_version);
+        while let Ok(Some(action)) = reader.read_action() {
+            let end = self.offset_of(reader.get_ref());
+            match action {
+                Action::Jump { offset } | Action::If { offset } => {
+                    targets.insert((end as isize + isize::from(offset)) as usize);
+                }
+                Action::DefineFunction { actions, .. }
+                | Action::DefineFunction2(Function { actions, .. })
+                | Action::With { actions } => self.collect_targets(actions, targets),
+                Action::Try(try_block) => {
+                    self.collect_targets(try_block.try_actions, targets);
+                    if let Some((_, catch_actions)) = try_block.catch {
+                        self.collect_targets(catch_actions, targets);
+                    }
+                    if let Some(finally_actions) = try_block.finally {
+                        self.collect_targets(finally_actions, targets);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn write_block<W: Write>(
+        &mut self,
+        output: &mut W,
+        data: &'a [u8],
+        registers: &HashMap<u8, String>,
+        targets: &BTreeSet<usize>,
+        depth: usize,
+    ) -> fmt::Result {
+        let indent = depth * INDENT;
+        let mut reader = Reader::new(data, self.swf_version);
+        loop {
+            let offset = self.offset_of(reader.get_ref());
+            if targets.contains(&offset) {
+                writeln!(output, "{:indent$}loc_{:04x}:", "", offset, indent = indent)?;
+            }
+
+            let action = match reader.read_action() {
+                Ok(Some(action)) => action,
+                Ok(None) => break,
+                Err(e) => {
+                    writeln!(
+                        output,
+                        "{:indent$}{:04x}: ; {}",
+                        "",
+                        offset,
+                        e,
+                        indent = indent
+                    )?;
+                    break;
+                }
+            };
+            let end = self.offset_of(reader.get_ref());
+
+            write!(output, "{:indent$}{:04x}: ", "", offset, indent = indent)?;
+            match action {
+                Action::ConstantPool(constants) => {
+                    writeln!(output, "ConstantPool")?;
+                    for (i, constant) in constants.iter().enumerate() {
+                        writeln!(
+                            output,
+                            "{:indent$}#{} {:?}",
+                            "",
+                            i,
+                            constant.to_str_lossy(self.encoding),
+                            indent = indent + INDENT + 2
+                        )?;
+                    }
+                    self.constant_pool = constants;
+                }
+                Action::Push(values) => {
+                    let values: Vec<String> = values
+                        .iter()
+                        .map(|value| self.format_value(value, registers))
+                        .collect();
+                    writeln!(output, "Push {}", values.join(", "))?;
+                }
+                Action::Jump { offset } => writeln!(
+                    output,
+                    "Jump loc_{:04x}",
+                    end as isize + isize::from(offset)
+                )?,
+                Action::If { offset } => {
+                    writeln!(output, "If loc_{:04x}", end as isize + isize::from(offset))?
+                }
+                Action::StoreRegister(register) => writeln!(
+                    output,
+                    "StoreRegister {}",
+                    format_register(register, registers)
+                )?,
+                Action::DefineFunction {
+                    name,
+                    params,
+                    actions,
+                } => {
+                    let params: Vec<_> = params
+                        .iter()
+                        .map(|param| param.to_str_lossy(self.encoding))
+                        .collect();
+                    writeln!(
+                        output,
+                        "DefineFunction {}({})",
+                        name.to_str_lossy(self.encoding),
+                        params.join(", ")
+                    )?;
+                    self.write_nested(output, actions, registers, targets, depth)?;
+                }
+                Action::DefineFunction2(function) => {
+                    let function_registers = self.function_registers(&function);
+                    let params: Vec<_> = function
+                        .params
+                        .iter()
+                        .map(|param| match param.register_index {
+                            Some(register) => {
+                                format!("r:{} {}", register, param.name.to_str_lossy(self.encoding))
+                            }
+                            None => param.name.to_string_lossy(self.encoding),
+                        })
+                        .collect();
+                    writeln!(
+                        output,
+                        "DefineFunction2 {}({}) registers={} flags={:?}",
+                        function.name.to_str_lossy(self.encoding),
+                        params.join(", "),
+                        function.register_count,
+                        function.flags
+                    )?;
+                    self.write_nested(
+                        output,
+                        function.actions,
+                        &function_registers,
+                        targets,
+                        depth,
+                    )?;
+                }
+                Action::With { actions } => {
+                    writeln!(output, "With")?;
+                    self.write_nested(output, actions, registers, targets, depth)?;
+                }
+                Action::Try(try_block) => {
+                    writeln!(output, "Try")?;
+                    self.write_nested(output, try_block.try_actions, registers, targets, depth)?;
+                    if let Some((catch_var, catch_actions)) = try_block.catch {
+                        let catch_var = match catch_var {
+                            CatchVar::Var(name) => name.to_string_lossy(self.encoding),
+                            CatchVar::Register(register) => format_register(register, registers),
+                        };
+                        writeln!(output, "{:indent$}Catch {}", "", catch_var, indent = indent)?;
+                        self.write_nested(output, catch_actions, registers, targets, depth)?;
+                    }
+                    if let Some(finally_actions) = try_block.finally {
+                        writeln!(output, "{:indent$}Finally", "", indent = indent)?;
+                        self.write_nested(output, finally_actions, registers, targets, depth)?;
+                    }
+                }
+                Action::GetUrl { url, target } => writeln!(
+                    output,
+                    "GetUrl {:?}, {:?}",
+                    url.to_str_lossy(self.encoding),
+                    target.to_str_lossy(self.encoding)
+                )?,
+                Action::GotoLabel(label) => {
+                    writeln!(output, "GotoLabel {:?}", label.to_str_lossy(self.encoding))?
+                }
+                Action::SetTarget(target) => {
+                    writeln!(output, "SetTarget {:?}", target.to_str_lossy(self.encoding))?
+                }
+                Action::GotoFrame(frame) => writeln!(output, "GotoFrame {}", frame)?,
+                Action::Unknown { opcode, data } => {
+                    writeln!(output, "Unknown 0x{:02x} ({} bytes)", opcode, data.len())?
+                }
+                action => writeln!(output, "{:?}", action)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_nested<W: Write>(
+        &mut self,
+        output: &mut W,
+        data: &'a [u8],
+        registers: &HashMap<u8, String>,
+        targets: &BTreeSet<usize>,
+        depth: usize,
+    ) -> fmt::Result {
+        let indent = depth * INDENT;
+        writeln!(output, "{:indent$}{{", "", indent = indent)?;
+        self.write_block(output, data, registers, targets, depth + 1)?;
+        writeln!(output, "{:indent$}}}", "", indent = indent)
+    }
+
+    /// Names the registers of a `DefineFunction2`, in the order the player preloads them.
+    fn function_registers(&self, function: &Function) -> HashMap<u8, String> {
+        let mut registers = HashMap::new();
+        let preloads = [
+            (FunctionFlags::PRELOAD_THIS, "this"),
+            (FunctionFlags::PRELOAD_ARGUMENTS, "arguments"),
+            (FunctionFlags::PRELOAD_SUPER, "super"),
+            (FunctionFlags::PRELOAD_ROOT, "_root"),
+            (FunctionFlags::PRELOAD_PARENT, "_parent"),
+            (FunctionFlags::PRELOAD_GLOBAL, "_global"),
+        ];
+        let mut register = 1;
+        for (flag, name) in preloads.iter() {
+            if function.flags.contains(*flag) {
+                registers.insert(register, name.to_string());
+                register += 1;
+            }
+        }
+        for param in &function.params {
+            if let Some(register) = param.register_index {
+                registers.insert(register, param.name.to_string_lossy(self.encoding));
+            }
+        }
+        registers
+    }
+
+    fn format_value(&self, value: &Value, registers: &HashMap<u8, String>) -> String {
+        match value {
+            Value::Undefined => "undefined".to_string(),
+            Value::Null => "null".to_string(),
+            Value::Bool(value) => value.to_string(),
+            Value::Int(value) => value.to_string(),
+            Value::Float(value) => format!("{:?}", value),
+            Value::Double(value) => format!("{:?}", value),
+            Value::Str(value) => format!("{:?}", value.to_str_lossy(self.encoding)),
+            Value::Register(register) => format_register(*register, registers),
+            Value::ConstantPool(index) => match self.constant_pool.get(usize::from(*index)) {
+                Some(constant) => {
+                    format!("{:?} (#{})", constant.to_str_lossy(self.encoding), index)
+                }
+                None => format!("#{}", index),
+            },
+        }
+    }
+}
+
+fn format_register(register: u8, registers: &HashMap<u8, String>) -> String {
+    match registers.get(&register) {
+        Some(name) => format!("r:{} ({})", register, name),
+        None => format!("r:{}", register),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disassemble_jump() {
+        let actions = [
+            0x99, 0x02, 0x00, 0x01, 0x00, // Jump +1
+            0x07, // Stop
+            0x06, // Play
+            0x00, // End
+        ];
+        assert_eq!(
+            disassemble(&actions, 6),
+            "0000: Jump loc_0006\n0005: Stop\nloc_0006:\n0006: Play\n"
+        );
+    }
+
+    #[test]
+    fn disassemble_constant_pool() {
+        let actions = [
+            0x88, 0x06, 0x00, 0x01, 0x00, b'f', b'o', b'o', 0x00, // ConstantPool "foo"
+            0x96, 0x02, 0x00, 0x08, 0x00, // Push #0
+            0x26, // Trace
+            0x00, // End
+        ];
+        assert_eq!(
+            disassemble(&actions, 6),
+            "0000: ConstantPool\n      #0 \"foo\"\n0009: Push \"foo\" (#0)\n000e: Trace\n"
+        );
+    }
+}
@@ -0,0 +1,874 @@
+//! Human-readable listings of ABC files.
+//!
+//! # Example
+//! ```no_run
+//! let data = std::fs::read("main.abc").unwrap();
+//! let abc = swf::avm2::read::Reader::new(&data).read().unwrap();
+//! println!("{}", swf::avm2::disassemble::disassemble(&abc));
+//! ```
+
+use crate::avm2::read::Reader;
+use crate::avm2::types::*;
+use crate::extensions::ReadSwfExt;
+use std::collections::BTreeSet;
+use std::fmt::{self, Write};
+
+/// The number of spaces each nested section is indented by.
+const INDENT: usize = 4;
+
+/// How deeply `TypeName` multinames are resolved before giving up, in case of cycles.
+const MAX_MULTINAME_DEPTH: usize = 8;
+
+/// Returns a listing of an entire ABC file.
+pub fn disassemble(abc: &AbcFile) -> String {
+    let mut output = String::new();
+    // Writing to a `String` can't fail.
+    let _ = Disassembler::new(abc).write(&mut output);
+    output
+}
+
+/// Writes a listing of an ABC file.
+///
+/// The listing contains the constant pool, every script and class with its
+/// traits, and every method signature followed by its body. Each bytecode
+/// instruction is prefixed with its offset in the method body; branch targets
+/// and exception ranges are written as labels.
+///
+/// Constant pool entries keep their index (`#n`), and method, class and
+/// exception references are written by index, so that two files that write
+/// the same bytes produce the same listing.
+pub struct Disassembler<'a> {
+    abc: &'a AbcFile,
+
+    /// The body of each method, by method index.
+    method_bodies: Vec<Option<&'a MethodBody>>,
+}
+
+impl<'a> Disassembler<'a> {
+    pub fn new(abc: &'a AbcFile) -> Self {
+        let mut method_bodies = vec![None; abc.methods.len()];
+        for body in &abc.method_bodies {
+            // If a method has several bodies, the first one is listed.
+            if let Some(slot) = method_bodies.get_mut(body.method.as_u30() as usize) {
+                if slot.is_none() {
+                    *slot = Some(body);
+                }
+            }
+        }
+        Self { abc, method_bodies }
+    }
+
+    pub fn write<W: Write>(&self, output: &mut W) -> fmt::Result {
+        writeln!(
+            output,
+            "// ABC version {}.{}",
+            self.abc.major_version, self.abc.minor_version
+        )?;
+        writeln!(output)?;
+        self.write_constant_pool(output)?;
+
+        for (i, script) in self.abc.scripts.iter().enumerate() {
+            writeln!(output)?;
+            writeln!(
+                output,
+                "script #{} init=method #{}",
+                i,
+                script.init_method.as_u30()
+            )?;
+            self.write_traits(output, &script.traits, 1)?;
+        }
+
+        for (i, (instance, class)) in self
+            .abc
+            .instances
+            .iter()
+            .zip(self.abc.classes.iter())
+            .enumerate()
+        {
+            writeln!(output)?;
+            self.write_class(output, i, instance, class)?;
+        }
+
+        for i in 0..self.abc.methods.len() {
+            writeln!(output)?;
+            self.write_method(output, i)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_constant_pool<W: Write>(&self, output: &mut W) -> fmt::Result {
+        let pool = &self.abc.constant_pool;
+        let indent = INDENT;
+        writeln!(output, "constant_pool")?;
+
+        writeln!(output, "{:indent$}ints", "", indent = indent)?;
+        for (i, value) in pool.ints.iter().enumerate() {
+            write_pool_entry(output, i, value)?;
+        }
+        writeln!(output, "{:indent$}uints", "", indent = indent)?;
+        for (i, value) in pool.uints.iter().enumerate() {
+            write_pool_entry(output, i, value)?;
+        }
+        writeln!(output, "{:indent$}doubles", "", indent = indent)?;
+        for (i, value) in pool.doubles.iter().enumerate() {
+            write_pool_entry(output, i, format!("{:?}", value))?;
+        }
+        writeln!(output, "{:indent$}strings", "", indent = indent)?;
+        for (i, value) in pool.strings.iter().enumerate() {
+            write_pool_entry(output, i, format!("{:?}", value))?;
+        }
+        writeln!(output, "{:indent$}namespaces", "", indent = indent)?;
+        for i in 0..pool.namespaces.len() {
+            write_pool_entry(output, i, self.namespace(&Index::new(i as u32 + 1)))?;
+        }
+        writeln!(output, "{:indent$}namespace_sets", "", indent = indent)?;
+        for i in 0..pool.namespace_sets.len() {
+            write_pool_entry(output, i, self.namespace_set(&Index::new(i as u32 + 1)))?;
+        }
+        writeln!(output, "{:indent$}multinames", "", indent = indent)?;
+        for i in 0..pool.multinames.len() {
+            write_pool_entry(output, i, self.multiname(&Index::new(i as u32 + 1)))?;
+        }
+
+        Ok(())
+    }
+
+    fn write_class<W: Write>(
+        &self,
+        output: &mut W,
+        index: usize,
+        instance: &Instance,
+        class: &Class,
+    ) -> fmt::Result {
+        write!(
+            output,
+            "class #{} {} extends {}",
+            index,
+            self.multiname(&instance.name),
+            self.multiname(&instance.super_name)
+        )?;
+        if !instance.interfaces.is_empty() {
+            let interfaces: Vec<_> = instance
+                .interfaces
+                .iter()
+                .map(|interface| self.multiname(interface))
+                .collect();
+            write!(output, " implements {}", interfaces.join(", "))?;
+        }
+        if instance.is_sealed {
+            write!(output, " sealed")?;
+        }
+        if instance.is_final {
+            write!(output, " final")?;
+        }
+        if instance.is_interface {
+            write!(output, " interface")?;
+        }
+        writeln!(output)?;
+
+        let indent = INDENT;
+        if let Some(protected_namespace) = &instance.protected_namespace {
+            writeln!(
+                output,
+                "{:indent$}protected_namespace {}",
+                "",
+                self.namespace(protected_namespace),
+                indent = indent
+            )?;
+        }
+        writeln!(
+            output,
+            "{:indent$}instance init=method #{}",
+            "",
+            instance.init_method.as_u30(),
+            indent = indent
+        )?;
+        self.write_traits(output, &instance.traits, 2)?;
+        writeln!(
+            output,
+            "{:indent$}class init=method #{}",
+            "",
+            class.init_method.as_u30(),
+            indent = indent
+        )?;
+        self.write_traits(output, &class.traits, 2)
+    }
+
+    fn write_traits<W: Write>(
+        &self,
+        output: &mut W,
+        traits: &[Trait],
+        depth: usize,
+    ) -> fmt::Result {
+        let indent = depth * INDENT;
+        for trait_info in traits {
+            write!(output, "{:indent$}", "", indent = indent)?;
+            for metadata in &trait_info.metadata {
+                write!(output, "{} ", self.metadata(metadata))?;
+            }
+            if trait_info.is_final {
+                write!(output, "final ")?;
+            }
+            if trait_info.is_override {
+                write!(output, "override ")?;
+            }
+
+            let name = self.multiname(&trait_info.name);
+            match &trait_info.kind {
+                TraitKind::Slot {
+                    slot_id,
+                    type_name,
+                    value,
+                } => {
+                    write!(
+                        output,
+                        "slot {}: {} slot_id={}",
+                        name,
+                        self.multiname(type_name),
+                        slot_id
+                    )?;
+                    if let Some(value) = value {
+                        write!(output, " = {}", self.default_value(value))?;
+                    }
+                }
+                TraitKind::Const {
+                    slot_id,
+                    type_name,
+                    value,
+                } => {
+                    write!(
+                        output,
+                        "const {}: {} slot_id={}",
+                        name,
+                        self.multiname(type_name),
+                        slot_id
+                    )?;
+                    if let Some(value) = value {
+                        write!(output, " = {}", self.default_value(value))?;
+                    }
+                }
+                TraitKind::Method { disp_id, method } => write!(
+                    output,
+                    "method {} disp_id={} method #{}",
+                    name,
+                    disp_id,
+                    method.as_u30()
+                )?,
+                TraitKind::Getter { disp_id, method } => write!(
+                    output,
+                    "getter {} disp_id={} method #{}",
+                    name,
+                    disp_id,
+                    method.as_u30()
+                )?,
+                TraitKind::Setter { disp_id, method } => write!(
+                    output,
+                    "setter {} disp_id={} method #{}",
+                    name,
+                    disp_id,
+                    method.as_u30()
+                )?,
+                TraitKind::Class { slot_id, class } => write!(
+                    output,
+                    "class {} slot_id={} class #{}",
+                    name,
+                    slot_id,
+                    class.as_u30()
+                )?,
+                TraitKind::Function { slot_id, function } => write!(
+                    output,
+                    "function {} slot_id={} method #{}",
+                    name,
+                    slot_id,
+                    function.as_u30()
+                )?,
+            }
+            writeln!(output)?;
+        }
+        Ok(())
+    }
+
+    fn write_method<W: Write>(&self, output: &mut W, index: usize) -> fmt::Result {
+        let method = &self.abc.methods[index];
+        let params: Vec<_> = method
+            .params
+            .iter()
+            .map(|param| {
+                let mut param_str = match &param.name {
+                    Some(name) => format!("{}: {}", self.string(name), self.multiname(&param.kind)),
+                    None => self.multiname(&param.kind),
+                };
+                if let Some(default_value) = &param.default_value {
+                    write!(param_str, " = {}", self.default_value(default_value)).unwrap();
+                }
+                param_str
+            })
+            .collect();
+        // Most methods are anonymous; only their traits give them a name.
+        let name = if method.name.0 == 0 {
+            String::new()
+        } else {
+            self.string(&method.name)
+        };
+        write!(
+            output,
+            "method #{} {}({}): {}",
+            index,
+            name,
+            params.join(", "),
+            self.multiname(&method.return_type)
+        )?;
+        if method.needs_arguments_object {
+            write!(output, " needs_arguments")?;
+        }
+        if method.needs_activation {
+            write!(output, " needs_activation")?;
+        }
+        if method.needs_rest {
+            write!(output, " needs_rest")?;
+        }
+        if method.needs_dxns {
+            write!(output, " needs_dxns")?;
+        }
+        writeln!(output)?;
+
+        if let Some(body) = self.method_bodies.get(index).copied().flatten() {
+            self.write_method_body(output, body)?;
+        }
+        Ok(())
+    }
+
+    fn write_method_body<W: Write>(&self, output: &mut W, body: &MethodBody) -> fmt::Result {
+        let indent = INDENT;
+        writeln!(
+            output,
+            "{:indent$}max_stack={} num_locals={} init_scope_depth={} max_scope_depth={}",
+            "",
+            body.max_stack,
+            body.num_locals,
+            body.init_scope_depth,
+            body.max_scope_depth,
+            indent = indent
+        )?;
+
+        for (i, exception) in body.exceptions.iter().enumerate() {
+            writeln!(
+                output,
+                "{:indent$}exception #{} loc_{:04x}..loc_{:04x} => loc_{:04x} {}: {}",
+                "",
+                i,
+                exception.from_offset,
+                exception.to_offset,
+                exception.target_offset,
+                self.string(&exception.variable_name),
+                self.multiname(&exception.type_name),
+                indent = indent
+            )?;
+        }
+
+        if !body.traits.is_empty() {
+            writeln!(output, "{:indent$}traits", "", indent = indent)?;
+            self.write_traits(output, &body.traits, 2)?;
+        }
+
+        writeln!(output, "{:indent$}code", "", indent = indent)?;
+        self.write_code(output, body, 2)
+    }
+
+    /// Writes each instruction of a method body, labelling every offset that
+    /// is jumped to or that starts or ends an exception range.
+    fn write_code<W: Write>(&self, output: &mut W, body: &MethodBody, depth: usize) -> fmt::Result {
+        let indent = depth * INDENT;
+        let code = &body.code[..];
+
+        let mut targets = BTreeSet::new();
+        for exception in &body.exceptions {
+            targets.insert(exception.from_offset as usize);
+            targets.insert(exception.to_offset as usize);
+            targets.insert(exception.target_offset as usize);
+        }
+        let mut reader = Reader::new(code);
+        while !reader.as_slice().is_empty() {
+            let start = code.len() - reader.as_slice().len();
+            let op = match reader.read_op() {
+                Ok(Some(op)) => op,
+                _ => break,
+            };
+            let end = code.len() - reader.as_slice().len();
+            targets.extend(branch_targets(&op, start, end));
+        }
+
+        let mut reader = Reader::new(code);
+        while !reader.as_slice().is_empty() {
+            let start = code.len() - reader.as_slice().len();
+            if targets.contains(&start) {
+                writeln!(output, "{:indent$}loc_{:04x}:", "", start, indent = indent)?;
+            }
+
+            let op = match reader.read_op() {
+                Ok(Some(op)) => op,
+                Ok(None) => break,
+                Err(e) => {
+                    writeln!(
+                        output,
+                        "{:indent$}{:04x}: ; {}",
+                        "",
+                        start,
+                        e,
+                        indent = indent
+                    )?;
+                    return Ok(());
+                }
+            };
+            let end = code.len() - reader.as_slice().len();
+            writeln!(
+                output,
+                "{:indent$}{:04x}: {}",
+                "",
+                start,
+                self.format_op(&op, start, end),
+                indent = indent
+            )?;
+        }
+
+        // Exception ranges may end at the very end of the code.
+        if targets.contains(&code.len()) {
+            writeln!(
+                output,
+                "{:indent$}loc_{:04x}:",
+                "",
+                code.len(),
+                indent = indent
+            )?;
+        }
+        Ok(())
+    }
+
+    fn format_op(&self, op: &Op, start: usize, end: usize) -> String {
+        let label = |offset: i32| format!("loc_{:04x}", end as i64 + i64::from(offset));
+        match op {
+            Op::AsType { type_name } => format!("AsType {}", self.multiname(type_name)),
+            Op::CallMethod { index, num_args } => {
+                format!("CallMethod method #{}, {}", index.as_u30(), num_args)
+            }
+            Op::CallStatic { index, num_args } => {
+                format!("CallStatic method #{}, {}", index.as_u30(), num_args)
+            }
+            Op::CallProperty { index, num_args } => {
+                format!("CallProperty {}, {}", self.multiname(index), num_args)
+            }
+            Op::CallPropLex { index, num_args } => {
+                format!("CallPropLex {}, {}", self.multiname(index), num_args)
+            }
+            Op::CallPropVoid { index, num_args } => {
+                format!("CallPropVoid {}, {}", self.multiname(index), num_args)
+            }
+            Op::CallSuper { index, num_args } => {
+                format!("CallSuper {}, {}", self.multiname(index), num_args)
+            }
+            Op::CallSuperVoid { index, num_args } => {
+                format!("CallSuperVoid {}, {}", self.multiname(index), num_args)
+            }
+            Op::ConstructProp { index, num_args } => {
+                format!("ConstructProp {}, {}", self.multiname(index), num_args)
+            }
+            Op::Coerce { index } => format!("Coerce {}", self.multiname(index)),
+            Op::DeleteProperty { index } => format!("DeleteProperty {}", self.multiname(index)),
+            Op::FindProperty { index } => format!("FindProperty {}", self.multiname(index)),
+            Op::FindPropStrict { index } => format!("FindPropStrict {}", self.multiname(index)),
+            Op::GetDescendants { index } => format!("GetDescendants {}", self.multiname(index)),
+            Op::GetLex { index } => format!("GetLex {}", self.multiname(index)),
+            Op::GetProperty { index } => format!("GetProperty {}", self.multiname(index)),
+            Op::GetSuper { index } => format!("GetSuper {}", self.multiname(index)),
+            Op::InitProperty { index } => format!("InitProperty {}", self.multiname(index)),
+            Op::IsType { index } => format!("IsType {}", self.multiname(index)),
+            Op::SetProperty { index } => format!("SetProperty {}", self.multiname(index)),
+            Op::SetSuper { index } => format!("SetSuper {}", self.multiname(index)),
+            Op::Debug {
+                is_local_register,
+                register_name,
+                register,
+            } => format!(
+                "Debug {}, {}, {}",
+                is_local_register,
+                self.string(register_name),
+                register
+            ),
+            Op::DebugFile { file_name } => format!("DebugFile {}", self.string(file_name)),
+            Op::Dxns { index } => format!("Dxns {}", self.string(index)),
+            Op::PushString { value } => format!("PushString {}", self.string(value)),
+            Op::PushInt { value } => format!("PushInt {}", self.int(value)),
+            Op::PushUint { value } => format!("PushUint {}", self.uint(value)),
+            Op::PushDouble { value } => format!("PushDouble {}", self.double(value)),
+            Op::PushNamespace { value } => format!("PushNamespace {}", self.namespace(value)),
+            // `pushbyte` sign-extends its operand.
+            Op::PushByte { value } => format!("PushByte {}", *value as i8),
+            Op::PushShort { value } => format!("PushShort {}", value),
+            Op::NewCatch { index } => format!("NewCatch exception #{}", index.as_u30()),
+            Op::NewClass { index } => format!("NewClass class #{}", index.as_u30()),
+            Op::NewFunction { index } => format!("NewFunction method #{}", index.as_u30()),
+            Op::ApplyType { num_types } => format!("ApplyType {}", num_types),
+            Op::Call { num_args } => format!("Call {}", num_args),
+            Op::Construct { num_args } => format!("Construct {}", num_args),
+            Op::ConstructSuper { num_args } => format!("ConstructSuper {}", num_args),
+            Op::NewArray { num_args } => format!("NewArray {}", num_args),
+            Op::NewObject { num_args } => format!("NewObject {}", num_args),
+            Op::DebugLine { line_num } => format!("DebugLine {}", line_num),
+            Op::DecLocal { index } => format!("DecLocal {}", index),
+            Op::DecLocalI { index } => format!("DecLocalI {}", index),
+            Op::IncLocal { index } => format!("IncLocal {}", index),
+            Op::IncLocalI { index } => format!("IncLocalI {}", index),
+            Op::GetLocal { index } => format!("GetLocal {}", index),
+            Op::SetLocal { index } => format!("SetLocal {}", index),
+            Op::Kill { index } => format!("Kill {}", index),
+            Op::GetGlobalSlot { index } => format!("GetGlobalSlot {}", index),
+            Op::SetGlobalSlot { index } => format!("SetGlobalSlot {}", index),
+            Op::GetSlot { index } => format!("GetSlot {}", index),
+            Op::SetSlot { index } => format!("SetSlot {}", index),
+            Op::GetScopeObject { index } => format!("GetScopeObject {}", index),
+            Op::HasNext2 {
+                object_register,
+                index_register,
+            } => format!("HasNext2 {}, {}", object_register, index_register),
+            Op::IfEq { offset } => format!("IfEq {}", label(*offset)),
+            Op::IfFalse { offset } => format!("IfFalse {}", label(*offset)),
+            Op::IfGe { offset } => format!("IfGe {}", label(*offset)),
+            Op::IfGt { offset } => format!("IfGt {}", label(*offset)),
+            Op::IfLe { offset } => format!("IfLe {}", label(*offset)),
+            Op::IfLt { offset } => format!("IfLt {}", label(*offset)),
+            Op::IfNge { offset } => format!("IfNge {}", label(*offset)),
+            Op::IfNgt { offset } => format!("IfNgt {}", label(*offset)),
+            Op::IfNle { offset } => format!("IfNle {}", label(*offset)),
+            Op::IfNlt { offset } => format!("IfNlt {}", label(*offset)),
+            Op::IfNe { offset } => format!("IfNe {}", label(*offset)),
+            Op::IfStrictEq { offset } => format!("IfStrictEq {}", label(*offset)),
+            Op::IfStrictNe { offset } => format!("IfStrictNe {}", label(*offset)),
+            Op::IfTrue { offset } => format!("IfTrue {}", label(*offset)),
+            Op::Jump { offset } => format!("Jump {}", label(*offset)),
+            Op::LookupSwitch {
+                default_offset,
+                case_offsets,
+            } => {
+                // Switch offsets are relative to the start of the instruction.
+                let label = |offset: i32| format!("loc_{:04x}", start as i64 + i64::from(offset));
+                let cases: Vec<_> = case_offsets.iter().map(|offset| label(*offset)).collect();
+                format!(
+                    "LookupSwitch {}, [{}]",
+                    label(*default_offset),
+                    cases.join(", ")
+                )
+            }
+            op => format!("{:?}", op),
+        }
+    }
+
+    fn string(&self, index: &Index<String>) -> String {
+        if index.0 == 0 {
+            return "null".to_string();
+        }
+        match self.abc.constant_pool.strings.get(index.0 as usize - 1) {
+            Some(string) => format!("{:?}", string),
+            None => format!("<invalid string #{}>", index.0),
+        }
+    }
+
+    fn int(&self, index: &Index<i32>) -> String {
+        match self
+            .abc
+            .constant_pool
+            .ints
+            .get((index.0 as usize).wrapping_sub(1))
+        {
+            Some(value) => value.to_string(),
+            None => format!("<invalid int #{}>", index.0),
+        }
+    }
+
+    fn uint(&self, index: &Index<u32>) -> String {
+        match self
+            .abc
+            .constant_pool
+            .uints
+            .get((index.0 as usize).wrapping_sub(1))
+        {
+            Some(value) => value.to_string(),
+            None => format!("<invalid uint #{}>", index.0),
+        }
+    }
+
+    fn double(&self, index: &Index<f64>) -> String {
+        match self
+            .abc
+            .constant_pool
+            .doubles
+            .get((index.0 as usize).wrapping_sub(1))
+        {
+            Some(value) => format!("{:?}", value),
+            None => format!("<invalid double #{}>", index.0),
+        }
+    }
+
+    fn namespace(&self, index: &Index<Namespace>) -> String {
+        if index.0 == 0 {
+            return "*".to_string();
+        }
+        let (kind, name) = match self.abc.constant_pool.namespaces.get(index.0 as usize - 1) {
+            Some(Namespace::Namespace(name)) => ("namespace", name),
+            Some(Namespace::Package(name)) => ("package", name),
+            Some(Namespace::PackageInternal(name)) => ("internal", name),
+            Some(Namespace::Protected(name)) => ("protected", name),
+            Some(Namespace::Explicit(name)) => ("explicit", name),
+            Some(Namespace::StaticProtected(name)) => ("static_protected", name),
+            Some(Namespace::Private(name)) => ("private", name),
+            None => return format!("<invalid namespace #{}>", index.0),
+        };
+        format!("{} {}", kind, self.string(name))
+    }
+
+    fn namespace_set(&self, index: &Index<NamespaceSet>) -> String {
+        let set = match index
+            .0
+            .checked_sub(1)
+            .and_then(|i| self.abc.constant_pool.namespace_sets.get(i as usize))
+        {
+            Some(set) => set,
+            None => return format!("<invalid namespace set #{}>", index.0),
+        };
+        let namespaces: Vec<_> = set
+            .iter()
+            .map(|namespace| self.namespace(namespace))
+            .collect();
+        format!("[{}]", namespaces.join(", "))
+    }
+
+    fn multiname(&self, index: &Index<Multiname>) -> String {
+        self.multiname_with_depth(index, 0)
+    }
+
+    fn multiname_with_depth(&self, index: &Index<Multiname>, depth: usize) -> String {
+        if index.0 == 0 {
+            return "*".to_string();
+        }
+        let multiname = match self.abc.constant_pool.multinames.get(index.0 as usize - 1) {
+            Some(multiname) if depth < MAX_MULTINAME_DEPTH => multiname,
+            _ => return format!("<invalid multiname #{}>", index.0),
+        };
+        match multiname {
+            Multiname::QName { namespace, name } => {
+                format!(
+                    "QName({}, {})",
+                    self.namespace(namespace),
+                    self.string(name)
+                )
+            }
+            Multiname::QNameA { namespace, name } => {
+                format!(
+                    "QNameA({}, {})",
+                    self.namespace(namespace),
+                    self.string(name)
+                )
+            }
+            Multiname::RTQName { name } => format!("RTQName({})", self.string(name)),
+            Multiname::RTQNameA { name } => format!("RTQNameA({})", self.string(name)),
+            Multiname::RTQNameL => "RTQNameL()".to_string(),
+            Multiname::RTQNameLA => "RTQNameLA()".to_string(),
+            Multiname::Multiname {
+                namespace_set,
+                name,
+            } => format!(
+                "Multiname({}, {})",
+                self.namespace_set(namespace_set),
+                self.string(name)
+            ),
+            Multiname::MultinameA {
+                namespace_set,
+                name,
+            } => format!(
+                "MultinameA({}, {})",
+                self.namespace_set(namespace_set),
+                self.string(name)
+            ),
+            Multiname::MultinameL { namespace_set } => {
+                format!("MultinameL({})", self.namespace_set(namespace_set))
+            }
+            Multiname::MultinameLA { namespace_set } => {
+                format!("MultinameLA({})", self.namespace_set(namespace_set))
+            }
+            Multiname::TypeName {
+                base_type,
+                parameters,
+            } => {
+                let parameters: Vec<_> = parameters
+                    .iter()
+                    .map(|parameter| self.multiname_with_depth(parameter, depth + 1))
+                    .collect();
+                format!(
+                    "{}.<{}>",
+                    self.multiname_with_depth(base_type, depth + 1),
+                    parameters.join(", ")
+                )
+            }
+        }
+    }
+
+    fn default_value(&self, value: &DefaultValue) -> String {
+        match value {
+            DefaultValue::Int(index) => self.int(index),
+            DefaultValue::Uint(index) => format!("{}u", self.uint(index)),
+            DefaultValue::Double(index) => self.double(index),
+            DefaultValue::String(index) => self.string(index),
+            DefaultValue::True => "true".to_string(),
+            DefaultValue::False => "false".to_string(),
+            DefaultValue::Null => "null".to_string(),
+            DefaultValue::Undefined => "undefined".to_string(),
+            DefaultValue::Namespace(index)
+            | DefaultValue::Package(index)
+            | DefaultValue::PackageInternal(index)
+            | DefaultValue::Protected(index)
+            | DefaultValue::Explicit(index)
+            | DefaultValue::StaticProtected(index)
+            | DefaultValue::Private(index) => self.namespace(index),
+        }
+    }
+
+    fn metadata(&self, index: &Index<Metadata>) -> String {
+        let metadata = match self.abc.metadata.get(index.0 as usize) {
+            Some(metadata) => metadata,
+            None => return format!("[<invalid metadata #{}>]", index.0),
+        };
+        let items: Vec<_> = metadata
+            .items
+            .iter()
+            .map(|item| format!("{}={}", self.string(&item.key), self.string(&item.value)))
+            .collect();
+        format!("[{}({})]", self.string(&metadata.name), items.join(", "))
+    }
+}
+
+fn write_pool_entry<W: Write, T: fmt::Display>(
+    output: &mut W,
+    index: usize,
+    value: T,
+) -> fmt::Result {
+    // Index 0 of every pool is implicit, so the first entry is #1.
+    writeln!(
+        output,
+        "{:indent$}#{} {}",
+        "",
+        index + 1,
+        value,
+        indent = INDENT * 2
+    )
+}
+
+/// The offsets that an instruction can branch to.
+fn branch_targets(op: &Op, start: usize, end: usize) -> Vec<usize> {
+    let relative = |base: usize, offset: i32| (base as i64 + i64::from(offset)) as usize;
+    match op {
+        Op::IfEq { offset }
+        | Op::IfFalse { offset }
+        | Op::IfGe { offset }
+        | Op::IfGt { offset }
+        | Op::IfLe { offset }
+        | Op::IfLt { offset }
+        | Op::IfNge { offset }
+        | Op::IfNgt { offset }
+        | Op::IfNle { offset }
+        | Op::IfNlt { offset }
+        | Op::IfNe { offset }
+        | Op::IfStrictEq { offset }
+        | Op::IfStrictNe { offset }
+        | Op::IfTrue { offset }
+        | Op::Jump { offset } => vec![relative(end, *offset)],
+        Op::LookupSwitch {
+            default_offset,
+            case_offsets,
+        } => std::iter::once(default_offset)
+            .chain(case_offsets.iter())
+            .map(|offset| relative(start, *offset))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::write::Writer;
+    use crate::test_data;
+
+    #[test]
+    fn disassemble_method_body() {
+        let tests = test_data::avm2_tests();
+        let (_, abc_file, _) = &tests[0];
+        let listing = disassemble(abc_file);
+        assert!(listing.contains(
+            "method #0 (): QName(package \"\", \"void\")\n\
+             \x20   max_stack=2 num_locals=1 init_scope_depth=1 max_scope_depth=2\n\
+             \x20   code\n\
+             \x20       0000: GetLocal 0\n\
+             \x20       0001: PushScope\n\
+             \x20       0002: FindPropStrict QName(package \"\", \"trace\")\n\
+             \x20       0004: PushString \"Test\"\n\
+             \x20       0006: CallPropVoid QName(package \"\", \"trace\"), 1\n\
+             \x20       0009: ReturnVoid\n"
+        ));
+    }
+
+    #[test]
+    fn disassemble_round_trip() {
+        for (_, abc_file, _) in test_data::avm2_tests() {
+            let mut written = vec![];
+            Writer::new(&mut written).write(abc_file.clone()).unwrap();
+            let reread = Reader::new(&written).read().unwrap();
+            assert_eq!(disassemble(&abc_file), disassemble(&reread));
+        }
+    }
+
+    #[test]
+    fn disassemble_branches() {
+        let abc_file = AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![],
+                uints: vec![],
+                doubles: vec![],
+                strings: vec![],
+                namespaces: vec![],
+                namespace_sets: vec![],
+                multinames: vec![],
+            },
+            methods: vec![],
+            metadata: vec![],
+            instances: vec![],
+            classes: vec![],
+            scripts: vec![],
+            method_bodies: vec![],
+        };
+        let body = MethodBody {
+            method: Index::new(0),
+            max_stack: 1,
+            num_locals: 1,
+            init_scope_depth: 0,
+            max_scope_depth: 0,
+            code: vec![
+                0x26, // PushTrue
+                0x11, 0x01, 0x00, 0x00, // IfTrue +1
+                0x02, // Nop
+                0x47, // ReturnVoid
+            ],
+            exceptions: vec![],
+            traits: vec![],
+        };
+        let mut listing = String::new();
+        Disassembler::new(&abc_file)
+            .write_code(&mut listing, &body, 0)
+            .unwrap();
+        assert_eq!(
+            listing,
+            "0000: PushTrue\n0001: IfTrue loc_0006\n0005: Nop\nloc_0006:\n0006: ReturnVoid\n"
+        );
+    }
+}
@@ -93,7 +93,22 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
         log::warn!("SWF length doesn't match header, may be corrupt");
     }
 
-    let mut reader = Reader::new(&data, version);
+    let (header, header_len) = read_swf_header(&data, compression, version, uncompressed_len)?;
+    data.drain(..header_len);
+    Ok(SwfBuf { header, data })
+}
+
+/// Parses the movie header at the start of the decompressed SWF data.
+///
+/// Returns the extended header and the length of the header in bytes; the tag stream
+/// begins right after it.
+fn read_swf_header(
+    data: &[u8],
+    compression: Compression,
+    version: u8,
+    uncompressed_len: u32,
+) -> Result<(HeaderExt, usize)> {
+    let mut reader = Reader::new(data, version);
     let stage_size = reader.read_rectangle()?;
     let frame_rate = reader.read_fixed8()?;
     let num_frames = reader.read_u16()?;
@@ -104,7 +119,7 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
         frame_rate,
         num_frames,
     };
-    let data = reader.get_ref().to_vec();
+    let header_len = data.len() - reader.get_ref().len();
 
     // Parse the first two tags, searching for the FileAttributes and SetBackgroundColor tags.
     // This metadata is useful, so we want to return it along with the header.
@@ -130,15 +145,358 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
         tag = reader.read_tag();
     }
 
-    Ok(SwfBuf {
-        header: HeaderExt {
+    Ok((
+        HeaderExt {
             header,
             file_attributes,
             background_color,
             uncompressed_len,
         },
-        data,
-    })
+        header_len,
+    ))
+}
+
+/// Returns whether `data` holds enough of the decompressed SWF for `read_swf_header`
+/// to see everything it looks at: the movie header and the first three tags.
+fn is_swf_header_complete(data: &[u8], version: u8) -> bool {
+    let mut reader = Reader::new(data, version);
+    if reader.read_rectangle().is_err()
+        || reader.read_fixed8().is_err()
+        || reader.read_u16().is_err()
+    {
+        return false;
+    }
+    for _ in 0..3 {
+        match reader.read_tag_code_and_length() {
+            Ok((code, length)) if length <= reader.get_ref().len() => {
+                if code == TagCode::End as u16 {
+                    return true;
+                }
+                *reader.get_mut() = &reader.get_ref()[length..];
+            }
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Decompresses an SWF file incrementally, as its bytes arrive.
+///
+/// Compressed data is pushed in with `write`. Once enough of the SWF has been
+/// decompressed to parse the header, `header` returns it and `take_data` drains
+/// the decompressed tag stream as it becomes available.
+///
+/// # Example
+/// ```
+/// # std::env::set_current_dir(env!("CARGO_MANIFEST_DIR"));
+/// let data = std::fs::read("tests/swfs/DefineSprite.swf").unwrap();
+/// let mut decompressor = swf::read::SwfDecompressor::new();
+/// let mut tags = Vec::new();
+/// for chunk in data.chunks(64) {
+///     decompressor.write(chunk).unwrap();
+///     tags.extend(decompressor.take_data());
+/// }
+/// decompressor.finish().unwrap();
+/// tags.extend(decompressor.take_data());
+/// println!("FPS: {}", decompressor.header().unwrap().frame_rate());
+/// ```
+pub struct SwfDecompressor {
+    /// Raw bytes buffered until the signature, version and uncompressed length are known.
+    input: Vec<u8>,
+
+    compression: Compression,
+    version: u8,
+    uncompressed_len: u32,
+
+    /// The decoder for the rest of the file, once the signature has been read.
+    decoder: Option<Decoder>,
+
+    /// Decompressed bytes that have not been taken yet.
+    /// Before the header is parsed, this begins with the movie header.
+    output: Vec<u8>,
+
+    /// The total number of bytes decompressed so far.
+    decompressed_len: usize,
+
+    /// The parsed header, along with its length in bytes.
+    header: Option<(HeaderExt, usize)>,
+}
+
+/// The decompressor used by `SwfDecompressor` for the body of the SWF.
+enum Decoder {
+    Uncompressed,
+
+    #[cfg(feature = "flate2")]
+    Zlib(flate2::write::ZlibDecoder<Vec<u8>>),
+
+    #[cfg(feature = "lzma")]
+    Lzma(Box<lzma_rs::decompress::Stream<Vec<u8>>>),
+
+    /// Compressed data for which no streaming decoder is available.
+    /// It is decompressed in one go once the download finishes.
+    #[allow(dead_code)]
+    Buffered(Vec<u8>),
+
+    /// Decompression has finished or failed; any further data is ignored.
+    Done,
+}
+
+impl SwfDecompressor {
+    pub fn new() -> Self {
+        Self {
+            input: Vec::with_capacity(12),
+            compression: Compression::None,
+            version: 0,
+            uncompressed_len: 0,
+            decoder: None,
+            output: Vec::new(),
+            decompressed_len: 0,
+            header: None,
+        }
+    }
+
+    /// The extended header of the SWF, once enough of it has been decompressed.
+    pub fn header(&self) -> Option<&HeaderExt> {
+        self.header.as_ref().map(|(header, _)| header)
+    }
+
+    /// The expected length of the decompressed tag stream, once the header is known.
+    pub fn tag_stream_len(&self) -> Option<usize> {
+        self.header.as_ref().map(|(header, header_len)| {
+            (header.uncompressed_len as usize).saturating_sub(8 + header_len)
+        })
+    }
+
+    /// Drains the part of the tag stream that has been decompressed since the last call.
+    ///
+    /// Returns nothing until the header has been parsed.
+    pub fn take_data(&mut self) -> Vec<u8> {
+        if self.header.is_some() {
+            std::mem::take(&mut self.output)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Pushes the next chunk of the SWF file into the decompressor.
+    ///
+    /// Returns an `Error` if this is not a valid SWF file. Errors from the
+    /// decompressor itself are logged, and any data after them is ignored.
+    pub fn write(&mut self, mut chunk: &[u8]) -> Result<()> {
+        if self.decoder.is_none() {
+            // LZMA files also store the compressed length, which we skip.
+            let needed = match self.input.first().or_else(|| chunk.first()) {
+                Some(b'Z') => 12,
+                _ => 8,
+            };
+            let len = chunk.len().min(needed - self.input.len());
+            self.input.extend_from_slice(&chunk[..len]);
+            chunk = &chunk[len..];
+            if self.input.len() < needed {
+                return Ok(());
+            }
+            self.start()?;
+        }
+
+        let mut output = Vec::new();
+        if let Some(decoder) = &mut self.decoder {
+            if let Err(e) = decoder.write(chunk, &mut output) {
+                log::error!("Error decompressing SWF: {}", e);
+                *decoder = Decoder::Done;
+            }
+        }
+        self.push_output(output, false)
+    }
+
+    /// Signals that the whole file has been pushed, flushing any remaining data.
+    ///
+    /// Returns an `Error` if the header could not be parsed.
+    pub fn finish(&mut self) -> Result<()> {
+        let decoder = match &mut self.decoder {
+            Some(decoder) => std::mem::replace(decoder, Decoder::Done),
+            None => return Err(Error::invalid_data("Invalid SWF")),
+        };
+        let mut output = Vec::new();
+        if let Err(e) = decoder.finish(&mut output) {
+            log::error!("Error decompressing SWF: {}", e);
+        }
+        self.push_output(output, true)?;
+
+        // Some SWF streams may not be compressed correctly,
+        // (e.g. incorrect data length in the stream), so decompressing
+        // may throw an error even though the data otherwise comes
+        // through the stream.
+        // (+ 8 for header size)
+        if self.decompressed_len as u64 + 8 != self.uncompressed_len as u64 {
+            log::warn!("SWF length doesn't match header, may be corrupt");
+        }
+        Ok(())
+    }
+
+    /// Reads the signature, version and uncompressed length, and sets up the decoder.
+    fn start(&mut self) -> Result<()> {
+        let mut input = &self.input[..];
+        self.compression = read_compression_type(&mut input)?;
+        self.version = input.read_u8()?;
+        self.uncompressed_len = input.read_u32::<LittleEndian>()?;
+        let version = self.version;
+        let decoder = match self.compression {
+            Compression::None => Decoder::Uncompressed,
+            Compression::Zlib => {
+                if version < 6 {
+                    log::warn!(
+                        "zlib compressed SWF is version {} but minimum version is 6",
+                        version
+                    );
+                }
+                Decoder::zlib()?
+            }
+            Compression::Lzma => {
+                if version < 13 {
+                    log::warn!(
+                        "LZMA compressed SWF is version {} but minimum version is 13",
+                        version
+                    );
+                }
+                // Uncompressed length includes the 4-byte header and 4-byte uncompressed length itself,
+                // subtract it here.
+                Decoder::lzma(self.uncompressed_len.saturating_sub(8))?
+            }
+        };
+        self.decoder = Some(decoder);
+        Ok(())
+    }
+
+    /// Collects freshly decompressed bytes, parsing the header as soon as it is complete.
+    fn push_output(&mut self, mut output: Vec<u8>, finished: bool) -> Result<()> {
+        self.decompressed_len += output.len();
+        self.output.append(&mut output);
+        if self.header.is_none() && (finished || is_swf_header_complete(&self.output, self.version))
+        {
+            let (header, header_len) = read_swf_header(
+                &self.output,
+                self.compression,
+                self.version,
+                self.uncompressed_len,
+            )?;
+            self.output.drain(..header_len);
+            self.header = Some((header, header_len));
+        }
+        Ok(())
+    }
+}
+
+impl Default for SwfDecompressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder {
+    #[cfg(feature = "flate2")]
+    #[allow(clippy::unnecessary_wraps)]
+    fn zlib() -> Result<Self> {
+        Ok(Self::Zlib(flate2::write::ZlibDecoder::new(Vec::new())))
+    }
+
+    #[cfg(all(feature = "libflate", not(feature = "flate2")))]
+    #[allow(clippy::unnecessary_wraps)]
+    fn zlib() -> Result<Self> {
+        Ok(Self::Buffered(Vec::new()))
+    }
+
+    #[cfg(not(any(feature = "flate2", feature = "libflate")))]
+    fn zlib() -> Result<Self> {
+        Err(Error::unsupported(
+            "Support for Zlib compressed SWFs is not enabled.",
+        ))
+    }
+
+    #[cfg(feature = "lzma")]
+    #[allow(clippy::unnecessary_wraps)]
+    fn lzma(uncompressed_length: u32) -> Result<Self> {
+        use lzma_rs::decompress::{Options, Stream, UnpackedSize};
+        // See `make_lzma_reader` for the layout of the LZMA header.
+        // The compressed length has already been skipped at this point.
+        let options = Options {
+            unpacked_size: UnpackedSize::UseProvided(Some(uncompressed_length.into())),
+            allow_incomplete: true,
+            memlimit: None,
+        };
+        Ok(Self::Lzma(Box::new(Stream::new_with_options(
+            &options,
+            Vec::new(),
+        ))))
+    }
+
+    #[cfg(not(feature = "lzma"))]
+    fn lzma(_uncompressed_length: u32) -> Result<Self> {
+        Err(Error::unsupported(
+            "Support for LZMA compressed SWFs is not enabled.",
+        ))
+    }
+
+    /// Decompresses `input`, appending whatever output is ready to `output`.
+    fn write(&mut self, input: &[u8], output: &mut Vec<u8>) -> io::Result<()> {
+        match self {
+            Self::Uncompressed => output.extend_from_slice(input),
+            #[cfg(feature = "flate2")]
+            Self::Zlib(decoder) => {
+                use std::io::Write;
+                let mut input = input;
+                // Once the zlib stream ends, `write` stops consuming data;
+                // anything after it is ignored.
+                while !input.is_empty() {
+                    match decoder.write(input)? {
+                        0 => break,
+                        len => input = &input[len..],
+                    }
+                }
+                decoder.flush()?;
+                output.append(decoder.get_mut());
+            }
+            #[cfg(feature = "lzma")]
+            Self::Lzma(stream) => {
+                use std::io::Write;
+                let mut input = input;
+                while !input.is_empty() {
+                    match stream.write(input)? {
+                        0 => break,
+                        len => input = &input[len..],
+                    }
+                }
+                if let Some(buffer) = stream.get_output_mut() {
+                    output.append(buffer);
+                }
+            }
+            Self::Buffered(buffer) => buffer.extend_from_slice(input),
+            Self::Done => (),
+        }
+        Ok(())
+    }
+
+    /// Flushes the rest of the decompressed data into `output`.
+    fn finish(self, output: &mut Vec<u8>) -> Result<()> {
+        match self {
+            #[cfg(feature = "flate2")]
+            Self::Zlib(mut decoder) => {
+                decoder.try_finish()?;
+                output.append(decoder.get_mut());
+            }
+            #[cfg(feature = "lzma")]
+            Self::Lzma(stream) => {
+                let mut buffer = stream
+                    .finish()
+                    .map_err(|_| Error::invalid_data("Unable to decompress LZMA SWF."))?;
+                output.append(&mut buffer);
+            }
+            Self::Buffered(buffer) => {
+                make_zlib_reader(&buffer[..])?.read_to_end(output)?;
+            }
+            _ => (),
+        }
+        Ok(())
+    }
 }
 
 #[cfg(feature = "flate2")]
@@ -2685,6 +3043,70 @@ pub mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn decompress_swf_in_chunks() {
+        let mut paths = vec!["tests/swfs/uncompressed.swf", "tests/swfs/zlib.swf"];
+        if cfg!(feature = "lzma") {
+            paths.push("tests/swfs/lzma.swf");
+        }
+        for path in paths {
+            let data = std::fs::read(path).unwrap();
+            let expected = decompress_swf(&data[..]).unwrap();
+            for &chunk_size in &[1, 5, 100, 4096] {
+                let mut decompressor = SwfDecompressor::new();
+                let mut tag_stream = Vec::new();
+                for chunk in data.chunks(chunk_size) {
+                    decompressor.write(chunk).unwrap();
+                    tag_stream.extend(decompressor.take_data());
+                }
+                decompressor.finish().unwrap();
+                tag_stream.extend(decompressor.take_data());
+
+                let header = decompressor.header().unwrap();
+                assert_eq!(
+                    header.swf_header(),
+                    expected.header.swf_header(),
+                    "{}",
+                    path
+                );
+                assert_eq!(
+                    header.uncompressed_len(),
+                    expected.header.uncompressed_len()
+                );
+                assert_eq!(
+                    header.background_color(),
+                    expected.header.background_color()
+                );
+                assert_eq!(
+                    header.is_action_script_3(),
+                    expected.header.is_action_script_3()
+                );
+                assert_eq!(decompressor.tag_stream_len(), Some(expected.data.len()));
+                assert_eq!(tag_stream, expected.data, "{}", path);
+            }
+        }
+    }
+
+    #[test]
+    fn decompress_swf_before_download_finishes() {
+        let data = std::fs::read("tests/swfs/zlib.swf").unwrap();
+        let mut decompressor = SwfDecompressor::new();
+        decompressor.write(&data[..data.len() / 2]).unwrap();
+        assert!(decompressor.header().is_some());
+        let tag_stream = decompressor.take_data();
+        assert!(!tag_stream.is_empty());
+        assert!(tag_stream.len() < decompressor.tag_stream_len().unwrap());
+    }
+
+    #[test]
+    fn decompress_invalid_swf_in_chunks() {
+        let mut decompressor = SwfDecompressor::new();
+        assert!(decompressor.write(&[0u8; 128]).is_err());
+        let mut decompressor = SwfDecompressor::new();
+        decompressor.write(b"FWS").unwrap();
+        assert!(decompressor.finish().is_err());
+    }
+
     #[test]
     fn read_compression_type() {
         assert_eq!(
@@ -8,24 +8,28 @@ use ruffle_core::backend::video::SoftwareVideoBackend;
 use ruffle_core::backend::video::VideoBackend;
 use ruffle_core::backend::{
     audio::NullAudioBackend,
-    locale::NullLocaleBackend,
+    locale::{LocaleBackend, NullLocaleBackend},
     log::LogBackend,
-    navigator::{NullExecutor, NullNavigatorBackend},
+    navigator::{NavigatorBackend, NullExecutor, NullNavigatorBackend},
     render::NullRenderer,
     storage::{MemoryStorageBackend, StorageBackend},
-    ui::NullUiBackend,
+    ui::{ImeCursorArea, MouseCursor, NullUiBackend, UiBackend},
     video::NullVideoBackend,
 };
 use ruffle_core::context::UpdateContext;
+use ruffle_core::events::{KeyCode, MouseWheelDelta};
 use ruffle_core::external::Value as ExternalValue;
 use ruffle_core::external::{ExternalInterfaceMethod, ExternalInterfaceProvider};
+use ruffle_core::recording::{Recording, Replayer};
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::Player;
+use ruffle_core::{Player, PlayerEvent};
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::target::TextureTarget;
 use ruffle_render_wgpu::wgpu;
 use ruffle_render_wgpu::WgpuRenderBackend;
+use serde::Deserialize;
 use std::cell::RefCell;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
@@ -37,6 +41,15 @@ fn get_img_platform_suffix(info: &wgpu::AdapterInfo) -> String {
 
 const RUN_IMG_TESTS: bool = cfg!(feature = "imgtests");
 
+/// Which renderer to draw the final frame of a test with, to compare it against a reference image.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ImageComparison {
+    None,
+    /// Only runs with the `imgtests` feature, as output depends on the GPU driver.
+    Wgpu,
+    Software,
+}
+
 fn set_logger() {
     let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
         .format_timestamp(None)
@@ -55,11 +68,22 @@ macro_rules! val_or_false {
     };
 }
 
+macro_rules! val_or_none {
+    ($val:literal) => {
+        Some($val)
+    };
+    () => {
+        None
+    };
+}
+
 // This macro generates test cases for a given list of SWFs.
 // If 'img' is true, then we will render an image of the final frame
 // of the SWF, and compare it against a reference image on disk.
+// If 'chunk_size' is given, the SWF is downloaded in chunks of that many bytes,
+// one chunk per frame, instead of being loaded up front.
 macro_rules! swf_tests {
-    ($($(#[$attr:meta])* ($name:ident, $path:expr, $num_frames:literal $(, img = $img:literal)? ),)*) => {
+    ($($(#[$attr:meta])* ($name:ident, $path:expr, $num_frames:literal $(, img = $img:literal)? $(, chunk_size = $chunk_size:literal)? ),)*) => {
         $(
         #[test]
         $(#[$attr])*
@@ -69,7 +93,8 @@ macro_rules! swf_tests {
                 concat!("tests/swfs/", $path, "/test.swf"),
                 $num_frames,
                 concat!("tests/swfs/", $path, "/output.txt"),
-                val_or_false!($($img)?)
+                val_or_false!($($img)?),
+                val_or_none!($($chunk_size)?),
             )
         }
         )*
@@ -99,6 +124,7 @@ macro_rules! swf_tests_approx {
 // Format: (test_name, test_folder, number_of_frames_to_run)
 // The test folder is a relative to core/tests/swfs
 // Inside the folder is expected to be "test.swf" and "output.txt" with the correct output.
+// A "recording.json" in the folder is replayed instead of running a fixed number of frames.
 swf_tests! {
     (add_property, "avm1/add_property", 1),
     (as_transformed_flag, "avm1/as_transformed_flag", 3),
@@ -130,6 +156,11 @@ swf_tests! {
     (point, "avm1/point", 1),
     (rectangle, "avm1/rectangle", 1),
     (date_is_special, "avm1/date_is_special", 1),
+    (recording, "avm1/recording", 1),
+    (input_button_and_text, "avm1/input_button_and_text", 3),
+    (edittext_restrict, "avm1/edittext_restrict", 3),
+    (stylesheet, "avm1/stylesheet", 1),
+    (html_image, "avm1/html_image", 2),
     (get_bytes_total, "avm1/get_bytes_total", 1),
     (goto_advance1, "avm1/goto_advance1", 2),
     (goto_advance2, "avm1/goto_advance2", 2),
@@ -252,6 +283,7 @@ swf_tests! {
     (uncaught_exception_bubbled, "avm1/uncaught_exception_bubbled", 1),
     (try_catch_finally, "avm1/try_catch_finally", 1),
     (try_finally_simple, "avm1/try_finally_simple", 1),
+    (action_blocks, "avm1/action_blocks", 1),
     (loadmovie, "avm1/loadmovie", 2),
     (loadmovienum, "avm1/loadmovienum", 2),
     (loadmovie_registerclass, "avm1/loadmovie_registerclass", 2),
@@ -262,6 +294,7 @@ swf_tests! {
     (unloadmovienum, "avm1/unloadmovienum", 11),
     (unloadmovie_method, "avm1/unloadmovie_method", 11),
     (mcl_loadclip, "avm1/mcl_loadclip", 11),
+    (frames_loaded, "avm1/frames_loaded", 20, chunk_size = 128),
     (mcl_unloadclip, "avm1/mcl_unloadclip", 11),
     (mcl_getprogress, "avm1/mcl_getprogress", 6),
     (load_vars, "avm1/load_vars", 2),
@@ -396,6 +429,7 @@ swf_tests! {
     (as3_has_own_property, "avm2/has_own_property", 1),
     (as3_property_is_enumerable, "avm2/property_is_enumerable", 1),
     (as3_set_property_is_enumerable, "avm2/set_property_is_enumerable", 1),
+    (as3_set_property_coercion, "avm2/set_property_coercion", 6),
     (as3_object_to_string, "avm2/object_to_string", 1),
     (as3_function_to_string, "avm2/function_to_string", 1),
     (as3_class_to_string, "avm2/class_to_string", 1),
@@ -588,13 +622,25 @@ swf_tests! {
     (as3_edittext_default_format, "avm2/edittext_default_format", 1),
     (as3_edittext_html_entity, "avm2/edittext_html_entity", 1),
     #[ignore] (as3_edittext_html_roundtrip, "avm2/edittext_html_roundtrip", 1),
+    (as3_edittext_metrics, "avm2/edittext_metrics", 1),
     (as3_edittext_newline_stripping, "avm2/edittext_newline_stripping", 1),
     (as3_shape_drawrect, "avm2/shape_drawrect", 1),
     (as3_movieclip_drawrect, "avm2/movieclip_drawrect", 1),
     (as3_get_timer, "avm2/get_timer", 1),
+    (as3_tab_order, "avm2/tab_order", 2),
+    (as3_ime_composition, "avm2/ime_composition", 4),
+    (as3_edittext_restrict, "avm2/edittext_restrict", 4),
+    (as3_stylesheet, "avm2/stylesheet", 1),
+    (as3_html_image, "avm2/html_image", 2),
+    (as3_sprite_startdrag, "avm2/sprite_startdrag", 10),
+    (as3_sprite_droptarget, "avm2/sprite_droptarget", 5),
+    (as3_sprite_mouse_picking, "avm2/sprite_mouse_picking", 2),
+    (as3_mouseevent_double_click, "avm2/mouseevent_double_click", 16),
+    (as3_mouseevent_hover, "avm2/mouseevent_hover", 2),
     (as3_op_escxattr, "avm2/op_escxattr", 1),
     (as3_op_escxelem, "avm2/op_escxelem", 1),
     (as3_op_lookupswitch, "avm2/op_lookupswitch", 1),
+    (as3_loaderinfo_progress, "avm2/loaderinfo_progress", 10, chunk_size = 128),
     (as3_loaderinfo_properties, "avm2/loaderinfo_properties", 2),
     (as3_loaderinfo_quine, "avm2/loaderinfo_quine", 2),
     (nan_scale, "avm1/nan_scale", 1),
@@ -708,54 +754,74 @@ fn external_interface_avm1() -> Result<(), Error> {
         "tests/swfs/avm1/external_interface/test.swf",
         1,
         "tests/swfs/avm1/external_interface/output.txt",
-        |player| {
-            player
-                .lock()
-                .unwrap()
-                .add_external_interface(Box::new(ExternalInterfaceTestProvider::new()));
-            Ok(())
-        },
-        |player| {
-            let mut player_locked = player.lock().unwrap();
-
-            let parroted =
-                player_locked.call_internal_interface("parrot", vec!["Hello World!".into()]);
-            player_locked.log_backend().avm_trace(&format!(
-                "After calling `parrot` with a string: {:?}",
-                parroted
-            ));
-
-            let mut nested = BTreeMap::new();
-            nested.insert(
-                "list".to_string(),
-                vec![
-                    "string".into(),
-                    100.into(),
-                    false.into(),
-                    ExternalValue::Object(BTreeMap::new()),
-                ]
-                .into(),
-            );
+        add_external_interface,
+        call_external_interface_callbacks,
+        ImageComparison::None,
+        None,
+    )
+}
 
-            let mut root = BTreeMap::new();
-            root.insert("number".to_string(), (-500.1).into());
-            root.insert("string".to_string(), "A string!".into());
-            root.insert("true".to_string(), true.into());
-            root.insert("false".to_string(), false.into());
-            root.insert("null".to_string(), ExternalValue::Null);
-            root.insert("nested".to_string(), nested.into());
-            let result = player_locked
-                .call_internal_interface("callWith", vec!["trace".into(), root.into()]);
-            player_locked.log_backend().avm_trace(&format!(
-                "After calling `callWith` with a complex payload: {:?}",
-                result
-            ));
-            Ok(())
-        },
-        false,
+#[test]
+fn external_interface_avm2() -> Result<(), Error> {
+    set_logger();
+    test_swf_with_hooks(
+        "tests/swfs/avm2/external_interface/test.swf",
+        1,
+        "tests/swfs/avm2/external_interface/output.txt",
+        add_external_interface,
+        call_external_interface_callbacks,
+        ImageComparison::None,
+        None,
     )
 }
 
+fn add_external_interface(player: Arc<Mutex<Player>>) -> Result<(), Error> {
+    player
+        .lock()
+        .unwrap()
+        .add_external_interface(Box::new(ExternalInterfaceTestProvider::new()));
+    Ok(())
+}
+
+/// Calls the `parrot` and `callWith` callbacks registered by the
+/// `external_interface` movies.
+fn call_external_interface_callbacks(player: Arc<Mutex<Player>>) -> Result<(), Error> {
+    let mut player_locked = player.lock().unwrap();
+
+    let parroted = player_locked.call_internal_interface("parrot", vec!["Hello World!".into()]);
+    player_locked.log_backend().avm_trace(&format!(
+        "After calling `parrot` with a string: {:?}",
+        parroted
+    ));
+
+    let mut nested = BTreeMap::new();
+    nested.insert(
+        "list".to_string(),
+        vec![
+            "string".into(),
+            100.into(),
+            false.into(),
+            ExternalValue::Object(BTreeMap::new()),
+        ]
+        .into(),
+    );
+
+    let mut root = BTreeMap::new();
+    root.insert("number".to_string(), (-500.1).into());
+    root.insert("string".to_string(), "A string!".into());
+    root.insert("true".to_string(), true.into());
+    root.insert("false".to_string(), false.into());
+    root.insert("null".to_string(), ExternalValue::Null);
+    root.insert("nested".to_string(), nested.into());
+    let result =
+        player_locked.call_internal_interface("callWith", vec!["trace".into(), root.into()]);
+    player_locked.log_backend().avm_trace(&format!(
+        "After calling `callWith` with a complex payload: {:?}",
+        result
+    ));
+    Ok(())
+}
+
 #[test]
 fn shared_object_avm1() -> Result<(), Error> {
     set_logger();
@@ -776,7 +842,8 @@ fn shared_object_avm1() -> Result<(), Error> {
             std::mem::swap(player.storage_mut(), &mut memory_storage_backend);
             Ok(())
         },
-        false,
+        ImageComparison::None,
+        None,
     )?;
 
     // Verify that the flash cookie matches the expected one
@@ -800,7 +867,8 @@ fn shared_object_avm1() -> Result<(), Error> {
             Ok(())
         },
         |_player| Ok(()),
-        false,
+        ImageComparison::None,
+        None,
     )?;
 
     Ok(())
@@ -821,7 +889,8 @@ fn timeout_avm1() -> Result<(), Error> {
             Ok(())
         },
         |_| Ok(()),
-        false,
+        ImageComparison::None,
+        None,
     )
 }
 
@@ -841,10 +910,192 @@ fn stage_scale_mode() -> Result<(), Error> {
             Ok(())
         },
         |_| Ok(()),
-        false,
+        ImageComparison::None,
+        None,
+    )
+}
+
+#[test]
+fn software_render() -> Result<(), Error> {
+    set_logger();
+    test_swf_with_hooks(
+        "tests/swfs/avm1/software_render/test.swf",
+        1,
+        "tests/swfs/avm1/software_render/output.txt",
+        |_| Ok(()),
+        |_| Ok(()),
+        ImageComparison::Software,
+        None,
+    )
+}
+
+#[test]
+fn inspect_display_list_avm1() -> Result<(), Error> {
+    set_logger();
+    test_swf_with_hooks(
+        "tests/swfs/avm1/inspect_display_list/test.swf",
+        1,
+        "tests/swfs/avm1/inspect_display_list/output.txt",
+        |_| Ok(()),
+        |player| {
+            check_display_list(
+                player,
+                "MovieClip avm1 _level0\n\
+                 \x20 MovieClip \"box\" at 16387 avm1 _level0.box\n\
+                 \x20   MovieClip \"inner\" at 16385 avm1 _level0.box.inner\n\
+                 \x20 EditText \"label\" at 16391 avm1 _level0.label\n",
+            )
+        },
+        ImageComparison::None,
+        None,
+    )
+}
+
+#[test]
+fn inspect_display_list_avm2() -> Result<(), Error> {
+    set_logger();
+    test_swf_with_hooks(
+        "tests/swfs/avm2/inspect_display_list/test.swf",
+        1,
+        "tests/swfs/avm2/inspect_display_list/output.txt",
+        |_| Ok(()),
+        |player| {
+            check_display_list(
+                player,
+                "MovieClip avm2 Test\n\
+                 \x20 MovieClip \"box\" at 0 avm2 flash.display::Sprite\n\
+                 \x20   MovieClip \"inner\" at 0 avm2 flash.display::Sprite\n\
+                 \x20 EditText \"label\" at 0 avm2 flash.text::TextField\n",
+            )
+        },
+        ImageComparison::None,
+        None,
     )
 }
 
+/// Checks the display list built by the `inspect_display_list` movies.
+///
+/// Both movies place a 50x30 rectangle at (10, 20) in a clip named "box" at x = 100.
+fn check_display_list(player: Arc<Mutex<Player>>, expected: &str) -> Result<(), Error> {
+    use ruffle_core::inspector::{DisplayObjectInfo, ObjectLink};
+
+    fn describe(info: &DisplayObjectInfo, indent: usize, out: &mut String) {
+        out.push_str(&"  ".repeat(indent));
+        out.push_str(info.kind);
+        if indent > 0 {
+            out.push_str(&format!(" {:?} at {}", info.name, info.depth));
+        }
+        match &info.object {
+            Some(ObjectLink::Avm1 { path }) => out.push_str(&format!(" avm1 {}", path)),
+            Some(ObjectLink::Avm2 { class_name }) => out.push_str(&format!(" avm2 {}", class_name)),
+            None => out.push_str(" no object"),
+        }
+        out.push('\n');
+        for child in &info.children {
+            describe(child, indent + 1, out);
+        }
+    }
+
+    let stage = player.lock().unwrap().inspect_display_list();
+    assert_eq!(stage.kind, "Stage");
+    let root = match stage.children.as_slice() {
+        [root] => root,
+        children => return Err(format!("Expected one root, found {}", children.len()).into()),
+    };
+    let mut tree = String::new();
+    describe(root, 0, &mut tree);
+    assert_eq!(tree, expected);
+
+    let bounds = root.children[0]
+        .bounds
+        .ok_or("The box should have bounds")?;
+    assert_eq!(
+        format!(
+            "{} {} {} {}",
+            bounds.x_min, bounds.y_min, bounds.x_max, bounds.y_max
+        ),
+        "110 20 160 50"
+    );
+    assert!(root.children[0].children[0].bounds.is_none());
+    Ok(())
+}
+
+#[test]
+fn as3_sprite_button_mode() -> Result<(), Error> {
+    set_logger();
+    test_swf_with_hooks(
+        "tests/swfs/avm2/sprite_button_mode/test.swf",
+        2,
+        "tests/swfs/avm2/sprite_button_mode/output.txt",
+        |player| {
+            *player.lock().unwrap().ui_mut() = Box::new(CursorUiBackend::default());
+            Ok(())
+        },
+        |player| {
+            let player = player.lock().unwrap();
+            let ui = player
+                .ui()
+                .downcast_ref::<CursorUiBackend>()
+                .ok_or("Expected the cursor UI backend")?;
+            assert_eq!(
+                format!("{:?}", ui.cursors),
+                "[Hand, Arrow, Hand, Arrow, Hand]"
+            );
+            Ok(())
+        },
+        ImageComparison::None,
+        None,
+    )
+}
+
+/// A UI backend that remembers every mouse cursor the player asks for.
+#[derive(Default)]
+struct CursorUiBackend {
+    cursors: Vec<MouseCursor>,
+}
+
+impl UiBackend for CursorUiBackend {
+    fn is_key_down(&self, _key: KeyCode) -> bool {
+        false
+    }
+
+    fn last_key_code(&self) -> KeyCode {
+        KeyCode::Unknown
+    }
+
+    fn last_key_char(&self) -> Option<char> {
+        None
+    }
+
+    fn mouse_visible(&self) -> bool {
+        true
+    }
+
+    fn set_mouse_visible(&mut self, _visible: bool) {}
+
+    fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
+        self.cursors.push(cursor);
+    }
+
+    fn set_clipboard_content(&mut self, _content: String) {}
+
+    fn get_clipboard_content(&mut self) -> String {
+        String::new()
+    }
+
+    fn is_fullscreen(&self) -> bool {
+        false
+    }
+
+    fn set_ime_cursor_area(&mut self, _area: ImeCursorArea) {}
+
+    fn display_unsupported_message(&self) {}
+
+    fn display_root_movie_download_failed_message(&self) {}
+
+    fn message(&self, _message: &str) {}
+}
+
 /// Wrapper around string slice that makes debug output `{:?}` to print string same way as `{}`.
 /// Used in different `assert*!` macros in combination with `pretty_assertions` crate to make
 /// test failures to show nice diffs.
@@ -880,14 +1131,21 @@ fn test_swf(
     num_frames: u32,
     expected_output_path: &str,
     check_img: bool,
+    download_chunk_size: Option<usize>,
 ) -> Result<(), Error> {
+    let image_comparison = if check_img {
+        ImageComparison::Wgpu
+    } else {
+        ImageComparison::None
+    };
     test_swf_with_hooks(
         swf_path,
         num_frames,
         expected_output_path,
         |_| Ok(()),
         |_| Ok(()),
-        check_img,
+        image_comparison,
+        download_chunk_size,
     )
 }
 
@@ -899,7 +1157,8 @@ fn test_swf_with_hooks(
     expected_output_path: &str,
     before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
     before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
-    check_img: bool,
+    image_comparison: ImageComparison,
+    download_chunk_size: Option<usize>,
 ) -> Result<(), Error> {
     let mut expected_output = std::fs::read_to_string(expected_output_path)?.replace("\r\n", "\n");
 
@@ -908,7 +1167,14 @@ fn test_swf_with_hooks(
         expected_output = expected_output[0..expected_output.len() - "\n".len()].to_string();
     }
 
-    let trace_log = run_swf(swf_path, num_frames, before_start, before_end, check_img)?;
+    let trace_log = run_swf(
+        swf_path,
+        num_frames,
+        before_start,
+        before_end,
+        image_comparison,
+        download_chunk_size,
+    )?;
     assert_eq!(
         trace_log, expected_output,
         "ruffle output != flash player output"
@@ -926,7 +1192,14 @@ fn test_swf_approx(
     expected_output_path: &str,
     approx_assert_fn: impl Fn(f64, f64),
 ) -> Result<(), Error> {
-    let trace_log = run_swf(swf_path, num_frames, |_| Ok(()), |_| Ok(()), false)?;
+    let trace_log = run_swf(
+        swf_path,
+        num_frames,
+        |_| Ok(()),
+        |_| Ok(()),
+        ImageComparison::None,
+        None,
+    )?;
     let mut expected_data = std::fs::read_to_string(expected_output_path)?;
 
     // Strip a trailing newline if it has one.
@@ -967,6 +1240,152 @@ fn test_swf_approx(
     Ok(())
 }
 
+/// A single step of a test's input script.
+///
+/// If a test directory contains an `input.json` file holding a list of these,
+/// the events are injected into the player as it runs. Each `Wait` runs a
+/// single frame; all events before it are handled before that frame runs.
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+enum AutomatedEvent {
+    /// Runs the next frame.
+    Wait,
+
+    /// Moves the mouse to the given position, in stage pixels.
+    MouseMove {
+        pos: (f64, f64),
+    },
+
+    /// Presses the mouse button at the given position.
+    MouseDown {
+        pos: (f64, f64),
+    },
+
+    /// Releases the mouse button at the given position.
+    MouseUp {
+        pos: (f64, f64),
+    },
+
+    /// Presses and releases the mouse button at the given position.
+    Click {
+        pos: (f64, f64),
+    },
+
+    /// Scrolls the mouse wheel by the given number of lines.
+    MouseWheel {
+        lines: f64,
+    },
+
+    KeyDown {
+        key_code: KeyCode,
+    },
+
+    KeyUp {
+        key_code: KeyCode,
+    },
+
+    /// Presses and releases the given key.
+    KeyPress {
+        key_code: KeyCode,
+    },
+
+    /// Types each character of the given text in turn.
+    TextInput {
+        text: String,
+    },
+
+    /// Starts composing text with an input method editor (IME).
+    ImeCompositionStart,
+
+    /// Shows the given text as being composed with an IME.
+    ImeCompositionUpdate {
+        text: String,
+    },
+
+    /// Finishes composing text with an IME, entering the given text.
+    /// An empty text cancels the composition.
+    ImeCompositionCommit {
+        text: String,
+    },
+}
+
+impl AutomatedEvent {
+    fn player_events(&self) -> Vec<PlayerEvent> {
+        match self {
+            AutomatedEvent::Wait => vec![],
+            AutomatedEvent::MouseMove { pos: (x, y) } => {
+                vec![PlayerEvent::MouseMove { x: *x, y: *y }]
+            }
+            AutomatedEvent::MouseDown { pos: (x, y) } => {
+                vec![PlayerEvent::MouseDown { x: *x, y: *y }]
+            }
+            AutomatedEvent::MouseUp { pos: (x, y) } => vec![PlayerEvent::MouseUp { x: *x, y: *y }],
+            AutomatedEvent::Click { pos: (x, y) } => vec![
+                PlayerEvent::MouseMove { x: *x, y: *y },
+                PlayerEvent::MouseDown { x: *x, y: *y },
+                PlayerEvent::MouseUp { x: *x, y: *y },
+            ],
+            AutomatedEvent::MouseWheel { lines } => vec![PlayerEvent::MouseWheel {
+                delta: MouseWheelDelta::Lines(*lines),
+            }],
+            AutomatedEvent::KeyDown { key_code } => vec![PlayerEvent::KeyDown {
+                key_code: *key_code,
+            }],
+            AutomatedEvent::KeyUp { key_code } => vec![PlayerEvent::KeyUp {
+                key_code: *key_code,
+            }],
+            AutomatedEvent::KeyPress { key_code } => vec![
+                PlayerEvent::KeyDown {
+                    key_code: *key_code,
+                },
+                PlayerEvent::KeyUp {
+                    key_code: *key_code,
+                },
+            ],
+            AutomatedEvent::TextInput { text } => text
+                .chars()
+                .map(|codepoint| PlayerEvent::TextInput { codepoint })
+                .collect(),
+            AutomatedEvent::ImeCompositionStart => vec![PlayerEvent::ImeCompositionStart],
+            AutomatedEvent::ImeCompositionUpdate { text } => {
+                vec![PlayerEvent::ImeCompositionUpdate { text: text.clone() }]
+            }
+            AutomatedEvent::ImeCompositionCommit { text } => {
+                vec![PlayerEvent::ImeCompositionCommit { text: text.clone() }]
+            }
+        }
+    }
+}
+
+/// Loads the input script next to the given SWF, if there is one.
+fn load_input_script(swf_path: &str) -> Result<Option<VecDeque<AutomatedEvent>>, Error> {
+    let input_path = Path::new(swf_path).with_file_name("input.json");
+    if !input_path.is_file() {
+        return Ok(None);
+    }
+
+    let input = std::fs::read_to_string(&input_path)?;
+    let events: VecDeque<AutomatedEvent> = serde_json::from_str(&input)
+        .map_err(|e| format!("Couldn't parse input script `{:?}`: {}", input_path, e))?;
+    Ok(Some(events))
+}
+
+/// Loads the recorded session next to the given SWF, if there is one.
+///
+/// A test directory containing a `recording.json` replays that session instead
+/// of running a fixed number of frames.
+fn load_recording(swf_path: &str) -> Result<Option<Replayer>, Error> {
+    let recording_path = Path::new(swf_path).with_file_name("recording.json");
+    if !recording_path.is_file() {
+        return Ok(None);
+    }
+
+    let recording = std::fs::read_to_string(&recording_path)?;
+    let recording: Recording = serde_json::from_str(&recording)
+        .map_err(|e| format!("Couldn't parse recording `{:?}`: {}", recording_path, e))?;
+    Ok(Some(Replayer::new(recording)))
+}
+
 /// Loads an SWF and runs it through the Ruffle core for a number of frames.
 /// Tests that the trace output matches the given expected output.
 fn run_swf(
@@ -974,85 +1393,165 @@ fn run_swf(
     num_frames: u32,
     before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
     before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
-    mut check_img: bool,
+    mut image_comparison: ImageComparison,
+    download_chunk_size: Option<usize>,
 ) -> Result<String, Error> {
-    check_img &= RUN_IMG_TESTS;
+    if image_comparison == ImageComparison::Wgpu && !RUN_IMG_TESTS {
+        image_comparison = ImageComparison::None;
+    }
 
     let base_path = Path::new(swf_path).parent().unwrap();
     let (mut executor, channel) = NullExecutor::new();
     let movie = SwfMovie::from_path(swf_path, None)?;
     let frame_time = 1000.0 / movie.frame_rate().to_f64();
+    let movie_width = movie.width().to_pixels() as u32;
+    let movie_height = movie.height().to_pixels() as u32;
+    let mut input_script = load_input_script(swf_path)?;
+    let mut replayer = load_recording(swf_path)?;
     let trace_output = Rc::new(RefCell::new(Vec::new()));
 
     let mut platform_id = None;
     let backend_bit = wgpu::BackendBit::PRIMARY;
 
     let (render_backend, video_backend): (Box<dyn RenderBackend>, Box<dyn VideoBackend>) =
-        if check_img {
-            let instance = wgpu::Instance::new(backend_bit);
-
-            let descriptors = WgpuRenderBackend::<TextureTarget>::build_descriptors(
-                backend_bit,
-                instance,
-                None,
-                Default::default(),
-                None,
-            )?;
-
-            platform_id = Some(get_img_platform_suffix(&descriptors.info));
-
-            let target = TextureTarget::new(
-                &descriptors.device,
-                (
-                    movie.width().to_pixels() as u32,
-                    movie.height().to_pixels() as u32,
-                ),
-            );
+        match image_comparison {
+            ImageComparison::Wgpu => {
+                let instance = wgpu::Instance::new(backend_bit);
 
-            let render_backend = Box::new(WgpuRenderBackend::new(descriptors, target)?);
-            let video_backend = Box::new(SoftwareVideoBackend::new());
-            (render_backend, video_backend)
-        } else {
-            (Box::new(NullRenderer), Box::new(NullVideoBackend::new()))
+                let descriptors = WgpuRenderBackend::<TextureTarget>::build_descriptors(
+                    backend_bit,
+                    instance,
+                    None,
+                    Default::default(),
+                    None,
+                )?;
+
+                platform_id = Some(get_img_platform_suffix(&descriptors.info));
+
+                let target = TextureTarget::new(&descriptors.device, (movie_width, movie_height));
+
+                let render_backend = Box::new(WgpuRenderBackend::new(descriptors, target)?);
+                let video_backend = Box::new(SoftwareVideoBackend::new());
+                (render_backend, video_backend)
+            }
+            ImageComparison::Software => {
+                // The software renderer gives the same output on every machine.
+                platform_id = Some("software".to_string());
+
+                let render_backend =
+                    Box::new(SoftwareRenderBackend::new(movie_width, movie_height));
+                let video_backend = Box::new(SoftwareVideoBackend::new());
+                (render_backend, video_backend)
+            }
+            ImageComparison::None => (Box::new(NullRenderer), Box::new(NullVideoBackend::new())),
         };
 
+    let mut navigator = NullNavigatorBackend::with_base_path(base_path, channel);
+    navigator.set_chunk_size(download_chunk_size);
+    let navigator = Box::new(navigator);
+    let (navigator, locale): (Box<dyn NavigatorBackend>, Box<dyn LocaleBackend>) = match &replayer {
+        Some(replayer) => (
+            Box::new(replayer.navigator(navigator)),
+            Box::new(replayer.locale()),
+        ),
+        None => (navigator, Box::new(NullLocaleBackend::new())),
+    };
+
     let player = Player::new(
         render_backend,
         Box::new(NullAudioBackend::new()),
-        Box::new(NullNavigatorBackend::with_base_path(base_path, channel)),
+        navigator,
         Box::new(MemoryStorageBackend::default()),
-        Box::new(NullLocaleBackend::new()),
+        locale,
         video_backend,
         Box::new(TestLogBackend::new(trace_output.clone())),
         Box::new(NullUiBackend::new()),
     )?;
-    player.lock().unwrap().set_root_movie(Arc::new(movie));
+    if download_chunk_size.is_some() {
+        // The movie streams in as the executor is polled each frame.
+        let file_name = Path::new(swf_path).file_name().unwrap().to_string_lossy();
+        player.lock().unwrap().fetch_root_movie(
+            &file_name,
+            vec![],
+            Box::new(|_: &ruffle_core::swf::HeaderExt| {}),
+        );
+    } else {
+        player.lock().unwrap().set_root_movie(Arc::new(movie));
+    }
     player
         .lock()
         .unwrap()
         .set_max_execution_duration(Duration::from_secs(300));
 
+    if input_script.is_some() || replayer.is_some() || image_comparison == ImageComparison::Software
+    {
+        // Map mouse positions in the input script and the rendered image 1:1 onto the stage.
+        player
+            .lock()
+            .unwrap()
+            .set_viewport_dimensions(movie_width, movie_height, 1.0);
+    }
+
     before_start(player.clone())?;
 
-    for _ in 0..num_frames {
-        player.lock().unwrap().run_frame();
-        player.lock().unwrap().update_timers(frame_time);
-        executor.poll_all().unwrap();
+    if let Some(replayer) = &mut replayer {
+        // The recorded ticks decide how many frames run.
+        {
+            let mut player_lock = player.lock().unwrap();
+            replayer.start(&mut player_lock);
+            player_lock.set_is_playing(true);
+        }
+
+        while replayer.step(&mut player.lock().unwrap()) {
+            executor.poll_all().unwrap();
+        }
+    } else {
+        for frame in 0..num_frames {
+            if let Some(input_script) = &mut input_script {
+                let mut player_lock = player.lock().unwrap();
+                // Input scripts play back at the movie's frame rate, so that
+                // timing-dependent input such as double clicks is deterministic.
+                player_lock
+                    .navigator_mut()
+                    .downcast_mut::<NullNavigatorBackend>()
+                    .unwrap()
+                    .set_time_since_launch(Duration::from_secs_f64(
+                        f64::from(frame) * frame_time / 1000.0,
+                    ));
+                while let Some(event) = input_script.pop_front() {
+                    if let AutomatedEvent::Wait = event {
+                        break;
+                    }
+                    for player_event in event.player_events() {
+                        player_lock.handle_event(player_event);
+                    }
+                }
+            }
+
+            player.lock().unwrap().run_frame();
+            player.lock().unwrap().update_timers(frame_time);
+            executor.poll_all().unwrap();
+        }
     }
 
     // Render the image to disk
     // FIXME: Determine how we want to compare against on on-disk image
-    if check_img {
+    if image_comparison != ImageComparison::None {
         player.lock().unwrap().render();
         let mut player_lock = player.lock().unwrap();
-        let renderer = player_lock
-            .renderer_mut()
-            .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-            .unwrap();
-        let target = renderer.target();
-        let image = target
-            .capture(renderer.device())
-            .expect("Failed to capture image");
+        let renderer = player_lock.renderer_mut();
+        let image =
+            if let Some(renderer) = renderer.downcast_mut::<WgpuRenderBackend<TextureTarget>>() {
+                let target = renderer.target();
+                target
+                    .capture(renderer.device())
+                    .expect("Failed to capture image")
+            } else {
+                renderer
+                    .downcast_mut::<SoftwareRenderBackend>()
+                    .unwrap()
+                    .capture()
+            };
 
         // The swf path ends in '<swf_name>/test.swf' - extract `swf_name`
         let mut swf_path_buf = PathBuf::from(swf_path);
@@ -1171,4 +1670,8 @@ impl ExternalInterfaceProvider for ExternalInterfaceTestProvider {
     fn on_fs_command(&self, _command: &str, _args: &str) -> bool {
         false
     }
+
+    fn object_id(&self) -> Option<String> {
+        Some("external_interface_test".to_string())
+    }
 }
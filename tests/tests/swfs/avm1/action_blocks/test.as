@@ -0,0 +1,62 @@
+// Jumps out of With, Try and WaitForFrame blocks.
+
+var o = {x: "x in o"};
+
+function returnFromWith() {
+	with (o) {
+		return x;
+	}
+	return "not reached";
+}
+trace("return from with: " + returnFromWith());
+
+function returnFromTry() {
+	try {
+		return "try";
+	} finally {
+		trace("finally after return");
+	}
+	return "not reached";
+}
+trace("return from try: " + returnFromTry());
+
+function returnFromWithInTry() {
+	try {
+		with (o) {
+			return x;
+		}
+	} finally {
+		trace("finally after return from with");
+	}
+	return "not reached";
+}
+trace("return from with in try: " + returnFromWithInTry());
+
+// The end of a try block jumps over its catch block.
+var i;
+for (i = 0; i < 2; i++) {
+	try {
+		if (i == 1) {
+			throw "thrown " + i;
+		}
+		trace("try " + i);
+	} catch (e) {
+		trace("catch " + e);
+	} finally {
+		trace("finally " + i);
+	}
+	trace("after try " + i);
+}
+
+// A with block in a loop is entered again on every iteration.
+for (i = 0; i < 2; i++) {
+	with (o) {
+		trace("with " + x + " " + i);
+	}
+}
+
+ifFrameLoaded(1) {
+	trace("frame 1 loaded");
+	trace("still in the block");
+}
+trace("after ifFrameLoaded");
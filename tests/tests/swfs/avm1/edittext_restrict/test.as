@@ -0,0 +1,34 @@
+// Frame 1 of a 3-frame, 200x200 movie at 10 fps.
+// input.json clicks each input field and types into it.
+
+createTextField("digits", 1, 0, 0, 100, 20);
+digits.type = "input";
+trace("restrict: " + digits.restrict);
+trace("maxChars: " + digits.maxChars);
+digits.restrict = "0-9";
+digits.maxChars = 4;
+trace("restrict: " + digits.restrict);
+trace("maxChars: " + digits.maxChars);
+
+// Typed lowercase letters are entered as uppercase, except for Q.
+createTextField("upper", 2, 0, 50, 100, 20);
+upper.type = "input";
+upper.restrict = "A-Z ^Q";
+
+// Scripts may set text longer than maxChars, but nothing more can be typed.
+createTextField("full", 3, 0, 100, 100, 20);
+full.type = "input";
+full.maxChars = 3;
+full.text = "abcdef";
+trace("full: " + full.text);
+
+onEnterFrame = function() {
+    trace("digits: " + digits.text);
+    trace("upper: " + upper.text);
+    trace("full: " + full.text);
+    digits.restrict = null;
+    digits.maxChars = null;
+    trace("restrict: " + digits.restrict);
+    trace("maxChars: " + digits.maxChars);
+    delete this.onEnterFrame;
+};
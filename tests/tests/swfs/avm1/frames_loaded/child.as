@@ -0,0 +1,7 @@
+// Frame 1
+trace("child frame 1: " + this._framesloaded + "/" + this._totalframes);
+
+// Frame 2
+trace("child frame 2: " + this._framesloaded + "/" + this._totalframes);
+trace("child frame 2 has a longer script, so it takes a while to download");
+stop();
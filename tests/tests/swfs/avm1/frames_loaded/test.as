@@ -0,0 +1,33 @@
+// Frame 1
+trace("frame 1: " + this._framesloaded + "/" + this._totalframes);
+trace("bytes: " + this.getBytesLoaded() + "/" + this.getBytesTotal());
+ifFrameLoaded(3) {
+	trace("frame 3 loaded too early");
+}
+
+// Frame 2
+trace("frame 2: " + this._framesloaded + "/" + this._totalframes);
+trace("bytes: " + this.getBytesLoaded() + "/" + this.getBytesTotal());
+
+// Frame 3
+trace("frame 3: " + this._framesloaded + "/" + this._totalframes);
+trace("bytes: " + this.getBytesLoaded() + "/" + this.getBytesTotal());
+ifFrameLoaded(3) {
+	trace("frame 3 loaded");
+}
+
+this.createEmptyMovieClip("holder", 1);
+listener = new Object();
+listener.onLoadProgress = function(target, loaded, total) {
+	trace("onLoadProgress: " + loaded + "/" + total);
+};
+listener.onLoadComplete = function(target) {
+	trace("onLoadComplete");
+};
+listener.onLoadInit = function(target) {
+	trace("onLoadInit: " + target._framesloaded + "/" + target._totalframes);
+};
+mcl = new MovieClipLoader();
+mcl.addListener(listener);
+mcl.loadClip("child.swf", this.holder);
+stop();
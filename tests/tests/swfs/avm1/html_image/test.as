@@ -0,0 +1,22 @@
+// Frame 1 of a 1-frame movie.
+// Loads an image into an HTML text field, and finds it by its id.
+
+createTextField("field", 1, 0, 0, 200, 100);
+field.html = true;
+field.htmlText = "before <img src='image.png' id='pic' width='20' height='10'/> after";
+trace("text: " + field.text);
+trace("pic: " + typeof field.pic);
+// Names are case sensitive from SWF 7 onwards.
+trace("PIC: " + typeof field.PIC);
+trace("missing: " + typeof field.missing);
+
+// The loaded bitmap is placed inside the image's clip once the load completes.
+function hasBitmap() {
+	return field.pic.getInstanceAtDepth(-16383) == field.pic;
+}
+trace("loaded: " + hasBitmap());
+
+onEnterFrame = function() {
+	trace("loaded: " + hasBitmap());
+	delete this.onEnterFrame;
+};
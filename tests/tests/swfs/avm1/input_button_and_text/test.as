@@ -0,0 +1,23 @@
+// Frame 1 of a 3-frame, 200x200 movie at 10 fps.
+// input.json clicks the button, then clicks the input field and types into it.
+
+createEmptyMovieClip("button", 1);
+button.beginFill(0x0000FF);
+button.moveTo(0, 0);
+button.lineTo(50, 0);
+button.lineTo(50, 50);
+button.lineTo(0, 50);
+button.lineTo(0, 0);
+button.endFill();
+button.onRelease = function() {
+    trace("button clicked");
+};
+
+createTextField("field", 2, 0, 100, 100, 20);
+field.type = "input";
+field.border = true;
+
+onEnterFrame = function() {
+    trace("text: " + field.text);
+    delete this.onEnterFrame;
+};
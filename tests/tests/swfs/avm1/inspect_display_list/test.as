@@ -0,0 +1,15 @@
+// Builds a small display list for `Player::inspect_display_list`.
+
+var box = createEmptyMovieClip("box", 3);
+box._x = 100;
+box.beginFill(0xFF0000);
+box.moveTo(10, 20);
+box.lineTo(60, 20);
+box.lineTo(60, 50);
+box.lineTo(10, 50);
+box.lineTo(10, 20);
+box.endFill();
+
+var inner = box.createEmptyMovieClip("inner", 1);
+createTextField("label", 7, 0, 100, 80, 20);
+trace("built " + box._name + " " + inner._name + " " + label._name);
@@ -0,0 +1,12 @@
+// Frame 1 of a 200x200 movie at 10 fps.
+// Logs the time of each mouse press, as seen by getTimer() and Date.
+
+trace("start " + getTimer());
+clicks = 0;
+listener = new Object();
+listener.onMouseDown = function() {
+    clicks++;
+    trace("down " + clicks + " " + getTimer() + " " + new Date().getTime());
+};
+Mouse.addListener(listener);
+stop();
@@ -0,0 +1,22 @@
+// Frame 1 of a 40x30 movie.
+// Draws a red rectangle partly covered by a green one.
+
+createEmptyMovieClip("red", 1);
+red.beginFill(0xFF0000);
+red.moveTo(5, 5);
+red.lineTo(20, 5);
+red.lineTo(20, 15);
+red.lineTo(5, 15);
+red.lineTo(5, 5);
+red.endFill();
+
+createEmptyMovieClip("green", 2);
+green.beginFill(0x00FF00);
+green.moveTo(15, 10);
+green.lineTo(35, 10);
+green.lineTo(35, 25);
+green.lineTo(15, 25);
+green.lineTo(15, 10);
+green.endFill();
+
+trace("rendered");
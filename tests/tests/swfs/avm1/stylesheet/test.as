@@ -0,0 +1,44 @@
+// Styles HTML text with a TextField.StyleSheet.
+
+var css = new TextField.StyleSheet();
+trace("parsed: " + css.parseCSS("p { color: #FF0000; font-size: 20px; } .big { font-weight: bold; }"));
+var names = css.getStyleNames();
+names.sort();
+trace("styles: " + names);
+
+// getStyle returns a copy of the style.
+var p = css.getStyle("p");
+trace("p: " + p.color + " " + p.fontSize);
+p.color = "#00FF00";
+trace("p: " + css.getStyle("p").color);
+trace("missing: " + (css.getStyle("missing") == undefined));
+
+css.setStyle(".em", {fontStyle: "italic"});
+trace(".em: " + css.getStyle(".em").fontStyle);
+css.setStyle(".em", null);
+trace(".em removed: " + (css.getStyle(".em") == undefined));
+
+// CSS that can't be parsed leaves the sheet unchanged.
+trace("parsed: " + css.parseCSS("p { color"));
+trace("p: " + css.getStyle("p").color);
+
+function traceFormat(field, index) {
+	var format = field.getTextFormat(index, index + 1);
+	trace(index + ": " + format.color + " " + format.size + " " + format.bold);
+}
+
+createTextField("field", 1, 0, 0, 200, 100);
+field.html = true;
+field.styleSheet = css;
+field.htmlText = "<p>red <span class='big'>bold</span></p>";
+trace("styled: " + (field.styleSheet == css));
+traceFormat(field, 0);
+traceFormat(field, 4);
+
+// Setting a stylesheet restyles the HTML that is already there.
+createTextField("late", 2, 0, 100, 200, 100);
+late.html = true;
+late.htmlText = "<p>plain</p>";
+traceFormat(late, 0);
+late.styleSheet = css;
+traceFormat(late, 0);
@@ -0,0 +1,41 @@
+package {
+	import flash.display.MovieClip;
+	import flash.text.TextField;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// 'é' is one UTF-16 unit but two UTF-8 bytes, and the emoji is
+			// a surrogate pair; indices count UTF-16 units.
+			var field = new TextField();
+			field.multiline = true;
+			field.text = "é😀ab\ncd";
+			trace("// field.numLines");
+			trace(field.numLines);
+			trace("// field.getLineOffset(0)");
+			trace(field.getLineOffset(0));
+			trace("// field.getLineOffset(1)");
+			trace(field.getLineOffset(1));
+			trace("// field.getLineLength(0)");
+			trace(field.getLineLength(0));
+			trace("// field.getLineLength(1)");
+			trace(field.getLineLength(1));
+			trace("// field.getLineIndexOfChar(4)");
+			trace(field.getLineIndexOfChar(4));
+			trace("// field.getLineIndexOfChar(6)");
+			trace(field.getLineIndexOfChar(6));
+			trace("// field.getLineIndexOfChar(8)");
+			trace(field.getLineIndexOfChar(8));
+			trace("// field.getFirstCharInParagraph(4)");
+			trace(field.getFirstCharInParagraph(4));
+			trace("// field.getFirstCharInParagraph(7)");
+			trace(field.getFirstCharInParagraph(7));
+			trace("// field.getLineText(1)");
+			trace(field.getLineText(1));
+			for each (var index in [3, 7]) {
+				var bounds = field.getCharBoundaries(index);
+				trace("// field.getCharIndexAtPoint(<middle of char " + index + ">)");
+				trace(field.getCharIndexAtPoint(bounds.x + bounds.width / 2, bounds.y + bounds.height / 2));
+			}
+		}
+	}
+}
@@ -0,0 +1,54 @@
+package {
+	import flash.display.MovieClip;
+	import flash.text.TextField;
+
+	public class Test extends MovieClip {
+		public var fields:Array = [];
+
+		public function Test() {
+			var digits = new TextField();
+			digits.type = "input";
+			trace("restrict: " + digits.restrict);
+			trace("maxChars: " + digits.maxChars);
+			digits.restrict = "0-9";
+			digits.maxChars = 4;
+			trace("restrict: " + digits.restrict);
+			trace("maxChars: " + digits.maxChars);
+
+			// Typed lowercase letters are entered as uppercase, except for Q.
+			var upper = new TextField();
+			upper.type = "input";
+			upper.restrict = "A-Z ^Q";
+
+			// Scripts may set text longer than maxChars, but nothing more can be typed.
+			var full = new TextField();
+			full.type = "input";
+			full.maxChars = 3;
+			full.text = "abcdef";
+			trace("full: " + full.text);
+
+			fields = [digits, upper, full];
+			for each (var field in fields) {
+				addChild(field);
+			}
+			stage.focus = digits;
+			digits.setSelection(0, 0);
+
+			// input.json types into each field in turn, one per frame.
+			addEventListener("enterFrame", function(e) {
+				var fields = e.target.fields;
+				var field = fields.shift();
+				trace("text: " + field.text);
+				if (fields.length > 0) {
+					e.target.stage.focus = fields[0];
+					fields[0].setSelection(fields[0].length, fields[0].length);
+				} else {
+					field.restrict = null;
+					field.maxChars = 0;
+					trace("restrict: " + field.restrict);
+					trace("maxChars: " + field.maxChars);
+				}
+			});
+		}
+	}
+}
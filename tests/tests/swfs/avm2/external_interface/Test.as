@@ -0,0 +1,31 @@
+package {
+	import flash.display.MovieClip;
+	import flash.external.ExternalInterface;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("// ExternalInterface.available");
+			trace(ExternalInterface.available);
+			trace("// ExternalInterface.objectID");
+			trace(ExternalInterface.objectID);
+			ExternalInterface.addCallback("parrot", function(value) {
+				trace("/// parrot(" + value + ")");
+				return value;
+			});
+			ExternalInterface.addCallback("callWith", function(name, payload) {
+				trace("/// callWith(" + name + ")");
+				return ExternalInterface.call(name, payload);
+			});
+			trace("// ExternalInterface.call(\"ping\")");
+			trace(ExternalInterface.call("ping"));
+			trace("// ExternalInterface.call(\"non_existent\")");
+			trace(ExternalInterface.call("non_existent"));
+			trace("// ExternalInterface.call(\"reentry\")");
+			trace(ExternalInterface.call("reentry"));
+			var cyclic = {name: "cyclic"};
+			cyclic.self = cyclic;
+			trace("// ExternalInterface.call(\"trace\", cyclic)");
+			trace(ExternalInterface.call("trace", cyclic));
+		}
+	}
+}
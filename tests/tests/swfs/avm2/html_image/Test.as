@@ -0,0 +1,25 @@
+package {
+	import flash.display.MovieClip;
+	import flash.text.TextField;
+
+	// Loads an image into an HTML text field, and finds it with getImageReference.
+	public class Test extends MovieClip {
+		public var field:TextField;
+
+		public function Test() {
+			field = new TextField();
+			field.htmlText = "before <img src='image.png' id='pic' width='20' height='10'/> after";
+			addChild(field);
+			trace("text: " + field.text);
+			trace("pic: " + field.getImageReference("pic"));
+			trace("PIC: " + field.getImageReference("PIC"));
+			trace("missing: " + field.getImageReference("missing"));
+
+			// The loaded bitmap is placed inside the image's clip once the load completes.
+			trace("loaded: " + field.getImageReference("pic").numChildren);
+			addEventListener("enterFrame", function(e) {
+				trace("loaded: " + e.target.field.getImageReference("pic").numChildren);
+			});
+		}
+	}
+}
@@ -0,0 +1,26 @@
+package {
+	import flash.display.MovieClip;
+	import flash.text.TextField;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// Composes text at the caret, types after it, then cancels a
+			// second composition.
+			var field = new TextField();
+			field.type = "input";
+			field.text = "ab";
+			addChild(field);
+			stage.focus = field;
+			field.setSelection(1, 1);
+			field.addEventListener("imeStartComposition", function(e) {
+				trace(e.type);
+			});
+			field.addEventListener("imeComposition", function(e) {
+				trace(e.type + " " + e.text);
+			});
+			addEventListener("enterFrame", function(e) {
+				trace("text: " + e.target.getChildAt(0).text);
+			});
+		}
+	}
+}
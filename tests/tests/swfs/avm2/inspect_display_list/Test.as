@@ -0,0 +1,28 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.text.TextField;
+
+	// Builds a small display list for `Player::inspect_display_list`.
+	public class Test extends MovieClip {
+		public function Test() {
+			var box = new Sprite();
+			box.name = "box";
+			box.x = 100;
+			box.graphics.beginFill(0xFF0000);
+			box.graphics.drawRect(10, 20, 50, 30);
+			box.graphics.endFill();
+			addChild(box);
+
+			var inner = new Sprite();
+			inner.name = "inner";
+			box.addChild(inner);
+
+			var label = new TextField();
+			label.name = "label";
+			label.y = 100;
+			addChild(label);
+			trace("built " + box.name + " " + inner.name + " " + label.name);
+		}
+	}
+}
@@ -0,0 +1,19 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.ProgressEvent;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("constructed: " + this.framesLoaded + "/" + this.totalFrames);
+			trace("bytes: " + this.loaderInfo.bytesLoaded + "/" + this.loaderInfo.bytesTotal);
+			trace(this.loaderInfo === this.loaderInfo);
+			this.loaderInfo.addEventListener("progress", function(e) {
+				trace("progress: " + e.bytesLoaded + "/" + e.bytesTotal + " " + (e is ProgressEvent));
+			});
+			this.loaderInfo.addEventListener("complete", function(e) {
+				trace("complete: " + e.target.bytesLoaded + "/" + e.target.bytesTotal);
+			});
+		}
+	}
+}
@@ -0,0 +1,33 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+
+	// Clicks on sprites in quick succession. Only the one with doubleClickEnabled
+	// receives double clicks, and only if the clicks are close enough in time.
+	public class Test extends MovieClip {
+		public function Test() {
+			var a = new Sprite();
+			a.name = "a";
+			a.graphics.beginFill(0);
+			a.graphics.drawRect(0, 0, 50, 50);
+			trace("doubleClickEnabled: " + a.doubleClickEnabled);
+			a.doubleClickEnabled = true;
+			trace("doubleClickEnabled: " + a.doubleClickEnabled);
+			addChild(a);
+
+			var b = new Sprite();
+			b.name = "b";
+			b.x = 100;
+			b.graphics.beginFill(0);
+			b.graphics.drawRect(0, 0, 50, 50);
+			addChild(b);
+
+			addEventListener("click", logClick);
+			addEventListener("doubleClick", logClick);
+		}
+
+		public function logClick(e) {
+			trace(e.type + " " + e.target.name);
+		}
+	}
+}
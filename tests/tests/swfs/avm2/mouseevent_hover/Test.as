@@ -0,0 +1,52 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+
+	// Moves the mouse into a sprite, into its child, over to a sibling and
+	// off of everything, scrolling the mouse wheel along the way.
+	public class Test extends MovieClip {
+		public function Test() {
+			var outer = box("outer", 20, 20, 100);
+			var inner = box("inner", 25, 25, 30);
+			outer.addChild(inner);
+			addChild(outer);
+			var other = box("other", 150, 20, 30);
+			addChild(other);
+
+			for each (var sprite in [outer, inner, other]) {
+				for each (var type in ["mouseOver", "mouseOut", "rollOver", "rollOut", "mouseWheel"]) {
+					sprite.addEventListener(type, logEvent);
+				}
+			}
+			stage.addEventListener("mouseWheel", logEvent);
+		}
+
+		public function box(name, x, y, size) {
+			var sprite = new Sprite();
+			sprite.name = name;
+			sprite.x = x;
+			sprite.y = y;
+			sprite.graphics.beginFill(0);
+			sprite.graphics.drawRect(0, 0, size, size);
+			return sprite;
+		}
+
+		public function label(object) {
+			if (object == null) {
+				return "null";
+			} else if (object == stage) {
+				return "stage";
+			}
+			return object.name;
+		}
+
+		public function logEvent(e) {
+			var line = e.type + " target=" + label(e.target) + " currentTarget=" + label(e.currentTarget);
+			line = line + " relatedObject=" + label(e.relatedObject) + " bubbles=" + e.bubbles;
+			if (e.type == "mouseWheel") {
+				line = line + " delta=" + e.delta;
+			}
+			trace(line);
+		}
+	}
+}
@@ -0,0 +1,56 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.MouseEvent;
+	import flash.text.TextFormat;
+	import flash.text.TextLineMetrics;
+
+	public class Test extends MovieClip {
+		public var count:int = 0;
+		public var total:int = 0;
+
+		public function Test() {
+			// `o.leading` has a cached name, and `o["leading"]` takes its
+			// name from the stack and is never cached. Both coerce to the
+			// slot type, which is Number on TextLineMetrics and Object on
+			// TextFormat. Alternating the classes refills the cache.
+			var metrics = new TextLineMetrics();
+			var format = new TextFormat();
+			for each (var o in [metrics, format, metrics, metrics]) {
+				o.leading = "7";
+				trace(typeof o.leading + " " + o.leading);
+				o["leading"] = "8";
+				trace(typeof o["leading"] + " " + o["leading"]);
+			}
+
+			total = "3.5";
+			trace(typeof total + " " + total);
+			this["total"] = "4.5";
+			trace(typeof total + " " + total);
+
+			format.bold = undefined;
+			trace(format.bold);
+			format["bold"] = undefined;
+			trace(format["bold"]);
+
+			// Frames 1 and 2 write relatedObject through a cached name, and
+			// frames 3 and 4 through a name from the stack. The writes on
+			// even frames can't be coerced and throw a TypeError.
+			addEventListener("enterFrame", function(e) {
+				var test = e.target;
+				var event = new MouseEvent("click");
+				test.count++;
+				if (test.count > 4) {
+					return;
+				}
+				var value = test.count % 2 == 1 ? test : "text";
+				trace("frame " + test.count);
+				if (test.count <= 2) {
+					event.relatedObject = value;
+				} else {
+					event["relatedObject"] = value;
+				}
+				trace("relatedObject set: " + (event.relatedObject == test));
+			});
+		}
+	}
+}
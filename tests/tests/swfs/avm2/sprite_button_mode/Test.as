@@ -0,0 +1,49 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+
+	// Hovers over sprites with and without buttonMode and useHandCursor.
+	// The test checks which mouse cursor is shown over each of them.
+	public class Test extends MovieClip {
+		public function Test() {
+			var button = box("button", 10, 10, 50);
+			trace("buttonMode: " + button.buttonMode);
+			button.buttonMode = true;
+			trace("buttonMode: " + button.buttonMode);
+			addChild(button);
+
+			var arrow = box("arrow", 110, 10, 50);
+			arrow.buttonMode = true;
+			trace("useHandCursor: " + arrow.useHandCursor);
+			arrow.useHandCursor = false;
+			trace("useHandCursor: " + arrow.useHandCursor);
+			addChild(arrow);
+
+			addChild(box("plain", 10, 110, 50));
+
+			// Children of a sprite in button mode show the hand cursor too.
+			var parent = box("parent", 110, 110, 0);
+			parent.buttonMode = true;
+			parent.addChild(box("child", 0, 0, 50));
+			addChild(parent);
+
+			addEventListener("mouseOver", logOver);
+		}
+
+		public function box(name, x, y, size) {
+			var sprite = new Sprite();
+			sprite.name = name;
+			sprite.x = x;
+			sprite.y = y;
+			if (size > 0) {
+				sprite.graphics.beginFill(0);
+				sprite.graphics.drawRect(0, 0, size, size);
+			}
+			return sprite;
+		}
+
+		public function logOver(e) {
+			trace("mouseOver " + e.target.name);
+		}
+	}
+}
@@ -0,0 +1,54 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.display.Sprite;
+
+	// Drags a sprite over other objects, and reads which one it would be dropped on.
+	public class Test extends MovieClip {
+		public var dragged:Sprite;
+
+		public function Test() {
+			// The deepest object under the mouse is the target, even if it's a shape.
+			var holder = new Sprite();
+			holder.name = "holder";
+			var shape = new Shape();
+			shape.name = "shape";
+			shape.graphics.beginFill(0);
+			shape.graphics.drawRect(0, 0, 50, 50);
+			holder.addChild(shape);
+			addChild(holder);
+
+			var solid = new Sprite();
+			solid.name = "solid";
+			solid.x = 100;
+			solid.graphics.beginFill(0);
+			solid.graphics.drawRect(0, 0, 50, 50);
+			addChild(solid);
+
+			// Objects that ignore the mouse can still be dropped on.
+			var disabled = new Sprite();
+			disabled.name = "disabled";
+			disabled.y = 100;
+			disabled.mouseEnabled = false;
+			disabled.mouseChildren = false;
+			disabled.graphics.beginFill(0);
+			disabled.graphics.drawRect(0, 0, 50, 50);
+			addChild(disabled);
+
+			dragged = new Sprite();
+			dragged.name = "dragged";
+			dragged.graphics.beginFill(0);
+			dragged.graphics.drawRect(-5, -5, 10, 10);
+			addChild(dragged);
+
+			trace("dropTarget: " + dragged.dropTarget);
+			dragged.startDrag(true);
+			addEventListener("enterFrame", logDropTarget);
+		}
+
+		public function logDropTarget(e) {
+			var target = dragged.dropTarget;
+			trace("at " + dragged.x + " " + dragged.y + ": " + (target == null ? "null" : target.name));
+		}
+	}
+}
@@ -0,0 +1,64 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+
+	// Clicks on sprites using hitArea, mouseEnabled and mouseChildren.
+	public class Test extends MovieClip {
+		public var button:Sprite;
+		public var container:Sprite;
+
+		public function Test() {
+			addChild(box("floor", 0, 0, 200));
+
+			// The button is clicked through its hit area instead of its own shape.
+			button = box("button", 0, 0, 50);
+			addChild(button);
+			var area = box("area", 100, 0, 50);
+			area.visible = false;
+			addChild(area);
+			trace("hitArea: " + button.hitArea);
+			button.hitArea = area;
+			trace("hitArea: " + button.hitArea.name);
+
+			// Clicks on the child are sent to its container instead.
+			container = box("container", 0, 100, 0);
+			container.addChild(box("child", 0, 0, 50));
+			trace("mouseChildren: " + container.mouseChildren);
+			container.mouseChildren = false;
+			trace("mouseChildren: " + container.mouseChildren);
+			addChild(container);
+
+			// Only the child of this sprite can be clicked. Clicks on the sprite's own
+			// shape are sent to its parent instead.
+			var ignored = box("ignored", 100, 100, 50);
+			ignored.addChild(box("inner", 0, 0, 20));
+			trace("mouseEnabled: " + ignored.mouseEnabled);
+			ignored.mouseEnabled = false;
+			trace("mouseEnabled: " + ignored.mouseEnabled);
+			addChild(ignored);
+
+			addEventListener("click", logClick);
+		}
+
+		public function box(name, x, y, size) {
+			var sprite = new Sprite();
+			sprite.name = name;
+			sprite.x = x;
+			sprite.y = y;
+			if (size > 0) {
+				sprite.graphics.beginFill(0);
+				sprite.graphics.drawRect(0, 0, size, size);
+			}
+			return sprite;
+		}
+
+		public function logClick(e) {
+			trace("click: " + (e.target == this ? "root" : e.target.name));
+			if (e.target == button) {
+				button.hitArea = null;
+			} else if (e.target == container) {
+				container.mouseChildren = true;
+			}
+		}
+	}
+}
@@ -0,0 +1,59 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.geom.Rectangle;
+
+	// Drags a box with each combination of startDrag's arguments, and stops
+	// the drag when the mouse is released.
+	public class Test extends MovieClip {
+		public var box:Sprite;
+		public var drags:int = 0;
+
+		public function Test() {
+			var background = new Sprite();
+			background.name = "background";
+			background.graphics.beginFill(0);
+			background.graphics.drawRect(0, 0, 200, 200);
+			addChild(background);
+
+			box = new Sprite();
+			box.name = "box";
+			box.x = 50;
+			box.y = 50;
+			box.graphics.beginFill(0);
+			box.graphics.drawRect(-10, -10, 20, 20);
+			addChild(box);
+
+			box.addEventListener("mouseDown", startDragging);
+			addEventListener("mouseUp", stopDragging);
+			addEventListener("enterFrame", logPosition);
+		}
+
+		public function startDragging(e) {
+			drags = drags + 1;
+			if (drags == 1) {
+				trace("startDrag()");
+				box.startDrag();
+			} else if (drags == 2) {
+				trace("startDrag(true)");
+				box.startDrag(true);
+			} else if (drags == 3) {
+				trace("startDrag(false, (0, 0, 100, 50))");
+				box.startDrag(false, new Rectangle(0, 0, 100, 50));
+			} else {
+				// A negative size extends the bounds to the left and top.
+				trace("startDrag(true, (150, 150, -50, -50))");
+				box.startDrag(true, new Rectangle(150, 150, -50, -50));
+			}
+		}
+
+		public function stopDragging(e) {
+			trace("stopDrag() on " + e.target.name);
+			box.stopDrag();
+		}
+
+		public function logPosition(e) {
+			trace("box: " + box.x + " " + box.y);
+		}
+	}
+}
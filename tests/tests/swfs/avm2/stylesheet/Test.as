@@ -0,0 +1,51 @@
+package {
+	import flash.display.MovieClip;
+	import flash.text.StyleSheet;
+	import flash.text.TextField;
+
+	// Styles HTML text with a flash.text.StyleSheet.
+	public class Test extends MovieClip {
+		public function Test() {
+			var css = new StyleSheet();
+			css.parseCSS("p { color: #FF0000; font-size: 20px; } .big { font-weight: bold; }");
+			var names = css.styleNames;
+			names.sort();
+			trace("styles: " + names);
+
+			// getStyle returns a copy of the style.
+			var p = css.getStyle("p");
+			trace("p: " + p.color + " " + p.fontSize);
+			p.color = "#00FF00";
+			trace("p: " + css.getStyle("p").color);
+
+			css.setStyle(".em", {fontStyle: "italic"});
+			trace(".em: " + css.getStyle(".em").fontStyle);
+			css.setStyle(".em", null);
+			trace(".em removed: " + (css.styleNames.length == 2));
+
+			// CSS that can't be parsed is ignored.
+			css.parseCSS("p { color");
+			trace("p: " + css.getStyle("p").color);
+
+			var field = new TextField();
+			field.styleSheet = css;
+			field.htmlText = "<p>red <span class='big'>bold</span></p>";
+			addChild(field);
+			trace("styled: " + (field.styleSheet == css));
+			traceFormat(field, 0);
+			traceFormat(field, 4);
+
+			// Setting a stylesheet restyles the HTML that is already there.
+			var late = new TextField();
+			late.htmlText = "<p>plain</p>";
+			traceFormat(late, 0);
+			late.styleSheet = css;
+			traceFormat(late, 0);
+		}
+
+		public function traceFormat(field, index) {
+			var format = field.getTextFormat(index, index + 1);
+			trace(index + ": " + format.color + " " + format.size + " " + format.bold);
+		}
+	}
+}
@@ -0,0 +1,31 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// Tabs through the sprites in reading order (b, c, a), activates
+			// one with Enter, then tabs through the ones with a tabIndex (c, a).
+			var log = function(e) {
+				trace(e.type + " " + e.target.name);
+			};
+			for each (var type in ["focusIn", "focusOut", "mouseDown", "mouseUp", "click"]) {
+				addEventListener(type, log);
+			}
+			addEventListener("click", function(e) {
+				e.target.parent.getChildByName("c").tabIndex = 1;
+				e.target.parent.getChildByName("a").tabIndex = 2;
+			});
+			for each (var args in [["a", 0, 50], ["b", 0, 0], ["c", 50, 0]]) {
+				var sprite = new Sprite();
+				sprite.name = args[0];
+				sprite.x = args[1];
+				sprite.y = args[2];
+				sprite.tabEnabled = true;
+				sprite.graphics.beginFill(0);
+				sprite.graphics.drawRect(0, 0, 10, 10);
+				addChild(sprite);
+			}
+		}
+	}
+}
@@ -38,8 +38,8 @@ use std::time::Duration;
 use std::{cell::RefCell, error::Error, num::NonZeroI32};
 use wasm_bindgen::{prelude::*, JsCast, JsValue};
 use web_sys::{
-    AddEventListenerOptions, Element, Event, EventTarget, HtmlCanvasElement, HtmlElement,
-    KeyboardEvent, PointerEvent, WheelEvent, Window,
+    AddEventListenerOptions, CompositionEvent, Element, Event, EventTarget, HtmlCanvasElement,
+    HtmlElement, HtmlTextAreaElement, KeyboardEvent, PointerEvent, WheelEvent, Window,
 };
 
 static RUFFLE_GLOBAL_PANIC: Once = Once::new();
@@ -59,6 +59,7 @@ struct RuffleInstance {
     core: Arc<Mutex<Player>>,
     js_player: JavascriptPlayer,
     canvas: HtmlCanvasElement,
+    ime_input: HtmlTextAreaElement,
     canvas_width: i32,
     canvas_height: i32,
     device_pixel_ratio: f64,
@@ -75,6 +76,9 @@ struct RuffleInstance {
     mouse_wheel_callback: Option<Closure<dyn FnMut(WheelEvent)>>,
     key_down_callback: Option<Closure<dyn FnMut(KeyboardEvent)>>,
     key_up_callback: Option<Closure<dyn FnMut(KeyboardEvent)>>,
+    composition_start_callback: Option<Closure<dyn FnMut(CompositionEvent)>>,
+    composition_update_callback: Option<Closure<dyn FnMut(CompositionEvent)>>,
+    composition_end_callback: Option<Closure<dyn FnMut(CompositionEvent)>>,
     unload_callback: Option<Closure<dyn FnMut(Event)>>,
     has_focus: bool,
     trace_observer: Arc<RefCell<JsValue>>,
@@ -290,6 +294,7 @@ impl Ruffle {
         // Remove instance from the active list.
         if let Ok(mut instance) = self.remove_instance() {
             instance.canvas.remove();
+            instance.ime_input.remove();
 
             // Stop all audio playing from the instance.
             let _ = instance.with_core_mut(|core| {
@@ -456,6 +461,40 @@ impl Ruffle {
         parent
             .append_child(&canvas.clone().into())
             .into_js_result()?;
+
+        // Composition events are only sent to editable elements, so text
+        // composed with an IME is typed into this hidden text area.
+        let ime_input: HtmlTextAreaElement = document
+            .create_element("textarea")
+            .into_js_result()?
+            .dyn_into()
+            .map_err(|_| "Expected HtmlTextAreaElement")?;
+        // Don't show a virtual keyboard when the player is focused.
+        ime_input.set_attribute("inputmode", "none").warn_on_error();
+        ime_input
+            .set_attribute("aria-hidden", "true")
+            .warn_on_error();
+        for (property, value) in &[
+            ("position", "absolute"),
+            ("left", "0"),
+            ("top", "0"),
+            ("width", "1px"),
+            ("height", "1px"),
+            ("padding", "0"),
+            ("border", "none"),
+            ("opacity", "0"),
+            ("resize", "none"),
+            ("pointer-events", "none"),
+        ] {
+            ime_input
+                .style()
+                .set_property(property, value)
+                .warn_on_error();
+        }
+        parent
+            .append_child(&ime_input.clone().into())
+            .into_js_result()?;
+
         let audio: Box<dyn AudioBackend> = if let Ok(audio) = audio::WebAudioBackend::new() {
             Box::new(audio)
         } else {
@@ -480,7 +519,11 @@ impl Ruffle {
         let trace_observer = Arc::new(RefCell::new(JsValue::UNDEFINED));
         let video = Box::new(SoftwareVideoBackend::new());
         let log = Box::new(log_adapter::WebLogBackend::new(trace_observer.clone()));
-        let ui = Box::new(ui::WebUiBackend::new(js_player.clone(), &canvas));
+        let ui = Box::new(ui::WebUiBackend::new(
+            js_player.clone(),
+            &canvas,
+            &ime_input,
+        ));
 
         let core =
             ruffle_core::Player::new(renderer, audio, navigator, storage, locale, video, log, ui)?;
@@ -505,6 +548,7 @@ impl Ruffle {
             core,
             js_player: js_player.clone(),
             canvas: canvas.clone(),
+            ime_input: ime_input.clone(),
             canvas_width: 0, // Initialize canvas width and height to 0 to force an initial canvas resize.
             canvas_height: 0,
             device_pixel_ratio: window.device_pixel_ratio(),
@@ -519,6 +563,9 @@ impl Ruffle {
             mouse_wheel_callback: None,
             key_down_callback: None,
             key_up_callback: None,
+            composition_start_callback: None,
+            composition_update_callback: None,
+            composition_end_callback: None,
             unload_callback: None,
             timestamp: None,
             has_focus: false,
@@ -607,6 +654,8 @@ impl Ruffle {
                         // Ensure the parent window gets focus. This is necessary for events
                         // to be received when the player is inside a frame.
                         instance.window.focus().warn_on_error();
+                        // Text composed with an IME is typed into the IME text area.
+                        instance.ime_input.focus().warn_on_error();
                     });
                 }) as Box<dyn FnMut(PointerEvent)>);
 
@@ -704,7 +753,8 @@ impl Ruffle {
             // Create keydown event handler.
             let key_down_callback = Closure::wrap(Box::new(move |js_event: KeyboardEvent| {
                 let _ = ruffle.with_instance(|instance| {
-                    if instance.has_focus {
+                    // Keys pressed while composing text are handled by the IME.
+                    if instance.has_focus && !is_composing(&js_event) {
                         let _ = instance.with_core_mut(|core| {
                             let ui = if let Some(ui) =
                                 core.ui_mut().downcast_mut::<ui::WebUiBackend>()
@@ -743,7 +793,7 @@ impl Ruffle {
             // Create keyup event handler.
             let key_up_callback = Closure::wrap(Box::new(move |js_event: KeyboardEvent| {
                 let _ = ruffle.with_instance(|instance| {
-                    if instance.has_focus {
+                    if instance.has_focus && !is_composing(&js_event) {
                         let _ = instance.with_core_mut(|core| {
                             let ui = if let Some(ui) =
                                 core.ui_mut().downcast_mut::<ui::WebUiBackend>()
@@ -769,6 +819,59 @@ impl Ruffle {
                 .warn_on_error();
             instance.key_up_callback = Some(key_up_callback);
 
+            // Create IME composition handlers.
+            let composition_start_callback =
+                Closure::wrap(Box::new(move |_js_event: CompositionEvent| {
+                    let _ = ruffle.with_core_mut(|core| {
+                        core.handle_event(PlayerEvent::ImeCompositionStart);
+                    });
+                }) as Box<dyn FnMut(CompositionEvent)>);
+
+            let ime_input_events: &EventTarget = ime_input.as_ref();
+            ime_input_events
+                .add_event_listener_with_callback(
+                    "compositionstart",
+                    composition_start_callback.as_ref().unchecked_ref(),
+                )
+                .warn_on_error();
+            instance.composition_start_callback = Some(composition_start_callback);
+
+            let composition_update_callback =
+                Closure::wrap(Box::new(move |js_event: CompositionEvent| {
+                    let text = js_event.data().unwrap_or_default();
+                    let _ = ruffle.with_core_mut(|core| {
+                        core.handle_event(PlayerEvent::ImeCompositionUpdate { text });
+                    });
+                }) as Box<dyn FnMut(CompositionEvent)>);
+
+            ime_input_events
+                .add_event_listener_with_callback(
+                    "compositionupdate",
+                    composition_update_callback.as_ref().unchecked_ref(),
+                )
+                .warn_on_error();
+            instance.composition_update_callback = Some(composition_update_callback);
+
+            let composition_end_callback =
+                Closure::wrap(Box::new(move |js_event: CompositionEvent| {
+                    let _ = ruffle.with_instance(|instance| {
+                        // The text is entered into the player instead.
+                        instance.ime_input.set_value("");
+                        let text = js_event.data().unwrap_or_default();
+                        let _ = instance.with_core_mut(|core| {
+                            core.handle_event(PlayerEvent::ImeCompositionCommit { text });
+                        });
+                    });
+                }) as Box<dyn FnMut(CompositionEvent)>);
+
+            ime_input_events
+                .add_event_listener_with_callback(
+                    "compositionend",
+                    composition_end_callback.as_ref().unchecked_ref(),
+                )
+                .warn_on_error();
+            instance.composition_end_callback = Some(composition_end_callback);
+
             let unload_callback = Closure::wrap(Box::new(move |_| {
                 let _ = ruffle.with_core_mut(|core| {
                     core.flush_shared_objects();
@@ -1290,6 +1393,12 @@ fn parse_movie_parameters(input: &JsValue) -> Vec<(String, String)> {
     params
 }
 
+/// Whether a key event is part of composing text with an IME.
+fn is_composing(event: &KeyboardEvent) -> bool {
+    // Some browsers only report the key as being processed by the IME.
+    event.is_composing() || event.key() == "Process"
+}
+
 fn parse_html_color(color: impl AsRef<str>) -> Option<Color> {
     // Parse classic HTML hex color (XXXXXX or #XXXXXX), attempting to match browser behavior.
     // Optional leading #.
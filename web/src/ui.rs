@@ -1,15 +1,16 @@
 use super::JavascriptPlayer;
-use ruffle_core::backend::ui::{MouseCursor, UiBackend};
+use ruffle_core::backend::ui::{ImeCursorArea, MouseCursor, UiBackend};
 use ruffle_core::events::KeyCode;
 use ruffle_web_common::JsResult;
 use std::collections::HashSet;
-use web_sys::{HtmlCanvasElement, KeyboardEvent};
+use web_sys::{HtmlCanvasElement, HtmlTextAreaElement, KeyboardEvent};
 
 /// An implementation of `UiBackend` utilizing `web_sys` bindings to input
 /// APIs.
 pub struct WebUiBackend {
     js_player: JavascriptPlayer,
     canvas: HtmlCanvasElement,
+    ime_input: HtmlTextAreaElement,
     keys_down: HashSet<String>,
     cursor_visible: bool,
     cursor: MouseCursor,
@@ -18,10 +19,15 @@ pub struct WebUiBackend {
 }
 
 impl WebUiBackend {
-    pub fn new(js_player: JavascriptPlayer, canvas: &HtmlCanvasElement) -> Self {
+    pub fn new(
+        js_player: JavascriptPlayer,
+        canvas: &HtmlCanvasElement,
+        ime_input: &HtmlTextAreaElement,
+    ) -> Self {
         Self {
             js_player,
             canvas: canvas.clone(),
+            ime_input: ime_input.clone(),
             keys_down: HashSet::new(),
             cursor_visible: true,
             cursor: MouseCursor::Arrow,
@@ -198,10 +204,27 @@ impl UiBackend for WebUiBackend {
         log::warn!("set clipboard not implemented");
     }
 
+    fn get_clipboard_content(&mut self) -> String {
+        log::warn!("get clipboard not implemented");
+        String::new()
+    }
+
     fn is_fullscreen(&self) -> bool {
         self.js_player.is_fullscreen()
     }
 
+    fn set_ime_cursor_area(&mut self, area: ImeCursorArea) {
+        // Browsers place the candidate window next to the focused element,
+        // so move the IME text area to the caret.
+        let scale = web_sys::window().map_or(1.0, |window| window.device_pixel_ratio());
+        let style = self.ime_input.style();
+        for (property, value) in &[("left", area.x), ("top", area.y), ("height", area.height)] {
+            style
+                .set_property(property, &format!("{}px", value / scale))
+                .warn_on_error();
+        }
+    }
+
     fn display_unsupported_message(&self) {
         self.js_player.display_unsupported_message()
     }